@@ -23,9 +23,10 @@ use crate::session::Session;
 use arrow_schema::SchemaRef;
 use async_trait::async_trait;
 use datafusion_common::Result;
-use datafusion_common::{not_impl_err, Constraints, Statistics};
+use datafusion_common::{not_impl_err, Constraints, DFSchemaRef, Statistics};
 use datafusion_expr::{
-    CreateExternalTable, Expr, LogicalPlan, TableProviderFilterPushDown, TableType,
+    CreateExternalTable, Expr, LogicalPlan, MergeOp, TableProviderFilterPushDown,
+    TableType,
 };
 use datafusion_physical_plan::ExecutionPlan;
 
@@ -276,6 +277,72 @@ pub trait TableProvider: Sync + Send {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         not_impl_err!("Insert into not implemented for this table")
     }
+
+    /// Return an [`ExecutionPlan`] to delete rows from this table, if
+    /// supported.
+    ///
+    /// Only rows for which *all* of the `filters` evaluate to `true` must be
+    /// deleted (aka the expressions are `AND`ed together). If `filters` is
+    /// empty, all rows must be deleted. The filters only reference columns of
+    /// [`Self::schema`] and their column references are unqualified.
+    ///
+    /// The returned plan should return a single row in a UInt64 column called
+    /// "count" with the number of deleted rows, as in [`Self::insert_into`].
+    async fn delete_from(
+        &self,
+        _state: &dyn Session,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Delete not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to update rows of this table, if
+    /// supported.
+    ///
+    /// `assignments` is a list of `(column name, value)` pairs: for each row
+    /// matching *all* of the `filters`, the value of every assigned column
+    /// must be replaced by the value expression evaluated against the row
+    /// before the update. If `filters` is empty, all rows must be updated.
+    /// Value and filter expressions only reference columns of
+    /// [`Self::schema`] and their column references are unqualified.
+    ///
+    /// The returned plan should return a single row in a UInt64 column called
+    /// "count" with the number of updated rows, as in [`Self::insert_into`].
+    async fn update(
+        &self,
+        _state: &dyn Session,
+        _assignments: Vec<(String, Expr)>,
+        _filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Update not implemented for this table")
+    }
+
+    /// Return an [`ExecutionPlan`] to merge the rows of `source` into this
+    /// table, if supported.
+    ///
+    /// The rows of this table matching a source row by the `on` condition of
+    /// `merge`, the source rows not matching any row and the rows not matching
+    /// any source row are each modified by the first of the `WHEN` clauses of
+    /// `merge` of the corresponding kind whose predicate holds. A row matching
+    /// more than one source row must result in an error.
+    ///
+    /// The expressions of `merge` reference columns of `schema`, which has
+    /// the columns of this table followed by the columns of `source`,
+    /// qualified as in the statement. Rows not matching any row of the other
+    /// input have null values for its columns.
+    ///
+    /// The returned plan should return a single row in a UInt64 column called
+    /// "count" with the number of inserted, updated and deleted rows, as in
+    /// [`Self::insert_into`].
+    async fn merge_into(
+        &self,
+        _state: &dyn Session,
+        _source: Arc<dyn ExecutionPlan>,
+        _schema: DFSchemaRef,
+        _merge: MergeOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Merge not implemented for this table")
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
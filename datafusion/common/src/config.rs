@@ -287,6 +287,35 @@ config_namespace! {
         /// batches and merged.
         pub sort_in_place_threshold_bytes: usize, default = 1024 * 1024
 
+        /// Number of partitions the inputs of a partitioned hash join are split
+        /// into when its build side does not fit in memory. Both inputs are
+        /// then spilled to disk by join key hash and the partitions are joined
+        /// one at a time.
+        ///
+        /// Set to 0 to disable spilling, in which case the join fails when the
+        /// memory pool is exhausted. Spilling also requires a `DiskManager`.
+        pub hash_join_spill_partitions: usize, default = 16
+
+        /// Compression codec for spill files written by operators that spill
+        /// to disk, such as sorts, aggregations and joins. Supported values
+        /// are `uncompressed`, `lz4_frame` and `zstd`.
+        pub spill_compression: SpillCompression, default = SpillCompression::Uncompressed
+
+        /// Maximum number of bytes the spill files written by the queries of
+        /// this session may use in total, together with the other temporary
+        /// files of the `DiskManager`. Queries fail once the limit is exceeded.
+        /// If not set, only the limit of the `DiskManager` applies, which
+        /// defaults to no limit.
+        pub max_temp_directory_size: Option<u64>, default = None
+
+        /// Should cached tables (`CACHE TABLE`) be spilled to disk when the
+        /// memory pool is exhausted. If true, the least recently scanned cached
+        /// tables are spilled to make room for a new one, which is itself
+        /// cached on disk if it still does not fit. If false, caching a table
+        /// fails when the memory pool is exhausted. Spilling requires a
+        /// `DiskManager`.
+        pub spill_cached_tables: bool, default = false
+
         /// Number of files to read in parallel when inferring schema and statistics
         pub meta_fetch_concurrency: usize, default = 32
 
@@ -607,14 +636,36 @@ config_namespace! {
         /// Number of times that the optimizer will attempt to optimize the plan
         pub max_passes: usize, default = 3
 
+        /// When set to true, the logical plan optimizer will replace subplans
+        /// equal to the query of a materialized view with a scan of the view.
+        /// The results are stale if the tables read by the view changed since
+        /// it was last refreshed
+        pub enable_materialized_view_rewrite: bool, default = false
+
         /// When set to true, the physical plan optimizer will run a top down
         /// process to reorder the join keys
         pub top_down_join_key_reordering: bool, default = true
 
+        /// When set to true, the physical plan optimizer will reorder trees of
+        /// inner equi-joins based on the statistics of their inputs, instead of
+        /// joining the tables in the order they are listed in the query
+        pub enable_join_reorder: bool, default = false
+
+        /// When set to true, hash joins that collect their build side into a
+        /// single partition publish the range of their build side join keys at
+        /// runtime, which Parquet scans on the probe side use to prune row
+        /// groups and pages
+        pub enable_join_dynamic_filter: bool, default = false
+
         /// When set to true, the physical plan optimizer will prefer HashJoin over SortMergeJoin.
         /// HashJoin can work more efficiently than SortMergeJoin but consumes more memory
         pub prefer_hash_join: bool, default = true
 
+        /// When set to true, inner joins without equijoin keys that have two or
+        /// more range conditions such as `a.start <= b.ts AND b.ts < a.end` are
+        /// planned as an IEJoin instead of a NestedLoopJoin
+        pub enable_ie_join: bool, default = true
+
         /// The maximum estimated size in bytes for one input side of a HashJoin
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold: usize, default = 1024 * 1024
@@ -1033,6 +1084,56 @@ impl ConfigField for CompressionTypeVariant {
     }
 }
 
+/// Compression codec of the spill files, see
+/// `datafusion.execution.spill_compression`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SpillCompression {
+    /// The spill files are not compressed
+    #[default]
+    Uncompressed,
+    /// The spill files are compressed with LZ4 frame compression
+    Lz4Frame,
+    /// The spill files are compressed with ZSTD
+    Zstd,
+}
+
+impl FromStr for SpillCompression {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "uncompressed" => Ok(Self::Uncompressed),
+            "lz4_frame" => Ok(Self::Lz4Frame),
+            "zstd" => Ok(Self::Zstd),
+            other => _config_err!(
+                "Unknown spill compression '{other}', expected one of uncompressed, lz4_frame or zstd"
+            ),
+        }
+    }
+}
+
+impl Display for SpillCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let out = match self {
+            Self::Uncompressed => "uncompressed",
+            Self::Lz4Frame => "lz4_frame",
+            Self::Zstd => "zstd",
+        };
+        write!(f, "{out}")
+    }
+}
+
+impl ConfigField for SpillCompression {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _: &str, value: &str) -> Result<()> {
+        *self = SpillCompression::from_str(value)?;
+        Ok(())
+    }
+}
+
 /// An implementation trait used to recursively walk configuration
 pub trait Visit {
     fn some<V: Display>(&mut self, key: &str, value: V, description: &'static str);
@@ -1789,8 +1890,8 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::config::{
-        ConfigEntry, ConfigExtension, ConfigFileType, ExtensionOptions, Extensions,
-        TableOptions,
+        ConfigEntry, ConfigExtension, ConfigFileType, ConfigOptions, ExtensionOptions,
+        Extensions, SpillCompression, TableOptions,
     };
 
     #[derive(Default, Debug, Clone)]
@@ -1936,4 +2037,22 @@ mod tests {
         let parsed_metadata = table_config.parquet.key_value_metadata;
         assert_eq!(parsed_metadata.get("key_dupe"), Some(&Some("B".into())));
     }
+
+    #[test]
+    fn spill_compression_config() {
+        let mut config = ConfigOptions::new();
+        config
+            .set("datafusion.execution.spill_compression", "ZSTD")
+            .unwrap();
+        assert_eq!(config.execution.spill_compression, SpillCompression::Zstd);
+
+        let err = config
+            .set("datafusion.execution.spill_compression", "snappy")
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: Unknown spill compression 'snappy', expected one of uncompressed, lz4_frame or zstd"
+        );
+        assert_eq!(config.execution.spill_compression, SpillCompression::Zstd);
+    }
 }
@@ -31,6 +31,11 @@
 /// If `preserve_nulls` is true (the default), nulls from the input
 /// column are carried through to the output.
 ///
+/// If `preserve_empty_lists` is true, empty lists from the input column
+/// are carried through to the output as a single null value as well. Together
+/// with `preserve_nulls` this is the behavior of Hive's `LATERAL VIEW OUTER`
+/// and Spark's `explode_outer`.
+///
 /// # Examples
 ///
 /// ## `Unnest(c1)`, preserve_nulls: false
@@ -64,6 +69,20 @@
 pub struct UnnestOptions {
     /// Should nulls in the input be preserved? Defaults to true
     pub preserve_nulls: bool,
+    /// Should a column with the 1-based position of each unnested value be
+    /// appended to the output? Defaults to false
+    ///
+    /// This is the behavior of `UNNEST ... WITH ORDINALITY` in SQL. The
+    /// position column is the last output column, has type `Int64`, and
+    /// counts the output rows produced for each input row, so that values
+    /// unnested from several lists at the same time share the same position.
+    /// Rows produced for preserved null or empty lists have a null position.
+    /// Its name is internal and does not clash with the input columns, the
+    /// SQL planner aliases it to `ordinality`.
+    pub ordinality: bool,
+    /// Should empty lists in the input be preserved as a single null value?
+    /// Defaults to false
+    pub preserve_empty_lists: bool,
 }
 
 impl Default for UnnestOptions {
@@ -71,6 +90,8 @@ impl Default for UnnestOptions {
         Self {
             // default to true to maintain backwards compatible behavior
             preserve_nulls: true,
+            ordinality: false,
+            preserve_empty_lists: false,
         }
     }
 }
@@ -87,4 +108,18 @@ impl UnnestOptions {
         self.preserve_nulls = preserve_nulls;
         self
     }
+
+    /// Set the behavior with empty lists in the input as described on
+    /// [`Self`]
+    pub fn with_preserve_empty_lists(mut self, preserve_empty_lists: bool) -> Self {
+        self.preserve_empty_lists = preserve_empty_lists;
+        self
+    }
+
+    /// Set whether a position column is appended to the output as described
+    /// on [`Self::ordinality`]
+    pub fn with_ordinality(mut self, ordinality: bool) -> Self {
+        self.ordinality = ordinality;
+        self
+    }
 }
@@ -914,6 +914,58 @@ impl DataFrame {
         })
     }
 
+    /// As-of join this `DataFrame` with another `DataFrame`, joining each row
+    /// to the closest row of `right` with the same `left_cols` and `right_cols`
+    /// that satisfies `match_condition`, see [`LogicalPlanBuilder::asof_join`].
+    ///
+    /// # Example
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let trades = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let quotes = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?
+    ///   .select(vec![
+    ///     col("a").alias("a2"),
+    ///     col("b").alias("b2"),
+    ///     col("c").alias("c2")])?;
+    /// // Join each row to the row of `quotes` with the same `a` and the
+    /// // largest `b2` that is at most `b`
+    /// let join = trades.asof_join(
+    ///     quotes,
+    ///     JoinType::Left,
+    ///     &["a"],
+    ///     &["a2"],
+    ///     col("b").gt_eq(col("b2")),
+    /// )?;
+    /// let batches = join.collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn asof_join(
+        self,
+        right: DataFrame,
+        join_type: JoinType,
+        left_cols: &[&str],
+        right_cols: &[&str],
+        match_condition: Expr,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .asof_join(
+                right.plan,
+                join_type,
+                (left_cols.to_vec(), right_cols.to_vec()),
+                match_condition,
+            )?
+            .build()?;
+        Ok(DataFrame {
+            session_state: self.session_state,
+            plan,
+        })
+    }
+
     /// Repartition a DataFrame based on a logical partitioning scheme.
     ///
     /// # Example
@@ -0,0 +1,30 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module contains code for writing `RecordBatch`es as [Avro] data
+//!
+//! [Avro]: https://avro.apache.org/docs/1.2.0/
+
+#[cfg(feature = "avro")]
+mod schema;
+#[cfg(feature = "avro")]
+mod writer;
+
+#[cfg(feature = "avro")]
+pub use schema::to_avro_schema_json;
+#[cfg(feature = "avro")]
+pub use writer::Writer;
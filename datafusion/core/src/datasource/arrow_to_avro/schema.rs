@@ -0,0 +1,266 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of arrow schemas to avro schemas
+
+use crate::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use crate::error::Result;
+use datafusion_common::{exec_err, not_impl_err};
+
+/// Name of the top level avro record, matching the name used by other writers
+/// such as Apache Spark
+pub(super) const TOP_LEVEL_RECORD_NAME: &str = "topLevelRecord";
+
+/// Converts an arrow schema to the JSON representation of an avro record schema
+///
+/// Nullable fields are mapped to unions of `null` and the field type. Named
+/// avro types (records and fixed) are namespaced by the path of the field they
+/// belong to, so that their full names are unique within the schema.
+pub fn to_avro_schema_json(schema: &Schema) -> Result<String> {
+    record_schema_json(TOP_LEVEL_RECORD_NAME, None, schema.fields().iter())
+}
+
+fn record_schema_json<'a>(
+    name: &str,
+    namespace: Option<&str>,
+    fields: impl Iterator<Item = &'a std::sync::Arc<Field>>,
+) -> Result<String> {
+    let child_namespace = qualified_name(name, namespace);
+    let fields = fields
+        .map(|field| {
+            check_name(field.name())?;
+            let field_type = field_schema_json(field, &child_namespace)?;
+            Ok(format!(
+                r#"{{"name":"{}","type":{field_type}}}"#,
+                field.name()
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(format!(
+        r#"{{"type":"record","name":"{name}",{}"fields":[{}]}}"#,
+        namespace_json(namespace),
+        fields.join(",")
+    ))
+}
+
+/// Returns the schema of `field`, as a union with `null` if the field is nullable
+fn field_schema_json(field: &Field, namespace: &str) -> Result<String> {
+    let schema = data_type_schema_json(field.data_type(), field.name(), namespace)?;
+    if field.is_nullable() && !matches!(field.data_type(), DataType::Null) {
+        Ok(format!(r#"["null",{schema}]"#))
+    } else {
+        Ok(schema)
+    }
+}
+
+fn data_type_schema_json(
+    data_type: &DataType,
+    name: &str,
+    namespace: &str,
+) -> Result<String> {
+    let schema = match data_type {
+        DataType::Null => r#""null""#.to_string(),
+        DataType::Boolean => r#""boolean""#.to_string(),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::UInt8
+        | DataType::UInt16 => r#""int""#.to_string(),
+        DataType::Int64 | DataType::UInt32 | DataType::UInt64 => r#""long""#.to_string(),
+        DataType::Float16 | DataType::Float32 => r#""float""#.to_string(),
+        DataType::Float64 => r#""double""#.to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            r#""string""#.to_string()
+        }
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => {
+            r#""bytes""#.to_string()
+        }
+        DataType::FixedSizeBinary(size) => format!(
+            r#"{{"type":"fixed","name":"{name}",{}"size":{size}}}"#,
+            namespace_json(Some(namespace))
+        ),
+        DataType::Decimal128(precision, scale)
+        | DataType::Decimal256(precision, scale) => {
+            if *scale < 0 {
+                return not_impl_err!(
+                    "Avro decimals with a negative scale are not supported: {data_type}"
+                );
+            }
+            format!(
+                r#"{{"type":"bytes","logicalType":"decimal","precision":{precision},"scale":{scale}}}"#
+            )
+        }
+        DataType::Date32 | DataType::Date64 => {
+            r#"{"type":"int","logicalType":"date"}"#.to_string()
+        }
+        DataType::Time32(_) => {
+            r#"{"type":"int","logicalType":"time-millis"}"#.to_string()
+        }
+        DataType::Time64(_) => {
+            r#"{"type":"long","logicalType":"time-micros"}"#.to_string()
+        }
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, _) => {
+            r#"{"type":"long","logicalType":"timestamp-millis"}"#.to_string()
+        }
+        DataType::Timestamp(TimeUnit::Microsecond | TimeUnit::Nanosecond, _) => {
+            r#"{"type":"long","logicalType":"timestamp-micros"}"#.to_string()
+        }
+        DataType::List(item)
+        | DataType::LargeList(item)
+        | DataType::FixedSizeList(item, _) => {
+            check_name(item.name())?;
+            let items = field_schema_json(item, &qualified_name(name, Some(namespace)))?;
+            format!(r#"{{"type":"array","items":{items}}}"#)
+        }
+        DataType::Struct(fields) => {
+            record_schema_json(name, Some(namespace), fields.iter())?
+        }
+        DataType::Map(entries, _) => {
+            let DataType::Struct(entry_fields) = entries.data_type() else {
+                return exec_err!(
+                    "Map entries must be a struct, got {}",
+                    entries.data_type()
+                );
+            };
+            let (key, value) = (&entry_fields[0], &entry_fields[1]);
+            if !matches!(
+                key.data_type(),
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            ) {
+                return not_impl_err!(
+                    "Avro maps only support string keys, got {}",
+                    key.data_type()
+                );
+            }
+            check_name(value.name())?;
+            let values =
+                field_schema_json(value, &qualified_name(name, Some(namespace)))?;
+            format!(r#"{{"type":"map","values":{values}}}"#)
+        }
+        DataType::Dictionary(_, value_type) => {
+            data_type_schema_json(value_type, name, namespace)?
+        }
+        _ => return not_impl_err!("Writing {data_type} to Avro is not supported"),
+    };
+    Ok(schema)
+}
+
+fn namespace_json(namespace: Option<&str>) -> String {
+    namespace
+        .map(|namespace| format!(r#""namespace":"{namespace}","#))
+        .unwrap_or_default()
+}
+
+fn qualified_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}.{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// Checks that `name` is a valid avro name, which must start with `[A-Za-z_]`
+/// and subsequently contain only `[A-Za-z0-9_]`
+fn check_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return exec_err!(
+            "Field name '{name}' is not a valid Avro name, consider aliasing it"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::datatypes::Fields;
+    use apache_avro::Schema as AvroSchema;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_nested_schema() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new(
+                "tags",
+                DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+                true,
+            ),
+            Field::new(
+                "point",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("x", DataType::Float64, false),
+                    Field::new("y", DataType::Float64, false),
+                ])),
+                false,
+            ),
+            Field::new("price", DataType::Decimal128(10, 2), false),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+                false,
+            ),
+        ]);
+        let json = to_avro_schema_json(&schema)?;
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"type":"record","name":"topLevelRecord","fields":["#,
+                r#"{"name":"id","type":"long"},"#,
+                r#"{"name":"name","type":["null","string"]},"#,
+                r#"{"name":"tags","type":["null",{"type":"array","items":["null","string"]}]},"#,
+                r#"{"name":"point","type":{"type":"record","name":"point","namespace":"topLevelRecord","fields":[{"name":"x","type":"double"},{"name":"y","type":"double"}]}},"#,
+                r#"{"name":"price","type":{"type":"bytes","logicalType":"decimal","precision":10,"scale":2}},"#,
+                r#"{"name":"ts","type":{"type":"long","logicalType":"timestamp-micros"}}"#,
+                r#"]}"#
+            )
+        );
+        // the generated schema must be valid
+        AvroSchema::parse_str(&json)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        let schema = Schema::new(vec![Field::new("count(*)", DataType::Int64, false)]);
+        let err = to_avro_schema_json(&schema).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: Field name 'count(*)' is not a valid Avro name, consider aliasing it"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let schema = Schema::new(vec![Field::new(
+            "d",
+            DataType::Duration(TimeUnit::Second),
+            false,
+        )]);
+        let err = to_avro_schema_json(&schema).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "This feature is not implemented: Writing Duration(Second) to Avro is not supported"
+        );
+    }
+}
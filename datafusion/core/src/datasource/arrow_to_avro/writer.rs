@@ -0,0 +1,360 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Writer encoding `RecordBatch`es to the avro object container file format
+
+use super::schema::to_avro_schema_json;
+use crate::arrow::array::{Array, ArrayRef, AsArray};
+use crate::arrow::datatypes::*;
+use crate::arrow::record_batch::RecordBatch;
+use crate::error::Result;
+use apache_avro::types::Value;
+use apache_avro::{read_marker, Decimal, Schema as AvroSchema, Writer as AvroWriter};
+use arrow::compute::cast;
+use datafusion_common::{exec_err, not_impl_err};
+
+/// Encodes `RecordBatch`es as blocks of avro object container files
+///
+/// The avro schema is derived from the arrow schema once, when the writer is
+/// created, and the writer can then be shared by all the files written with
+/// that schema. The rows are converted to avro records and encoded by
+/// [`apache_avro::Writer`]: [`Writer::write_header`] starts a new file, and
+/// [`Writer::write_batch`] encodes the rows of a batch as the data blocks of
+/// that file. Blocks are not compressed.
+#[derive(Debug, Clone)]
+pub struct Writer {
+    /// The schema of the written batches
+    schema: SchemaRef,
+    /// The avro schema of the written files
+    avro_schema: AvroSchema,
+}
+
+impl Writer {
+    /// Creates a writer for batches of `schema`
+    pub fn try_new(schema: SchemaRef) -> Result<Self> {
+        let avro_schema = AvroSchema::parse_str(&to_avro_schema_json(&schema)?)?;
+        Ok(Self {
+            schema,
+            avro_schema,
+        })
+    }
+
+    /// Returns the header of a new file, along with the randomly generated
+    /// sync marker that separates the data blocks of that file
+    pub fn write_header(&self) -> Result<(Vec<u8>, [u8; 16])> {
+        let header = AvroWriter::new(&self.avro_schema, Vec::new()).into_inner()?;
+        let sync_marker = read_marker(&header);
+        Ok((header, sync_marker))
+    }
+
+    /// Returns the data blocks holding all rows of `batch`, for the file whose
+    /// header declared `sync_marker`
+    pub fn write_batch(
+        &self,
+        batch: &RecordBatch,
+        sync_marker: [u8; 16],
+    ) -> Result<Vec<u8>> {
+        if batch.num_rows() == 0 {
+            return Ok(vec![]);
+        }
+        if batch.num_columns() != self.schema.fields().len() {
+            return exec_err!(
+                "Expected a batch with {} columns to write to Avro, got {}",
+                self.schema.fields().len(),
+                batch.num_columns()
+            );
+        }
+
+        // The columns are cast to the types of the schema, from which the avro
+        // schema was derived, and dictionaries are decoded once for the batch
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, column)| match field.data_type() {
+                DataType::Dictionary(_, value_type) => Ok(cast(column, value_type)?),
+                data_type if data_type != column.data_type() => {
+                    Ok(cast(column, data_type)?)
+                }
+                _ => Ok(ArrayRef::clone(column)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut writer =
+            AvroWriter::append_to(&self.avro_schema, Vec::new(), sync_marker);
+        for row in 0..batch.num_rows() {
+            let record = self
+                .schema
+                .fields()
+                .iter()
+                .zip(&columns)
+                .map(|(field, column)| {
+                    Ok((field.name().clone(), field_value(field, column, row)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            writer.append(Value::Record(record))?;
+        }
+        Ok(writer.into_inner()?)
+    }
+}
+
+/// Returns the avro value of `field` at `row` of `array`, as a union with
+/// `null` if the field is nullable (see [`to_avro_schema_json`])
+fn field_value(field: &Field, array: &ArrayRef, row: usize) -> Result<Value> {
+    if matches!(field.data_type(), DataType::Null) {
+        return Ok(Value::Null);
+    }
+    match (field.is_nullable(), array.is_null(row)) {
+        (true, true) => Ok(Value::Union(0, Box::new(Value::Null))),
+        (true, false) => Ok(Value::Union(1, Box::new(value(field, array, row)?))),
+        (false, true) => exec_err!(
+            "Unexpected null value in non-nullable field {}",
+            field.name()
+        ),
+        (false, false) => value(field, array, row),
+    }
+}
+
+/// Returns the avro value of the non-null `row` of `array`
+fn value(field: &Field, array: &ArrayRef, row: usize) -> Result<Value> {
+    let value = match array.data_type() {
+        DataType::Boolean => Value::Boolean(array.as_boolean().value(row)),
+        DataType::Int8 => Value::Int(array.as_primitive::<Int8Type>().value(row) as i32),
+        DataType::Int16 => {
+            Value::Int(array.as_primitive::<Int16Type>().value(row) as i32)
+        }
+        DataType::Int32 => Value::Int(array.as_primitive::<Int32Type>().value(row)),
+        DataType::Int64 => Value::Long(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => {
+            Value::Int(array.as_primitive::<UInt8Type>().value(row) as i32)
+        }
+        DataType::UInt16 => {
+            Value::Int(array.as_primitive::<UInt16Type>().value(row) as i32)
+        }
+        DataType::UInt32 => {
+            Value::Long(array.as_primitive::<UInt32Type>().value(row) as i64)
+        }
+        DataType::UInt64 => {
+            let v = array.as_primitive::<UInt64Type>().value(row);
+            match i64::try_from(v) {
+                Ok(v) => Value::Long(v),
+                Err(_) => {
+                    return exec_err!(
+                        "Value {v} of field {} does not fit in an Avro long",
+                        field.name()
+                    )
+                }
+            }
+        }
+        DataType::Float16 => {
+            Value::Float(array.as_primitive::<Float16Type>().value(row).to_f32())
+        }
+        DataType::Float32 => Value::Float(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => {
+            Value::Double(array.as_primitive::<Float64Type>().value(row))
+        }
+        DataType::Utf8 => Value::String(array.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => {
+            Value::String(array.as_string::<i64>().value(row).to_string())
+        }
+        DataType::Utf8View => {
+            Value::String(array.as_string_view().value(row).to_string())
+        }
+        DataType::Binary => Value::Bytes(array.as_binary::<i32>().value(row).to_vec()),
+        DataType::LargeBinary => {
+            Value::Bytes(array.as_binary::<i64>().value(row).to_vec())
+        }
+        DataType::BinaryView => Value::Bytes(array.as_binary_view().value(row).to_vec()),
+        DataType::FixedSizeBinary(size) => Value::Fixed(
+            *size as usize,
+            array.as_fixed_size_binary().value(row).to_vec(),
+        ),
+        DataType::Decimal128(_, _) => {
+            let v = array.as_primitive::<Decimal128Type>().value(row);
+            Value::Decimal(Decimal::from(v.to_be_bytes()))
+        }
+        DataType::Decimal256(_, _) => {
+            let v = array.as_primitive::<Decimal256Type>().value(row);
+            Value::Decimal(Decimal::from(v.to_be_bytes()))
+        }
+        DataType::Date32 => Value::Date(array.as_primitive::<Date32Type>().value(row)),
+        DataType::Date64 => {
+            let millis = array.as_primitive::<Date64Type>().value(row);
+            let Ok(days) = i32::try_from(millis.div_euclid(MILLIS_PER_DAY)) else {
+                return exec_err!(
+                    "Date {millis} of field {} is out of range for Avro date",
+                    field.name()
+                );
+            };
+            Value::Date(days)
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            let seconds = array.as_primitive::<Time32SecondType>().value(row);
+            let Some(millis) = seconds.checked_mul(1_000) else {
+                return exec_err!(
+                    "Time {seconds} of field {} is out of range for Avro time-millis",
+                    field.name()
+                );
+            };
+            Value::TimeMillis(millis)
+        }
+        DataType::Time32(_) => {
+            Value::TimeMillis(array.as_primitive::<Time32MillisecondType>().value(row))
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            Value::TimeMicros(array.as_primitive::<Time64MicrosecondType>().value(row))
+        }
+        DataType::Time64(_) => Value::TimeMicros(
+            array.as_primitive::<Time64NanosecondType>().value(row) / 1_000,
+        ),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            let seconds = array.as_primitive::<TimestampSecondType>().value(row);
+            let Some(millis) = seconds.checked_mul(1_000) else {
+                return exec_err!(
+                    "Timestamp {seconds} of field {} is out of range for Avro timestamp-millis",
+                    field.name()
+                );
+            };
+            Value::TimestampMillis(millis)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => Value::TimestampMillis(
+            array.as_primitive::<TimestampMillisecondType>().value(row),
+        ),
+        DataType::Timestamp(TimeUnit::Microsecond, _) => Value::TimestampMicros(
+            array.as_primitive::<TimestampMicrosecondType>().value(row),
+        ),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => Value::TimestampMicros(
+            array
+                .as_primitive::<TimestampNanosecondType>()
+                .value(row)
+                .div_euclid(1_000),
+        ),
+        DataType::List(item) => array_value(item, &array.as_list::<i32>().value(row))?,
+        DataType::LargeList(item) => {
+            array_value(item, &array.as_list::<i64>().value(row))?
+        }
+        DataType::FixedSizeList(item, _) => {
+            array_value(item, &array.as_fixed_size_list().value(row))?
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let record = fields
+                .iter()
+                .zip(array.columns())
+                .map(|(field, column)| {
+                    Ok((field.name().clone(), field_value(field, column, row)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Value::Record(record)
+        }
+        DataType::Map(_, _) => {
+            let entries = array.as_map().value(row);
+            let keys = cast(entries.column(0), &DataType::Utf8)?;
+            let keys = keys.as_string::<i32>();
+            let value_field = entries.fields()[1].as_ref();
+            let values = entries.column(1);
+            let map = (0..entries.len())
+                .map(|i| {
+                    Ok((
+                        keys.value(i).to_string(),
+                        field_value(value_field, values, i)?,
+                    ))
+                })
+                .collect::<Result<_>>()?;
+            Value::Map(map)
+        }
+        DataType::Dictionary(_, value_type) => {
+            let array = cast(&array.slice(row, 1), value_type)?;
+            value(field, &array, 0)?
+        }
+        data_type => {
+            return not_impl_err!("Writing {data_type} to Avro is not supported")
+        }
+    };
+    Ok(value)
+}
+
+/// Returns all the values of `array` as an avro array
+fn array_value(item: &Field, array: &ArrayRef) -> Result<Value> {
+    let items = (0..array.len())
+        .map(|i| field_value(item, array, i))
+        .collect::<Result<_>>()?;
+    Ok(Value::Array(items))
+}
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::array::{Int64Array, StringArray, TimestampSecondArray};
+    use apache_avro::Reader as AvroReader;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_write_file() -> Result<()> {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+            ),
+        ])?;
+        let writer = Writer::try_new(batch.schema())?;
+        let (mut file, sync_marker) = writer.write_header()?;
+        file.extend(writer.write_batch(&batch, sync_marker)?);
+        file.extend(writer.write_batch(&batch.slice(1, 1), sync_marker)?);
+
+        let records = AvroReader::new(file.as_slice())?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let record = |id: i64, name: Option<&str>| {
+            let name = match name {
+                Some(name) => Value::Union(1, Box::new(Value::String(name.into()))),
+                None => Value::Union(0, Box::new(Value::Null)),
+            };
+            Value::Record(vec![
+                ("id".to_string(), Value::Long(id)),
+                ("name".to_string(), name),
+            ])
+        };
+        assert_eq!(
+            records,
+            vec![record(1, Some("a")), record(2, None), record(2, None)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_seconds_overflow() -> Result<()> {
+        let batch = RecordBatch::try_from_iter_with_nullable(vec![(
+            "ts",
+            Arc::new(TimestampSecondArray::from(vec![1, i64::MAX])) as ArrayRef,
+            false,
+        )])?;
+        let writer = Writer::try_new(batch.schema())?;
+        let err = writer.write_batch(&batch, [0; 16]).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            format!(
+                "Execution error: Timestamp {} of field ts is out of range for Avro timestamp-millis",
+                i64::MAX
+            )
+        );
+        Ok(())
+    }
+}
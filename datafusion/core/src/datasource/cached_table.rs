@@ -0,0 +1,462 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Cached table data source which holds the contents of another table in
+//! memory, or on disk once spilled.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::datasource::{TableProvider, TableType};
+use crate::error::{DataFusionError, Result};
+use crate::logical_expr::Expr;
+use crate::physical_plan::spill::{read_spill, spill_record_batches};
+use crate::physical_plan::stream::{RecordBatchReceiverStream, RecordBatchStreamAdapter};
+use crate::physical_plan::streaming::{PartitionStream, StreamingTableExec};
+use crate::physical_plan::{ExecutionPlan, SendableRecordBatchStream};
+
+use arrow::datatypes::SchemaRef;
+use arrow::ipc::writer::IpcWriteOptions;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use datafusion_catalog::Session;
+use datafusion_common::Constraints;
+use datafusion_common_runtime::SpawnedTask;
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
+use datafusion_execution::{DiskManager, TaskContext};
+use futures::{stream, StreamExt};
+use parking_lot::Mutex;
+
+/// The contents of a [`CachedTable`]
+enum CachedData {
+    /// Batches held in memory
+    Memory(Arc<CachedBatches>),
+    /// Batches of each partition, spilled to one file per partition
+    Disk(Vec<Arc<RefCountedTempFile>>),
+}
+
+/// Batches of each partition of a [`CachedTable`] held in memory.
+///
+/// They are shared with the running scans of the table, so that their memory
+/// stays reserved until the last scan drops them, even once the table was
+/// spilled.
+struct CachedBatches {
+    partitions: Vec<Vec<RecordBatch>>,
+    /// Accounts for the memory used by `partitions`
+    reservation: MemoryReservation,
+}
+
+/// An implementation of `TableProvider` that caches the contents of another
+/// table, as created by `CACHE TABLE`.
+///
+/// The cached batches are held in memory and accounted against the memory
+/// pool of the [`RuntimeEnv`] until they are [spilled](Self::spill) to disk,
+/// after which every scan reads them back from disk.
+pub struct CachedTable {
+    schema: SchemaRef,
+    /// The table whose contents are cached
+    original: Arc<dyn TableProvider>,
+    data: Mutex<CachedData>,
+    disk_manager: Arc<DiskManager>,
+    /// When the table was last scanned, or created if it was never scanned
+    last_scan: Mutex<Instant>,
+}
+
+impl CachedTable {
+    /// Cache `partitions`, the contents of `original`, in memory.
+    ///
+    /// Returns a resources exhausted error if the memory pool of `runtime`
+    /// can not hold them.
+    pub fn try_new(
+        name: &str,
+        original: Arc<dyn TableProvider>,
+        schema: SchemaRef,
+        partitions: Vec<Vec<RecordBatch>>,
+        runtime: &RuntimeEnv,
+    ) -> Result<Self> {
+        let mut reservation = MemoryConsumer::new(format!("CachedTable[{name}]"))
+            .with_can_spill(true)
+            .register(&runtime.memory_pool);
+        reservation.try_grow(
+            partitions
+                .iter()
+                .flatten()
+                .map(RecordBatch::get_array_memory_size)
+                .sum(),
+        )?;
+        let data = CachedData::Memory(Arc::new(CachedBatches {
+            partitions,
+            reservation,
+        }));
+        Ok(Self::new(original, schema, data, runtime))
+    }
+
+    /// Cache `partitions`, the contents of `original`, on disk
+    pub async fn try_new_spilled(
+        original: Arc<dyn TableProvider>,
+        schema: SchemaRef,
+        partitions: Vec<Vec<RecordBatch>>,
+        runtime: &RuntimeEnv,
+        write_options: IpcWriteOptions,
+    ) -> Result<Self> {
+        let files = spill_partitions(
+            Arc::clone(&runtime.disk_manager),
+            Arc::clone(&schema),
+            partitions,
+            write_options,
+        )
+        .await?;
+        Ok(Self::new(
+            original,
+            schema,
+            CachedData::Disk(files),
+            runtime,
+        ))
+    }
+
+    fn new(
+        original: Arc<dyn TableProvider>,
+        schema: SchemaRef,
+        data: CachedData,
+        runtime: &RuntimeEnv,
+    ) -> Self {
+        Self {
+            schema,
+            original,
+            data: Mutex::new(data),
+            disk_manager: Arc::clone(&runtime.disk_manager),
+            last_scan: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Get the table whose contents are cached
+    pub fn original(&self) -> &Arc<dyn TableProvider> {
+        &self.original
+    }
+
+    /// Returns true if the cached batches were spilled to disk
+    pub fn is_spilled(&self) -> bool {
+        matches!(*self.data.lock(), CachedData::Disk(_))
+    }
+
+    /// Returns the memory used by the cached batches, which is 0 once they
+    /// were spilled to disk
+    pub fn memory_size(&self) -> usize {
+        match &*self.data.lock() {
+            CachedData::Memory(batches) => batches.reservation.size(),
+            CachedData::Disk(_) => 0,
+        }
+    }
+
+    /// Returns when the table was last scanned, or created if it was never
+    /// scanned
+    pub fn last_scan(&self) -> Instant {
+        *self.last_scan.lock()
+    }
+
+    /// Spill the cached batches to disk, releasing their memory once the
+    /// running scans of the table finish.
+    ///
+    /// Returns the number of bytes to be released, which is 0 if the batches
+    /// were already spilled.
+    pub async fn spill(&self, write_options: IpcWriteOptions) -> Result<usize> {
+        let batches = match &*self.data.lock() {
+            CachedData::Memory(batches) => Arc::clone(batches),
+            CachedData::Disk(_) => return Ok(0),
+        };
+
+        let released = batches.reservation.size();
+        let files = spill_partitions(
+            Arc::clone(&self.disk_manager),
+            Arc::clone(&self.schema),
+            batches.partitions.clone(),
+            write_options,
+        )
+        .await?;
+
+        let mut data = self.data.lock();
+        match &*data {
+            // Dropping the last reference to the batches releases their memory
+            CachedData::Memory(current) if Arc::ptr_eq(current, &batches) => {
+                *data = CachedData::Disk(files);
+                Ok(released)
+            }
+            // The table was spilled concurrently
+            _ => Ok(0),
+        }
+    }
+}
+
+/// Spill each of `partitions` to its own file, without blocking the runtime
+async fn spill_partitions(
+    disk_manager: Arc<DiskManager>,
+    schema: SchemaRef,
+    partitions: Vec<Vec<RecordBatch>>,
+    write_options: IpcWriteOptions,
+) -> Result<Vec<Arc<RefCountedTempFile>>> {
+    SpawnedTask::spawn_blocking(move || {
+        partitions
+            .into_iter()
+            .map(|batches| {
+                let mut file = disk_manager.create_tmp_file("CachedTable")?;
+                spill_record_batches(
+                    batches,
+                    &mut file,
+                    Arc::clone(&schema),
+                    write_options.clone(),
+                )?;
+                Ok(Arc::new(file))
+            })
+            .collect()
+    })
+    .join_unwind()
+    .await
+    .map_err(DataFusionError::ExecutionJoin)?
+}
+
+/// A partition of a [`CachedTable`] held in memory, which keeps the cached
+/// batches alive while it is scanned
+struct MemoryPartition {
+    schema: SchemaRef,
+    batches: Arc<CachedBatches>,
+    partition: usize,
+}
+
+impl PartitionStream for MemoryPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let batches = Arc::clone(&self.batches);
+        let partition = self.partition;
+        let num_batches = batches.partitions[partition].len();
+        let stream = stream::iter(0..num_batches)
+            .map(move |i| Ok(batches.partitions[partition][i].clone()));
+        Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&self.schema),
+            stream,
+        ))
+    }
+}
+
+/// A partition of a spilled [`CachedTable`], read back from its file on
+/// every execution
+struct SpilledPartition {
+    schema: SchemaRef,
+    file: Arc<RefCountedTempFile>,
+}
+
+impl PartitionStream for SpilledPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = RecordBatchReceiverStream::builder(Arc::clone(&self.schema), 2);
+        let sender = builder.tx();
+        let file = Arc::clone(&self.file);
+        builder.spawn_blocking(move || read_spill(sender, file.path()));
+        builder.build()
+    }
+}
+
+#[async_trait]
+impl TableProvider for CachedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.original.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.original.table_type()
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.original.get_table_definition()
+    }
+
+    async fn scan(
+        &self,
+        _state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        *self.last_scan.lock() = Instant::now();
+        let partitions: Vec<Arc<dyn PartitionStream>> = match &*self.data.lock() {
+            CachedData::Memory(batches) => (0..batches.partitions.len())
+                .map(|partition| {
+                    Arc::new(MemoryPartition {
+                        schema: self.schema(),
+                        batches: Arc::clone(batches),
+                        partition,
+                    }) as _
+                })
+                .collect(),
+            CachedData::Disk(files) => files
+                .iter()
+                .map(|file| {
+                    Arc::new(SpilledPartition {
+                        schema: self.schema(),
+                        file: Arc::clone(file),
+                    }) as _
+                })
+                .collect(),
+        };
+        Ok(Arc::new(StreamingTableExec::try_new(
+            self.schema(),
+            partitions,
+            projection,
+            vec![],
+            false,
+            None,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::MemTable;
+    use crate::execution::runtime_env::RuntimeEnvBuilder;
+    use crate::physical_plan::collect;
+    use crate::prelude::{SessionConfig, SessionContext};
+
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn batch(values: impl IntoIterator<Item = i64>) -> RecordBatch {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let array = Int64Array::from_iter_values(values);
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)]).unwrap()
+    }
+
+    fn cached_table(runtime: &RuntimeEnv) -> Result<CachedTable> {
+        let partitions = vec![vec![batch(0..2)], vec![batch(2..3)]];
+        let schema = partitions[0][0].schema();
+        let original = MemTable::try_new(Arc::clone(&schema), vec![vec![batch(0..3)]])?;
+        CachedTable::try_new("t", Arc::new(original), schema, partitions, runtime)
+    }
+
+    #[tokio::test]
+    async fn spill_cached_table() -> Result<()> {
+        let ctx = SessionContext::new();
+        let runtime = ctx.runtime_env();
+        let table = cached_table(&runtime)?;
+        assert!(!table.is_spilled());
+        assert!(table.memory_size() > 0);
+        assert_eq!(runtime.memory_pool.reserved(), table.memory_size());
+
+        let expected = [
+            "+---+", "| a |", "+---+", "| 0 |", "| 1 |", "| 2 |", "+---+",
+        ];
+        let exec = table.scan(&ctx.state(), None, &[], None).await?;
+        assert_batches_eq!(expected, &collect(Arc::clone(&exec), ctx.task_ctx()).await?);
+
+        let size = table.memory_size();
+        let released = table.spill(IpcWriteOptions::default()).await?;
+        assert_eq!(released, size);
+        assert!(table.is_spilled());
+        assert_eq!(table.memory_size(), 0);
+        // The memory stays reserved until the plan scanning the batches is dropped
+        assert_eq!(runtime.memory_pool.reserved(), size);
+        assert_batches_eq!(expected, &collect(exec, ctx.task_ctx()).await?);
+        assert_eq!(runtime.memory_pool.reserved(), 0);
+        assert_eq!(table.spill(IpcWriteOptions::default()).await?, 0);
+
+        let exec = table.scan(&ctx.state(), Some(&vec![0]), &[], None).await?;
+        assert_batches_eq!(expected, &collect(exec, ctx.task_ctx()).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cached_table_memory_limit() -> Result<()> {
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(10, 1.0)
+            .build()?;
+        let err = cached_table(&runtime).err().unwrap();
+        assert!(
+            matches!(err, DataFusionError::ResourcesExhausted(_)),
+            "unexpected error: {err}"
+        );
+        assert_eq!(runtime.memory_pool.reserved(), 0);
+        Ok(())
+    }
+
+    async fn is_spilled(ctx: &SessionContext, name: &str) -> Result<bool> {
+        let table = ctx.table_provider(name).await?;
+        let table = table.as_any().downcast_ref::<CachedTable>().unwrap();
+        Ok(table.is_spilled())
+    }
+
+    #[tokio::test]
+    async fn cache_table_spills_cold_tables() -> Result<()> {
+        // Room for a single cached table of 1000 Int64 values
+        let runtime = RuntimeEnvBuilder::new()
+            .with_memory_limit(12_000, 1.0)
+            .build()?;
+        let config = SessionConfig::new()
+            .with_target_partitions(1)
+            .set_bool("datafusion.execution.spill_cached_tables", true);
+        let ctx = SessionContext::new_with_config_rt(config, Arc::new(runtime));
+        ctx.register_batch("t1", batch(0..1000))?;
+        ctx.register_batch("t2", batch(1000..2000))?;
+
+        ctx.sql("CACHE TABLE t1").await?.collect().await?;
+        assert!(!is_spilled(&ctx, "t1").await?);
+
+        ctx.sql("CACHE TABLE t2").await?.collect().await?;
+        assert!(is_spilled(&ctx, "t1").await?);
+        assert!(!is_spilled(&ctx, "t2").await?);
+
+        // Does not fit in memory even if all other tables are spilled
+        ctx.sql("CACHE TABLE t3 AS SELECT * FROM t1 UNION ALL SELECT * FROM t2")
+            .await?
+            .collect()
+            .await?;
+        assert!(is_spilled(&ctx, "t2").await?);
+        assert!(is_spilled(&ctx, "t3").await?);
+
+        let expected = [
+            "+----------+",
+            "| count(*) |",
+            "+----------+",
+            "| 2000     |",
+            "+----------+",
+        ];
+        let results = ctx.sql("SELECT count(*) FROM t3").await?.collect().await?;
+        assert_batches_eq!(expected, &results);
+
+        // Uncaching restores the original tables
+        ctx.sql("UNCACHE TABLE t1").await?.collect().await?;
+        let table = ctx.table_provider("t1").await?;
+        assert!(table.as_any().is::<MemTable>());
+        Ok(())
+    }
+}
@@ -20,27 +20,48 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
 use async_trait::async_trait;
+use datafusion_common::file_options::avro_writer::AvroWriterOptions;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::DataFusionError;
 use datafusion_common::GetExt;
-use datafusion_common::DEFAULT_AVRO_EXTENSION;
+use datafusion_common::{not_impl_err, DEFAULT_AVRO_EXTENSION};
+use datafusion_execution::TaskContext;
 use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
+use datafusion_physical_plan::metrics::MetricsSet;
 use object_store::{GetResultPayload, ObjectMeta, ObjectStore};
 
+#[cfg(feature = "avro")]
+use arrow_array::RecordBatch;
+#[cfg(feature = "avro")]
+use bytes::Bytes;
+#[cfg(feature = "avro")]
+use parking_lot::Mutex;
+
 use super::file_compression_type::FileCompressionType;
+#[cfg(feature = "avro")]
+use super::write::{orchestration::stateless_multipart_put, BatchSerializer};
 use super::FileFormat;
 use super::FileFormatFactory;
+#[cfg(feature = "avro")]
+use crate::datasource::arrow_to_avro::Writer as AvroWriter;
 use crate::datasource::avro_to_arrow::read_avro_schema_from_reader;
-use crate::datasource::physical_plan::{AvroExec, FileScanConfig};
+use crate::datasource::physical_plan::{
+    AvroExec, FileGroupDisplay, FileScanConfig, FileSinkConfig,
+};
 use crate::error::Result;
 use crate::execution::context::SessionState;
-use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::insert::{DataSink, DataSinkExec};
 use crate::physical_plan::Statistics;
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream,
+};
 
 #[derive(Default)]
 /// Factory struct used to create [AvroFormat]
@@ -155,6 +176,205 @@ impl FileFormat for AvroFormat {
         let exec = AvroExec::new(conf);
         Ok(Arc::new(exec))
     }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &SessionState,
+        conf: FileSinkConfig,
+        order_requirements: Option<LexRequirement>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if conf.overwrite {
+            return not_impl_err!("Overwrites are not implemented yet for Avro");
+        }
+
+        let sink_schema = conf.output_schema().clone();
+        let sink = Arc::new(AvroSink::new(conf, AvroWriterOptions {}));
+
+        Ok(Arc::new(DataSinkExec::new(
+            input,
+            sink,
+            sink_schema,
+            order_requirements,
+        )) as _)
+    }
+}
+
+/// Define a struct for serializing Avro records to a stream
+///
+/// Every serializer writes a single Avro object container file: the file
+/// header is written along with the first batch, and the batches are then
+/// written as data blocks separated by the sync marker of the header.
+#[cfg(feature = "avro")]
+pub struct AvroSerializer {
+    /// Writer encoding the batches, shared by all the files of a sink
+    writer: Arc<AvroWriter>,
+    /// Header of the file and its sync marker, created by whichever batch is
+    /// serialized first as batches may be serialized concurrently
+    header: Mutex<Option<(Bytes, [u8; 16])>>,
+}
+
+#[cfg(feature = "avro")]
+impl AvroSerializer {
+    /// Constructor for the AvroSerializer object
+    pub fn new(writer: Arc<AvroWriter>) -> Self {
+        Self {
+            writer,
+            header: Mutex::new(None),
+        }
+    }
+
+    /// Returns the header of the file and its sync marker
+    fn header(&self) -> Result<(Bytes, [u8; 16])> {
+        let mut header = self.header.lock();
+        if let Some(header) = header.as_ref() {
+            return Ok(header.clone());
+        }
+        let (bytes, sync_marker) = self.writer.write_header()?;
+        Ok(header.insert((Bytes::from(bytes), sync_marker)).clone())
+    }
+}
+
+#[cfg(feature = "avro")]
+impl BatchSerializer for AvroSerializer {
+    fn serialize(&self, batch: RecordBatch, initial: bool) -> Result<Bytes> {
+        let (header, sync_marker) = self.header()?;
+        let blocks = self.writer.write_batch(&batch, sync_marker)?;
+        if initial {
+            let mut buffer = Vec::with_capacity(header.len() + blocks.len());
+            buffer.extend_from_slice(&header);
+            buffer.extend(blocks);
+            Ok(Bytes::from(buffer))
+        } else {
+            Ok(Bytes::from(blocks))
+        }
+    }
+}
+
+/// Implements [`DataSink`] for writing to an Avro file.
+pub struct AvroSink {
+    /// Config options for writing data
+    config: FileSinkConfig,
+    /// Writer options for underlying Avro writer
+    writer_options: AvroWriterOptions,
+}
+
+impl Debug for AvroSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AvroSink").finish()
+    }
+}
+
+impl DisplayAs for AvroSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "AvroSink(file_groups=",)?;
+                FileGroupDisplay(&self.config.file_groups).fmt_as(t, f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl AvroSink {
+    /// Create from config.
+    pub fn new(config: FileSinkConfig, writer_options: AvroWriterOptions) -> Self {
+        Self {
+            config,
+            writer_options,
+        }
+    }
+
+    /// Retrieve the inner [`FileSinkConfig`].
+    pub fn config(&self) -> &FileSinkConfig {
+        &self.config
+    }
+
+    /// Retrieve the writer options
+    pub fn writer_options(&self) -> &AvroWriterOptions {
+        &self.writer_options
+    }
+
+    /// Returns the schema of the written files, which don't contain the
+    /// partition columns unless `keep_partition_by_columns` is set
+    #[cfg(feature = "avro")]
+    fn get_writer_schema(&self) -> SchemaRef {
+        if !self.config.table_partition_cols.is_empty()
+            && !self.config.keep_partition_by_columns
+        {
+            let schema = self.config.output_schema();
+            let partition_names: Vec<_> = self
+                .config
+                .table_partition_cols
+                .iter()
+                .map(|(s, _)| s)
+                .collect();
+            Arc::new(Schema::new(
+                schema
+                    .fields()
+                    .iter()
+                    .filter(|f| !partition_names.contains(&f.name()))
+                    .map(|f| (**f).clone())
+                    .collect::<Vec<_>>(),
+            ))
+        } else {
+            Arc::clone(self.config.output_schema())
+        }
+    }
+
+    #[cfg(not(feature = "avro"))]
+    async fn multipartput_all(
+        &self,
+        _data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        Err(DataFusionError::NotImplemented(
+            "Cannot write avro files without avro feature enabled".to_string(),
+        ))
+    }
+
+    #[cfg(feature = "avro")]
+    async fn multipartput_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        // The Avro schema is only derived once for all the files
+        let writer = Arc::new(AvroWriter::try_new(self.get_writer_schema())?);
+        let get_serializer =
+            move || Arc::new(AvroSerializer::new(Arc::clone(&writer))) as _;
+
+        stateless_multipart_put(
+            data,
+            context,
+            "avro".into(),
+            Box::new(get_serializer),
+            &self.config,
+            FileCompressionType::UNCOMPRESSED,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl DataSink for AvroSink {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let total_count = self.multipartput_all(data, context).await?;
+        Ok(total_count)
+    }
 }
 
 #[cfg(test)]
@@ -501,6 +721,134 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn write_nested_types() -> Result<()> {
+        use apache_avro::types::Value;
+        use apache_avro::Decimal;
+        use arrow::array::{
+            ArrayRef, Decimal128Array, Float64Array, Int64Array, ListBuilder,
+            StringArray, StringBuilder, StructArray, TimestampNanosecondArray,
+        };
+        use arrow::datatypes::{DataType, Field};
+
+        let mut tags = ListBuilder::new(StringBuilder::new());
+        tags.values().append_value("x");
+        tags.values().append_null();
+        tags.append(true);
+        tags.append(false);
+        let point = StructArray::from(vec![
+            (
+                Arc::new(Field::new("x", DataType::Float64, false)),
+                Arc::new(Float64Array::from(vec![1.5, 3.0])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("y", DataType::Float64, false)),
+                Arc::new(Float64Array::from(vec![-2.0, 4.0])) as ArrayRef,
+            ),
+        ]);
+        let batch = arrow_array::RecordBatch::try_from_iter_with_nullable(vec![
+            (
+                "id",
+                Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+                false,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![Some("a"), None])) as _,
+                true,
+            ),
+            ("tags", Arc::new(tags.finish()) as _, true),
+            ("point", Arc::new(point) as _, false),
+            (
+                "price",
+                Arc::new(
+                    Decimal128Array::from(vec![12345, -100])
+                        .with_precision_and_scale(10, 2)?,
+                ) as _,
+                false,
+            ),
+            (
+                "ts",
+                Arc::new(
+                    TimestampNanosecondArray::from(vec![1_000_001_000, 2_000_000_999])
+                        .with_timezone("UTC"),
+                ) as _,
+                false,
+            ),
+        ])?;
+
+        let session_ctx = SessionContext::new();
+        session_ctx.register_batch("t", batch)?;
+        let tmp_dir = tempfile::TempDir::new()?;
+        let path = tmp_dir.path().join("out.avro");
+        let path = path.to_str().unwrap();
+        session_ctx
+            .sql(&format!("COPY t TO '{path}' STORED AS AVRO"))
+            .await?
+            .collect()
+            .await?;
+
+        let reader = apache_avro::Reader::new(std::fs::File::open(path)?)?;
+        let values = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        let decimal = |v: i128| Value::Decimal(Decimal::from(v.to_be_bytes().to_vec()));
+        let expected = vec![
+            Value::Record(vec![
+                ("id".to_string(), Value::Long(1)),
+                (
+                    "name".to_string(),
+                    Value::Union(1, Box::new(Value::String("a".to_string()))),
+                ),
+                (
+                    "tags".to_string(),
+                    Value::Union(
+                        1,
+                        Box::new(Value::Array(vec![
+                            Value::Union(1, Box::new(Value::String("x".to_string()))),
+                            Value::Union(0, Box::new(Value::Null)),
+                        ])),
+                    ),
+                ),
+                (
+                    "point".to_string(),
+                    Value::Record(vec![
+                        ("x".to_string(), Value::Double(1.5)),
+                        ("y".to_string(), Value::Double(-2.0)),
+                    ]),
+                ),
+                ("price".to_string(), decimal(12345)),
+                ("ts".to_string(), Value::TimestampMicros(1_000_001)),
+            ]),
+            Value::Record(vec![
+                ("id".to_string(), Value::Long(2)),
+                ("name".to_string(), Value::Union(0, Box::new(Value::Null))),
+                ("tags".to_string(), Value::Union(0, Box::new(Value::Null))),
+                (
+                    "point".to_string(),
+                    Value::Record(vec![
+                        ("x".to_string(), Value::Double(3.0)),
+                        ("y".to_string(), Value::Double(4.0)),
+                    ]),
+                ),
+                ("price".to_string(), decimal(-100)),
+                ("ts".to_string(), Value::TimestampMicros(2_000_000)),
+            ]),
+        ];
+        assert_eq!(values, expected);
+
+        // the schema of the written file can be inferred
+        let store = Arc::new(object_store::local::LocalFileSystem::new()) as _;
+        let meta = crate::test::object_store::local_unpartitioned_file(path);
+        let schema = AvroFormat {}
+            .infer_schema(&session_ctx.state(), &store, &[meta])
+            .await?;
+        assert_eq!(
+            schema.field_with_name("price")?.data_type(),
+            &DataType::Decimal128(10, 2)
+        );
+
+        Ok(())
+    }
+
     async fn get_exec(
         state: &SessionState,
         file_name: &str,
@@ -19,7 +19,8 @@
 
 use super::TableProvider;
 
-use datafusion_common::Result;
+use datafusion_common::{plan_err, Result};
+use datafusion_expr::planner::TableFunctionArg;
 use datafusion_expr::Expr;
 
 use std::sync::Arc;
@@ -28,6 +29,35 @@ use std::sync::Arc;
 pub trait TableFunctionImpl: Sync + Send {
     /// Create a table provider
     fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>>;
+
+    /// Create a table provider from arguments that may be relations, such as
+    /// `TABLE(t)` in `SELECT * FROM my_func(TABLE(t), 3)`
+    ///
+    /// Table arguments are passed as their [`LogicalPlan`]s. A function can build
+    /// its own plan on top of them and return it as a [`ViewTable`], for example.
+    ///
+    /// `name` is the name the function was registered under. The default
+    /// implementation calls [`Self::call`] if all arguments are expressions and
+    /// returns an error naming the function otherwise.
+    ///
+    /// [`LogicalPlan`]: datafusion_expr::LogicalPlan
+    /// [`ViewTable`]: crate::datasource::ViewTable
+    fn call_with_args(
+        &self,
+        name: &str,
+        args: &[TableFunctionArg],
+    ) -> Result<Arc<dyn TableProvider>> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                TableFunctionArg::Expr(expr) => Ok(expr.clone()),
+                TableFunctionArg::Table(_) => {
+                    plan_err!("Table function '{name}' does not accept table arguments")
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.call(&args)
+    }
 }
 
 /// A table that uses a function to generate data
@@ -58,4 +88,13 @@ impl TableFunction {
     pub fn create_table_provider(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
         self.fun.call(args)
     }
+
+    /// Get the function implementation and generate a table from arguments that
+    /// may be relations
+    pub fn create_table_provider_with_args(
+        &self,
+        args: &[TableFunctionArg],
+    ) -> Result<Arc<dyn TableProvider>> {
+        self.fun.call_with_args(&self.name, args)
+    }
 }
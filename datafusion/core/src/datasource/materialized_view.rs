@@ -0,0 +1,297 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Materialized view data source which stores the results of a LogicalPlan
+//! in another table.
+
+use std::{any::Any, collections::HashSet, sync::Arc};
+
+use crate::{
+    datasource::listing::ListingTable,
+    error::Result,
+    execution::context::SessionState,
+    logical_expr::{Expr, LogicalPlan},
+    physical_plan::{collect, ExecutionPlan},
+};
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+use datafusion_catalog::Session;
+use datafusion_common::{plan_err, Constraints, SchemaExt, Statistics};
+use datafusion_expr::TableProviderFilterPushDown;
+use futures::TryStreamExt;
+use object_store::path::Path;
+
+use crate::datasource::{TableProvider, TableType};
+
+/// An implementation of `TableProvider` that stores the results of a logical
+/// plan in another table.
+///
+/// The results are only computed by [`MaterializedView::refresh`]. Scans read
+/// the stored results, which may be stale.
+pub struct MaterializedView {
+    /// LogicalPlan of the view
+    logical_plan: LogicalPlan,
+    /// The table storing the results
+    table: Arc<dyn TableProvider>,
+    /// SQL used to create the view, if available
+    definition: Option<String>,
+}
+
+impl MaterializedView {
+    /// Create a new materialized view storing the results of `logical_plan`
+    /// in `table`, which must have the same schema as the plan.
+    ///
+    /// The view is empty until it is refreshed.
+    pub fn try_new(
+        logical_plan: LogicalPlan,
+        table: Arc<dyn TableProvider>,
+        definition: Option<String>,
+    ) -> Result<Self> {
+        if !table
+            .schema()
+            .logically_equivalent_names_and_types(logical_plan.schema().as_arrow())
+        {
+            return plan_err!(
+                "The query of a materialized view must have the same schema as the table storing its results"
+            );
+        }
+
+        Ok(Self {
+            logical_plan,
+            table,
+            definition,
+        })
+    }
+
+    /// Get definition ref
+    pub fn definition(&self) -> Option<&String> {
+        self.definition.as_ref()
+    }
+
+    /// Get logical_plan ref
+    pub fn logical_plan(&self) -> &LogicalPlan {
+        &self.logical_plan
+    }
+
+    /// Get the table storing the results
+    pub fn table(&self) -> &Arc<dyn TableProvider> {
+        &self.table
+    }
+
+    /// Compute the results of the view, replacing the stored ones.
+    ///
+    /// The new results of a [`ListingTable`] are written to new files, and
+    /// the previous files are only deleted once all the results were written.
+    /// If the results can't be computed, the files written so far are deleted
+    /// and the previous results are kept.
+    pub async fn refresh(&self, state: &SessionState) -> Result<()> {
+        let input = state.create_physical_plan(&self.logical_plan).await?;
+        let Some(table) = self.table.as_any().downcast_ref::<ListingTable>() else {
+            let plan = self.table.insert_into(state, input, true).await?;
+            collect(plan, state.task_ctx()).await?;
+            return Ok(());
+        };
+
+        // Listing tables can only be appended to
+        let previous_files = list_files(state, table).await?;
+        let result = match self.table.insert_into(state, input, false).await {
+            Ok(plan) => collect(plan, state.task_ctx()).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                delete_files(state, table, |file| previous_files.contains(file)).await
+            }
+            Err(e) => {
+                delete_files(state, table, |file| !previous_files.contains(file)).await?;
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Returns the locations of the files of `table`
+async fn list_files(state: &SessionState, table: &ListingTable) -> Result<HashSet<Path>> {
+    let file_extension = &table.options().file_extension;
+    let mut locations = HashSet::new();
+    for table_path in table.table_paths() {
+        let store = state.runtime_env().object_store(table_path)?;
+        let files = table_path
+            .list_all_files(state, store.as_ref(), file_extension)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        locations.extend(files.into_iter().map(|file| file.location));
+    }
+    Ok(locations)
+}
+
+/// Delete the files of `table` whose location matches `predicate`
+async fn delete_files(
+    state: &SessionState,
+    table: &ListingTable,
+    predicate: impl Fn(&Path) -> bool,
+) -> Result<()> {
+    let file_extension = &table.options().file_extension;
+    for table_path in table.table_paths() {
+        let store = state.runtime_env().object_store(table_path)?;
+        let files = table_path
+            .list_all_files(state, store.as_ref(), file_extension)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        for file in files.iter().filter(|file| predicate(&file.location)) {
+            store.delete(&file.location).await?;
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl TableProvider for MaterializedView {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table.schema()
+    }
+
+    fn constraints(&self) -> Option<&Constraints> {
+        self.table.constraints()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    fn get_table_definition(&self) -> Option<&str> {
+        self.definition.as_deref()
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        self.table.supports_filters_pushdown(filters)
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.table.scan(state, projection, filters, limit).await
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.table.statistics()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_batches_eq;
+    use crate::prelude::SessionContext;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn materialized_view_stores_results() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t AS VALUES (1, 10), (2, 20), (1, 30)")
+            .await?
+            .collect()
+            .await?;
+        ctx.sql(
+            "CREATE MATERIALIZED VIEW mv AS \
+             SELECT column1 AS a, sum(column2) AS b FROM t GROUP BY column1",
+        )
+        .await?
+        .collect()
+        .await?;
+
+        let view = ctx.table_provider("mv").await?;
+        assert_eq!(view.table_type(), TableType::View);
+        let view = view.as_any().downcast_ref::<MaterializedView>().unwrap();
+        assert_eq!(
+            view.definition().map(String::as_str),
+            Some(
+                "CREATE MATERIALIZED VIEW mv AS \
+                 SELECT column1 AS a, sum(column2) AS b FROM t GROUP BY column1"
+            )
+        );
+
+        // the stored results are read until the view is refreshed
+        ctx.sql("INSERT INTO t VALUES (3, 40)")
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 1 | 40 |",
+            "| 2 | 20 |",
+            "+---+----+",
+        ];
+        let results = ctx
+            .sql("SELECT * FROM mv ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(expected, &results);
+
+        ctx.sql("REFRESH MATERIALIZED VIEW mv")
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 1 | 40 |",
+            "| 2 | 20 |",
+            "| 3 | 40 |",
+            "+---+----+",
+        ];
+        let results = ctx
+            .sql("SELECT * FROM mv ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn materialized_view_schema_mismatch() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t (a INT)").await?.collect().await?;
+        let err = ctx
+            .sql("CREATE MATERIALIZED VIEW mv TO t AS SELECT 'a' AS a")
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: The query of a materialized view must have the same schema as the table storing its results"
+        );
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@
 //! [`MemTable`] for querying `Vec<RecordBatch>` by DataFusion.
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug};
 use std::sync::Arc;
 
@@ -26,19 +26,38 @@ use crate::datasource::{TableProvider, TableType};
 use crate::error::Result;
 use crate::execution::context::SessionState;
 use crate::logical_expr::Expr;
-use crate::physical_plan::insert::{DataSink, DataSinkExec};
+use crate::physical_plan::insert::{
+    make_count_batch, make_count_schema, DataSink, DataSinkExec,
+};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
 use crate::physical_plan::{
-    common, DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
-    Partitioning, SendableRecordBatchStream,
+    common, DisplayAs, DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
+    ExecutionPlanProperties, Partitioning, PhysicalExpr, PlanProperties,
+    SendableRecordBatchStream,
 };
 use crate::physical_planner::create_physical_sort_exprs;
 
-use arrow::datatypes::SchemaRef;
-use arrow::record_batch::RecordBatch;
-use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
+use arrow::array::{new_null_array, Array, BooleanArray, UInt32Array};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{
+    and, concat_batches, filter_record_batch, is_not_null, not, prep_null_mask_filter,
+    take,
+};
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use arrow::row::{RowConverter, SortField};
+use datafusion_common::cast::as_boolean_array;
+use datafusion_common::{
+    exec_err, internal_err, plan_err, Constraints, DFSchema, DFSchemaRef, SchemaExt,
+};
 use datafusion_execution::TaskContext;
+use datafusion_expr::utils::{conjunction, split_conjunction};
+use datafusion_expr::{
+    BinaryExpr, ExprSchemable, MergeAction, MergeClauseKind, MergeOp, Operator,
+};
+use datafusion_physical_expr::EquivalenceProperties;
 use datafusion_physical_plan::metrics::MetricsSet;
 
 use async_trait::async_trait;
@@ -253,6 +272,7 @@ impl TableProvider for MemTable {
     ///
     /// * `state` - The [`SessionState`] containing the context for executing the plan.
     /// * `input` - The [`ExecutionPlan`] to execute and insert.
+    /// * `overwrite` - Whether to replace the existing rows of the table.
     ///
     /// # Returns
     ///
@@ -263,9 +283,6 @@ impl TableProvider for MemTable {
         input: Arc<dyn ExecutionPlan>,
         overwrite: bool,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        // If we are inserting into the table, any sort order may be messed up so reset it here
-        *self.sort_order.lock() = vec![];
-
         // Create a physical plan from the logical plan.
         // Check that the schema of the plan matches the schema of this table.
         if !self
@@ -276,10 +293,11 @@ impl TableProvider for MemTable {
                 "Inserting query must have the same schema with the table."
             );
         }
-        if overwrite {
-            return not_impl_err!("Overwrite not implemented for MemoryTable yet");
-        }
-        let sink = Arc::new(MemSink::new(self.batches.clone()));
+        let sink = Arc::new(MemSink::new(
+            self.batches.clone(),
+            Arc::clone(&self.sort_order),
+            overwrite,
+        ));
         Ok(Arc::new(DataSinkExec::new(
             input,
             sink,
@@ -288,21 +306,969 @@ impl TableProvider for MemTable {
         )))
     }
 
+    /// Returns an ExecutionPlan that deletes the rows matching all `filters` from this [`MemTable`].
+    ///
+    /// The returned plan modifies the table in place when executed and returns the number of deleted rows.
+    async fn delete_from(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let filter = self.create_dml_filter(state, filters)?;
+        Ok(Arc::new(MemTableDmlExec::new(
+            Arc::clone(&self.schema),
+            self.batches.clone(),
+            Arc::clone(&self.sort_order),
+            MemTableDmlOp::Delete,
+            filter,
+        )))
+    }
+
+    /// Returns an ExecutionPlan that updates the rows matching all `filters` in this [`MemTable`].
+    ///
+    /// The returned plan modifies the table in place when executed and returns the number of updated rows.
+    async fn update(
+        &self,
+        state: &dyn Session,
+        assignments: Vec<(String, Expr)>,
+        filters: Vec<Expr>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        let assignments = assignments
+            .into_iter()
+            .map(|(name, value)| {
+                let index = self.schema.index_of(&name)?;
+                let value =
+                    value.cast_to(self.schema.field(index).data_type(), &df_schema)?;
+                Ok((index, state.create_physical_expr(value, &df_schema)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let filter = self.create_dml_filter(state, filters)?;
+        Ok(Arc::new(MemTableDmlExec::new(
+            Arc::clone(&self.schema),
+            self.batches.clone(),
+            Arc::clone(&self.sort_order),
+            MemTableDmlOp::Update(assignments),
+            filter,
+        )))
+    }
+
+    /// Returns an ExecutionPlan that merges the rows of `source` into this [`MemTable`].
+    ///
+    /// The returned plan modifies the table in place when executed and returns the number
+    /// of inserted, updated and deleted rows. Inserted rows are appended to the first
+    /// partition.
+    async fn merge_into(
+        &self,
+        state: &dyn Session,
+        source: Arc<dyn ExecutionPlan>,
+        schema: DFSchemaRef,
+        merge: MergeOp,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let keys = merge_keys(&merge.on, &schema, self.schema.fields().len())?
+            .into_iter()
+            .map(|(target_key, source_key)| {
+                Ok((
+                    state.create_physical_expr(target_key, &schema)?,
+                    state.create_physical_expr(source_key, &schema)?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        let on = state.create_physical_expr(merge.on, &schema)?;
+        let clauses = merge
+            .clauses
+            .into_iter()
+            .map(|clause| {
+                let predicate = clause
+                    .predicate
+                    .map(|predicate| state.create_physical_expr(predicate, &schema))
+                    .transpose()?;
+                let action = match (clause.kind, clause.action) {
+                    (MergeClauseKind::NotMatched, MergeAction::Insert(values)) => {
+                        if values.len() != self.schema.fields().len() {
+                            return plan_err!(
+                                "MERGE INSERT requires a value for each of the {} columns of the table",
+                                self.schema.fields().len()
+                            );
+                        }
+                        let values = values
+                            .into_iter()
+                            .zip(self.schema.fields())
+                            .map(|(value, field)| {
+                                let value =
+                                    value.cast_to(field.data_type(), schema.as_ref())?;
+                                state.create_physical_expr(value, &schema)
+                            })
+                            .collect::<Result<_>>()?;
+                        MemTableMergeAction::Insert(values)
+                    }
+                    (
+                        MergeClauseKind::Matched | MergeClauseKind::NotMatchedBySource,
+                        MergeAction::Update(assignments),
+                    ) => {
+                        let assignments = assignments
+                            .into_iter()
+                            .map(|(name, value)| {
+                                let index = self.schema.index_of(&name)?;
+                                let value = value.cast_to(
+                                    self.schema.field(index).data_type(),
+                                    schema.as_ref(),
+                                )?;
+                                Ok((index, state.create_physical_expr(value, &schema)?))
+                            })
+                            .collect::<Result<_>>()?;
+                        MemTableMergeAction::Update(assignments)
+                    }
+                    (
+                        MergeClauseKind::Matched | MergeClauseKind::NotMatchedBySource,
+                        MergeAction::Delete,
+                    ) => MemTableMergeAction::Delete,
+                    (kind, action) => {
+                        return plan_err!(
+                            "MERGE action {action} is not allowed in a WHEN {kind} clause"
+                        )
+                    }
+                };
+                Ok(MemTableMergeClause {
+                    kind: clause.kind,
+                    predicate,
+                    action,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Rows not matching any row of the other input have null values for its columns
+        let merge_schema = Arc::new(Schema::new(
+            schema
+                .fields()
+                .iter()
+                .map(|field| field.as_ref().clone().with_nullable(true))
+                .collect::<Vec<_>>(),
+        ));
+        let merge = MemTableMerge {
+            schema: Arc::clone(&self.schema),
+            merge_schema,
+            on,
+            keys,
+            clauses,
+        };
+        Ok(Arc::new(MemTableMergeExec::new(
+            self.batches.clone(),
+            Arc::clone(&self.sort_order),
+            source,
+            Arc::new(merge),
+        )))
+    }
+
     fn get_column_default(&self, column: &str) -> Option<&Expr> {
         self.column_defaults.get(column)
     }
 }
 
+/// Returns the pairs of expressions of the columns of the table and of the
+/// columns of the source that the `MERGE` condition `on` requires to be equal.
+/// The first `num_table_columns` columns of `schema` are the ones of the table
+fn merge_keys(
+    on: &Expr,
+    schema: &DFSchema,
+    num_table_columns: usize,
+) -> Result<Vec<(Expr, Expr)>> {
+    // Whether the columns of `expr` are all columns of the table or all
+    // columns of the source, if any
+    let is_table_expr = |expr: &Expr| -> Result<Option<bool>> {
+        let sides = expr
+            .column_refs()
+            .into_iter()
+            .map(|column| Ok(schema.index_of_column(column)? < num_table_columns))
+            .collect::<Result<HashSet<_>>>()?;
+        Ok((sides.len() == 1).then(|| sides.contains(&true)))
+    };
+
+    let mut keys = vec![];
+    for expr in split_conjunction(on) {
+        let Expr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) = expr
+        else {
+            continue;
+        };
+        if left.is_volatile()?
+            || right.is_volatile()?
+            || left.get_type(schema)? != right.get_type(schema)?
+        {
+            continue;
+        }
+        match (is_table_expr(left)?, is_table_expr(right)?) {
+            (Some(true), Some(false)) => {
+                keys.push((left.as_ref().clone(), right.as_ref().clone()))
+            }
+            (Some(false), Some(true)) => {
+                keys.push((right.as_ref().clone(), left.as_ref().clone()))
+            }
+            _ => {}
+        }
+    }
+    Ok(keys)
+}
+
+impl MemTable {
+    /// Creates the physical predicate selecting the rows to delete or update
+    fn create_dml_filter(
+        &self,
+        state: &dyn Session,
+        filters: Vec<Expr>,
+    ) -> Result<Option<Arc<dyn PhysicalExpr>>> {
+        let df_schema = DFSchema::try_from(self.schema.as_ref().clone())?;
+        conjunction(filters)
+            .map(|filter| state.create_physical_expr(filter, &df_schema))
+            .transpose()
+    }
+}
+
+/// The modification a [`MemTableDmlExec`] applies to the matching rows
+#[derive(Debug, Clone)]
+enum MemTableDmlOp {
+    /// Delete the rows
+    Delete,
+    /// Replace the values of the columns at the given indices
+    Update(Vec<(usize, Arc<dyn PhysicalExpr>)>),
+}
+
+/// Deletes or updates rows of a [`MemTable`] in place
+///
+/// Returns a single row with the number of affected rows
+#[derive(Debug)]
+struct MemTableDmlExec {
+    /// The schema of the table to modify
+    schema: SchemaRef,
+    /// The partitions of the table to modify
+    batches: Vec<PartitionData>,
+    /// The sort order of the table, reset once rows are updated
+    sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+    /// The modification to apply
+    op: MemTableDmlOp,
+    /// Predicate selecting the rows to modify, all rows if `None`
+    filter: Option<Arc<dyn PhysicalExpr>>,
+    cache: PlanProperties,
+}
+
+impl MemTableDmlExec {
+    fn new(
+        schema: SchemaRef,
+        batches: Vec<PartitionData>,
+        sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+        op: MemTableDmlOp,
+        filter: Option<Arc<dyn PhysicalExpr>>,
+    ) -> Self {
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(make_count_schema()),
+            Partitioning::UnknownPartitioning(1),
+            ExecutionMode::Bounded,
+        );
+        Self {
+            schema,
+            batches,
+            sort_order,
+            op,
+            filter,
+            cache,
+        }
+    }
+}
+
+impl DisplayAs for MemTableDmlExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "MemTableDmlExec: ")?;
+                match &self.op {
+                    MemTableDmlOp::Delete => write!(f, "op=Delete")?,
+                    MemTableDmlOp::Update(assignments) => {
+                        let assignments = assignments
+                            .iter()
+                            .map(|(index, value)| {
+                                format!("{}={value}", self.schema.field(*index).name())
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        write!(f, "op=Update, assignments=[{assignments}]")?
+                    }
+                }
+                if let Some(filter) = &self.filter {
+                    write!(f, ", filter={filter}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for MemTableDmlExec {
+    fn name(&self) -> &'static str {
+        "MemTableDmlExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!("MemTableDmlExec can only be called on partition 0!");
+        }
+
+        let batches = self.batches.clone();
+        let sort_order = Arc::clone(&self.sort_order);
+        let op = self.op.clone();
+        let filter = self.filter.clone();
+        let stream = futures::stream::once(async move {
+            // Lock all partitions up front, so that either all of them or
+            // none are modified
+            let mut partitions = Vec::with_capacity(batches.len());
+            for partition in batches.iter() {
+                partitions.push(partition.write().await);
+            }
+
+            let mut count = 0;
+            let mut new_partitions = Vec::with_capacity(partitions.len());
+            for partition in partitions.iter() {
+                let new_batches = partition
+                    .iter()
+                    .map(|batch| {
+                        let (batch, num_rows) = op.apply(batch, filter.as_ref())?;
+                        count += num_rows;
+                        Ok(batch)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                new_partitions.push(new_batches);
+            }
+
+            for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+                **partition = new_batches;
+            }
+            // Updated values may not follow the sort order anymore
+            if matches!(op, MemTableDmlOp::Update(_)) {
+                sort_order.lock().clear();
+            }
+
+            Ok(make_count_batch(count as u64))
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            make_count_schema(),
+            stream,
+        )))
+    }
+}
+
+impl MemTableDmlOp {
+    /// Applies the modification to the rows of `batch` matching `filter`,
+    /// returning the new batch and the number of affected rows
+    fn apply(
+        &self,
+        batch: &RecordBatch,
+        filter: Option<&Arc<dyn PhysicalExpr>>,
+    ) -> Result<(RecordBatch, usize)> {
+        let mask = match filter {
+            Some(filter) => evaluate_mask(filter, batch)?,
+            None => BooleanArray::from(vec![true; batch.num_rows()]),
+        };
+
+        let num_rows = mask.true_count();
+        if num_rows == 0 {
+            return Ok((batch.clone(), 0));
+        }
+
+        let batch = match self {
+            MemTableDmlOp::Delete => filter_record_batch(batch, &not(&mask)?)?,
+            MemTableDmlOp::Update(assignments) => {
+                let mut columns = batch.columns().to_vec();
+                for (index, value) in assignments {
+                    // only evaluated on the updated rows, which may be the
+                    // only ones the assignment is valid for
+                    let value = value
+                        .evaluate_selection(batch, &mask)?
+                        .into_array(batch.num_rows())?;
+                    columns[*index] = zip(&mask, &value, batch.column(*index))?;
+                }
+                RecordBatch::try_new(batch.schema(), columns)?
+            }
+        };
+        Ok((batch, num_rows))
+    }
+}
+
+/// Evaluates `predicate` against `batch`. Rows for which the predicate
+/// evaluates to null are not selected
+fn evaluate_mask(
+    predicate: &Arc<dyn PhysicalExpr>,
+    batch: &RecordBatch,
+) -> Result<BooleanArray> {
+    let mask = predicate.evaluate(batch)?.into_array(batch.num_rows())?;
+    Ok(prep_null_mask_filter(as_boolean_array(&mask)?))
+}
+
+/// The modification a [`MemTableMergeClause`] applies to its rows
+#[derive(Debug, Clone)]
+enum MemTableMergeAction {
+    /// Replace the values of the columns at the given indices
+    Update(Vec<(usize, Arc<dyn PhysicalExpr>)>),
+    /// Delete the rows
+    Delete,
+    /// Insert a row with the given value for each column
+    Insert(Vec<Arc<dyn PhysicalExpr>>),
+}
+
+/// A `WHEN` clause of a [`MemTableMergeExec`]
+#[derive(Debug, Clone)]
+struct MemTableMergeClause {
+    /// The rows the clause applies to
+    kind: MergeClauseKind,
+    /// Additional condition the rows must satisfy
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    /// The modification applied to the rows
+    action: MemTableMergeAction,
+}
+
+impl MemTableMergeClause {
+    /// Returns the rows of `batch` among `candidates` the clause applies to.
+    /// The predicate of the clause is only evaluated on the candidates
+    fn select(
+        &self,
+        batch: &RecordBatch,
+        candidates: &BooleanArray,
+    ) -> Result<BooleanArray> {
+        match &self.predicate {
+            Some(_) if candidates.true_count() == 0 => Ok(candidates.clone()),
+            Some(predicate) => {
+                let mask = predicate
+                    .evaluate_selection(batch, candidates)?
+                    .into_array(batch.num_rows())?;
+                let mask = prep_null_mask_filter(as_boolean_array(&mask)?);
+                Ok(and(candidates, &mask)?)
+            }
+            None => Ok(candidates.clone()),
+        }
+    }
+}
+
+/// The join condition and clauses of a `MERGE INTO` a [`MemTable`]
+#[derive(Debug)]
+struct MemTableMerge {
+    /// The schema of the table to modify
+    schema: SchemaRef,
+    /// The columns of the table followed by the columns of the source, all
+    /// nullable. The expressions are evaluated against rows of this schema
+    merge_schema: SchemaRef,
+    /// Condition matching rows of the table with source rows
+    on: Arc<dyn PhysicalExpr>,
+    /// Expressions of the table and of the source that `on` requires to be
+    /// equal. Rows of the table are only compared with the source rows with
+    /// the same values, or with all of them without keys
+    keys: Vec<(Arc<dyn PhysicalExpr>, Arc<dyn PhysicalExpr>)>,
+    /// The `WHEN` clauses, in order
+    clauses: Vec<MemTableMergeClause>,
+}
+
+impl MemTableMerge {
+    /// Returns the batch of `merge_schema` that pairs the rows of `target`
+    /// at `target_indices` with the rows of `source` at `source_indices`.
+    /// Null indices produce null values
+    fn join_rows(
+        &self,
+        target: &RecordBatch,
+        target_indices: &UInt32Array,
+        source: &RecordBatch,
+        source_indices: &UInt32Array,
+    ) -> Result<RecordBatch> {
+        let take_columns = |batch: &RecordBatch, indices: &UInt32Array| {
+            batch
+                .columns()
+                .iter()
+                .map(|column| {
+                    if indices.null_count() == indices.len() {
+                        Ok(new_null_array(column.data_type(), indices.len()))
+                    } else {
+                        take(column, indices, None)
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut columns = take_columns(target, target_indices)?;
+        columns.extend(take_columns(source, source_indices)?);
+        let options =
+            RecordBatchOptions::new().with_row_count(Some(target_indices.len()));
+        Ok(RecordBatch::try_new_with_options(
+            Arc::clone(&self.merge_schema),
+            columns,
+            &options,
+        )?)
+    }
+
+    /// Indexes the rows of `source` by the values of the source keys
+    fn index_source(&self, source: RecordBatch) -> Result<MemTableMergeSource> {
+        let mut rows_by_key: HashMap<Box<[u8]>, Vec<u32>> = HashMap::new();
+        if self.keys.is_empty() {
+            return Ok(MemTableMergeSource {
+                batch: source,
+                converter: None,
+                rows_by_key,
+            });
+        }
+
+        let num_rows = source.num_rows();
+        let target = RecordBatch::new_empty(Arc::clone(&self.schema));
+        let pairs = self.join_rows(
+            &target,
+            &UInt32Array::new_null(num_rows),
+            &source,
+            &UInt32Array::from_iter_values(0..num_rows as u32),
+        )?;
+        let keys = self
+            .keys
+            .iter()
+            .map(|(_, key)| key.evaluate(&pairs)?.into_array(num_rows))
+            .collect::<Result<Vec<_>>>()?;
+        let converter = RowConverter::new(
+            keys.iter()
+                .map(|key| SortField::new(key.data_type().clone()))
+                .collect(),
+        )?;
+        let rows = converter.convert_columns(&keys)?;
+        for (row, key_row) in rows.iter().enumerate() {
+            // Null keys are not equal to any key of the table
+            if keys.iter().any(|key| key.is_null(row)) {
+                continue;
+            }
+            rows_by_key
+                .entry(key_row.as_ref().into())
+                .or_default()
+                .push(row as u32);
+        }
+        Ok(MemTableMergeSource {
+            batch: source,
+            converter: Some(converter),
+            rows_by_key,
+        })
+    }
+
+    /// Returns the source row matching each row of `target`, if any, marking
+    /// the matched source rows in `source_matched`. The candidate pairs of
+    /// rows are compared in batches of about `batch_size` pairs
+    fn match_rows(
+        &self,
+        target: &RecordBatch,
+        source: &MemTableMergeSource,
+        source_matched: &mut [bool],
+        batch_size: usize,
+    ) -> Result<Vec<Option<u32>>> {
+        let num_rows = target.num_rows();
+        let target_keys = match &source.converter {
+            Some(converter) => {
+                let pairs = self.join_rows(
+                    target,
+                    &UInt32Array::from_iter_values(0..num_rows as u32),
+                    &source.batch,
+                    &UInt32Array::new_null(num_rows),
+                )?;
+                let keys = self
+                    .keys
+                    .iter()
+                    .map(|(key, _)| key.evaluate(&pairs)?.into_array(num_rows))
+                    .collect::<Result<Vec<_>>>()?;
+                Some((converter.convert_columns(&keys)?, keys))
+            }
+            None => None,
+        };
+        // Without keys, each row of the table is compared with all source rows
+        let all_source_rows = match target_keys {
+            Some(_) => vec![],
+            None => (0..source.batch.num_rows() as u32).collect(),
+        };
+
+        let mut matches = vec![None; num_rows];
+        let mut target_indices = vec![];
+        let mut source_indices = vec![];
+        for row in 0..num_rows {
+            let candidates = match &target_keys {
+                Some((_, keys)) if keys.iter().any(|key| key.is_null(row)) => &[][..],
+                Some((rows, _)) => source
+                    .rows_by_key
+                    .get(rows.row(row).as_ref())
+                    .map_or(&[][..], Vec::as_slice),
+                None => all_source_rows.as_slice(),
+            };
+            target_indices.extend(std::iter::repeat(row as u32).take(candidates.len()));
+            source_indices.extend_from_slice(candidates);
+            if target_indices.len() >= batch_size {
+                self.match_pairs(
+                    target,
+                    &source.batch,
+                    std::mem::take(&mut target_indices),
+                    std::mem::take(&mut source_indices),
+                    &mut matches,
+                    source_matched,
+                )?;
+            }
+        }
+        if !target_indices.is_empty() {
+            self.match_pairs(
+                target,
+                &source.batch,
+                target_indices,
+                source_indices,
+                &mut matches,
+                source_matched,
+            )?;
+        }
+        Ok(matches)
+    }
+
+    /// Evaluates `on` against the pairs of rows of `target` and `source` at
+    /// the given indices, recording the matching source row of each target
+    /// row in `matches`
+    fn match_pairs(
+        &self,
+        target: &RecordBatch,
+        source: &RecordBatch,
+        target_indices: Vec<u32>,
+        source_indices: Vec<u32>,
+        matches: &mut [Option<u32>],
+        source_matched: &mut [bool],
+    ) -> Result<()> {
+        let target_indices = UInt32Array::from(target_indices);
+        let source_indices = UInt32Array::from(source_indices);
+        let pairs = self.join_rows(target, &target_indices, source, &source_indices)?;
+        let mask = evaluate_mask(&self.on, &pairs)?;
+        for pair in mask.values().set_indices() {
+            let source_row = source_indices.value(pair);
+            if matches[target_indices.value(pair) as usize]
+                .replace(source_row)
+                .is_some()
+            {
+                return exec_err!(
+                    "MERGE INTO matched a row of the target table with more than one source row"
+                );
+            }
+            source_matched[source_row as usize] = true;
+        }
+        Ok(())
+    }
+
+    /// Applies the `WHEN MATCHED` and `WHEN NOT MATCHED BY SOURCE` clauses to
+    /// the rows of `target`, marking the source rows matching one of them
+    /// in `source_matched` and returning the new batch and the number of
+    /// affected rows
+    fn merge_batch(
+        &self,
+        target: &RecordBatch,
+        source: &MemTableMergeSource,
+        source_matched: &mut [bool],
+        batch_size: usize,
+    ) -> Result<(RecordBatch, usize)> {
+        let num_rows = target.num_rows();
+        let matches = self.match_rows(target, source, source_matched, batch_size)?;
+        let source = &source.batch;
+
+        let source_indices = UInt32Array::from(matches);
+        let target_indices = UInt32Array::from_iter_values(0..num_rows as u32);
+        let pairs = self.join_rows(target, &target_indices, source, &source_indices)?;
+        let is_matched = is_not_null(&source_indices)?;
+        let is_not_matched = not(&is_matched)?;
+
+        let mut count = 0;
+        let mut remaining = BooleanArray::from(vec![true; num_rows]);
+        let mut keep = BooleanArray::from(vec![true; num_rows]);
+        let mut columns = target.columns().to_vec();
+        for clause in &self.clauses {
+            let candidates = match clause.kind {
+                MergeClauseKind::Matched => &is_matched,
+                MergeClauseKind::NotMatchedBySource => &is_not_matched,
+                MergeClauseKind::NotMatched => continue,
+            };
+            let selected = clause.select(&pairs, &and(&remaining, candidates)?)?;
+            if selected.true_count() == 0 {
+                continue;
+            }
+            count += selected.true_count();
+            remaining = and(&remaining, &not(&selected)?)?;
+            match &clause.action {
+                MemTableMergeAction::Update(assignments) => {
+                    // only evaluated on the pairs the clause applies to, which
+                    // may be the only ones the assignment is valid for
+                    for (index, value) in assignments {
+                        let value = value
+                            .evaluate_selection(&pairs, &selected)?
+                            .into_array(num_rows)?;
+                        columns[*index] = zip(&selected, &value, &columns[*index])?;
+                    }
+                }
+                MemTableMergeAction::Delete => keep = and(&keep, &not(&selected)?)?,
+                MemTableMergeAction::Insert(_) => {
+                    return internal_err!("Unexpected MERGE INSERT for target rows")
+                }
+            }
+        }
+
+        let batch = RecordBatch::try_new(target.schema(), columns)?;
+        Ok((filter_record_batch(&batch, &keep)?, count))
+    }
+
+    /// Applies the `WHEN NOT MATCHED` clauses to the rows of `source` not
+    /// marked in `source_matched`, returning the inserted rows, if any, and
+    /// their number
+    fn insert_unmatched(
+        &self,
+        source: &RecordBatch,
+        source_matched: &[bool],
+    ) -> Result<(Option<RecordBatch>, usize)> {
+        let source_indices = UInt32Array::from_iter_values(
+            (0..source.num_rows() as u32).filter(|&row| !source_matched[row as usize]),
+        );
+        let num_rows = source_indices.len();
+        let target_indices = UInt32Array::new_null(num_rows);
+        let target = RecordBatch::new_empty(Arc::clone(&self.schema));
+        let pairs = self.join_rows(&target, &target_indices, source, &source_indices)?;
+
+        let mut count = 0;
+        let mut remaining = BooleanArray::from(vec![true; num_rows]);
+        let mut batches = vec![];
+        for clause in &self.clauses {
+            let MemTableMergeAction::Insert(values) = &clause.action else {
+                continue;
+            };
+            let selected = clause.select(&pairs, &remaining)?;
+            if selected.true_count() == 0 {
+                continue;
+            }
+            count += selected.true_count();
+            remaining = and(&remaining, &not(&selected)?)?;
+            // only the selected source rows are inserted
+            let selected_pairs = filter_record_batch(&pairs, &selected)?;
+            let columns = values
+                .iter()
+                .map(|value| {
+                    value
+                        .evaluate(&selected_pairs)?
+                        .into_array(selected_pairs.num_rows())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            batches.push(RecordBatch::try_new(Arc::clone(&self.schema), columns)?);
+        }
+
+        if batches.is_empty() {
+            return Ok((None, 0));
+        }
+        Ok((Some(concat_batches(&self.schema, &batches)?), count))
+    }
+}
+
+/// The source rows of a [`MemTableMergeExec`], indexed by the values of the
+/// source keys of the merge
+struct MemTableMergeSource {
+    /// The source rows
+    batch: RecordBatch,
+    /// Converts the values of the keys to rows, `None` without keys
+    converter: Option<RowConverter>,
+    /// The source rows by the row of their keys. Rows with null keys don't
+    /// match any row of the table and are not indexed
+    rows_by_key: HashMap<Box<[u8]>, Vec<u32>>,
+}
+
+/// Merges the rows of its input into a [`MemTable`] in place
+///
+/// Returns a single row with the number of inserted, updated and deleted rows
+#[derive(Debug)]
+struct MemTableMergeExec {
+    /// The partitions of the table to modify
+    batches: Vec<PartitionData>,
+    /// The sort order of the table, reset once rows are merged
+    sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+    /// The source rows to merge
+    source: Arc<dyn ExecutionPlan>,
+    /// The join condition and clauses of the merge
+    merge: Arc<MemTableMerge>,
+    cache: PlanProperties,
+}
+
+impl MemTableMergeExec {
+    fn new(
+        batches: Vec<PartitionData>,
+        sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+        source: Arc<dyn ExecutionPlan>,
+        merge: Arc<MemTableMerge>,
+    ) -> Self {
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(make_count_schema()),
+            Partitioning::UnknownPartitioning(1),
+            ExecutionMode::Bounded,
+        );
+        Self {
+            batches,
+            sort_order,
+            source,
+            merge,
+            cache,
+        }
+    }
+}
+
+impl DisplayAs for MemTableMergeExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "MemTableMergeExec: on={}", self.merge.on)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for MemTableMergeExec {
+    fn name(&self) -> &'static str {
+        "MemTableMergeExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.source]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::new(
+            self.batches.clone(),
+            Arc::clone(&self.sort_order),
+            Arc::clone(&children[0]),
+            Arc::clone(&self.merge),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return internal_err!("MemTableMergeExec can only be called on partition 0!");
+        }
+
+        let batch_size = context.session_config().batch_size();
+        let source = self.source.execute(0, context)?;
+        let batches = self.batches.clone();
+        let sort_order = Arc::clone(&self.sort_order);
+        let merge = Arc::clone(&self.merge);
+        let stream = futures::stream::once(async move {
+            let source_schema = source.schema();
+            let source_batches = common::collect(source).await?;
+            let source =
+                merge.index_source(concat_batches(&source_schema, &source_batches)?)?;
+
+            // Lock all partitions up front, so that either all of them or
+            // none are modified
+            let mut partitions = Vec::with_capacity(batches.len());
+            for partition in batches.iter() {
+                partitions.push(partition.write().await);
+            }
+
+            let mut count = 0;
+            let mut source_matched = vec![false; source.batch.num_rows()];
+            let mut new_partitions = Vec::with_capacity(partitions.len());
+            for partition in partitions.iter() {
+                let new_batches = partition
+                    .iter()
+                    .map(|batch| {
+                        let (batch, num_rows) = merge.merge_batch(
+                            batch,
+                            &source,
+                            &mut source_matched,
+                            batch_size,
+                        )?;
+                        count += num_rows;
+                        Ok(batch)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                new_partitions.push(new_batches);
+            }
+
+            let (inserted, num_rows) =
+                merge.insert_unmatched(&source.batch, &source_matched)?;
+            count += num_rows;
+            if let Some(inserted) = inserted {
+                match new_partitions.first_mut() {
+                    Some(new_batches) => new_batches.push(inserted),
+                    None => {
+                        return exec_err!(
+                            "Cannot insert into a MemTable without partitions"
+                        )
+                    }
+                }
+            }
+
+            for (partition, new_batches) in partitions.iter_mut().zip(new_partitions) {
+                **partition = new_batches;
+            }
+            // Updated and inserted values may not follow the sort order anymore
+            sort_order.lock().clear();
+
+            Ok(make_count_batch(count as u64))
+        });
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            make_count_schema(),
+            stream,
+        )))
+    }
+}
+
 /// Implements for writing to a [`MemTable`]
 struct MemSink {
     /// Target locations for writing data
     batches: Vec<PartitionData>,
+    /// The sort order of the table, reset once data is written
+    sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+    /// Whether to replace the existing data instead of appending to it
+    overwrite: bool,
 }
 
 impl Debug for MemSink {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MemSink")
             .field("num_partitions", &self.batches.len())
+            .field("overwrite", &self.overwrite)
             .finish()
     }
 }
@@ -319,8 +1285,16 @@ impl DisplayAs for MemSink {
 }
 
 impl MemSink {
-    fn new(batches: Vec<PartitionData>) -> Self {
-        Self { batches }
+    fn new(
+        batches: Vec<PartitionData>,
+        sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+        overwrite: bool,
+    ) -> Self {
+        Self {
+            batches,
+            sort_order,
+            overwrite,
+        }
     }
 }
 
@@ -355,8 +1329,14 @@ impl DataSink for MemSink {
         // write the outputs into the batches
         for (target, mut batches) in self.batches.iter().zip(new_batches.into_iter()) {
             // Append all the new batches in one go to minimize locking overhead
-            target.write().await.append(&mut batches);
+            let mut target = target.write().await;
+            if self.overwrite {
+                target.clear();
+            }
+            target.append(&mut batches);
         }
+        // The written data may not follow the sort order of the table
+        self.sort_order.lock().clear();
 
         Ok(row_count as u64)
     }
@@ -371,10 +1351,10 @@ mod tests {
     use crate::prelude::SessionContext;
 
     use arrow::array::{AsArray, Int32Array};
-    use arrow::datatypes::{DataType, Field, Schema, UInt64Type};
+    use arrow::datatypes::{DataType, Field, UInt64Type};
     use arrow::error::ArrowError;
-    use datafusion_common::DataFusionError;
-    use datafusion_expr::LogicalPlanBuilder;
+    use datafusion_common::{assert_contains, DataFusionError};
+    use datafusion_expr::{col, LogicalPlanBuilder};
 
     #[tokio::test]
     async fn test_with_projection() -> Result<()> {
@@ -764,4 +1744,314 @@ mod tests {
         assert_eq!(resulting_data_in_table[0].len(), 2);
         Ok(())
     }
+
+    /// Registers a [`MemTable`] with columns `a` and `b` spread over two
+    /// partitions and runs `sql` against it
+    async fn dml_experiment(sql: &str) -> Result<(u64, Vec<RecordBatch>)> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batch1 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Int32Array::from(vec![Some(10), None, Some(30)])),
+            ],
+        )?;
+        let batch2 = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![4, 5])),
+                Arc::new(Int32Array::from(vec![Some(40), Some(50)])),
+            ],
+        )?;
+
+        let session_ctx = SessionContext::new();
+        let table = MemTable::try_new(schema, vec![vec![batch1], vec![batch2]])?;
+        session_ctx.register_table("t", Arc::new(table))?;
+
+        let count = extract_count(session_ctx.sql(sql).await?.collect().await?);
+        let data = session_ctx
+            .sql("SELECT * FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        Ok((count, data))
+    }
+
+    #[tokio::test]
+    async fn test_insert_overwrite() -> Result<()> {
+        let (count, data) =
+            dml_experiment("INSERT OVERWRITE t VALUES (6, 60), (7, NULL)").await?;
+        assert_eq!(count, 2);
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 6 | 60 |",
+            "| 7 |    |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &data);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_from() -> Result<()> {
+        let (count, data) = dml_experiment("DELETE FROM t WHERE b > 20").await?;
+        assert_eq!(count, 3);
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 1 | 10 |",
+            "| 2 |    |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        let (count, data) = dml_experiment("DELETE FROM t").await?;
+        assert_eq!(count, 5);
+        assert!(data.iter().all(|batch| batch.num_rows() == 0));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_update() -> Result<()> {
+        let (count, data) =
+            dml_experiment("UPDATE t SET b = a * 100 WHERE a % 2 = 1 OR b IS NULL")
+                .await?;
+        assert_eq!(count, 4);
+        let expected = [
+            "+---+-----+",
+            "| a | b   |",
+            "+---+-----+",
+            "| 1 | 100 |",
+            "| 2 | 200 |",
+            "| 3 | 300 |",
+            "| 4 | 40  |",
+            "| 5 | 500 |",
+            "+---+-----+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        // the assignment is only evaluated on the updated rows
+        let (count, data) =
+            dml_experiment("UPDATE t SET b = 10 / (a - 3) WHERE a <> 3").await?;
+        assert_eq!(count, 4);
+        let expected = [
+            "+---+-----+",
+            "| a | b   |",
+            "+---+-----+",
+            "| 1 | -5  |",
+            "| 2 | -10 |",
+            "| 3 | 30  |",
+            "| 4 | 10  |",
+            "| 5 | 5   |",
+            "+---+-----+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        // rows for which the predicate is null are not updated
+        let (count, data) =
+            dml_experiment("UPDATE t SET a = a + 10 WHERE b < 35").await?;
+        assert_eq!(count, 2);
+        let expected = [
+            "+----+----+",
+            "| a  | b  |",
+            "+----+----+",
+            "| 2  |    |",
+            "| 4  | 40 |",
+            "| 5  | 50 |",
+            "| 11 | 10 |",
+            "| 13 | 30 |",
+            "+----+----+",
+        ];
+        assert_batches_eq!(expected, &data);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dml_resets_sort_order() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )?;
+        let table = Arc::new(
+            MemTable::try_new(schema, vec![vec![batch]])?
+                .with_sort_order(vec![vec![col("a").sort(true, false)]]),
+        );
+        let session_ctx = SessionContext::new();
+        session_ctx.register_table("t", Arc::clone(&table) as _)?;
+
+        // the sort order is kept until the rows are updated
+        let plan = session_ctx
+            .sql("UPDATE t SET a = 4 - a")
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert!(!table.sort_order.lock().is_empty());
+        collect(plan, session_ctx.task_ctx()).await?;
+        assert!(table.sort_order.lock().is_empty());
+
+        // and until the rows are merged
+        *table.sort_order.lock() = vec![vec![col("a").sort(true, false)]];
+        let plan = session_ctx
+            .sql(
+                "MERGE INTO t USING (VALUES (1)) AS s(a) ON t.a = s.a \
+                 WHEN MATCHED THEN DELETE",
+            )
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert!(!table.sort_order.lock().is_empty());
+        collect(plan, session_ctx.task_ctx()).await?;
+        assert!(table.sort_order.lock().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merge_into() -> Result<()> {
+        let (count, data) = dml_experiment(
+            "MERGE INTO t USING (VALUES (1, 100), (2, 200), (6, 600)) AS s(a, b) \
+             ON t.a = s.a \
+             WHEN MATCHED AND t.b IS NULL THEN DELETE \
+             WHEN MATCHED THEN UPDATE SET b = s.b \
+             WHEN NOT MATCHED THEN INSERT (a, b) VALUES (s.a, s.b)",
+        )
+        .await?;
+        assert_eq!(count, 3);
+        let expected = [
+            "+---+-----+",
+            "| a | b   |",
+            "+---+-----+",
+            "| 1 | 100 |",
+            "| 3 | 30  |",
+            "| 4 | 40  |",
+            "| 5 | 50  |",
+            "| 6 | 600 |",
+            "+---+-----+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        // rows of the target without a source row, and a not matched insert
+        // with a predicate
+        let (count, data) = dml_experiment(
+            "MERGE INTO t AS target USING (VALUES (2), (7), (8)) AS s(a) \
+             ON target.a = s.a \
+             WHEN NOT MATCHED BY SOURCE AND target.a > 3 THEN DELETE \
+             WHEN NOT MATCHED BY SOURCE THEN UPDATE SET b = 0 \
+             WHEN NOT MATCHED AND s.a > 7 THEN INSERT (a) VALUES (s.a)",
+        )
+        .await?;
+        assert_eq!(count, 5);
+        let expected = [
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | 0 |",
+            "| 2 |   |",
+            "| 3 | 0 |",
+            "| 8 |   |",
+            "+---+---+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        // source rows with the same key as a target row but not matching the
+        // rest of the condition, and source rows with a null key
+        let (count, data) = dml_experiment(
+            "MERGE INTO t USING (VALUES (1, 5), (3, 50), (NULL, 1)) AS s(a, c) \
+             ON t.a = s.a AND t.b > s.c \
+             WHEN MATCHED THEN UPDATE SET b = s.c \
+             WHEN NOT MATCHED THEN INSERT (a, b) VALUES (s.c + 100, s.c)",
+        )
+        .await?;
+        assert_eq!(count, 3);
+        let expected = [
+            "+-----+----+",
+            "| a   | b  |",
+            "+-----+----+",
+            "| 1   | 5  |",
+            "| 2   |    |",
+            "| 3   | 30 |",
+            "| 4   | 40 |",
+            "| 5   | 50 |",
+            "| 101 | 1  |",
+            "| 150 | 50 |",
+            "+-----+----+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        // the conditions and values of a clause are only evaluated on the rows
+        // it applies to, dividing by zero for the other ones
+        let (count, data) = dml_experiment(
+            "MERGE INTO t USING (VALUES (1, 0), (3, 3), (6, 0), (7, 2)) AS s(a, c) \
+             ON t.a = s.a \
+             WHEN MATCHED AND s.c = 0 THEN DELETE \
+             WHEN MATCHED AND t.b / s.c > 5 THEN UPDATE SET b = t.b / s.c \
+             WHEN NOT MATCHED AND s.c <> 0 THEN INSERT (a, b) VALUES (s.a, 60 / s.c)",
+        )
+        .await?;
+        assert_eq!(count, 3);
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 2 |    |",
+            "| 3 | 10 |",
+            "| 4 | 40 |",
+            "| 5 | 50 |",
+            "| 7 | 30 |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &data);
+
+        // a condition without equality compares all the pairs of rows
+        let (count, data) = dml_experiment(
+            "MERGE INTO t USING (VALUES (4)) AS s(a) ON t.a > s.a \
+             WHEN MATCHED THEN DELETE",
+        )
+        .await?;
+        assert_eq!(count, 1);
+        let expected = [
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 1 | 10 |",
+            "| 2 |    |",
+            "| 3 | 30 |",
+            "| 4 | 40 |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &data);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_merge_into_errors() -> Result<()> {
+        let err = dml_experiment(
+            "MERGE INTO t USING (VALUES (1), (1)) AS s(a) ON t.a = s.a \
+             WHEN MATCHED THEN DELETE",
+        )
+        .await
+        .unwrap_err();
+        assert_contains!(
+            err.strip_backtrace(),
+            "MERGE INTO matched a row of the target table with more than one source row"
+        );
+
+        let err = dml_experiment(
+            "MERGE INTO t USING (VALUES (1)) AS s(a) ON t.a = s.a \
+             WHEN NOT MATCHED BY SOURCE THEN INSERT (a) VALUES (s.a)",
+        )
+        .await
+        .unwrap_err();
+        assert_contains!(
+            err.strip_backtrace(),
+            "is not allowed in a WHEN NOT MATCHED BY SOURCE clause"
+        );
+        Ok(())
+    }
 }
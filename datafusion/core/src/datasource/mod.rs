@@ -19,7 +19,9 @@
 //!
 //! [`ListingTable`]: crate::datasource::listing::ListingTable
 
+pub mod arrow_to_avro;
 pub mod avro_to_arrow;
+pub mod cached_table;
 pub mod cte_worktable;
 pub mod default_table_source;
 pub mod empty;
@@ -27,6 +29,7 @@ pub mod file_format;
 pub mod function;
 pub mod listing;
 pub mod listing_table_factory;
+pub mod materialized_view;
 pub mod memory;
 pub mod physical_plan;
 pub mod provider;
@@ -39,9 +42,11 @@ pub mod view;
 // backwards compatibility
 pub use datafusion_execution::object_store;
 
+pub use self::cached_table::CachedTable;
 pub use self::default_table_source::{
     provider_as_source, source_as_provider, DefaultTableSource,
 };
+pub use self::materialized_view::MaterializedView;
 pub use self::memory::MemTable;
 pub use self::view::ViewTable;
 pub use crate::catalog::TableProvider;
@@ -38,6 +38,8 @@ use datafusion_common::{exec_err, ColumnStatistics, DataFusionError, Statistics}
 use datafusion_physical_expr::{LexOrdering, PhysicalSortExpr};
 
 use log::warn;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 /// Convert type to a type suitable for use as a [`ListingTable`]
 /// partition column. Returns `Dictionary(UInt16, val_type)`, which is
@@ -128,6 +130,49 @@ pub struct FileScanConfig {
     pub table_partition_cols: Vec<Field>,
     /// All equivalent lexicographical orderings that describe the schema.
     pub output_ordering: Vec<LexOrdering>,
+    /// Only read a random sample of the blocks of the files, such as the
+    /// row groups of Parquet files. Only honored by [`ParquetExec`].
+    ///
+    /// [`ParquetExec`]: super::ParquetExec
+    pub sample: Option<FileSample>,
+}
+
+/// Block level sampling of files, used to implement `TABLESAMPLE SYSTEM`.
+///
+/// Each block of a file, such as a Parquet row group, is read with
+/// probability `fraction`. With a `seed`, the same blocks of a file are
+/// always chosen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileSample {
+    /// The fraction of the blocks to read, between 0 and 1
+    pub fraction: f64,
+    /// Seed of the random number generator
+    pub seed: Option<u64>,
+}
+
+impl FileSample {
+    /// Create a new `FileSample`
+    pub fn new(fraction: f64, seed: Option<u64>) -> Self {
+        Self { fraction, seed }
+    }
+
+    /// Returns for each of the `num_blocks` blocks of the file at `location`
+    /// whether it is part of the sample
+    pub fn sample_blocks(&self, location: &str, num_blocks: usize) -> Vec<bool> {
+        let mut rng = match self.seed {
+            Some(seed) => {
+                // FNV-1a, so that the sample is stable across runs
+                let hash = location.bytes().fold(0xcbf29ce484222325_u64, |h, b| {
+                    (h ^ b as u64).wrapping_mul(0x100000001b3)
+                });
+                StdRng::seed_from_u64(seed ^ hash)
+            }
+            None => StdRng::from_entropy(),
+        };
+        (0..num_blocks)
+            .map(|_| rng.gen_bool(self.fraction))
+            .collect()
+    }
 }
 
 impl FileScanConfig {
@@ -152,6 +197,7 @@ impl FileScanConfig {
             limit: None,
             table_partition_cols: vec![],
             output_ordering: vec![],
+            sample: None,
         }
     }
 
@@ -211,9 +257,18 @@ impl FileScanConfig {
         self
     }
 
+    /// Set the block level sample of the files, see [`Self::sample`]
+    pub fn with_sample(mut self, sample: Option<FileSample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
     /// Project the schema and the statistics on the given column indices
     pub fn project(&self) -> (SchemaRef, Statistics, Vec<LexOrdering>) {
-        if self.projection.is_none() && self.table_partition_cols.is_empty() {
+        if self.projection.is_none()
+            && self.table_partition_cols.is_empty()
+            && self.sample.is_none()
+        {
             return (
                 Arc::clone(&self.file_schema),
                 self.statistics.clone(),
@@ -243,12 +298,18 @@ impl FileScanConfig {
             }
         }
 
-        let table_stats = Statistics {
+        let mut table_stats = Statistics {
             num_rows: self.statistics.num_rows,
             // TODO correct byte size?
             total_byte_size: Precision::Absent,
             column_statistics: table_cols_stats,
         };
+        if let Some(sample) = &self.sample {
+            table_stats.num_rows = table_stats
+                .num_rows
+                .map(|n| (n as f64 * sample.fraction).ceil() as usize);
+            table_stats = table_stats.to_inexact();
+        }
 
         let projected_schema = Arc::new(
             Schema::new(table_fields).with_metadata(self.file_schema.metadata().clone()),
@@ -949,6 +1010,36 @@ mod tests {
         assert_eq!(projection.fields(), schema.fields());
     }
 
+    #[test]
+    fn physical_plan_config_with_sample() {
+        let file_schema = aggr_test_schema();
+        let conf = config_for_projection(
+            Arc::clone(&file_schema),
+            None,
+            Statistics {
+                num_rows: Precision::Exact(1000),
+                total_byte_size: Precision::Absent,
+                column_statistics: Statistics::unknown_column(&file_schema),
+            },
+            vec![],
+        )
+        .with_sample(Some(FileSample::new(0.1, Some(42))));
+
+        let (_, stats, _) = conf.project();
+        assert_eq!(stats.num_rows, Precision::Inexact(100));
+
+        let sample = conf.sample.unwrap();
+        let blocks = sample.sample_blocks("file.parquet", 1000);
+        let sampled = blocks.iter().filter(|b| **b).count();
+        assert!(sampled > 50 && sampled < 150, "{sampled} blocks");
+        // the sample is the same for the same file and seed
+        assert_eq!(sample.sample_blocks("file.parquet", 1000), blocks);
+        assert_ne!(sample.sample_blocks("other.parquet", 1000), blocks);
+
+        let all = FileSample::new(1.0, None).sample_blocks("file.parquet", 10);
+        assert_eq!(all, vec![true; 10]);
+    }
+
     #[test]
     fn test_split_groups_by_statistics() -> Result<()> {
         use chrono::TimeZone;
@@ -38,7 +38,7 @@ pub use avro::AvroExec;
 pub use csv::{CsvConfig, CsvExec, CsvExecBuilder, CsvOpener};
 pub use file_groups::FileGroupPartitioner;
 pub use file_scan_config::{
-    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileScanConfig,
+    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileSample, FileScanConfig,
 };
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream, OnError};
 pub use json::{JsonOpener, NdJsonExec};
@@ -121,6 +121,13 @@ impl DisplayAs for FileScanConfig {
             write!(f, ", limit={limit}")?;
         }
 
+        if let Some(sample) = &self.sample {
+            write!(f, ", sample={}", sample.fraction)?;
+            if let Some(seed) = sample.seed {
+                write!(f, " seed={seed}")?;
+            }
+        }
+
         display_orderings(f, &orderings)?;
 
         Ok(())
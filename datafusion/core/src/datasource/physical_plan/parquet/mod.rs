@@ -25,7 +25,7 @@ use crate::datasource::listing::PartitionedFile;
 use crate::datasource::physical_plan::file_stream::FileStream;
 use crate::datasource::physical_plan::{
     parquet::page_filter::PagePruningAccessPlanFilter, DisplayAs, FileGroupPartitioner,
-    FileScanConfig,
+    FileSample, FileScanConfig,
 };
 use crate::{
     config::{ConfigOptions, TableParquetOptions},
@@ -40,7 +40,9 @@ use crate::{
 };
 
 use arrow::datatypes::SchemaRef;
+use datafusion_physical_expr::expressions::Column;
 use datafusion_physical_expr::{EquivalenceProperties, LexOrdering, PhysicalExpr};
+use datafusion_physical_plan::joins::DynamicFilter;
 
 use itertools::Itertools;
 use log::debug;
@@ -244,6 +246,9 @@ pub struct ParquetExec {
     pruning_predicate: Option<Arc<PruningPredicate>>,
     /// Optional predicate for pruning pages (derived from `predicate`)
     page_pruning_predicate: Option<Arc<PagePruningAccessPlanFilter>>,
+    /// Runtime filters published by joins on columns of the file schema, used
+    /// for pruning in addition to `predicate`
+    dynamic_filters: Vec<(Column, Arc<DynamicFilter>)>,
     /// Optional hint for the size of the parquet metadata
     metadata_size_hint: Option<usize>,
     /// Optional user defined parquet file reader factory
@@ -417,6 +422,7 @@ impl ParquetExecBuilder {
             predicate,
             pruning_predicate,
             page_pruning_predicate,
+            dynamic_filters: vec![],
             metadata_size_hint,
             parquet_file_reader_factory,
             cache,
@@ -477,6 +483,36 @@ impl ParquetExec {
         self.pruning_predicate.as_ref()
     }
 
+    /// Prune row groups and pages by runtime filters, such as those published
+    /// by a [`HashJoinExec`] once its build side is collected, given as
+    /// `(column of the file schema, filter)`.
+    ///
+    /// Filters not yet published when a file is opened are ignored for that
+    /// file.
+    ///
+    /// [`HashJoinExec`]: datafusion_physical_plan::joins::HashJoinExec
+    pub fn with_dynamic_filters(
+        mut self,
+        dynamic_filters: Vec<(Column, Arc<DynamicFilter>)>,
+    ) -> Self {
+        self.dynamic_filters = dynamic_filters;
+        self
+    }
+
+    /// Runtime filters used for pruning, see [`Self::with_dynamic_filters`]
+    pub fn dynamic_filters(&self) -> &[(Column, Arc<DynamicFilter>)] {
+        &self.dynamic_filters
+    }
+
+    /// Only read a random sample of the row groups of each file, see
+    /// [`FileScanConfig::sample`]
+    pub fn with_sample(mut self, sample: Option<FileSample>) -> Self {
+        self.base_config.sample = sample;
+        let (_, projected_statistics, _) = self.base_config.project();
+        self.projected_statistics = projected_statistics;
+        self
+    }
+
     /// Optional user defined parquet file reader factory.
     ///
     /// See documentation on [`ParquetExecBuilder::with_parquet_file_reader_factory`]
@@ -623,9 +659,25 @@ impl DisplayAs for ParquetExec {
                     })
                     .unwrap_or_default();
 
+                let dynamic_filters_string = if self.dynamic_filters.is_empty() {
+                    "".to_string()
+                } else {
+                    format!(
+                        ", dynamic_filters=[{}]",
+                        self.dynamic_filters
+                            .iter()
+                            .map(|(column, _)| column.to_string())
+                            .join(", ")
+                    )
+                };
+
                 write!(f, "ParquetExec: ")?;
                 self.base_config.fmt_as(t, f)?;
-                write!(f, "{}{}", predicate_string, pruning_predicate_string,)
+                write!(
+                    f,
+                    "{}{}{}",
+                    predicate_string, pruning_predicate_string, dynamic_filters_string
+                )
             }
         }
     }
@@ -716,6 +768,8 @@ impl ExecutionPlan for ParquetExec {
             predicate: self.predicate.clone(),
             pruning_predicate: self.pruning_predicate.clone(),
             page_pruning_predicate: self.page_pruning_predicate.clone(),
+            dynamic_filters: self.dynamic_filters.clone(),
+            sample: self.base_config.sample,
             table_schema: self.base_config.file_schema.clone(),
             metadata_size_hint: self.metadata_size_hint,
             metrics: self.metrics.clone(),
@@ -759,6 +813,7 @@ impl ExecutionPlan for ParquetExec {
             predicate: self.predicate.clone(),
             pruning_predicate: self.pruning_predicate.clone(),
             page_pruning_predicate: self.page_pruning_predicate.clone(),
+            dynamic_filters: self.dynamic_filters.clone(),
             metadata_size_hint: self.metadata_size_hint,
             parquet_file_reader_factory: self.parquet_file_reader_factory.clone(),
             cache: self.cache.clone(),
@@ -842,6 +897,7 @@ mod tests {
         predicate: Option<Expr>,
         pushdown_predicate: bool,
         page_index_predicate: bool,
+        dynamic_filters: Vec<(Column, Arc<DynamicFilter>)>,
     }
 
     impl RoundTrip {
@@ -874,6 +930,15 @@ mod tests {
             self
         }
 
+        fn with_dynamic_filter(
+            mut self,
+            column: Column,
+            filter: Arc<DynamicFilter>,
+        ) -> Self {
+            self.dynamic_filters.push((column, filter));
+            self
+        }
+
         /// run the test, returning only the resulting RecordBatches
         async fn round_trip_to_batches(
             self,
@@ -890,6 +955,7 @@ mod tests {
                 predicate,
                 pushdown_predicate,
                 page_index_predicate,
+                dynamic_filters,
             } = self;
 
             let file_schema = match schema {
@@ -932,6 +998,10 @@ mod tests {
                 parquet_exec = parquet_exec.with_enable_page_index(true);
             }
 
+            if !dynamic_filters.is_empty() {
+                parquet_exec = parquet_exec.with_dynamic_filters(dynamic_filters);
+            }
+
             let session_ctx = SessionContext::new();
             let task_ctx = session_ctx.task_ctx();
             let parquet_exec = Arc::new(parquet_exec);
@@ -1841,6 +1911,56 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn parquet_exec_dynamic_filter() -> Result<()> {
+        let c1: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let batch1 = create_batch(vec![("c1", c1)]);
+        let c1: ArrayRef = Arc::new(Int32Array::from(vec![10, 11, 12]));
+        let batch2 = create_batch(vec![("c1", c1)]);
+
+        // the build side of a join only has keys between 2 and 5
+        let filter = Arc::new(DynamicFilter::new());
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![5, 2]));
+        filter.update(&keys)?;
+
+        let rt = RoundTrip::new()
+            .with_dynamic_filter(Column::new("c1", 0), filter)
+            .round_trip(vec![batch1, batch2])
+            .await;
+
+        let display = displayable(rt.parquet_exec.as_ref())
+            .indent(true)
+            .to_string();
+        assert_contains!(&display, "dynamic_filters=[c1@0]");
+
+        // the file with c1 in 10..12 is skipped, the other one is read in full
+        let expected = [
+            "+----+", "| c1 |", "+----+", "| 1  |", "| 2  |", "| 3  |", "+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &rt.batches?);
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(get_value(&metrics, "row_groups_pruned_statistics"), 1);
+        assert_eq!(get_value(&metrics, "row_groups_matched_statistics"), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn parquet_exec_unpublished_dynamic_filter() -> Result<()> {
+        let c1: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let batch1 = create_batch(vec![("c1", c1)]);
+
+        // the build side of the join is not collected yet, so nothing is pruned
+        let rt = RoundTrip::new()
+            .with_dynamic_filter(Column::new("c1", 0), Arc::new(DynamicFilter::new()))
+            .round_trip(vec![batch1])
+            .await;
+
+        assert_eq!(rt.batches?.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+        let metrics = rt.parquet_exec.metrics().unwrap();
+        assert_eq!(get_value(&metrics, "row_groups_pruned_statistics"), 0);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_exec_display() {
         // batch1: c1(string)
@@ -24,13 +24,17 @@ use crate::datasource::physical_plan::parquet::{
     row_filter, should_enable_page_index, ParquetAccessPlan,
 };
 use crate::datasource::physical_plan::{
-    FileMeta, FileOpenFuture, FileOpener, ParquetFileMetrics, ParquetFileReaderFactory,
+    FileMeta, FileOpenFuture, FileOpener, FileSample, ParquetFileMetrics,
+    ParquetFileReaderFactory,
 };
 use crate::datasource::schema_adapter::SchemaAdapterFactory;
 use crate::physical_optimizer::pruning::PruningPredicate;
 use arrow_schema::{ArrowError, SchemaRef};
 use datafusion_common::{exec_err, Result};
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column};
 use datafusion_physical_expr_common::physical_expr::PhysicalExpr;
+use datafusion_physical_plan::joins::DynamicFilter;
 use datafusion_physical_plan::metrics::ExecutionPlanMetricsSet;
 use futures::{StreamExt, TryStreamExt};
 use log::debug;
@@ -48,6 +52,8 @@ pub(super) struct ParquetOpener {
     pub predicate: Option<Arc<dyn PhysicalExpr>>,
     pub pruning_predicate: Option<Arc<PruningPredicate>>,
     pub page_pruning_predicate: Option<Arc<PagePruningAccessPlanFilter>>,
+    pub dynamic_filters: Vec<(Column, Arc<DynamicFilter>)>,
+    pub sample: Option<FileSample>,
     pub table_schema: SchemaRef,
     pub metadata_size_hint: Option<usize>,
     pub metrics: ExecutionPlanMetricsSet,
@@ -81,20 +87,50 @@ impl FileOpener for ParquetOpener {
         let projected_schema = SchemaRef::from(self.table_schema.project(&projection)?);
         let schema_adapter = self.schema_adapter_factory.create(projected_schema);
         let predicate = self.predicate.clone();
-        let pruning_predicate = self.pruning_predicate.clone();
-        let page_pruning_predicate = self.page_pruning_predicate.clone();
+        let mut pruning_predicate = self.pruning_predicate.clone();
+        let mut page_pruning_predicate = self.page_pruning_predicate.clone();
+        let dynamic_filters = self.dynamic_filters.clone();
+        let sample = self.sample;
         let table_schema = self.table_schema.clone();
         let reorder_predicates = self.reorder_filters;
         let pushdown_filters = self.pushdown_filters;
-        let enable_page_index = should_enable_page_index(
-            self.enable_page_index,
-            &self.page_pruning_predicate,
-        );
+        let enable_page_index = self.enable_page_index;
         let enable_bloom_filter = self.enable_bloom_filter;
         let limit = self.limit;
         let schema_force_string_view = self.schema_force_string_view;
 
         Ok(Box::pin(async move {
+            // Also prune by the runtime filters that joins have published by
+            // the time the file is opened, such as the range of the join keys
+            // of a hash join build side
+            if let Some(dynamic_predicate) = dynamic_filter_predicate(&dynamic_filters) {
+                let pruning_expr: Arc<dyn PhysicalExpr> = match &predicate {
+                    Some(predicate) => Arc::new(BinaryExpr::new(
+                        Arc::clone(predicate),
+                        Operator::And,
+                        dynamic_predicate,
+                    )),
+                    None => dynamic_predicate,
+                };
+                match PruningPredicate::try_new(
+                    Arc::clone(&pruning_expr),
+                    Arc::clone(&table_schema),
+                ) {
+                    Ok(p) if !p.always_true() => pruning_predicate = Some(Arc::new(p)),
+                    Ok(_) => {}
+                    Err(e) => {
+                        debug!("Could not create dynamic pruning predicate for: {e}")
+                    }
+                }
+                page_pruning_predicate =
+                    Some(Arc::new(PagePruningAccessPlanFilter::new(
+                        &pruning_expr,
+                        Arc::clone(&table_schema),
+                    )));
+            }
+            let enable_page_index =
+                should_enable_page_index(enable_page_index, &page_pruning_predicate);
+
             let options = ArrowReaderOptions::new().with_page_index(enable_page_index);
 
             let metadata =
@@ -163,6 +199,10 @@ impl FileOpener for ParquetOpener {
             if let Some(range) = file_range.as_ref() {
                 row_groups.prune_by_range(rg_metadata, range);
             }
+            // if only a sample of the row groups should be read
+            if let Some(sample) = sample.as_ref() {
+                row_groups.prune_by_sample(&file_name, sample);
+            }
             // If there is a predicate that can be evaluated against the metadata
             if let Some(predicate) = predicate.as_ref() {
                 row_groups.prune_by_statistics(
@@ -231,6 +271,19 @@ impl FileOpener for ParquetOpener {
     }
 }
 
+/// Returns the conjunction of the predicates of the `dynamic_filters` that
+/// have been published, if any
+fn dynamic_filter_predicate(
+    dynamic_filters: &[(Column, Arc<DynamicFilter>)],
+) -> Option<Arc<dyn PhysicalExpr>> {
+    dynamic_filters
+        .iter()
+        .filter_map(|(column, filter)| filter.predicate(Arc::new(column.clone())))
+        .reduce(|acc, predicate| {
+            Arc::new(BinaryExpr::new(acc, Operator::And, predicate)) as _
+        })
+}
+
 /// Return the initial [`ParquetAccessPlan`]
 ///
 /// If the user has supplied one as an extension, use that
@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::datasource::listing::FileRange;
+use crate::datasource::physical_plan::FileSample;
 use crate::physical_optimizer::pruning::{PruningPredicate, PruningStatistics};
 use arrow::{array::ArrayRef, datatypes::Schema};
 use arrow_array::BooleanArray;
@@ -91,6 +92,22 @@ impl RowGroupAccessPlanFilter {
             }
         }
     }
+
+    /// Prune remaining row groups that are not part of the block level
+    /// `sample` of the file `file_name`.
+    ///
+    /// Updates this set to mark row groups that should not be scanned
+    pub fn prune_by_sample(&mut self, file_name: &str, sample: &FileSample) {
+        // every row group is drawn, so that which row groups are sampled does
+        // not depend on the other pruning
+        let sampled = sample.sample_blocks(file_name, self.access_plan.len());
+        for (idx, sampled) in sampled.into_iter().enumerate() {
+            if !sampled {
+                self.access_plan.skip(idx);
+            }
+        }
+    }
+
     /// Prune remaining row groups using min/max/null_count statistics and
     /// the [`PruningPredicate`] to determine if the predicate can not be true.
     ///
@@ -34,18 +34,22 @@ use crate::{
         function::{TableFunction, TableFunctionImpl},
         listing::{ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl},
     },
-    datasource::{provider_as_source, MemTable, ViewTable},
+    datasource::{
+        provider_as_source, CachedTable, MaterializedView, MemTable, ViewTable,
+    },
     error::{DataFusionError, Result},
     execution::{options::ArrowReadOptions, runtime_env::RuntimeEnv, FunctionRegistry},
     logical_expr::AggregateUDF,
     logical_expr::ScalarUDF,
     logical_expr::{
-        CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-        CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, LogicalPlan, LogicalPlanBuilder, SetVariable, TableType, UNNAMED_TABLE,
+        CacheTable, CreateCatalog, CreateCatalogSchema, CreateExternalTable,
+        CreateFunction, CreateMaterializedView, CreateMemoryTable, CreateView,
+        DropCatalogSchema, DropFunction, DropTable, DropView, LogicalPlan,
+        LogicalPlanBuilder, RefreshMaterializedView, SetVariable, TableType,
+        UncacheTable, UNNAMED_TABLE,
     },
     physical_expr::PhysicalExpr,
-    physical_plan::ExecutionPlan,
+    physical_plan::{collect_partitioned, spill::spill_write_options, ExecutionPlan},
     variable::{VarProvider, VarType},
 };
 
@@ -79,6 +83,7 @@ use crate::execution::session_state::SessionStateBuilder;
 pub use datafusion_execution::config::SessionConfig;
 pub use datafusion_execution::TaskContext;
 pub use datafusion_expr::execution_props::ExecutionProps;
+use datafusion_optimizer::materialized_view_rewrite::MaterializedViewRewrite;
 use datafusion_optimizer::{AnalyzerRule, OptimizerRule};
 
 mod avro;
@@ -267,6 +272,8 @@ pub struct SessionContext {
     session_start_time: DateTime<Utc>,
     /// Shared session state for the session
     state: Arc<RwLock<SessionState>>,
+    /// Rewrites queries to read the materialized views of the session
+    materialized_views: Arc<MaterializedViewRewrite>,
 }
 
 impl Default for SessionContext {
@@ -353,6 +360,7 @@ impl SessionContext {
             session_id: state.session_id().to_string(),
             session_start_time: Utc::now(),
             state: Arc::new(RwLock::new(state)),
+            materialized_views: Arc::new(MaterializedViewRewrite::new()),
         }
     }
 
@@ -588,6 +596,18 @@ impl SessionContext {
                     DdlStatement::CreateView(cmd) => {
                         Box::pin(self.create_view(cmd)).await
                     }
+                    DdlStatement::CreateMaterializedView(cmd) => {
+                        Box::pin(self.create_materialized_view(cmd)).await
+                    }
+                    DdlStatement::RefreshMaterializedView(cmd) => {
+                        Box::pin(self.refresh_materialized_view(cmd)).await
+                    }
+                    DdlStatement::CacheTable(cmd) => {
+                        Box::pin(self.cache_table(cmd)).await
+                    }
+                    DdlStatement::UncacheTable(cmd) => {
+                        Box::pin(self.uncache_table(cmd)).await
+                    }
                     DdlStatement::CreateCatalogSchema(cmd) => {
                         Box::pin(self.create_catalog_schema(cmd)).await
                     }
@@ -759,6 +779,203 @@ impl SessionContext {
         }
     }
 
+    async fn create_materialized_view(
+        &self,
+        cmd: CreateMaterializedView,
+    ) -> Result<DataFrame> {
+        let CreateMaterializedView {
+            name,
+            input,
+            or_replace,
+            target,
+            definition,
+        } = cmd;
+
+        let exists = self.table(name.clone()).await.is_ok();
+        if exists && !or_replace {
+            return exec_err!("Table '{name}' already exists");
+        }
+
+        // The replaced view must not be used to compute the new one
+        let mut state = self.state();
+        state.remove_optimizer_rule(self.materialized_views.name());
+        let input = state.analyzer().execute_and_check(
+            Arc::unwrap_or_clone(input),
+            state.config_options(),
+            |_, _| {},
+        )?;
+        let table: Arc<dyn TableProvider> = match target {
+            Some(target) => self.table_provider(target).await?,
+            None => {
+                let schema = Arc::new(input.schema().as_ref().into());
+                Arc::new(MemTable::try_new(schema, vec![vec![]])?)
+            }
+        };
+        let view = Arc::new(MaterializedView::try_new(input.clone(), table, definition)?);
+        view.refresh(&state).await?;
+
+        if exists {
+            self.deregister_table(name.clone())?;
+        }
+        self.register_table(name.clone(), Arc::clone(&view))?;
+
+        let mut state = self.state.write();
+        let view_name = state.resolve_table_ref(name).into();
+        self.materialized_views
+            .register_view(view_name, input, provider_as_source(view));
+        // The rule may be missing if the state was replaced
+        state.remove_optimizer_rule(self.materialized_views.name());
+        state.prepend_optimizer_rule(Arc::clone(&self.materialized_views) as _);
+        drop(state);
+        self.return_empty_dataframe()
+    }
+
+    async fn refresh_materialized_view(
+        &self,
+        cmd: RefreshMaterializedView,
+    ) -> Result<DataFrame> {
+        let RefreshMaterializedView { name, .. } = cmd;
+        let table = self.table_provider(name.clone()).await?;
+        let Some(view) = table.as_any().downcast_ref::<MaterializedView>() else {
+            return exec_err!("'{name}' is not a materialized view");
+        };
+
+        // The view must not be computed from its own previous results
+        let mut state = self.state();
+        state.remove_optimizer_rule(self.materialized_views.name());
+        view.refresh(&state).await?;
+        self.return_empty_dataframe()
+    }
+
+    async fn cache_table(&self, cmd: CacheTable) -> Result<DataFrame> {
+        let CacheTable {
+            name,
+            input,
+            from_query,
+            ..
+        } = cmd;
+
+        let original: Arc<dyn TableProvider> = if from_query {
+            if self.table_exist(name.clone())? {
+                return exec_err!("Table '{name}' already exists");
+            }
+            // Uncaching the table leaves a view computing the query
+            Arc::new(ViewTable::try_new(input.as_ref().clone(), None)?)
+        } else {
+            self.table_provider(name.clone()).await?
+        };
+        // Caching a cached table again is a no-op
+        if original.as_any().is::<CachedTable>() {
+            return self.return_empty_dataframe();
+        }
+
+        let state = self.state();
+        let plan = state.create_physical_plan(&input).await?;
+        let schema = plan.schema();
+        let partitions = collect_partitioned(plan, state.task_ctx()).await?;
+        let table = self
+            .new_cached_table(&name, original, schema, partitions)
+            .await?;
+
+        if !from_query {
+            self.deregister_table(name.clone())?;
+        }
+        self.register_table(name, Arc::new(table))?;
+        self.return_empty_dataframe()
+    }
+
+    /// Cache `partitions` in memory. If they do not fit in the memory pool
+    /// and `datafusion.execution.spill_cached_tables` is enabled, the least
+    /// recently scanned cached tables are spilled to disk until they fit, or
+    /// they are cached on disk if they still do not.
+    async fn new_cached_table(
+        &self,
+        name: &TableReference,
+        original: Arc<dyn TableProvider>,
+        schema: SchemaRef,
+        partitions: Vec<Vec<RecordBatch>>,
+    ) -> Result<CachedTable> {
+        let state = self.state();
+        let runtime = state.runtime_env();
+        let options = &state.config_options().execution;
+        let name = name.to_string();
+        let try_new = || {
+            CachedTable::try_new(
+                &name,
+                Arc::clone(&original),
+                Arc::clone(&schema),
+                partitions.clone(),
+                runtime,
+            )
+        };
+        match try_new() {
+            Err(DataFusionError::ResourcesExhausted(_))
+                if options.spill_cached_tables => {}
+            result => return result,
+        }
+
+        let write_options = spill_write_options(options.spill_compression)?;
+        let tables = self.cached_tables().await?;
+        let mut tables = tables
+            .iter()
+            .filter_map(|table| table.as_any().downcast_ref::<CachedTable>())
+            .collect::<Vec<_>>();
+        tables.sort_by_key(|table| table.last_scan());
+        for table in tables {
+            if table.spill(write_options.clone()).await? == 0 {
+                continue;
+            }
+            match try_new() {
+                Err(DataFusionError::ResourcesExhausted(_)) => {}
+                result => return result,
+            }
+        }
+        CachedTable::try_new_spilled(original, schema, partitions, runtime, write_options)
+            .await
+    }
+
+    /// Returns the [`CachedTable`]s registered in all catalogs
+    async fn cached_tables(&self) -> Result<Vec<Arc<dyn TableProvider>>> {
+        let catalog_list = Arc::clone(self.state.read().catalog_list());
+        let mut tables = vec![];
+        for catalog_name in catalog_list.catalog_names() {
+            let Some(catalog) = catalog_list.catalog(&catalog_name) else {
+                continue;
+            };
+            for schema_name in catalog.schema_names() {
+                let Some(schema) = catalog.schema(&schema_name) else {
+                    continue;
+                };
+                for table_name in schema.table_names() {
+                    if let Some(table) = schema.table(&table_name).await? {
+                        if table.as_any().is::<CachedTable>() {
+                            tables.push(table);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(tables)
+    }
+
+    async fn uncache_table(&self, cmd: UncacheTable) -> Result<DataFrame> {
+        let UncacheTable {
+            name, if_exists, ..
+        } = cmd;
+        let table = match self.table_provider(name.clone()).await {
+            Ok(table) => table,
+            Err(_) if if_exists => return self.return_empty_dataframe(),
+            Err(e) => return Err(e),
+        };
+        // Uncaching a table which is not cached is a no-op
+        if let Some(table) = table.as_any().downcast_ref::<CachedTable>() {
+            let original = Arc::clone(table.original());
+            self.deregister_table(name.clone())?;
+            self.register_table(name, original)?;
+        }
+        self.return_empty_dataframe()
+    }
+
     async fn create_catalog_schema(&self, cmd: CreateCatalogSchema) -> Result<DataFrame> {
         let CreateCatalogSchema {
             schema_name,
@@ -859,14 +1076,18 @@ impl SessionContext {
             cascade,
             schema: _,
         } = cmd;
+        let catalog_name = match &name {
+            SchemaReference::Full { catalog, .. } => catalog.to_string(),
+            SchemaReference::Bare { .. } => self
+                .state
+                .read()
+                .config_options()
+                .catalog
+                .default_catalog
+                .to_string(),
+        };
         let catalog = {
             let state = self.state.read();
-            let catalog_name = match &name {
-                SchemaReference::Full { catalog, .. } => catalog.to_string(),
-                SchemaReference::Bare { .. } => {
-                    state.config_options().catalog.default_catalog.to_string()
-                }
-            };
             if let Some(catalog) = state.catalog_list().catalog(&catalog_name) {
                 catalog
             } else if allow_missing {
@@ -879,7 +1100,11 @@ impl SessionContext {
         match (dereg, allow_missing) {
             (None, true) => self.return_empty_dataframe(),
             (None, false) => self.schema_doesnt_exist_err(name),
-            (Some(_), _) => self.return_empty_dataframe(),
+            (Some(_), _) => {
+                self.materialized_views
+                    .deregister_schema_views(&catalog_name, name.schema_name());
+                self.return_empty_dataframe()
+            }
         }
     }
 
@@ -926,19 +1151,19 @@ impl SessionContext {
     ) -> Result<bool> {
         let table_ref = table_ref.into();
         let table = table_ref.table().to_owned();
-        let maybe_schema = {
-            let state = self.state.read();
-            let resolved = state.resolve_table_ref(table_ref);
-            state
-                .catalog_list()
-                .catalog(&resolved.catalog)
-                .and_then(|c| c.schema(&resolved.schema))
-        };
+        let resolved = self.state.read().resolve_table_ref(table_ref);
+        let maybe_schema = self
+            .state
+            .read()
+            .catalog_list()
+            .catalog(&resolved.catalog)
+            .and_then(|c| c.schema(&resolved.schema));
 
         if let Some(schema) = maybe_schema {
             if let Some(table_provider) = schema.table(&table).await? {
                 if table_provider.table_type() == table_type {
                     schema.deregister_table(&table)?;
+                    self.materialized_views.deregister_view(&resolved.into());
                     return Ok(true);
                 }
             }
@@ -1288,10 +1513,15 @@ impl SessionContext {
     ) -> Result<Option<Arc<dyn TableProvider>>> {
         let table_ref = table_ref.into();
         let table = table_ref.table().to_owned();
-        self.state
-            .read()
-            .schema_for_ref(table_ref)?
-            .deregister_table(&table)
+        let state = self.state.read();
+        let deregistered = state
+            .schema_for_ref(table_ref.clone())?
+            .deregister_table(&table)?;
+        if deregistered.is_some() {
+            let view = state.resolve_table_ref(table_ref).into();
+            self.materialized_views.deregister_view(&view);
+        }
+        Ok(deregistered)
     }
 
     /// Return `true` if the specified table exists in the schema provider.
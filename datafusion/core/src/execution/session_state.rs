@@ -48,7 +48,7 @@ use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
 use datafusion_expr::execution_props::ExecutionProps;
 use datafusion_expr::expr_rewriter::FunctionRewrite;
-use datafusion_expr::planner::ExprPlanner;
+use datafusion_expr::planner::{ExprPlanner, TableFunctionArg};
 use datafusion_expr::registry::{FunctionRegistry, SerializerRegistry};
 use datafusion_expr::simplify::SimplifyInfo;
 use datafusion_expr::var_provider::{is_system_variables, VarType};
@@ -69,7 +69,7 @@ use datafusion_sql::planner::{ContextProvider, ParserOptions, PlannerContext, Sq
 use itertools::Itertools;
 use log::{debug, info};
 use sqlparser::ast::Expr as SQLExpr;
-use sqlparser::dialect::dialect_from_str;
+use sqlparser::dialect::{dialect_from_str, DatabricksDialect, Dialect};
 use std::any::Any;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
@@ -415,6 +415,23 @@ impl SessionState {
         self.optimizer.rules.push(optimizer_rule);
     }
 
+    /// Add `optimizer_rule` to the start of the list of [`OptimizerRule`]s,
+    /// so that it sees the plans as produced by the [`Analyzer`]
+    pub(crate) fn prepend_optimizer_rule(
+        &mut self,
+        optimizer_rule: Arc<dyn OptimizerRule + Send + Sync>,
+    ) {
+        self.optimizer.rules.insert(0, optimizer_rule);
+    }
+
+    /// Remove the [`OptimizerRule`]s named `name`, returning true if any
+    /// rule was removed
+    pub(crate) fn remove_optimizer_rule(&mut self, name: &str) -> bool {
+        let num_rules = self.optimizer.rules.len();
+        self.optimizer.rules.retain(|rule| rule.name() != name);
+        self.optimizer.rules.len() != num_rules
+    }
+
     #[deprecated(since = "40.0.0", note = "Use SessionStateBuilder")]
     /// Add `physical_optimizer_rule` to the end of the list of
     /// [`PhysicalOptimizerRule`]s used to rewrite queries.
@@ -487,13 +504,7 @@ impl SessionState {
         sql: &str,
         dialect: &str,
     ) -> datafusion_common::Result<Statement> {
-        let dialect = dialect_from_str(dialect).ok_or_else(|| {
-            plan_datafusion_err!(
-                "Unsupported SQL dialect: {dialect}. Available dialects: \
-                     Generic, MySQL, PostgreSQL, Hive, SQLite, Snowflake, Redshift, \
-                     MsSQL, ClickHouse, BigQuery, Ansi."
-            )
-        })?;
+        let dialect = parse_dialect(dialect)?;
         let mut statements = DFParser::parse_sql_with_dialect(sql, dialect.as_ref())?;
         if statements.len() > 1 {
             return not_impl_err!(
@@ -514,13 +525,7 @@ impl SessionState {
         sql: &str,
         dialect: &str,
     ) -> datafusion_common::Result<SQLExpr> {
-        let dialect = dialect_from_str(dialect).ok_or_else(|| {
-            plan_datafusion_err!(
-                "Unsupported SQL dialect: {dialect}. Available dialects: \
-                     Generic, MySQL, PostgreSQL, Hive, SQLite, Snowflake, Redshift, \
-                     MsSQL, ClickHouse, BigQuery, Ansi."
-            )
-        })?;
+        let dialect = parse_dialect(dialect)?;
 
         let expr = DFParser::parse_sql_into_expr_with_dialect(sql, dialect.as_ref())?;
 
@@ -1547,6 +1552,22 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
         Ok(provider_as_source(provider))
     }
 
+    fn get_table_function_source_with_args(
+        &self,
+        name: &str,
+        args: Vec<TableFunctionArg>,
+    ) -> datafusion_common::Result<Arc<dyn TableSource>> {
+        let tbl_func = self
+            .state
+            .table_functions
+            .get(name)
+            .cloned()
+            .ok_or_else(|| plan_datafusion_err!("table function '{name}' not found"))?;
+        let provider = tbl_func.create_table_provider_with_args(&args)?;
+
+        Ok(provider_as_source(provider))
+    }
+
     /// Create a new CTE work table for a recursive CTE logical plan
     /// This table will be used in conjunction with a Worktable physical plan
     /// to read and write each iteration of a recursive CTE
@@ -1769,6 +1790,23 @@ impl From<&SessionState> for TaskContext {
     }
 }
 
+/// Returns the SQL dialect named `dialect`
+///
+/// `Databricks` is the only dialect that parses lambda functions such as
+/// `x -> x + 1`, which are not part of the dialects of [`dialect_from_str`].
+fn parse_dialect(dialect: &str) -> datafusion_common::Result<Box<dyn Dialect>> {
+    if dialect.eq_ignore_ascii_case("databricks") {
+        return Ok(Box::new(DatabricksDialect {}));
+    }
+    dialect_from_str(dialect).ok_or_else(|| {
+        plan_datafusion_err!(
+            "Unsupported SQL dialect: {dialect}. Available dialects: \
+                 Generic, MySQL, PostgreSQL, Hive, SQLite, Snowflake, Redshift, \
+                 MsSQL, ClickHouse, BigQuery, Ansi, DuckDB, Databricks."
+        )
+    })
+}
+
 /// The query planner used if no user defined planner is provided
 struct DefaultQueryPlanner {}
 
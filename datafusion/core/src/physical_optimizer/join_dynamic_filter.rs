@@ -0,0 +1,310 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`JoinDynamicFilter`] rule connects hash joins to the scans of their
+//! probe side, so that the scans can skip data that cannot match any row of
+//! the build side of the join.
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+#[cfg(feature = "parquet")]
+use crate::datasource::physical_plan::ParquetExec;
+use crate::error::Result;
+use crate::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use crate::physical_plan::filter::FilterExec;
+use crate::physical_plan::joins::{DynamicFilter, HashJoinExec, PartitionMode};
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sorts::sort::SortExec;
+use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use crate::physical_plan::ExecutionPlan;
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_optimizer::PhysicalOptimizerRule;
+
+/// The JoinDynamicFilter rule attaches a [`DynamicFilter`] to each column
+/// join key of a [`HashJoinExec`] in [`PartitionMode::CollectLeft`] mode,
+/// and hands the same filter to the scan producing that key on the probe
+/// side of the join.
+///
+/// Once the join has collected its build side, it publishes the range of the
+/// build side keys, and the scans use it to prune data before reading it,
+/// e.g. [`ParquetExec`] skips row groups and pages whose statistics show
+/// that none of their rows can match.
+///
+/// The filter is only pushed through operators that do not change which
+/// probe side rows can match, such as filters, projections of columns and
+/// repartitioning, and never through limits.
+///
+/// This rule should run after all rules that may replace or rebuild the
+/// joins and scans of the plan.
+///
+/// [`ParquetExec`]: crate::datasource::physical_plan::ParquetExec
+#[derive(Default)]
+pub struct JoinDynamicFilter {}
+
+impl JoinDynamicFilter {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for JoinDynamicFilter {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_join_dynamic_filter {
+            return Ok(plan);
+        }
+        plan.transform_up(add_dynamic_filters).data()
+    }
+
+    fn name(&self) -> &str {
+        "join_dynamic_filter"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Attaches dynamic filters to the join keys of `plan`, if it is a
+/// [`HashJoinExec`] whose probe side scans accept them
+fn add_dynamic_filters(
+    plan: Arc<dyn ExecutionPlan>,
+) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
+    let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+        return Ok(Transformed::no(plan));
+    };
+    if join.partition_mode() != &PartitionMode::CollectLeft
+        || !HashJoinExec::supports_dynamic_filters(*join.join_type())
+        // null keys can match each other
+        || join.null_equals_null()
+        || !join.dynamic_filters().is_empty()
+    {
+        return Ok(Transformed::no(plan));
+    }
+
+    let mut right = Arc::clone(join.right());
+    let mut dynamic_filters = vec![];
+    for (index, (_, right_key)) in join.on().iter().enumerate() {
+        let Some(column) = right_key.as_any().downcast_ref::<Column>() else {
+            continue;
+        };
+        let filter = Arc::new(DynamicFilter::new());
+        if let Some(new_right) = push_dynamic_filter(&right, column.index(), &filter)? {
+            right = new_right;
+            dynamic_filters.push((index, filter));
+        }
+    }
+    if dynamic_filters.is_empty() {
+        return Ok(Transformed::no(plan));
+    }
+
+    let new_join = HashJoinExec::try_new(
+        Arc::clone(join.left()),
+        right,
+        join.on().to_vec(),
+        join.filter().cloned(),
+        join.join_type(),
+        join.projection.clone(),
+        *join.partition_mode(),
+        join.null_equals_null(),
+    )?
+    .with_dynamic_filters(dynamic_filters)?;
+    Ok(Transformed::yes(Arc::new(new_join)))
+}
+
+/// Hands `filter` on the output column `index` of `plan` to the scan
+/// producing that column. Returns the rewritten plan, or `None` if the column
+/// can not be traced to a scan that accepts dynamic filters.
+fn push_dynamic_filter(
+    plan: &Arc<dyn ExecutionPlan>,
+    index: usize,
+    filter: &Arc<DynamicFilter>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    #[cfg(feature = "parquet")]
+    if let Some(parquet) = plan.as_any().downcast_ref::<ParquetExec>() {
+        let config = parquet.base_config();
+        let file_index = match &config.projection {
+            Some(projection) => projection[index],
+            None => index,
+        };
+        // partition columns are not part of the files
+        if file_index >= config.file_schema.fields().len() {
+            return Ok(None);
+        }
+        let column = Column::new(config.file_schema.field(file_index).name(), file_index);
+        let mut dynamic_filters = parquet.dynamic_filters().to_vec();
+        dynamic_filters.push((column, Arc::clone(filter)));
+        let new_parquet = parquet.clone().with_dynamic_filters(dynamic_filters);
+        return Ok(Some(Arc::new(new_parquet)));
+    }
+
+    let input_index =
+        if let Some(projection) = plan.as_any().downcast_ref::<ProjectionExec>() {
+            match projection.expr()[index].0.as_any().downcast_ref::<Column>() {
+                Some(column) => column.index(),
+                None => return Ok(None),
+            }
+        } else if plan.fetch().is_none()
+            && (plan.as_any().is::<FilterExec>()
+                || plan.as_any().is::<RepartitionExec>()
+                || plan.as_any().is::<CoalesceBatchesExec>()
+                || plan.as_any().is::<CoalescePartitionsExec>()
+                || plan.as_any().is::<SortExec>()
+                || plan.as_any().is::<SortPreservingMergeExec>())
+        {
+            index
+        } else {
+            return Ok(None);
+        };
+
+    let input = Arc::clone(plan.children()[0]);
+    match push_dynamic_filter(&input, input_index, filter)? {
+        Some(new_input) => Ok(Some(Arc::clone(plan).with_new_children(vec![new_input])?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(all(test, feature = "parquet"))]
+mod tests {
+    use super::*;
+    use crate::datasource::listing::PartitionedFile;
+    use crate::datasource::object_store::ObjectStoreUrl;
+    use crate::datasource::physical_plan::FileScanConfig;
+    use crate::physical_plan::displayable;
+    use crate::physical_plan::expressions::{col, lit, BinaryExpr};
+    use crate::physical_plan::joins::utils::JoinOn;
+    use crate::physical_plan::limit::GlobalLimitExec;
+
+    use arrow_schema::{DataType, Field, Schema, SchemaRef};
+    use datafusion_common::JoinType;
+    use datafusion_expr::Operator;
+
+    fn schema(prefix: &str) -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new(format!("{prefix}_a"), DataType::Int32, true),
+            Field::new(format!("{prefix}_b"), DataType::Int32, true),
+        ]))
+    }
+
+    fn parquet_exec(schema: SchemaRef) -> Arc<dyn ExecutionPlan> {
+        ParquetExec::builder(
+            FileScanConfig::new(ObjectStoreUrl::parse("test:///").unwrap(), schema)
+                .with_file(PartitionedFile::new("x".to_string(), 100)),
+        )
+        .build_arc()
+    }
+
+    fn hash_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        join_type: JoinType,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let on: JoinOn =
+            vec![(col("l_a", &left.schema())?, col("r_b", &right.schema())?)];
+        Ok(Arc::new(HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &join_type,
+            None,
+            PartitionMode::CollectLeft,
+            false,
+        )?))
+    }
+
+    fn optimize(plan: Arc<dyn ExecutionPlan>) -> Result<Vec<String>> {
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_join_dynamic_filter = true;
+        let optimized = JoinDynamicFilter::new().optimize(plan, &config)?;
+        Ok(displayable(optimized.as_ref())
+            .indent(true)
+            .to_string()
+            .trim()
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    #[test]
+    fn push_filter_to_probe_side_scan() -> Result<()> {
+        let left = parquet_exec(schema("l"));
+        let scan = parquet_exec(schema("r"));
+        // r_b > 10, with the columns swapped by a projection
+        let predicate = Arc::new(BinaryExpr::new(
+            col("r_b", &scan.schema())?,
+            Operator::Gt,
+            lit(10),
+        ));
+        let filter = Arc::new(FilterExec::try_new(predicate, scan)?);
+        let right = Arc::new(ProjectionExec::try_new(
+            vec![
+                (col("r_b", &filter.schema())?, "r_b".to_string()),
+                (col("r_a", &filter.schema())?, "r_a".to_string()),
+            ],
+            filter,
+        )?);
+        let join = hash_join(left, right, JoinType::Inner)?;
+
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(l_a@0, r_b@0)]",
+            "  ParquetExec: file_groups={1 group: [[x]]}, projection=[l_a, l_b]",
+            "  ProjectionExec: expr=[r_b@1 as r_b, r_a@0 as r_a]",
+            "    FilterExec: r_b@1 > 10",
+            "      ParquetExec: file_groups={1 group: [[x]]}, projection=[r_a, r_b], dynamic_filters=[r_b@1]",
+        ];
+        assert_eq!(optimize(join)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_unsupported_joins() -> Result<()> {
+        // unmatched probe side rows are part of the output
+        let join = hash_join(
+            parquet_exec(schema("l")),
+            parquet_exec(schema("r")),
+            JoinType::Right,
+        )?;
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Right, on=[(l_a@0, r_b@1)]",
+            "  ParquetExec: file_groups={1 group: [[x]]}, projection=[l_a, l_b]",
+            "  ParquetExec: file_groups={1 group: [[x]]}, projection=[r_a, r_b]",
+        ];
+        assert_eq!(optimize(join)?, expected);
+
+        // filtering the input of a limit changes which rows are returned
+        let right = Arc::new(GlobalLimitExec::new(parquet_exec(schema("r")), 0, Some(5)));
+        let join = hash_join(parquet_exec(schema("l")), right, JoinType::Inner)?;
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(l_a@0, r_b@1)]",
+            "  ParquetExec: file_groups={1 group: [[x]]}, projection=[l_a, l_b]",
+            "  GlobalLimitExec: skip=0, fetch=5",
+            "    ParquetExec: file_groups={1 group: [[x]]}, projection=[r_a, r_b]",
+        ];
+        assert_eq!(optimize(join)?, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,572 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The [`JoinReorder`] rule reorders trees of inner equi-joins using the
+//! statistics of their inputs, so that the join order no longer depends on
+//! the order in which the tables are listed in the query.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_plan::joins::{HashJoinExec, PartitionMode};
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::{ExecutionPlan, ExecutionPlanProperties};
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{internal_datafusion_err, JoinType};
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::PhysicalExpr;
+use datafusion_physical_optimizer::PhysicalOptimizerRule;
+
+/// The JoinReorder rule collects trees of inner [`HashJoinExec`]s with
+/// equi-join conditions on columns (a "join graph") and searches for a
+/// cheaper join order using the statistics of the join inputs.
+///
+/// Joins are enumerated greedily: starting from the join inputs, the pair of
+/// connected relations with the smallest estimated join output is joined
+/// first, until a single relation remains. The cost of a join order is the
+/// sum of the estimated number of rows of all intermediate results. The
+/// smaller relation of each pair becomes the build (left) side.
+///
+/// The plan is only changed if all join inputs have a row count estimate
+/// and the new join order is estimated to be cheaper than the original one.
+/// Join cardinalities are estimated as by [`ExecutionPlan::statistics`] of
+/// the joins, falling back to the row count of the larger input (i.e.
+/// assuming a foreign key join) when no estimate is available.
+///
+/// This rule should run before [`JoinSelection`], which picks the final
+/// partition mode and build side of each join.
+///
+/// [`JoinSelection`]: crate::physical_optimizer::join_selection::JoinSelection
+#[derive(Default)]
+pub struct JoinReorder {}
+
+impl JoinReorder {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PhysicalOptimizerRule for JoinReorder {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.enable_join_reorder {
+            return Ok(plan);
+        }
+        plan.transform_down(reorder_join_graph).data()
+    }
+
+    fn name(&self) -> &str {
+        "join_reorder"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Reorders the join graph rooted at `plan`, if any
+fn reorder_join_graph(
+    plan: Arc<dyn ExecutionPlan>,
+) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
+    let Some(graph) = JoinGraph::try_new(&plan)? else {
+        return Ok(Transformed::no(plan));
+    };
+    match graph.reorder(&plan)? {
+        Some(new_plan) => Ok(Transformed::yes(new_plan)),
+        None => Ok(Transformed::no(plan)),
+    }
+}
+
+/// An input or intermediate result of a join graph
+struct JoinRelation {
+    plan: Arc<dyn ExecutionPlan>,
+    /// The ids of the join graph columns in the output of `plan`
+    columns: Vec<usize>,
+    /// The estimated number of rows of `plan`
+    num_rows: usize,
+}
+
+impl JoinRelation {
+    /// Returns a column expression for the join graph column `id`, if it is
+    /// part of this relation
+    fn column(&self, id: usize) -> Option<Arc<dyn PhysicalExpr>> {
+        let index = self.columns.iter().position(|c| *c == id)?;
+        let schema = self.plan.schema();
+        Some(Arc::new(Column::new(schema.field(index).name(), index)))
+    }
+}
+
+/// A tree of inner equi-joins flattened into its inputs and join conditions
+struct JoinGraph {
+    /// The inputs of the joins
+    inputs: Vec<JoinRelation>,
+    /// Equi-join conditions as pairs of column ids
+    on: Vec<(usize, usize)>,
+    /// The column ids of the output of the join tree
+    output_columns: Vec<usize>,
+    /// The estimated cost of the original join order
+    cost: usize,
+    /// The id assigned to the next input column
+    next_column: usize,
+    partition_mode: PartitionMode,
+    null_equals_null: bool,
+}
+
+impl JoinGraph {
+    /// Collects the join graph rooted at `plan`. Returns `None` if `plan` is
+    /// not a join graph of at least three inputs with row count estimates.
+    fn try_new(plan: &Arc<dyn ExecutionPlan>) -> Result<Option<Self>> {
+        let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() else {
+            return Ok(None);
+        };
+        let mut graph = Self {
+            inputs: vec![],
+            on: vec![],
+            output_columns: vec![],
+            cost: 0,
+            next_column: 0,
+            partition_mode: *join.partition_mode(),
+            null_equals_null: join.null_equals_null(),
+        };
+        if !graph.is_reorderable(join) {
+            return Ok(None);
+        }
+        let Some((output_columns, _)) = graph.add(plan)? else {
+            return Ok(None);
+        };
+        if graph.inputs.len() < 3 {
+            return Ok(None);
+        }
+        graph.output_columns = output_columns;
+        Ok(Some(graph))
+    }
+
+    /// Whether `join` can be part of this join graph
+    fn is_reorderable(&self, join: &HashJoinExec) -> bool {
+        join.join_type() == &JoinType::Inner
+            && join.filter().is_none()
+            && !join.contain_projection()
+            && join.partition_mode() == &self.partition_mode
+            && join.null_equals_null() == self.null_equals_null
+            && join.on().iter().all(|(left, right)| {
+                left.as_any().is::<Column>() && right.as_any().is::<Column>()
+            })
+    }
+
+    /// Adds `plan` to the join graph, returning the column ids of its output
+    /// and its estimated number of rows, or `None` if some input of the join
+    /// graph has no row count estimate
+    fn add(
+        &mut self,
+        plan: &Arc<dyn ExecutionPlan>,
+    ) -> Result<Option<(Vec<usize>, usize)>> {
+        if let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() {
+            if self.is_reorderable(join) {
+                let Some((left_columns, left_rows)) = self.add(join.left())? else {
+                    return Ok(None);
+                };
+                let Some((right_columns, right_rows)) = self.add(join.right())? else {
+                    return Ok(None);
+                };
+                for (left, right) in join.on() {
+                    let left = column_index(left)?;
+                    let right = column_index(right)?;
+                    self.on.push((left_columns[left], right_columns[right]));
+                }
+                let num_rows = estimate_join_rows(plan, left_rows, right_rows)?;
+                self.cost += num_rows;
+                return Ok(Some(([left_columns, right_columns].concat(), num_rows)));
+            }
+        } else if let Some(projection) = plan.as_any().downcast_ref::<ProjectionExec>() {
+            // Look through projections of columns in between joins
+            let is_join = projection
+                .input()
+                .as_any()
+                .downcast_ref::<HashJoinExec>()
+                .is_some_and(|join| self.is_reorderable(join));
+            let indices = projection
+                .expr()
+                .iter()
+                .map(|(expr, _)| {
+                    expr.as_any().downcast_ref::<Column>().map(|c| c.index())
+                })
+                .collect::<Option<Vec<_>>>();
+            if let (true, Some(indices)) = (is_join, indices) {
+                let Some((columns, num_rows)) = self.add(projection.input())? else {
+                    return Ok(None);
+                };
+                let columns = indices.into_iter().map(|i| columns[i]).collect();
+                return Ok(Some((columns, num_rows)));
+            }
+        }
+
+        if plan.execution_mode().is_unbounded() {
+            return Ok(None);
+        }
+        let Some(num_rows) = plan.statistics()?.num_rows.get_value().copied() else {
+            return Ok(None);
+        };
+        let num_columns = plan.schema().fields().len();
+        let columns =
+            (self.next_column..self.next_column + num_columns).collect::<Vec<_>>();
+        self.next_column += num_columns;
+        self.inputs.push(JoinRelation {
+            plan: Arc::clone(plan),
+            columns: columns.clone(),
+            num_rows,
+        });
+        Ok(Some((columns, num_rows)))
+    }
+
+    /// Searches for a cheaper join order of this join graph, returning the
+    /// reordered replacement for `plan`, or `None` if the original join order
+    /// is estimated to be at least as cheap
+    fn reorder(
+        self,
+        plan: &Arc<dyn ExecutionPlan>,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        // Only keep the input columns needed by the output or a join condition
+        let required = self
+            .output_columns
+            .iter()
+            .chain(self.on.iter().flat_map(|(left, right)| [left, right]))
+            .copied()
+            .collect::<HashSet<_>>();
+        let mut relations = vec![];
+        for input in &self.inputs {
+            relations.push(prune_relation(input, &required)?);
+        }
+
+        let mut cost = 0;
+        while relations.len() > 1 {
+            let mut best: Option<(usize, usize, JoinRelation)> = None;
+            for i in 0..relations.len() {
+                for j in i + 1..relations.len() {
+                    let Some(joined) = self.join(&relations[i], &relations[j])? else {
+                        continue;
+                    };
+                    let is_better = best
+                        .as_ref()
+                        .map_or(true, |(_, _, best)| joined.num_rows < best.num_rows);
+                    if is_better {
+                        best = Some((i, j, joined));
+                    }
+                }
+            }
+            // The join graph is not connected
+            let Some((i, j, joined)) = best else {
+                return Ok(None);
+            };
+            cost += joined.num_rows;
+            relations.remove(j);
+            relations.remove(i);
+            relations.push(joined);
+        }
+        if cost >= self.cost {
+            return Ok(None);
+        }
+
+        // Restore the column order and names of the original join tree
+        let joined = relations.pop().unwrap();
+        let exprs = self
+            .output_columns
+            .iter()
+            .zip(plan.schema().fields())
+            .map(|(id, field)| {
+                let expr = joined.column(*id).ok_or_else(|| {
+                    internal_datafusion_err!("Join graph column {id} not found")
+                })?;
+                Ok((expr, field.name().to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Some(Arc::new(ProjectionExec::try_new(exprs, joined.plan)?)))
+    }
+
+    /// Joins `a` and `b` on all join conditions between them, using the
+    /// smaller one as the build side. Returns `None` if there are no join
+    /// conditions between `a` and `b`.
+    fn join(&self, a: &JoinRelation, b: &JoinRelation) -> Result<Option<JoinRelation>> {
+        let (left, right) = if a.num_rows <= b.num_rows {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let on = self
+            .on
+            .iter()
+            .filter_map(|(x, y)| {
+                left.column(*x)
+                    .zip(right.column(*y))
+                    .or_else(|| left.column(*y).zip(right.column(*x)))
+            })
+            .collect::<Vec<_>>();
+        if on.is_empty() {
+            return Ok(None);
+        }
+
+        let plan: Arc<dyn ExecutionPlan> = Arc::new(HashJoinExec::try_new(
+            Arc::clone(&left.plan),
+            Arc::clone(&right.plan),
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            self.partition_mode,
+            self.null_equals_null,
+        )?);
+        let num_rows = estimate_join_rows(&plan, left.num_rows, right.num_rows)?;
+        Ok(Some(JoinRelation {
+            plan,
+            columns: [left.columns.as_slice(), right.columns.as_slice()].concat(),
+            num_rows,
+        }))
+    }
+}
+
+/// Projects `relation` to the `required` columns
+fn prune_relation(
+    relation: &JoinRelation,
+    required: &HashSet<usize>,
+) -> Result<JoinRelation> {
+    let (columns, exprs): (Vec<_>, Vec<_>) = relation
+        .columns
+        .iter()
+        .filter(|id| required.contains(id))
+        .map(|id| {
+            let expr = relation.column(*id).unwrap();
+            let name = expr.as_any().downcast_ref::<Column>().unwrap().name();
+            (*id, (Arc::clone(&expr), name.to_string()))
+        })
+        .unzip();
+    if columns.len() == relation.columns.len() {
+        return Ok(JoinRelation {
+            plan: Arc::clone(&relation.plan),
+            columns,
+            num_rows: relation.num_rows,
+        });
+    }
+    Ok(JoinRelation {
+        plan: Arc::new(ProjectionExec::try_new(exprs, Arc::clone(&relation.plan))?),
+        columns,
+        num_rows: relation.num_rows,
+    })
+}
+
+/// Returns the index of a join key column
+fn column_index(expr: &Arc<dyn PhysicalExpr>) -> Result<usize> {
+    expr.as_any()
+        .downcast_ref::<Column>()
+        .map(|column| column.index())
+        .ok_or_else(|| internal_datafusion_err!("Expected join key column, got {expr}"))
+}
+
+/// Estimates the number of output rows of `join`, assuming a foreign key
+/// join if its statistics have no estimate
+fn estimate_join_rows(
+    join: &Arc<dyn ExecutionPlan>,
+    left_rows: usize,
+    right_rows: usize,
+) -> Result<usize> {
+    Ok(join
+        .statistics()?
+        .num_rows
+        .get_value()
+        .copied()
+        .unwrap_or(left_rows.max(right_rows)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::{displayable, ColumnStatistics, Statistics};
+    use crate::test::StatisticsExec;
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::stats::Precision;
+    use datafusion_common::ScalarValue;
+
+    /// Returns a table of `num_rows` rows whose columns have `max + 1`
+    /// distinct values between 0 and `max`
+    fn table(
+        num_rows: usize,
+        columns: &[(&str, i32)],
+        num_rows_known: bool,
+    ) -> Arc<dyn ExecutionPlan> {
+        let schema = Schema::new(
+            columns
+                .iter()
+                .map(|(name, _)| Field::new(*name, DataType::Int32, false))
+                .collect::<Vec<_>>(),
+        );
+        let column_statistics = columns
+            .iter()
+            .map(|(_, max)| ColumnStatistics {
+                null_count: Precision::Exact(0),
+                max_value: Precision::Exact(ScalarValue::Int32(Some(*max))),
+                min_value: Precision::Exact(ScalarValue::Int32(Some(0))),
+                distinct_count: Precision::Exact(*max as usize + 1),
+            })
+            .collect();
+        let num_rows = if num_rows_known {
+            Precision::Inexact(num_rows)
+        } else {
+            Precision::Absent
+        };
+        Arc::new(StatisticsExec::new(
+            Statistics {
+                num_rows,
+                total_byte_size: Precision::Absent,
+                column_statistics,
+            },
+            schema,
+        ))
+    }
+
+    fn hash_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: &[(&str, &str)],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let on = on
+            .iter()
+            .map(|(l, r)| {
+                Ok((
+                    Arc::new(Column::new_with_schema(l, &left.schema())?) as _,
+                    Arc::new(Column::new_with_schema(r, &right.schema())?) as _,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Arc::new(HashJoinExec::try_new(
+            left,
+            right,
+            on,
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::CollectLeft,
+            false,
+        )?))
+    }
+
+    /// Star schema join of a fact table with a large and a small dimension,
+    /// joining the large dimension first
+    fn star_join(num_rows_known: bool) -> Result<Arc<dyn ExecutionPlan>> {
+        let fact = table(1_000_000, &[("f_d1", 999), ("f_d2", 999)], num_rows_known);
+        let d1 = table(1000, &[("d1_id", 999)], true);
+        let d2 = table(10, &[("d2_id", 9)], true);
+        hash_join(
+            hash_join(fact, d1, &[("f_d1", "d1_id")])?,
+            d2,
+            &[("f_d2", "d2_id")],
+        )
+    }
+
+    fn optimize(plan: Arc<dyn ExecutionPlan>, enabled: bool) -> Result<Vec<String>> {
+        let mut config = ConfigOptions::new();
+        config.optimizer.enable_join_reorder = enabled;
+        let optimized = JoinReorder::new().optimize(Arc::clone(&plan), &config)?;
+        assert_eq!(optimized.schema(), plan.schema());
+        Ok(displayable(optimized.as_ref())
+            .indent(true)
+            .to_string()
+            .trim()
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    #[test]
+    fn reorder_star_join() -> Result<()> {
+        let expected = [
+            "ProjectionExec: expr=[f_d1@2 as f_d1, f_d2@3 as f_d2, d1_id@0 as d1_id, d2_id@1 as d2_id]",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(d1_id@0, f_d1@1)]",
+            "    StatisticsExec: col_count=1, row_count=Inexact(1000)",
+            "    HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(d2_id@0, f_d2@1)]",
+            "      StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "      StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+        ];
+        assert_eq!(optimize(star_join(true)?, true)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn reorder_prunes_unused_columns() -> Result<()> {
+        let fact = table(1_000_000, &[("f_d1", 999), ("f_d2", 999)], true);
+        let d1 = table(1000, &[("d1_id", 999), ("d1_attr", 999)], true);
+        let d2 = table(10, &[("d2_id", 9)], true);
+        let join = hash_join(fact, d1, &[("f_d1", "d1_id")])?;
+        // only keep the columns of the fact table between the joins
+        let join = Arc::new(ProjectionExec::try_new(
+            vec![
+                (col_expr("f_d1", &join)?, "f_d1".to_string()),
+                (col_expr("f_d2", &join)?, "f_d2".to_string()),
+            ],
+            join,
+        )?);
+        let plan = hash_join(join, d2, &[("f_d2", "d2_id")])?;
+
+        let expected = [
+            "ProjectionExec: expr=[f_d1@2 as f_d1, f_d2@3 as f_d2, d2_id@1 as d2_id]",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(d1_id@0, f_d1@1)]",
+            "    ProjectionExec: expr=[d1_id@0 as d1_id]",
+            "      StatisticsExec: col_count=2, row_count=Inexact(1000)",
+            "    HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(d2_id@0, f_d2@2)]",
+            "      StatisticsExec: col_count=1, row_count=Inexact(10)",
+            "      StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+        ];
+        assert_eq!(optimize(plan, true)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_join_order() -> Result<()> {
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(f_d2@1, d2_id@0)]",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(f_d1@0, d1_id@0)]",
+            "    StatisticsExec: col_count=2, row_count=Absent",
+            "    StatisticsExec: col_count=1, row_count=Inexact(1000)",
+            "  StatisticsExec: col_count=1, row_count=Inexact(10)",
+        ];
+        // no row count estimate for the fact table
+        assert_eq!(optimize(star_join(false)?, true)?, expected);
+
+        // the rule is disabled
+        let expected = [
+            "HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(f_d2@1, d2_id@0)]",
+            "  HashJoinExec: mode=CollectLeft, join_type=Inner, on=[(f_d1@0, d1_id@0)]",
+            "    StatisticsExec: col_count=2, row_count=Inexact(1000000)",
+            "    StatisticsExec: col_count=1, row_count=Inexact(1000)",
+            "  StatisticsExec: col_count=1, row_count=Inexact(10)",
+        ];
+        assert_eq!(optimize(star_join(true)?, false)?, expected);
+        Ok(())
+    }
+
+    fn col_expr(
+        name: &str,
+        plan: &Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(Arc::new(Column::new_with_schema(name, &plan.schema())?))
+    }
+}
@@ -25,6 +25,8 @@ pub mod coalesce_batches;
 pub mod combine_partial_final_agg;
 pub mod enforce_distribution;
 pub mod enforce_sorting;
+pub mod join_dynamic_filter;
+pub mod join_reorder;
 pub mod join_selection;
 pub mod optimizer;
 pub mod projection_pushdown;
@@ -27,6 +27,8 @@ use crate::physical_optimizer::coalesce_batches::CoalesceBatches;
 use crate::physical_optimizer::combine_partial_final_agg::CombinePartialFinalAggregate;
 use crate::physical_optimizer::enforce_distribution::EnforceDistribution;
 use crate::physical_optimizer::enforce_sorting::EnforceSorting;
+use crate::physical_optimizer::join_dynamic_filter::JoinDynamicFilter;
+use crate::physical_optimizer::join_reorder::JoinReorder;
 use crate::physical_optimizer::join_selection::JoinSelection;
 use crate::physical_optimizer::limit_pushdown::LimitPushdown;
 use crate::physical_optimizer::limited_distinct_aggregation::LimitedDistinctAggregation;
@@ -55,6 +57,10 @@ impl PhysicalOptimizer {
             // this information is not lost across different rules during optimization.
             Arc::new(OutputRequirements::new_add_mode()),
             Arc::new(AggregateStatistics::new()),
+            // The JoinReorder rule picks the order of inner joins using statistics. It
+            // should run before JoinSelection, which picks the build side and partition
+            // mode of each (possibly reordered) join.
+            Arc::new(JoinReorder::new()),
             // Statistics-based join selection will change the Auto mode to a real join implementation,
             // like collect left, or hash join, or future sort merge join, which will influence the
             // EnforceDistribution and EnforceSorting rules as they decide whether to add additional
@@ -103,6 +109,10 @@ impl PhysicalOptimizer {
             // replacing operators with fetching variants, or adding limits
             // past operators that support limit pushdown.
             Arc::new(LimitPushdown::new()),
+            // The JoinDynamicFilter rule connects hash joins to the scans on their probe
+            // side, which can then skip data that cannot match the build side at runtime.
+            // It should run after all rules that may rebuild the joins or scans.
+            Arc::new(JoinDynamicFilter::new()),
             // The SanityCheckPlan rule checks whether the order and
             // distribution requirements of each node in the plan
             // is satisfied. It will also reject non-runnable query
@@ -24,18 +24,20 @@ use std::sync::Arc;
 use crate::datasource::file_format::file_type_to_format;
 use crate::datasource::listing::ListingTableUrl;
 use crate::datasource::physical_plan::FileSinkConfig;
+#[cfg(feature = "parquet")]
+use crate::datasource::physical_plan::{FileSample, ParquetExec};
 use crate::datasource::source_as_provider;
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::{ExecutionProps, SessionState};
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
-    Aggregate, EmptyRelation, Join, Projection, Sort, TableScan, Unnest, Window,
+    Aggregate, AsOfJoin, EmptyRelation, Join, Projection, Sort, TableScan, Unnest, Window,
 };
 use crate::logical_expr::{
     Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType, Repartition,
     UserDefinedLogicalNode,
 };
-use crate::logical_expr::{Limit, Values};
+use crate::logical_expr::{Limit, Sample, SampleMethod, Values};
 use crate::physical_expr::{create_physical_expr, create_physical_exprs};
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
 use crate::physical_plan::analyze::AnalyzeExec;
@@ -45,13 +47,15 @@ use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::utils as join_utils;
 use crate::physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
+    AsOfJoinExec, AsOfMatchCondition, CrossJoinExec, HashJoinExec, IEJoinExec,
+    NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
 };
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sample::SampleExec;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::union::UnionExec;
 use crate::physical_plan::unnest::UnnestExec;
@@ -67,20 +71,24 @@ use arrow::datatypes::{Schema, SchemaRef};
 use arrow_array::builder::StringBuilder;
 use arrow_array::RecordBatch;
 use datafusion_common::display::ToStringifiedPlan;
+use datafusion_common::tree_node::TreeNode;
 use datafusion_common::{
     exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, DFSchema,
     ScalarValue,
 };
 use datafusion_expr::dml::CopyTo;
 use datafusion_expr::expr::{
-    self, physical_name, AggregateFunction, Alias, GroupingSet, WindowFunction,
+    self, physical_name, AggregateFunction, Alias, BinaryExpr, GroupingSet,
+    WindowFunction,
 };
 use datafusion_expr::expr_rewriter::unnormalize_cols;
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
+use datafusion_expr::utils::split_conjunction_owned;
 use datafusion_expr::{
-    DescribeTable, DmlStatement, Extension, Filter, RecursiveQuery, StringifiedPlan,
-    WindowFrame, WindowFrameBound, WriteOp,
+    lit, DescribeTable, DmlStatement, Extension, Filter, JoinType, RecursiveQuery,
+    StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
 };
+use datafusion_optimizer::push_down_filter::replace_cols_by_name;
 use datafusion_physical_expr::aggregate::{AggregateExprBuilder, AggregateFunctionExpr};
 use datafusion_physical_expr::expressions::Literal;
 use datafusion_physical_expr::LexOrdering;
@@ -571,6 +579,78 @@ impl DefaultPhysicalPlanner {
                     return exec_err!("Table '{table_name}' does not exist");
                 }
             }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                op: WriteOp::Delete,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let mut filters = vec![];
+                    resolve_dml_input(input, &mut vec![], &mut filters)?;
+                    provider
+                        .delete_from(session_state, unnormalize_cols(filters))
+                        .await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                table_schema,
+                op: WriteOp::Update,
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    // The input produces the updated value of every column of the table
+                    let mut values = input
+                        .schema()
+                        .columns()
+                        .into_iter()
+                        .map(Expr::Column)
+                        .collect();
+                    let mut filters = vec![];
+                    resolve_dml_input(input, &mut values, &mut filters)?;
+                    let assignments = table_schema
+                        .fields()
+                        .iter()
+                        .zip(unnormalize_cols(values))
+                        .filter(|(field, value)| {
+                            !matches!(value, Expr::Column(c) if c.name == *field.name())
+                        })
+                        .map(|(field, value)| (field.name().clone(), value))
+                        .collect();
+                    provider
+                        .update(session_state, assignments, unnormalize_cols(filters))
+                        .await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
+            LogicalPlan::Dml(DmlStatement {
+                table_name,
+                table_schema,
+                op: WriteOp::Merge(merge),
+                input,
+                ..
+            }) => {
+                let name = table_name.table();
+                let schema = session_state.schema_for_ref(table_name.clone())?;
+                if let Some(provider) = schema.table(name).await? {
+                    let source = children.one()?;
+                    let merge_schema = Arc::new(table_schema.join(input.schema())?);
+                    provider
+                        .merge_into(session_state, source, merge_schema, merge.clone())
+                        .await?
+                } else {
+                    return exec_err!("Table '{table_name}' does not exist");
+                }
+            }
             LogicalPlan::Window(Window {
                 input, window_expr, ..
             }) => {
@@ -839,6 +919,29 @@ impl DefaultPhysicalPlanner {
 
                 Arc::new(GlobalLimitExec::new(input, *skip, *fetch))
             }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => {
+                let input = children.one()?;
+
+                // SYSTEM sampling of a Parquet scan skips whole row groups
+                #[cfg(feature = "parquet")]
+                if *method == SampleMethod::System {
+                    if let Some(parquet) = input.as_any().downcast_ref::<ParquetExec>() {
+                        if parquet.base_config().sample.is_none() {
+                            let sample = FileSample::new(*fraction, *seed);
+                            return Ok(Arc::new(
+                                parquet.clone().with_sample(Some(sample)),
+                            ));
+                        }
+                    }
+                }
+
+                Arc::new(SampleExec::try_new(input, *method, *fraction, *seed)?)
+            }
             LogicalPlan::Unnest(Unnest {
                 list_type_columns,
                 struct_type_columns,
@@ -1063,14 +1166,33 @@ impl DefaultPhysicalPlanner {
                     session_state.config_options().optimizer.prefer_hash_join;
 
                 let join: Arc<dyn ExecutionPlan> = if join_on.is_empty() {
-                    // there is no equal join condition, use the nested loop join
-                    // TODO optimize the plan, and use the config of `target_partitions` and `repartition_joins`
-                    Arc::new(NestedLoopJoinExec::try_new(
-                        physical_left,
-                        physical_right,
-                        join_filter,
-                        join_type,
-                    )?)
+                    let range_conditions = match &join_filter {
+                        Some(filter)
+                            if *join_type == JoinType::Inner
+                                && session_state
+                                    .config_options()
+                                    .optimizer
+                                    .enable_ie_join =>
+                        {
+                            IEJoinExec::range_conditions(filter)?.len()
+                        }
+                        _ => 0,
+                    };
+                    match join_filter {
+                        // there is no equal join condition, but the range
+                        // conditions avoid comparing every pair of rows
+                        Some(filter) if range_conditions >= 2 => Arc::new(
+                            IEJoinExec::try_new(physical_left, physical_right, filter)?,
+                        ),
+                        // there is no equal join condition, use the nested loop join
+                        // TODO optimize the plan, and use the config of `target_partitions` and `repartition_joins`
+                        join_filter => Arc::new(NestedLoopJoinExec::try_new(
+                            physical_left,
+                            physical_right,
+                            join_filter,
+                            join_type,
+                        )?),
+                    }
                 } else if session_state.config().target_partitions() > 1
                     && session_state.config().repartition_joins()
                     && !prefer_hash_join
@@ -1134,6 +1256,50 @@ impl DefaultPhysicalPlanner {
                 let [left, right] = children.two()?;
                 Arc::new(CrossJoinExec::new(left, right))
             }
+            LogicalPlan::AsOfJoin(AsOfJoin {
+                left,
+                right,
+                on,
+                match_condition,
+                join_type,
+                ..
+            }) => {
+                let [physical_left, physical_right] = children.two()?;
+                let left_df_schema = left.schema();
+                let right_df_schema = right.schema();
+                let execution_props = session_state.execution_props();
+                let join_on = on
+                    .iter()
+                    .map(|(l, r)| {
+                        let l = create_physical_expr(l, left_df_schema, execution_props)?;
+                        let r =
+                            create_physical_expr(r, right_df_schema, execution_props)?;
+                        Ok((l, r))
+                    })
+                    .collect::<Result<join_utils::JoinOn>>()?;
+                let Expr::BinaryExpr(BinaryExpr {
+                    left: match_left,
+                    op,
+                    right: match_right,
+                }) = match_condition
+                else {
+                    return internal_err!(
+                        "ASOF JOIN match condition must be a comparison, got {match_condition}"
+                    );
+                };
+                let match_condition = AsOfMatchCondition::new(
+                    create_physical_expr(match_left, left_df_schema, execution_props)?,
+                    *op,
+                    create_physical_expr(match_right, right_df_schema, execution_props)?,
+                );
+                Arc::new(AsOfJoinExec::try_new(
+                    physical_left,
+                    physical_right,
+                    join_on,
+                    match_condition,
+                    *join_type,
+                )?)
+            }
             LogicalPlan::RecursiveQuery(RecursiveQuery {
                 name, is_distinct, ..
             }) => {
@@ -1282,6 +1448,80 @@ impl DefaultPhysicalPlanner {
     }
 }
 
+/// Rewrites `exprs` over the output of the input plan of a DELETE or UPDATE
+/// statement into expressions over the scanned target table, collecting the
+/// filters applied to the table along the way.
+///
+/// The input plan must only consist of filters and projections on top of a
+/// scan of the target table.
+fn resolve_dml_input(
+    plan: &LogicalPlan,
+    exprs: &mut Vec<Expr>,
+    filters: &mut Vec<Expr>,
+) -> Result<()> {
+    // Only the rows of the target table can be deleted or updated, which
+    // can't depend on the rows of other tables
+    let mut reads_other_tables = matches!(
+        plan,
+        LogicalPlan::Join(_) | LogicalPlan::CrossJoin(_) | LogicalPlan::Subquery(_)
+    );
+    for expr in plan.expressions() {
+        reads_other_tables |= expr.exists(|expr| {
+            Ok(matches!(
+                expr,
+                Expr::ScalarSubquery(_) | Expr::InSubquery(_) | Expr::Exists(_)
+            ))
+        })?;
+    }
+    if reads_other_tables {
+        return not_impl_err!(
+            "DELETE and UPDATE reading other tables, such as UPDATE ... FROM or subqueries, are not supported"
+        );
+    }
+
+    match plan {
+        LogicalPlan::Filter(filter) => {
+            filters.extend(split_conjunction_owned(filter.predicate.clone()));
+            resolve_dml_input(&filter.input, exprs, filters)
+        }
+        LogicalPlan::Projection(projection) => {
+            let replace_map = projection
+                .schema
+                .columns()
+                .into_iter()
+                .zip(projection.expr.iter())
+                .map(|(column, expr)| (column.flat_name(), expr.clone().unalias()))
+                .collect::<HashMap<_, _>>();
+            for list in [&mut *exprs, &mut *filters] {
+                *list = list
+                    .drain(..)
+                    .map(|expr| replace_cols_by_name(expr, &replace_map))
+                    .collect::<Result<_>>()?;
+            }
+            resolve_dml_input(&projection.input, exprs, filters)
+        }
+        LogicalPlan::SubqueryAlias(alias) => {
+            resolve_dml_input(&alias.input, exprs, filters)
+        }
+        LogicalPlan::TableScan(scan) => {
+            filters.extend(scan.filters.iter().cloned());
+            Ok(())
+        }
+        // The filters were simplified to `false`, nothing to delete or update
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            ..
+        }) => {
+            filters.push(lit(false));
+            Ok(())
+        }
+        _ => not_impl_err!(
+            "Unsupported input plan for DELETE or UPDATE: {}",
+            plan.display()
+        ),
+    }
+}
+
 /// Expand and align a GROUPING SET expression.
 /// (see <https://www.postgresql.org/docs/current/queries-table-expressions.html#QUERIES-GROUPING-SETS>)
 ///
@@ -22,15 +22,18 @@ use async_trait::async_trait;
 use datafusion::arrow::datatypes::SchemaRef;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::datasource::function::TableFunctionImpl;
-use datafusion::datasource::TableProvider;
+use datafusion::datasource::{TableProvider, ViewTable};
 use datafusion::error::Result;
 use datafusion::execution::TaskContext;
 use datafusion::physical_plan::memory::MemoryExec;
 use datafusion::physical_plan::{collect, ExecutionPlan};
 use datafusion::prelude::SessionContext;
 use datafusion_catalog::Session;
-use datafusion_common::{assert_batches_eq, DFSchema, ScalarValue};
-use datafusion_expr::{EmptyRelation, Expr, LogicalPlan, Projection, TableType};
+use datafusion_common::{assert_batches_eq, plan_err, Column, DFSchema, ScalarValue};
+use datafusion_expr::planner::TableFunctionArg;
+use datafusion_expr::{
+    EmptyRelation, Expr, LogicalPlan, LogicalPlanBuilder, Projection, TableType,
+};
 use std::fs::File;
 use std::io::Seek;
 use std::path::Path;
@@ -105,6 +108,91 @@ async fn test_deregister_udtf() -> Result<()> {
     Ok(())
 }
 
+/// test udtf that takes a relation as an argument
+#[tokio::test]
+async fn test_table_arg_udtf() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_udtf("top_n", Arc::new(TopNTableFunc {}));
+    ctx.register_udtf("read_csv", Arc::new(SimpleCsvTableFunc {}));
+
+    ctx.sql("CREATE TABLE t AS VALUES (3, 'c'), (1, 'a'), (4, 'd'), (2, 'b')")
+        .await?
+        .collect()
+        .await?;
+
+    let rbs = ctx
+        .sql("SELECT * FROM top_n(TABLE(t), 2)")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+---------+---------+",
+        "| column1 | column2 |",
+        "+---------+---------+",
+        "| 1       | a       |",
+        "| 2       | b       |",
+        "+---------+---------+",
+    ];
+    assert_batches_eq!(expected, &rbs);
+
+    // subqueries can be passed as table arguments, too
+    let rbs = ctx
+        .sql("SELECT column2 FROM top_n((SELECT column2 FROM t WHERE column1 > 1), 1)")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+---------+",
+        "| column2 |",
+        "+---------+",
+        "| b       |",
+        "+---------+",
+    ];
+    assert_batches_eq!(expected, &rbs);
+
+    // functions only implementing `call` reject table arguments
+    let err = ctx
+        .sql("SELECT * FROM read_csv(TABLE(t))")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: Table function 'read_csv' does not accept table arguments"
+    );
+
+    Ok(())
+}
+
+/// Returns the first `n` rows of its table argument ordered by the first column
+struct TopNTableFunc {}
+
+impl TableFunctionImpl for TopNTableFunc {
+    fn call(&self, _args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        plan_err!("top_n expects a table argument")
+    }
+
+    fn call_with_args(
+        &self,
+        _name: &str,
+        args: &[TableFunctionArg],
+    ) -> Result<Arc<dyn TableProvider>> {
+        let (Some(TableFunctionArg::Table(input)), Some(TableFunctionArg::Expr(n))) =
+            (args.first(), args.get(1))
+        else {
+            return plan_err!("top_n expects a table and a row count");
+        };
+        let Expr::Literal(ScalarValue::Int64(Some(n))) = n else {
+            return plan_err!("top_n expects an integer row count, got {n}");
+        };
+        let first_column = Column::from(input.schema().qualified_field(0));
+        let plan = LogicalPlanBuilder::from(input.clone())
+            .sort(vec![Expr::Column(first_column).sort(true, false)])?
+            .limit(0, Some(*n as usize))?
+            .build()?;
+        Ok(Arc::new(ViewTable::try_new(plan, None)?))
+    }
+}
+
 struct SimpleCsvTable {
     schema: SchemaRef,
     exprs: Vec<Expr>,
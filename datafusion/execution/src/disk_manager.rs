@@ -23,6 +23,7 @@ use log::debug;
 use parking_lot::Mutex;
 use rand::{thread_rng, Rng};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tempfile::{Builder, NamedTempFile, TempDir};
 
@@ -76,6 +77,26 @@ pub struct DiskManager {
     /// If `Some(vec![])` a new OS specified temporary directory will be created
     /// If `None` an error will be returned (configured not to spill)
     local_dirs: Mutex<Option<Vec<Arc<TempDir>>>>,
+    /// Disk space used by the temporary files created by this manager
+    disk_usage: Arc<DiskUsage>,
+}
+
+/// Tracks the disk space used by the temporary files of a [`DiskManager`]
+#[derive(Debug)]
+struct DiskUsage {
+    /// The maximum number of bytes the temporary files may use in total
+    max_bytes: AtomicU64,
+    /// The number of bytes currently used by the temporary files
+    used_bytes: AtomicU64,
+}
+
+impl Default for DiskUsage {
+    fn default() -> Self {
+        Self {
+            max_bytes: AtomicU64::new(u64::MAX),
+            used_bytes: AtomicU64::new(0),
+        }
+    }
 }
 
 impl DiskManager {
@@ -85,6 +106,7 @@ impl DiskManager {
             DiskManagerConfig::Existing(manager) => Ok(manager),
             DiskManagerConfig::NewOs => Ok(Arc::new(Self {
                 local_dirs: Mutex::new(Some(vec![])),
+                disk_usage: Default::default(),
             })),
             DiskManagerConfig::NewSpecified(conf_dirs) => {
                 let local_dirs = create_local_dirs(conf_dirs)?;
@@ -94,10 +116,12 @@ impl DiskManager {
                 );
                 Ok(Arc::new(Self {
                     local_dirs: Mutex::new(Some(local_dirs)),
+                    disk_usage: Default::default(),
                 }))
             }
             DiskManagerConfig::Disabled => Ok(Arc::new(Self {
                 local_dirs: Mutex::new(None),
+                disk_usage: Default::default(),
             })),
         }
     }
@@ -109,6 +133,30 @@ impl DiskManager {
         self.local_dirs.lock().is_some()
     }
 
+    /// Sets the maximum number of bytes all temporary files created by this
+    /// disk manager may use in total. Defaults to no limit.
+    ///
+    /// The limit is checked by [`RefCountedTempFile::update_disk_usage`],
+    /// which spilling operators call while writing to a temporary file.
+    pub fn set_max_temp_directory_size(&self, max_bytes: u64) {
+        self.disk_usage
+            .max_bytes
+            .store(max_bytes, Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of bytes all temporary files created by
+    /// this disk manager may use in total
+    pub fn max_temp_directory_size(&self) -> u64 {
+        self.disk_usage.max_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes currently used by the temporary files
+    /// created by this disk manager, as of their last
+    /// [`RefCountedTempFile::update_disk_usage`]
+    pub fn used_disk_space(&self) -> u64 {
+        self.disk_usage.used_bytes.load(Ordering::Relaxed)
+    }
+
     /// Return a temporary file from a randomized choice in the configured locations
     ///
     /// If the file can not be created for some reason, returns an
@@ -143,6 +191,9 @@ impl DiskManager {
             tempfile: Builder::new()
                 .tempfile_in(local_dirs[dir_index].as_ref())
                 .map_err(DataFusionError::IoError)?,
+            disk_usage: Arc::clone(&self.disk_usage),
+            current_file_disk_usage: 0,
+            max_temp_directory_size: None,
         })
     }
 }
@@ -156,6 +207,12 @@ pub struct RefCountedTempFile {
     #[allow(dead_code)]
     parent_temp_dir: Arc<TempDir>,
     tempfile: NamedTempFile,
+    /// Disk space used by all temporary files of the owning [`DiskManager`]
+    disk_usage: Arc<DiskUsage>,
+    /// The size of this file as of the last call to `update_disk_usage`
+    current_file_disk_usage: u64,
+    /// Lower limit than the one of the owning [`DiskManager`], if any
+    max_temp_directory_size: Option<u64>,
 }
 
 impl RefCountedTempFile {
@@ -166,6 +223,73 @@ impl RefCountedTempFile {
     pub fn inner(&self) -> &NamedTempFile {
         &self.tempfile
     }
+
+    /// Limits the disk space the temporary files of the owning
+    /// [`DiskManager`] may use while writing to this file to `max_bytes`,
+    /// if lower than the maximum temporary directory size of the manager.
+    ///
+    /// Used to apply the `datafusion.execution.max_temp_directory_size`
+    /// setting of a session.
+    pub fn with_max_temp_directory_size(mut self, max_bytes: Option<u64>) -> Self {
+        self.max_temp_directory_size = max_bytes;
+        self
+    }
+
+    /// Updates the disk space accounted to this file with its current size
+    /// on disk. To be called regularly while writing to the file, such as
+    /// after each written batch, so that the limit is enforced before the
+    /// whole file is written.
+    ///
+    /// Returns a resources exhausted error if the temporary files of the
+    /// owning [`DiskManager`] now exceed the maximum temporary directory size.
+    pub fn update_disk_usage(&mut self) -> Result<()> {
+        let metadata = self.tempfile.as_file().metadata()?;
+        let new_disk_usage = metadata.len();
+
+        let used_bytes = if new_disk_usage >= self.current_file_disk_usage {
+            let delta = new_disk_usage - self.current_file_disk_usage;
+            self.disk_usage
+                .used_bytes
+                .fetch_add(delta, Ordering::Relaxed)
+                + delta
+        } else {
+            let delta = self.current_file_disk_usage - new_disk_usage;
+            self.disk_usage
+                .used_bytes
+                .fetch_sub(delta, Ordering::Relaxed)
+                - delta
+        };
+        self.current_file_disk_usage = new_disk_usage;
+
+        let max_bytes = self
+            .disk_usage
+            .max_bytes
+            .load(Ordering::Relaxed)
+            .min(self.max_temp_directory_size.unwrap_or(u64::MAX));
+        if used_bytes > max_bytes {
+            return Err(resources_datafusion_err!(
+                "The used disk space during the spilling process has exceeded the allowable limit of {max_bytes} bytes. \
+                Try increasing the max temp directory size of the DiskManager \
+                or the datafusion.execution.max_temp_directory_size setting"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the size of this file as of the last call to
+    /// [`Self::update_disk_usage`]
+    pub fn current_disk_usage(&self) -> u64 {
+        self.current_file_disk_usage
+    }
+}
+
+impl Drop for RefCountedTempFile {
+    fn drop(&mut self) {
+        self.disk_usage
+            .used_bytes
+            .fetch_sub(self.current_file_disk_usage, Ordering::Relaxed);
+    }
 }
 
 /// Setup local dirs by creating one new dir in each of the given dirs
@@ -260,6 +384,54 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_max_temp_directory_size() -> Result<()> {
+        use std::io::Write;
+
+        let dm = DiskManager::try_new(DiskManagerConfig::new())?;
+        assert_eq!(dm.max_temp_directory_size(), u64::MAX);
+        dm.set_max_temp_directory_size(100);
+
+        let mut file1 = dm.create_tmp_file("Testing")?;
+        file1.inner().as_file().write_all(&[0; 60])?;
+        file1.update_disk_usage()?;
+        assert_eq!(file1.current_disk_usage(), 60);
+        assert_eq!(dm.used_disk_space(), 60);
+
+        let mut file2 = dm.create_tmp_file("Testing")?;
+        file2.inner().as_file().write_all(&[0; 60])?;
+        let err = file2.update_disk_usage().unwrap_err();
+        assert!(
+            err.strip_backtrace().contains(
+                "The used disk space during the spilling process has exceeded the allowable limit of 100 bytes"
+            ),
+            "{err}"
+        );
+        assert_eq!(dm.used_disk_space(), 120);
+
+        // dropping a file releases its disk space
+        drop(file1);
+        assert_eq!(dm.used_disk_space(), 60);
+        file2.update_disk_usage()?;
+        drop(file2);
+        assert_eq!(dm.used_disk_space(), 0);
+
+        // a file can have a lower limit than the disk manager
+        let mut file3 = dm
+            .create_tmp_file("Testing")?
+            .with_max_temp_directory_size(Some(50));
+        file3.inner().as_file().write_all(&[0; 60])?;
+        let err = file3.update_disk_usage().unwrap_err();
+        assert!(
+            err.strip_backtrace().contains(
+                "The used disk space during the spilling process has exceeded the allowable limit of 50 bytes"
+            ),
+            "{err}"
+        );
+
+        Ok(())
+    }
+
     /// Asserts that `file_path` is found anywhere in any of `dir` directories
     fn assert_path_in_dirs<'a>(
         file_path: &'a Path,
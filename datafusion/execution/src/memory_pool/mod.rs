@@ -82,6 +82,10 @@ pub use pool::*;
 ///
 /// * [`FairSpillPool`]: Limits memory usage to a fixed size, allocating memory
 ///   to all spilling operators fairly
+///
+/// * [`HierarchicalMemoryPool`]: Limits memory usage to a fixed size, divided
+///   between groups of consumers (e.g. queries or tenants) with their own
+///   quotas and priorities
 pub trait MemoryPool: Send + Sync + std::fmt::Debug {
     /// Registers a new [`MemoryConsumer`]
     ///
@@ -16,13 +16,16 @@
 // under the License.
 
 use crate::memory_pool::{MemoryConsumer, MemoryPool, MemoryReservation};
-use datafusion_common::{resources_datafusion_err, DataFusionError, Result};
+use datafusion_common::{
+    resources_datafusion_err, resources_err, DataFusionError, Result,
+};
 use hashbrown::HashMap;
 use log::debug;
 use parking_lot::Mutex;
 use std::{
     num::NonZeroUsize,
     sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    sync::Arc,
 };
 
 /// A [`MemoryPool`] that enforces no limit
@@ -395,10 +398,334 @@ fn provide_top_memory_consumers_to_error_msg(
     format!("Additional allocation failed with top memory consumers (across reservations) as: {}. Error: {}", top_consumers, error_msg)
 }
 
+/// A [`MemoryPool`] that divides a fixed amount of memory between groups of
+/// consumers, such as the queries of different tenants sharing a `RuntimeEnv`.
+///
+/// Each group is created with [`Self::new_group`], which returns a
+/// [`GroupMemoryPool`] that should be used as the memory pool of the group's
+/// queries. A group has a
+///
+/// * `quota`: memory set aside for the group, that is always available to it,
+///   whether other groups are using it or not
+///
+/// * `priority`: the weight of the group when sharing the memory that is not
+///   set aside for any group (the "slack")
+///
+/// A group can use up to its quota plus its share of the slack, which is
+/// divided between the groups with registered consumers in proportion to
+/// their priorities:
+///
+/// ```text
+///   limit = quota + slack * priority / sum(priorities of active groups)
+/// ```
+///
+/// Consumers registered directly with the [`HierarchicalMemoryPool`] belong to
+/// a `default` group without a quota and with a priority of 1.
+///
+/// Within a group, memory is allocated in a first-come, first-serve fashion.
+/// Errors name the group that failed to allocate, so that the query exceeding
+/// its budget can be identified.
+///
+/// # Example
+///
+/// ```
+/// # use std::num::NonZeroUsize;
+/// # use std::sync::Arc;
+/// # use datafusion_execution::memory_pool::{HierarchicalMemoryPool, MemoryConsumer, MemoryPool};
+/// let pool = HierarchicalMemoryPool::new(100);
+/// // 40 bytes are set aside for "etl", the remaining 60 bytes are shared
+/// // between "etl" and "adhoc" in a 1:2 ratio
+/// let etl: Arc<dyn MemoryPool> = Arc::new(
+///     pool.new_group("etl", 40, NonZeroUsize::new(1).unwrap()).unwrap(),
+/// );
+/// let adhoc: Arc<dyn MemoryPool> = Arc::new(
+///     pool.new_group("adhoc", 0, NonZeroUsize::new(2).unwrap()).unwrap(),
+/// );
+/// let mut etl_sort = MemoryConsumer::new("etl sort").register(&etl);
+/// let mut adhoc_sort = MemoryConsumer::new("adhoc sort").register(&adhoc);
+///
+/// etl_sort.try_grow(40 + 20).unwrap();
+/// assert!(etl_sort.try_grow(1).is_err());
+/// adhoc_sort.try_grow(40).unwrap();
+/// assert!(adhoc_sort.try_grow(1).is_err());
+/// ```
+#[derive(Debug)]
+pub struct HierarchicalMemoryPool {
+    inner: Arc<HierarchicalPoolInner>,
+}
+
+/// A group of a [`HierarchicalMemoryPool`] with its own quota and priority
+///
+/// The group is removed from the [`HierarchicalMemoryPool`] when dropped,
+/// releasing its quota.
+#[derive(Debug)]
+pub struct GroupMemoryPool {
+    id: usize,
+    name: String,
+    inner: Arc<HierarchicalPoolInner>,
+}
+
+#[derive(Debug)]
+struct HierarchicalPoolInner {
+    /// The total memory limit
+    pool_size: usize,
+
+    state: Mutex<HierarchicalPoolState>,
+}
+
+#[derive(Debug)]
+struct HierarchicalPoolState {
+    groups: HashMap<usize, MemoryGroup>,
+    next_id: usize,
+}
+
+#[derive(Debug)]
+struct MemoryGroup {
+    name: String,
+    quota: usize,
+    priority: usize,
+    /// The number of registered consumers
+    consumers: usize,
+    /// The memory reserved by the consumers
+    used: usize,
+}
+
+/// The id of the group of consumers registered with the [`HierarchicalMemoryPool`]
+const DEFAULT_GROUP_ID: usize = 0;
+
+impl HierarchicalMemoryPool {
+    /// Allocate up to `pool_size` bytes between all groups
+    pub fn new(pool_size: usize) -> Self {
+        debug!("Created new HierarchicalMemoryPool(pool_size={pool_size})");
+        let default_group = MemoryGroup {
+            name: "default".to_string(),
+            quota: 0,
+            priority: 1,
+            consumers: 0,
+            used: 0,
+        };
+        Self {
+            inner: Arc::new(HierarchicalPoolInner {
+                pool_size,
+                state: Mutex::new(HierarchicalPoolState {
+                    groups: HashMap::from([(DEFAULT_GROUP_ID, default_group)]),
+                    next_id: DEFAULT_GROUP_ID + 1,
+                }),
+            }),
+        }
+    }
+
+    /// Create a new group named `name` that is guaranteed `quota` bytes and
+    /// shares the slack of the pool with the given `priority`
+    ///
+    /// Returns an error if the quota cannot be set aside because the quotas of
+    /// all groups would exceed the pool size.
+    pub fn new_group(
+        &self,
+        name: impl Into<String>,
+        quota: usize,
+        priority: NonZeroUsize,
+    ) -> Result<GroupMemoryPool> {
+        let name = name.into();
+        let mut state = self.inner.state.lock();
+        let reserved = state.reserved_quota();
+        if reserved + quota > self.inner.pool_size {
+            return resources_err!(
+                "Failed to create memory pool group '{name}' with a quota of {quota} bytes - {} bytes remain available for quotas",
+                self.inner.pool_size.saturating_sub(reserved)
+            );
+        }
+        let id = state.next_id;
+        state.next_id += 1;
+        state.groups.insert(
+            id,
+            MemoryGroup {
+                name: name.clone(),
+                quota,
+                priority: priority.get(),
+                consumers: 0,
+                used: 0,
+            },
+        );
+        Ok(GroupMemoryPool {
+            id,
+            name,
+            inner: Arc::clone(&self.inner),
+        })
+    }
+}
+
+impl GroupMemoryPool {
+    /// Returns the name of this group
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the maximum amount of memory this group can currently reserve,
+    /// its quota plus its share of the slack
+    pub fn limit(&self) -> usize {
+        self.inner.state.lock().limit(self.id, self.inner.pool_size)
+    }
+}
+
+impl Drop for GroupMemoryPool {
+    fn drop(&mut self) {
+        self.inner.state.lock().groups.remove(&self.id);
+    }
+}
+
+impl HierarchicalPoolState {
+    /// The memory set aside for the quotas of all groups
+    fn reserved_quota(&self) -> usize {
+        self.groups.values().map(|group| group.quota).sum()
+    }
+
+    /// The quota of the group `id` plus its share of the slack
+    fn limit(&self, id: usize, pool_size: usize) -> usize {
+        let group = &self.groups[&id];
+        let slack = pool_size.saturating_sub(self.reserved_quota());
+        // The group always takes part in sharing the slack, even if it has no
+        // consumers yet
+        let priorities: usize = self
+            .groups
+            .iter()
+            .filter(|(group_id, group)| **group_id == id || group.consumers > 0)
+            .map(|(_, group)| group.priority)
+            .sum();
+        let share =
+            (slack as u128 * group.priority as u128 / priorities as u128) as usize;
+        group.quota + share
+    }
+
+    fn register(&mut self, id: usize) {
+        if let Some(group) = self.groups.get_mut(&id) {
+            group.consumers += 1;
+        }
+    }
+
+    fn unregister(&mut self, id: usize) {
+        if let Some(group) = self.groups.get_mut(&id) {
+            debug_assert!(group.consumers > 0, "unregister without register");
+            group.consumers = group.consumers.saturating_sub(1);
+        }
+    }
+
+    fn grow(&mut self, id: usize, additional: usize) {
+        if let Some(group) = self.groups.get_mut(&id) {
+            group.used += additional;
+        }
+    }
+
+    fn shrink(&mut self, id: usize, shrink: usize) {
+        if let Some(group) = self.groups.get_mut(&id) {
+            group.used -= shrink;
+        }
+    }
+
+    fn try_grow(
+        &mut self,
+        id: usize,
+        pool_size: usize,
+        reservation: &MemoryReservation,
+        additional: usize,
+    ) -> Result<()> {
+        // Memory used by, or set aside for, the other groups
+        let committed: usize = self
+            .groups
+            .iter()
+            .filter(|(group_id, _)| **group_id != id)
+            .map(|(_, group)| group.used.max(group.quota))
+            .sum();
+        let limit = self
+            .limit(id, pool_size)
+            .min(pool_size.saturating_sub(committed));
+
+        let group = self.groups.get_mut(&id).unwrap();
+        let available = limit.saturating_sub(group.used);
+        if available < additional {
+            return Err(resources_datafusion_err!(
+                "Failed to allocate additional {} bytes for {} in memory pool group '{}' with {} bytes already allocated for this group - {} bytes remain available for the group (quota {} bytes)",
+                additional,
+                reservation.registration.consumer.name,
+                group.name,
+                group.used,
+                available,
+                group.quota
+            ));
+        }
+        group.used += additional;
+        Ok(())
+    }
+}
+
+impl MemoryPool for HierarchicalMemoryPool {
+    fn register(&self, _consumer: &MemoryConsumer) {
+        self.inner.state.lock().register(DEFAULT_GROUP_ID);
+    }
+
+    fn unregister(&self, _consumer: &MemoryConsumer) {
+        self.inner.state.lock().unregister(DEFAULT_GROUP_ID);
+    }
+
+    fn grow(&self, _reservation: &MemoryReservation, additional: usize) {
+        self.inner.state.lock().grow(DEFAULT_GROUP_ID, additional);
+    }
+
+    fn shrink(&self, _reservation: &MemoryReservation, shrink: usize) {
+        self.inner.state.lock().shrink(DEFAULT_GROUP_ID, shrink);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.inner.state.lock().try_grow(
+            DEFAULT_GROUP_ID,
+            self.inner.pool_size,
+            reservation,
+            additional,
+        )
+    }
+
+    /// Returns the memory reserved by all groups
+    fn reserved(&self) -> usize {
+        let state = self.inner.state.lock();
+        state.groups.values().map(|group| group.used).sum()
+    }
+}
+
+impl MemoryPool for GroupMemoryPool {
+    fn register(&self, _consumer: &MemoryConsumer) {
+        self.inner.state.lock().register(self.id);
+    }
+
+    fn unregister(&self, _consumer: &MemoryConsumer) {
+        self.inner.state.lock().unregister(self.id);
+    }
+
+    fn grow(&self, _reservation: &MemoryReservation, additional: usize) {
+        self.inner.state.lock().grow(self.id, additional);
+    }
+
+    fn shrink(&self, _reservation: &MemoryReservation, shrink: usize) {
+        self.inner.state.lock().shrink(self.id, shrink);
+    }
+
+    fn try_grow(&self, reservation: &MemoryReservation, additional: usize) -> Result<()> {
+        self.inner.state.lock().try_grow(
+            self.id,
+            self.inner.pool_size,
+            reservation,
+            additional,
+        )
+    }
+
+    /// Returns the memory reserved by this group
+    fn reserved(&self) -> usize {
+        self.inner.state.lock().groups[&self.id].used
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Arc;
 
     #[test]
     fn test_fair() {
@@ -686,4 +1013,83 @@ mod tests {
             res
         );
     }
+
+    #[test]
+    fn test_hierarchical_share_slack() {
+        let pool = HierarchicalMemoryPool::new(100);
+        let etl: Arc<dyn MemoryPool> = Arc::new(
+            pool.new_group("etl", 40, NonZeroUsize::new(1).unwrap())
+                .unwrap(),
+        );
+        let adhoc: Arc<dyn MemoryPool> = Arc::new(
+            pool.new_group("adhoc", 0, NonZeroUsize::new(2).unwrap())
+                .unwrap(),
+        );
+
+        // The slack of 60 bytes is shared 1:2 between the active groups
+        let mut r1 = MemoryConsumer::new("r1").register(&etl);
+        let mut r2 = MemoryConsumer::new("r2").register(&adhoc);
+        r1.try_grow(60).unwrap();
+        let err = r1.try_grow(1).unwrap_err().strip_backtrace();
+        assert_eq!(err, "Resources exhausted: Failed to allocate additional 1 bytes for r1 in memory pool group 'etl' with 60 bytes already allocated for this group - 0 bytes remain available for the group (quota 40 bytes)");
+
+        r2.try_grow(40).unwrap();
+        let err = r2.try_grow(1).unwrap_err().strip_backtrace();
+        assert_eq!(err, "Resources exhausted: Failed to allocate additional 1 bytes for r2 in memory pool group 'adhoc' with 40 bytes already allocated for this group - 0 bytes remain available for the group (quota 0 bytes)");
+
+        assert_eq!(etl.reserved(), 60);
+        assert_eq!(adhoc.reserved(), 40);
+        assert_eq!(pool.reserved(), 100);
+
+        // Once the adhoc group has no consumers, etl can use all the slack
+        drop(r2);
+        r1.try_grow(40).unwrap();
+        assert_eq!(pool.reserved(), 100);
+    }
+
+    #[test]
+    fn test_hierarchical_quota() {
+        let pool = Arc::new(HierarchicalMemoryPool::new(100));
+        let group = pool
+            .new_group("q1", 50, NonZeroUsize::new(1).unwrap())
+            .unwrap();
+        assert_eq!(group.name(), "q1");
+        assert_eq!(group.limit(), 100);
+        let group: Arc<dyn MemoryPool> = Arc::new(group);
+
+        // Consumers of the default group cannot use the quota of q1
+        let default_pool = Arc::clone(&pool) as Arc<dyn MemoryPool>;
+        let mut r1 = MemoryConsumer::new("r1").register(&default_pool);
+        r1.try_grow(50).unwrap();
+        let err = r1.try_grow(1).unwrap_err().strip_backtrace();
+        assert_eq!(err, "Resources exhausted: Failed to allocate additional 1 bytes for r1 in memory pool group 'default' with 50 bytes already allocated for this group - 0 bytes remain available for the group (quota 0 bytes)");
+
+        // ... which is still available to q1
+        let mut r2 = MemoryConsumer::new("r2").register(&group);
+        r2.try_grow(50).unwrap();
+        assert!(r2.try_grow(1).is_err());
+
+        // Infallible growth is still tracked
+        r2.grow(10);
+        assert_eq!(pool.reserved(), 110);
+        assert!(r1.try_grow(1).is_err());
+    }
+
+    #[test]
+    fn test_hierarchical_group_quota_exceeds_pool() {
+        let pool = HierarchicalMemoryPool::new(100);
+        let group = pool
+            .new_group("q1", 60, NonZeroUsize::new(1).unwrap())
+            .unwrap();
+        let err = pool
+            .new_group("q2", 50, NonZeroUsize::new(1).unwrap())
+            .unwrap_err()
+            .strip_backtrace();
+        assert_eq!(err, "Resources exhausted: Failed to create memory pool group 'q2' with a quota of 50 bytes - 40 bytes remain available for quotas");
+
+        // Dropping a group releases its quota
+        drop(group);
+        pool.new_group("q2", 50, NonZeroUsize::new(1).unwrap())
+            .unwrap();
+    }
 }
@@ -71,6 +71,7 @@ impl RuntimeEnv {
         let RuntimeConfig {
             memory_pool,
             disk_manager,
+            max_temp_directory_size,
             cache_manager,
             object_store_registry,
         } = config;
@@ -78,9 +79,14 @@ impl RuntimeEnv {
         let memory_pool =
             memory_pool.unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
 
+        let disk_manager = DiskManager::try_new(disk_manager)?;
+        if let Some(max_temp_directory_size) = max_temp_directory_size {
+            disk_manager.set_max_temp_directory_size(max_temp_directory_size);
+        }
+
         Ok(Self {
             memory_pool,
-            disk_manager: DiskManager::try_new(disk_manager)?,
+            disk_manager,
             cache_manager: CacheManager::try_new(&cache_manager)?,
             object_store_registry,
         })
@@ -160,6 +166,11 @@ pub type RuntimeConfig = RuntimeEnvBuilder;
 pub struct RuntimeEnvBuilder {
     /// DiskManager to manage temporary disk file usage
     pub disk_manager: DiskManagerConfig,
+    /// Maximum number of bytes the temporary files of the [`DiskManager`]
+    /// may use in total, set with [`Self::with_max_temp_directory_size`]
+    ///
+    /// Defaults to no limit if `None`
+    max_temp_directory_size: Option<u64>,
     /// [`MemoryPool`] from which to allocate memory
     ///
     /// Defaults to using an [`UnboundedMemoryPool`] if `None`
@@ -181,6 +192,7 @@ impl RuntimeEnvBuilder {
     pub fn new() -> Self {
         Self {
             disk_manager: Default::default(),
+            max_temp_directory_size: None,
             memory_pool: Default::default(),
             cache_manager: Default::default(),
             object_store_registry: Arc::new(DefaultObjectStoreRegistry::default()),
@@ -233,15 +245,27 @@ impl RuntimeEnvBuilder {
         self.with_disk_manager(DiskManagerConfig::new_specified(vec![path.into()]))
     }
 
+    /// Specify the maximum number of bytes the temporary files created while
+    /// spilling may use in total
+    pub fn with_max_temp_directory_size(mut self, max_temp_directory_size: u64) -> Self {
+        self.max_temp_directory_size = Some(max_temp_directory_size);
+        self
+    }
+
     /// Build a RuntimeEnv
     pub fn build(self) -> Result<RuntimeEnv> {
         let memory_pool = self
             .memory_pool
             .unwrap_or_else(|| Arc::new(UnboundedMemoryPool::default()));
 
+        let disk_manager = DiskManager::try_new(self.disk_manager)?;
+        if let Some(max_temp_directory_size) = self.max_temp_directory_size {
+            disk_manager.set_max_temp_directory_size(max_temp_directory_size);
+        }
+
         Ok(RuntimeEnv {
             memory_pool,
-            disk_manager: DiskManager::try_new(self.disk_manager)?,
+            disk_manager,
             cache_manager: CacheManager::try_new(&self.cache_manager)?,
             object_store_registry: self.object_store_registry,
         })
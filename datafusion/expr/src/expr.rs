@@ -332,6 +332,11 @@ pub enum Expr {
     OuterReferenceColumn(DataType, Column),
     /// Unnest expression
     Unnest(Unnest),
+    /// A lambda function, such as `x -> x + 1`, passed as an argument to a
+    /// higher-order function such as `array_transform`
+    Lambda(Lambda),
+    /// A reference to a parameter of the enclosing [`Expr::Lambda`]
+    LambdaVariable(LambdaVariable),
 }
 
 impl Default for Expr {
@@ -937,6 +942,51 @@ impl Placeholder {
     }
 }
 
+/// Lambda function expression, such as `(acc, x) -> acc + x`.
+///
+/// Lambda functions can only be arguments of higher-order scalar functions,
+/// which evaluate them for the values of their parameters, see
+/// [`ScalarUDFImpl::invoke_with_lambdas`]. The body of the lambda can only
+/// reference its parameters, using [`Expr::LambdaVariable`].
+///
+/// [`ScalarUDFImpl::invoke_with_lambdas`]: crate::ScalarUDFImpl::invoke_with_lambdas
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Lambda {
+    /// The names of the parameters
+    pub params: Vec<String>,
+    /// The body of the lambda
+    pub body: Box<Expr>,
+}
+
+impl Lambda {
+    /// Create a new lambda function expression
+    pub fn new(params: Vec<String>, body: Expr) -> Self {
+        Self {
+            params,
+            body: Box::new(body),
+        }
+    }
+}
+
+/// A reference to a parameter of a [`Lambda`] in its body
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LambdaVariable {
+    /// The name of the parameter
+    pub name: String,
+    /// The type of the parameter, determined by the higher-order function
+    pub data_type: DataType,
+}
+
+impl LambdaVariable {
+    /// Create a new lambda variable expression
+    pub fn new(name: impl Into<String>, data_type: DataType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+        }
+    }
+}
+
 /// Grouping sets
 ///
 /// See <https://www.postgresql.org/docs/current/queries-table-expressions.html#QUERIES-GROUPING-SETS>
@@ -1146,6 +1196,8 @@ impl Expr {
             Expr::WindowFunction { .. } => "WindowFunction",
             Expr::Wildcard { .. } => "Wildcard",
             Expr::Unnest { .. } => "Unnest",
+            Expr::Lambda { .. } => "Lambda",
+            Expr::LambdaVariable { .. } => "LambdaVariable",
         }
     }
 
@@ -1656,6 +1708,8 @@ impl Expr {
             | Expr::WindowFunction(..)
             | Expr::Literal(..)
             | Expr::Sort(..)
+            | Expr::Lambda(..)
+            | Expr::LambdaVariable(..)
             | Expr::Placeholder(..) => false,
         }
     }
@@ -1828,6 +1882,15 @@ impl Expr {
                 column.hash(hasher);
             }
             Expr::Unnest(Unnest { expr: _expr }) => {}
+            Expr::Lambda(Lambda {
+                params,
+                body: _body,
+            }) => {
+                params.hash(hasher);
+            }
+            Expr::LambdaVariable(lambda_variable) => {
+                lambda_variable.hash(hasher);
+            }
         };
     }
 }
@@ -1874,6 +1937,7 @@ impl<'a> Display for SchemaDisplay<'a> {
             | Expr::Sort(_)
             | Expr::OuterReferenceColumn(..)
             | Expr::Placeholder(_)
+            | Expr::LambdaVariable(_)
             | Expr::Wildcard { .. } => write!(f, "{}", self.0),
 
             Expr::AggregateFunction(AggregateFunction {
@@ -2042,6 +2106,10 @@ impl<'a> Display for SchemaDisplay<'a> {
             Expr::Unnest(Unnest { expr }) => {
                 write!(f, "UNNEST({})", SchemaDisplay(expr))
             }
+            Expr::Lambda(Lambda { params, body }) => {
+                fmt_lambda_params(f, params)?;
+                write!(f, " -> {}", SchemaDisplay(body))
+            }
             Expr::ScalarFunction(ScalarFunction { func, args }) => {
                 match func.schema_name(args) {
                     Ok(name) => {
@@ -2116,6 +2184,15 @@ impl<'a> Display for SchemaDisplay<'a> {
     }
 }
 
+/// Formats the parameters of a lambda, in parentheses unless there is a single
+/// one, as in `x -> x + 1` or `(acc, x) -> acc + x`
+fn fmt_lambda_params(f: &mut Formatter<'_>, params: &[String]) -> fmt::Result {
+    match params {
+        [param] => write!(f, "{param}"),
+        params => write!(f, "({})", params.join(", ")),
+    }
+}
+
 /// Get schema_name for Vector of expressions
 ///
 /// Internal usage. Please call `schema_name_from_exprs` instead
@@ -2363,6 +2440,11 @@ impl fmt::Display for Expr {
                 // TODO: use Display instead of Debug, there is non-unique expression name in projection issue.
                 write!(f, "UNNEST({expr:?})")
             }
+            Expr::Lambda(Lambda { params, body }) => {
+                fmt_lambda_params(f, params)?;
+                write!(f, " -> {body}")
+            }
+            Expr::LambdaVariable(LambdaVariable { name, .. }) => write!(f, "{name}"),
         }
     }
 }
@@ -18,8 +18,8 @@
 //! Functions for creating logical expressions
 
 use crate::expr::{
-    AggregateFunction, BinaryExpr, Cast, Exists, GroupingSet, InList, InSubquery,
-    Placeholder, TryCast, Unnest, WildcardOptions, WindowFunction,
+    AggregateFunction, BinaryExpr, Cast, Exists, GroupingSet, InList, InSubquery, Lambda,
+    LambdaVariable, Placeholder, TryCast, Unnest, WildcardOptions, WindowFunction,
 };
 use crate::function::{
     AccumulatorArgs, AccumulatorFactoryFunction, PartitionEvaluatorFactory,
@@ -109,6 +109,30 @@ pub fn placeholder(id: impl Into<String>) -> Expr {
     })
 }
 
+/// Create a lambda function [`Expr::Lambda`], to pass as argument of a
+/// higher-order function
+///
+/// # Example
+///
+/// ```rust
+/// # use arrow::datatypes::DataType;
+/// # use datafusion_expr::{lambda, lambda_variable, lit};
+/// let f = lambda(["x"], lambda_variable("x", DataType::Int64) + lit(1i64));
+/// assert_eq!(f.to_string(), "x -> x + Int64(1)")
+/// ```
+pub fn lambda(params: impl IntoIterator<Item = impl Into<String>>, body: Expr) -> Expr {
+    Expr::Lambda(Lambda::new(
+        params.into_iter().map(Into::into).collect(),
+        body,
+    ))
+}
+
+/// Create a reference to the parameter `name` of type `data_type` of the
+/// enclosing [`lambda`]
+pub fn lambda_variable(name: impl Into<String>, data_type: DataType) -> Expr {
+    Expr::LambdaVariable(LambdaVariable::new(name, data_type))
+}
+
 /// Create an '*' [`Expr::Wildcard`] expression that matches all columns
 ///
 /// # Example
@@ -17,8 +17,8 @@
 
 use super::{Between, Expr, Like};
 use crate::expr::{
-    AggregateFunction, Alias, BinaryExpr, Cast, InList, InSubquery, Placeholder,
-    ScalarFunction, Sort, TryCast, Unnest, WindowFunction,
+    AggregateFunction, Alias, BinaryExpr, Cast, InList, InSubquery, Lambda,
+    LambdaVariable, Placeholder, ScalarFunction, Sort, TryCast, Unnest, WindowFunction,
 };
 use crate::type_coercion::binary::get_result_type;
 use crate::type_coercion::functions::{
@@ -111,6 +111,12 @@ impl ExprSchemable for Expr {
             Expr::Column(c) => Ok(schema.data_type(c)?.clone()),
             Expr::OuterReferenceColumn(ty, _) => Ok(ty.clone()),
             Expr::ScalarVariable(ty, _) => Ok(ty.clone()),
+            Expr::LambdaVariable(LambdaVariable { data_type, .. }) => {
+                Ok(data_type.clone())
+            }
+            // a lambda has the type of its body, which only refers to the
+            // lambda parameters
+            Expr::Lambda(Lambda { body, .. }) => body.get_type(schema),
             Expr::Literal(l) => Ok(l.data_type()),
             Expr::Case(case) => {
                 for (_, then_expr) in &case.when_then_expr {
@@ -356,6 +362,8 @@ impl ExprSchemable for Expr {
                 WindowFunctionDefinition::WindowUDF(udwf) => Ok(udwf.nullable()),
             },
             Expr::ScalarVariable(_, _)
+            | Expr::Lambda(_)
+            | Expr::LambdaVariable(_)
             | Expr::TryCast { .. }
             | Expr::Unnest(_)
             | Expr::Placeholder(_) => Ok(true),
@@ -23,25 +23,25 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::dml::CopyTo;
-use crate::expr::Alias;
+use crate::expr::{Alias, BinaryExpr};
 use crate::expr_rewriter::{
     coerce_plan_expr_for_schema, normalize_col,
     normalize_col_with_schemas_and_ambiguity_check, normalize_cols,
     rewrite_sort_cols_by_aggs,
 };
 use crate::logical_plan::{
-    Aggregate, Analyze, CrossJoin, Distinct, DistinctOn, EmptyRelation, Explain, Filter,
-    Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Aggregate, Analyze, AsOfJoin, CrossJoin, Distinct, DistinctOn, EmptyRelation,
+    Explain, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning,
+    PlanType, Prepare, Projection, Repartition, Sample, SampleMethod, Sort,
+    SubqueryAlias, TableScan, Union, Unnest, Values, Window,
 };
 use crate::type_coercion::binary::values_coercion;
 use crate::utils::{
-    can_hash, columnize_expr, compare_sort_expr, expr_to_columns,
+    can_hash, columnize_expr, compare_sort_expr, disjunction, expr_to_columns,
     find_valid_equijoin_key_pair, group_window_expr_by_sort_keys,
 };
 use crate::{
-    and, binary_expr, DmlStatement, Expr, ExprSchemable, Operator, RecursiveQuery,
+    and, binary_expr, lit, DmlStatement, Expr, ExprSchemable, Operator, RecursiveQuery,
     TableProviderFilterPushDown, TableSource, WriteOp,
 };
 
@@ -57,6 +57,10 @@ use datafusion_common::{
 /// Default table name for unnamed table
 pub const UNNAMED_TABLE: &str = "?table?";
 
+/// Name of the position column added by an unnest with ordinality. A numeric
+/// suffix is appended when the input already has a column of this name.
+pub const ORDINALITY_COLUMN_NAME: &str = "__unnest_ordinality";
+
 /// Builder for logical plans
 ///
 /// # Example building a simple plan
@@ -426,6 +430,21 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Return a random sample of `fraction` of the rows, using `method`.
+    ///
+    /// `seed` - Seed of the random number generator, to return the same
+    ///          sample of the same input, if specified.
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        Sample::try_new(self.plan, method, fraction, seed)
+            .map(LogicalPlan::Sample)
+            .map(Self::new)
+    }
+
     /// Apply an alias
     pub fn alias(self, alias: impl Into<TableReference>) -> Result<Self> {
         subquery_alias(Arc::unwrap_or_clone(self.plan), alias).map(Self::new)
@@ -924,6 +943,72 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply an as-of join to `right`, which joins each row to the closest
+    /// row of `right` with the same `join_keys` that satisfies
+    /// `match_condition`.
+    ///
+    /// `match_condition` compares an expression of this plan with an
+    /// expression of `right` using one of `>`, `>=`, `<` or `<=`. For example
+    /// `col("trades.ts").gt_eq(col("quotes.ts"))` joins each trade to the
+    /// latest quote at or before the time of the trade.
+    ///
+    /// `join_type` is either `Inner`, which omits rows without a match, or
+    /// `Left`, which joins them to nulls.
+    pub fn asof_join(
+        self,
+        right: LogicalPlan,
+        join_type: JoinType,
+        join_keys: (Vec<impl Into<Column>>, Vec<impl Into<Column>>),
+        match_condition: Expr,
+    ) -> Result<Self> {
+        let LogicalPlan::Join(Join {
+            left, right, on, ..
+        }) = self
+            .join_detailed(right, JoinType::Inner, join_keys, None, false)?
+            .build()?
+        else {
+            return internal_err!("Expected a join");
+        };
+
+        let match_condition = normalize_col_with_schemas_and_ambiguity_check(
+            match_condition,
+            &[&[left.schema(), right.schema()]],
+            &[],
+        )?;
+        let Expr::BinaryExpr(BinaryExpr {
+            left: match_left,
+            op,
+            right: match_right,
+        }) = match_condition.clone().unalias()
+        else {
+            return plan_err!(
+                "ASOF JOIN match condition must be a comparison, got {match_condition}"
+            );
+        };
+        // the left side of the match condition must refer to this plan
+        let match_condition = match find_valid_equijoin_key_pair(
+            &match_left,
+            &match_right,
+            left.schema(),
+            right.schema(),
+        )? {
+            Some((l, r)) if l == *match_left => binary_expr(l, op, r),
+            Some((l, r)) => match op.swap() {
+                Some(op) => binary_expr(l, op, r),
+                None => return plan_err!("Cannot swap the operands of {op}"),
+            },
+            None => {
+                return plan_err!(
+                    "ASOF JOIN match condition must compare an expression of each input, got {match_left} {op} {match_right}"
+                )
+            }
+        };
+
+        AsOfJoin::try_new(left, right, on, match_condition, join_type)
+            .map(LogicalPlan::AsOfJoin)
+            .map(Self::new)
+    }
+
     /// Repartition
     pub fn repartition(self, partitioning_scheme: Partitioning) -> Result<Self> {
         Ok(Self::new(LogicalPlan::Repartition(Repartition {
@@ -1163,6 +1248,72 @@ impl LogicalPlanBuilder {
         unnest_with_options(Arc::unwrap_or_clone(self.plan), columns, options)
             .map(Self::new)
     }
+
+    /// Unpivot columns into rows, as in SQL `UNPIVOT`.
+    ///
+    /// Each `(name, columns)` of `columns` produces one output row per input
+    /// row, with `name` in the column `name_column` and the values of
+    /// `columns` in the columns `value_columns`. The other columns of the
+    /// input are repeated in each of these rows.
+    ///
+    /// Rows whose values are all null are skipped, unless `include_nulls` is
+    /// true.
+    pub fn unpivot(
+        self,
+        value_columns: Vec<String>,
+        name_column: impl Into<String>,
+        columns: Vec<(String, Vec<Column>)>,
+        include_nulls: bool,
+    ) -> Result<Self> {
+        let name_column = name_column.into();
+        let schema = self.plan.schema();
+        let mut unpivoted = HashSet::new();
+        for (name, cols) in &columns {
+            if cols.len() != value_columns.len() {
+                return plan_err!(
+                    "UNPIVOT of {name} requires {} columns, got {}",
+                    value_columns.len(),
+                    cols.len()
+                );
+            }
+            for col in cols {
+                unpivoted.insert(schema.index_of_column(col)?);
+            }
+        }
+        let passthrough = schema
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !unpivoted.contains(i))
+            .map(|(_, qualified_field)| Expr::Column(Column::from(qualified_field)))
+            .collect::<Vec<_>>();
+
+        let mut branches = columns.into_iter().map(|(name, cols)| {
+            let mut branch = Self::new_from_arc(Arc::clone(&self.plan));
+            if !include_nulls {
+                let not_null = cols
+                    .iter()
+                    .map(|col| Expr::Column(col.clone()).is_not_null());
+                if let Some(not_null) = disjunction(not_null) {
+                    branch = branch.filter(not_null)?;
+                }
+            }
+            let values = cols
+                .into_iter()
+                .zip(&value_columns)
+                .map(|(col, value_column)| Expr::Column(col).alias(value_column));
+            let exprs = passthrough
+                .iter()
+                .cloned()
+                .chain(std::iter::once(lit(name).alias(&name_column)))
+                .chain(values);
+            branch.project(exprs)?.build()
+        });
+
+        let Some(first) = branches.next() else {
+            return plan_err!("UNPIVOT requires at least one column");
+        };
+        branches.try_fold(Self::from(first?), |plan, branch| plan.union(branch?))
+    }
 }
 
 impl From<LogicalPlan> for LogicalPlanBuilder {
@@ -1581,7 +1732,7 @@ pub fn unnest_with_options(
     // Transform input schema into new schema
     // e.g int, unnest([]int), unnest(struct(varchar,varchar))
     // becomes int, int, varchar, varchar
-    let fields = input_schema
+    let mut fields = input_schema
         .iter()
         .enumerate()
         .map(|(index, (original_qualifier, original_field))| {
@@ -1626,6 +1777,24 @@ pub fn unnest_with_options(
         .flatten()
         .collect::<Vec<_>>();
 
+    // The position column depends on the lengths of the unnested columns
+    if options.ordinality {
+        if let Some(column) = columns.first() {
+            dependency_indices.push(input_schema.index_of_column(column)?);
+        }
+        // Preserved null or empty lists have no position
+        let nullable = options.preserve_nulls || options.preserve_empty_lists;
+        // An internal name not used by the other columns, the SQL planner
+        // aliases it to `ordinality`
+        let mut name = ORDINALITY_COLUMN_NAME.to_string();
+        let mut suffix = 0;
+        while fields.iter().any(|(_, field)| field.name() == &name) {
+            suffix += 1;
+            name = format!("{ORDINALITY_COLUMN_NAME}_{suffix}");
+        }
+        fields.push((None, Arc::new(Field::new(name, DataType::Int64, nullable))));
+    }
+
     let metadata = input_schema.metadata().clone();
     let df_schema = DFSchema::new_with_metadata(fields, metadata)?;
     // We can use the existing functional dependencies:
@@ -2057,6 +2226,146 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_unnest_with_ordinality() -> Result<()> {
+        // the position column does not collide with the input columns
+        let schema = Schema::new(vec![
+            Field::new("ordinality", DataType::Int64, false),
+            Field::new(ORDINALITY_COLUMN_NAME, DataType::Int64, false),
+            Field::new_list("strings", Field::new("item", DataType::Utf8, false), false),
+        ]);
+        let options = UnnestOptions::new().with_ordinality(true);
+        let plan = table_scan(Some("t"), &schema, None)?
+            .unnest_column_with_options("strings", options)?
+            .build()?;
+
+        let LogicalPlan::Unnest(unnest) = &plan else {
+            panic!("Expected an unnest, got {plan}");
+        };
+        let position = unnest.ordinality_column().unwrap();
+        assert_eq!(
+            position,
+            Column::from_name(format!("{ORDINALITY_COLUMN_NAME}_1"))
+        );
+        let names = plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "ordinality",
+                ORDINALITY_COLUMN_NAME,
+                "strings",
+                &format!("{ORDINALITY_COLUMN_NAME}_1")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_unpivot() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("t")?)
+            .unpivot(
+                vec!["v".to_string()],
+                "name",
+                vec![
+                    ("b".to_string(), vec![Column::from_name("b")]),
+                    ("c".to_string(), vec![Column::from_name("c")]),
+                ],
+                false,
+            )?
+            .build()?;
+        let expected = "\
+        Union\
+        \n  Projection: t.a, Utf8(\"b\") AS name, t.b AS v\
+        \n    Filter: t.b IS NOT NULL\
+        \n      TableScan: t\
+        \n  Projection: t.a, Utf8(\"c\") AS name, t.c AS v\
+        \n    Filter: t.c IS NOT NULL\
+        \n      TableScan: t";
+        assert_eq!(expected, format!("{plan}"));
+
+        // multiple value columns, keeping nulls
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("t")?)
+            .unpivot(
+                vec!["v1".to_string(), "v2".to_string()],
+                "name",
+                vec![(
+                    "x".to_string(),
+                    vec![Column::from_name("b"), Column::from_name("c")],
+                )],
+                true,
+            )?
+            .build()?;
+        let expected = "Projection: t.a, Utf8(\"x\") AS name, t.b AS v1, t.c AS v2\
+        \n  TableScan: t";
+        assert_eq!(expected, format!("{plan}"));
+
+        let err = LogicalPlanBuilder::from(test_table_scan_with_name("t")?)
+            .unpivot(
+                vec!["v1".to_string(), "v2".to_string()],
+                "name",
+                vec![("x".to_string(), vec![Column::from_name("b")])],
+                true,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: UNPIVOT of x requires 2 columns, got 1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_asof_join() -> Result<()> {
+        let quotes = test_table_scan_with_name("q")?;
+        // the match condition is oriented from left to right
+        let plan = LogicalPlanBuilder::from(test_table_scan_with_name("t")?)
+            .asof_join(
+                quotes.clone(),
+                JoinType::Left,
+                (vec!["t.a"], vec!["q.a"]),
+                col("q.b").lt_eq(col("t.b")),
+            )?
+            .build()?;
+        let expected = "\
+        Left AsOf Join: t.a = q.a MatchCondition: t.b >= q.b\
+        \n  TableScan: t\
+        \n  TableScan: q";
+        assert_eq!(expected, format!("{plan}"));
+        assert!(plan.schema().field(3).is_nullable());
+
+        let err = LogicalPlanBuilder::from(test_table_scan_with_name("t")?)
+            .asof_join(
+                quotes.clone(),
+                JoinType::Inner,
+                (vec!["t.a"], vec!["q.a"]),
+                col("t.b").eq(col("q.b")),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: ASOF JOIN match condition must use one of >, >=, < or <=, got ="
+        );
+
+        let err = LogicalPlanBuilder::from(test_table_scan_with_name("t")?)
+            .asof_join(
+                quotes,
+                JoinType::Full,
+                (vec!["t.a"], vec!["q.a"]),
+                col("t.b").gt(col("q.b")),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "This feature is not implemented: ASOF JOIN does not support Full joins"
+        );
+        Ok(())
+    }
+
     fn nested_table_scan(table_name: &str) -> Result<LogicalPlanBuilder> {
         // Create a schema with a scalar field, a list of strings, a list of structs
         // and a singular struct
@@ -37,6 +37,14 @@ pub enum DdlStatement {
     CreateMemoryTable(CreateMemoryTable),
     /// Creates a new view.
     CreateView(CreateView),
+    /// Creates a new materialized view.
+    CreateMaterializedView(CreateMaterializedView),
+    /// Recomputes the contents of a materialized view.
+    RefreshMaterializedView(RefreshMaterializedView),
+    /// Caches the contents of a table.
+    CacheTable(CacheTable),
+    /// Removes the cached contents of a table.
+    UncacheTable(UncacheTable),
     /// Creates a new catalog schema.
     CreateCatalogSchema(CreateCatalogSchema),
     /// Creates a new catalog (aka "Database").
@@ -63,7 +71,17 @@ impl DdlStatement {
                 schema
             }
             DdlStatement::CreateMemoryTable(CreateMemoryTable { input, .. })
-            | DdlStatement::CreateView(CreateView { input, .. }) => input.schema(),
+            | DdlStatement::CreateView(CreateView { input, .. })
+            | DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                input,
+                ..
+            }) => input.schema(),
+            DdlStatement::RefreshMaterializedView(RefreshMaterializedView {
+                schema,
+                ..
+            }) => schema,
+            DdlStatement::CacheTable(CacheTable { schema, .. }) => schema,
+            DdlStatement::UncacheTable(UncacheTable { schema, .. }) => schema,
             DdlStatement::CreateCatalogSchema(CreateCatalogSchema { schema, .. }) => {
                 schema
             }
@@ -84,6 +102,10 @@ impl DdlStatement {
             DdlStatement::CreateExternalTable(_) => "CreateExternalTable",
             DdlStatement::CreateMemoryTable(_) => "CreateMemoryTable",
             DdlStatement::CreateView(_) => "CreateView",
+            DdlStatement::CreateMaterializedView(_) => "CreateMaterializedView",
+            DdlStatement::RefreshMaterializedView(_) => "RefreshMaterializedView",
+            DdlStatement::CacheTable(_) => "CacheTable",
+            DdlStatement::UncacheTable(_) => "UncacheTable",
             DdlStatement::CreateCatalogSchema(_) => "CreateCatalogSchema",
             DdlStatement::CreateCatalog(_) => "CreateCatalog",
             DdlStatement::CreateIndex(_) => "CreateIndex",
@@ -105,6 +127,13 @@ impl DdlStatement {
                 vec![input]
             }
             DdlStatement::CreateView(CreateView { input, .. }) => vec![input],
+            DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                input,
+                ..
+            }) => vec![input],
+            DdlStatement::RefreshMaterializedView(_) => vec![],
+            DdlStatement::CacheTable(CacheTable { input, .. }) => vec![input],
+            DdlStatement::UncacheTable(_) => vec![],
             DdlStatement::CreateIndex(_) => vec![],
             DdlStatement::DropTable(_) => vec![],
             DdlStatement::DropView(_) => vec![],
@@ -141,6 +170,31 @@ impl DdlStatement {
                     DdlStatement::CreateView(CreateView { name, .. }) => {
                         write!(f, "CreateView: {name:?}")
                     }
+                    DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                        name,
+                        target,
+                        ..
+                    }) => {
+                        write!(f, "CreateMaterializedView: {name:?}")?;
+                        if let Some(target) = target {
+                            write!(f, " target={target:?}")?;
+                        }
+                        Ok(())
+                    }
+                    DdlStatement::RefreshMaterializedView(RefreshMaterializedView {
+                        name,
+                        ..
+                    }) => {
+                        write!(f, "RefreshMaterializedView: {name:?}")
+                    }
+                    DdlStatement::CacheTable(CacheTable { name, .. }) => {
+                        write!(f, "CacheTable: {name:?}")
+                    }
+                    DdlStatement::UncacheTable(UncacheTable {
+                        name, if_exists, ..
+                    }) => {
+                        write!(f, "UncacheTable: {name:?} if not exist:={if_exists}")
+                    }
                     DdlStatement::CreateCatalogSchema(CreateCatalogSchema {
                         schema_name,
                         ..
@@ -261,6 +315,58 @@ pub struct CreateView {
     pub definition: Option<String>,
 }
 
+/// Creates a materialized view, whose results are computed when it is
+/// created or refreshed and stored in a table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateMaterializedView {
+    /// The view name
+    pub name: TableReference,
+    /// The logical plan
+    pub input: Arc<LogicalPlan>,
+    /// Option to replace the view if it already exists
+    pub or_replace: bool,
+    /// The existing table storing the results. If not set, the results are
+    /// stored in a new in memory table
+    pub target: Option<TableReference>,
+    /// SQL used to create the view, if available
+    pub definition: Option<String>,
+}
+
+/// Recomputes the results of a materialized view.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefreshMaterializedView {
+    /// The view name
+    pub name: TableReference,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Caches the contents of a table, or the results of a query stored as a
+/// new table, until the table is uncached.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheTable {
+    /// The table name
+    pub name: TableReference,
+    /// The logical plan computing the contents to cache
+    pub input: Arc<LogicalPlan>,
+    /// Whether `input` is a query defining a new table rather than a scan
+    /// of the existing table `name`
+    pub from_query: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Removes the cached contents of a table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UncacheTable {
+    /// The table name
+    pub name: TableReference,
+    /// If the table exists
+    pub if_exists: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
 /// Creates a catalog (aka "Database").
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CreateCatalog {
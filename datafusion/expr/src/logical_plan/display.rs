@@ -20,10 +20,10 @@ use std::collections::HashMap;
 use std::fmt;
 
 use crate::{
-    expr_vec_fmt, Aggregate, DescribeTable, Distinct, DistinctOn, DmlStatement, Expr,
-    Filter, Join, Limit, LogicalPlan, Partitioning, Prepare, Projection, RecursiveQuery,
-    Repartition, Sort, Subquery, SubqueryAlias, TableProviderFilterPushDown, TableScan,
-    Unnest, Values, Window,
+    expr_vec_fmt, Aggregate, AsOfJoin, DescribeTable, Distinct, DistinctOn, DmlStatement,
+    Expr, Filter, Join, Limit, LogicalPlan, Partitioning, Prepare, Projection,
+    RecursiveQuery, Repartition, Sample, Sort, Subquery, SubqueryAlias,
+    TableProviderFilterPushDown, TableScan, Unnest, Values, Window,
 };
 
 use crate::dml::CopyTo;
@@ -509,6 +509,20 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                     "Node Type": "Cross Join"
                 })
             }
+            LogicalPlan::AsOfJoin(AsOfJoin {
+                on: ref keys,
+                match_condition,
+                join_type,
+                ..
+            }) => {
+                let join_expr: Vec<String> =
+                    keys.iter().map(|(l, r)| format!("{l} = {r}")).collect();
+                json!({
+                    "Node Type": format!("{} AsOf Join", join_type),
+                    "Join Keys": join_expr.join(", "),
+                    "Match Condition": format!("{}", match_condition)
+                })
+            }
             LogicalPlan::Repartition(Repartition {
                 partitioning_scheme,
                 ..
@@ -557,6 +571,22 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 };
                 object
             }
+            LogicalPlan::Sample(Sample {
+                ref method,
+                ref fraction,
+                ref seed,
+                ..
+            }) => {
+                let mut object = json!({
+                    "Node Type": "Sample",
+                    "Method": method.to_string(),
+                    "Fraction": fraction,
+                });
+                if let Some(seed) = seed {
+                    object["Seed"] = serde_json::Value::Number((*seed).into());
+                };
+                object
+            }
             LogicalPlan::Subquery(Subquery { .. }) => {
                 json!({
                     "Node Type": "Subquery"
@@ -24,7 +24,7 @@ use arrow::datatypes::{DataType, Field, Schema};
 use datafusion_common::file_options::file_type::FileType;
 use datafusion_common::{DFSchemaRef, TableReference};
 
-use crate::LogicalPlan;
+use crate::{Expr, LogicalPlan};
 
 /// Operator that copies the contents of a database to file(s)
 #[derive(Clone)]
@@ -119,6 +119,7 @@ pub enum WriteOp {
     Delete,
     Update,
     Ctas,
+    Merge(MergeOp),
 }
 
 impl WriteOp {
@@ -130,6 +131,7 @@ impl WriteOp {
             WriteOp::Delete => "Delete",
             WriteOp::Update => "Update",
             WriteOp::Ctas => "Ctas",
+            WriteOp::Merge(_) => "Merge",
         }
     }
 }
@@ -140,6 +142,104 @@ impl Display for WriteOp {
     }
 }
 
+/// The join condition and `WHEN` clauses of a `MERGE INTO` statement
+///
+/// The expressions refer to the columns of the `table_schema` of the
+/// [`DmlStatement`], i.e. the target table, followed by the columns of its
+/// `input`, i.e. the source rows merged into the target table.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeOp {
+    /// Condition matching rows of the target table with rows of the source
+    pub on: Expr,
+    /// The `WHEN` clauses. Each row is modified by the first clause whose
+    /// kind and predicate apply to it, if any
+    pub clauses: Vec<MergeClause>,
+}
+
+impl Display for MergeOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "on=[{}]", self.on)?;
+        for clause in &self.clauses {
+            write!(f, " {clause}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A `WHEN ... [AND predicate] THEN action` clause of a `MERGE INTO` statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MergeClause {
+    /// The rows the clause applies to
+    pub kind: MergeClauseKind,
+    /// Additional condition the rows must satisfy
+    pub predicate: Option<Expr>,
+    /// The modification applied to the rows
+    pub action: MergeAction,
+}
+
+impl Display for MergeClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "WHEN {}", self.kind)?;
+        if let Some(predicate) = &self.predicate {
+            write!(f, " AND {predicate}")?;
+        }
+        write!(f, " THEN {}", self.action)
+    }
+}
+
+/// The rows a [`MergeClause`] applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeClauseKind {
+    /// `WHEN MATCHED`: rows of the target table matching a source row
+    Matched,
+    /// `WHEN NOT MATCHED [BY TARGET]`: source rows not matching any row of
+    /// the target table
+    NotMatched,
+    /// `WHEN NOT MATCHED BY SOURCE`: rows of the target table not matching
+    /// any source row
+    NotMatchedBySource,
+}
+
+impl Display for MergeClauseKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeClauseKind::Matched => write!(f, "MATCHED"),
+            MergeClauseKind::NotMatched => write!(f, "NOT MATCHED"),
+            MergeClauseKind::NotMatchedBySource => write!(f, "NOT MATCHED BY SOURCE"),
+        }
+    }
+}
+
+/// The modification applied by a [`MergeClause`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MergeAction {
+    /// Replace the values of the named columns of the target row
+    Update(Vec<(String, Expr)>),
+    /// Delete the target row
+    Delete,
+    /// Insert a row with the given value for each column of the target table
+    Insert(Vec<Expr>),
+}
+
+impl Display for MergeAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeAction::Update(assignments) => {
+                let assignments = assignments
+                    .iter()
+                    .map(|(name, value)| format!("{name} = {value}"))
+                    .collect::<Vec<_>>();
+                write!(f, "UPDATE SET {}", assignments.join(", "))
+            }
+            MergeAction::Delete => write!(f, "DELETE"),
+            MergeAction::Insert(values) => {
+                let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(f, "INSERT VALUES ({})", values.join(", "))
+            }
+        }
+    }
+}
+
 fn make_count_schema() -> DFSchemaRef {
     Arc::new(
         Schema::new(vec![Field::new("count", DataType::UInt64, false)])
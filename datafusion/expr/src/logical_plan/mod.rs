@@ -26,20 +26,23 @@ pub mod tree_node;
 
 pub use builder::{
     build_join_schema, table_scan, union, wrap_projection_for_join_if_necessary,
-    LogicalPlanBuilder, LogicalTableSource, UNNAMED_TABLE,
+    LogicalPlanBuilder, LogicalTableSource, ORDINALITY_COLUMN_NAME, UNNAMED_TABLE,
 };
 pub use ddl::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
-    CreateFunctionBody, CreateIndex, CreateMemoryTable, CreateView, DdlStatement,
-    DropCatalogSchema, DropFunction, DropTable, DropView, OperateFunctionArg,
+    CacheTable, CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
+    CreateFunctionBody, CreateIndex, CreateMaterializedView, CreateMemoryTable,
+    CreateView, DdlStatement, DropCatalogSchema, DropFunction, DropTable, DropView,
+    OperateFunctionArg, RefreshMaterializedView, UncacheTable,
+};
+pub use dml::{
+    DmlStatement, MergeAction, MergeClause, MergeClauseKind, MergeOp, WriteOp,
 };
-pub use dml::{DmlStatement, WriteOp};
 pub use plan::{
-    projection_schema, Aggregate, Analyze, CrossJoin, DescribeTable, Distinct,
+    projection_schema, Aggregate, Analyze, AsOfJoin, CrossJoin, DescribeTable, Distinct,
     DistinctOn, EmptyRelation, Explain, Extension, Filter, Join, JoinConstraint,
     JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare, Projection,
-    RecursiveQuery, Repartition, Sort, StringifiedPlan, Subquery, SubqueryAlias,
-    TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
+    RecursiveQuery, Repartition, Sample, SampleMethod, Sort, StringifiedPlan, Subquery,
+    SubqueryAlias, TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
 };
 pub use statement::{
     SetVariable, Statement, TransactionAccessMode, TransactionConclusion, TransactionEnd,
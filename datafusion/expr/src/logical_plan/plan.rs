@@ -29,24 +29,25 @@ use crate::expr::{Placeholder, Sort as SortExpr, WindowFunction};
 use crate::expr_rewriter::{create_col_from_scalar_expr, normalize_cols, NamePreserver};
 use crate::logical_plan::display::{GraphvizVisitor, IndentVisitor};
 use crate::logical_plan::extension::UserDefinedLogicalNode;
-use crate::logical_plan::{DmlStatement, Statement};
+use crate::logical_plan::{DmlStatement, Statement, WriteOp};
 use crate::utils::{
     enumerate_grouping_sets, exprlist_len, exprlist_to_fields, find_base_plan,
     find_out_reference_exprs, grouping_set_expr_count, grouping_set_to_exprlist,
     split_conjunction,
 };
 use crate::{
-    build_join_schema, expr_vec_fmt, BinaryExpr, CreateMemoryTable, CreateView, Expr,
-    ExprSchemable, LogicalPlanBuilder, Operator, TableProviderFilterPushDown,
-    TableSource, WindowFunctionDefinition,
+    build_join_schema, expr_vec_fmt, BinaryExpr, CacheTable, CreateMaterializedView,
+    CreateMemoryTable, CreateView, Expr, ExprSchemable, LogicalPlanBuilder, Operator,
+    TableProviderFilterPushDown, TableSource, WindowFunctionDefinition,
 };
 
 use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
 use datafusion_common::{
-    aggregate_functional_dependencies, internal_err, plan_err, Column, Constraints,
-    DFSchema, DFSchemaRef, DataFusionError, Dependency, FunctionalDependence,
-    FunctionalDependencies, ParamValues, Result, TableReference, UnnestOptions,
+    aggregate_functional_dependencies, internal_err, not_impl_err, plan_err, Column,
+    Constraints, DFSchema, DFSchemaRef, DataFusionError, Dependency,
+    FunctionalDependence, FunctionalDependencies, ParamValues, Result, TableReference,
+    UnnestOptions,
 };
 
 // backwards compatibility
@@ -277,6 +278,12 @@ pub enum LogicalPlan {
     Unnest(Unnest),
     /// A variadic query (e.g. "Recursive CTEs")
     RecursiveQuery(RecursiveQuery),
+    /// Returns a random sample of the rows of its input. This is used to
+    /// implement SQL `TABLESAMPLE`
+    Sample(Sample),
+    /// Joins each row of the left input to the closest matching row of the
+    /// right input. This is used to implement SQL `ASOF JOIN`
+    AsOfJoin(AsOfJoin),
 }
 
 impl Default for LogicalPlan {
@@ -306,8 +313,10 @@ impl LogicalPlan {
             LogicalPlan::Sort(Sort { input, .. }) => input.schema(),
             LogicalPlan::Join(Join { schema, .. }) => schema,
             LogicalPlan::CrossJoin(CrossJoin { schema, .. }) => schema,
+            LogicalPlan::AsOfJoin(AsOfJoin { schema, .. }) => schema,
             LogicalPlan::Repartition(Repartition { input, .. }) => input.schema(),
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
+            LogicalPlan::Sample(Sample { input, .. }) => input.schema(),
             LogicalPlan::Statement(statement) => statement.schema(),
             LogicalPlan::Subquery(Subquery { subquery, .. }) => subquery.schema(),
             LogicalPlan::SubqueryAlias(SubqueryAlias { schema, .. }) => schema,
@@ -339,7 +348,8 @@ impl LogicalPlan {
             | LogicalPlan::Aggregate(_)
             | LogicalPlan::Unnest(_)
             | LogicalPlan::Join(_)
-            | LogicalPlan::CrossJoin(_) => self
+            | LogicalPlan::CrossJoin(_)
+            | LogicalPlan::AsOfJoin(_) => self
                 .inputs()
                 .iter()
                 .map(|input| input.schema().as_ref())
@@ -451,7 +461,9 @@ impl LogicalPlan {
             LogicalPlan::Sort(Sort { input, .. }) => vec![input],
             LogicalPlan::Join(Join { left, right, .. }) => vec![left, right],
             LogicalPlan::CrossJoin(CrossJoin { left, right, .. }) => vec![left, right],
+            LogicalPlan::AsOfJoin(AsOfJoin { left, right, .. }) => vec![left, right],
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
+            LogicalPlan::Sample(Sample { input, .. }) => vec![input],
             LogicalPlan::Subquery(Subquery { subquery, .. }) => vec![subquery],
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => vec![input],
             LogicalPlan::Extension(extension) => extension.node.inputs(),
@@ -538,6 +550,7 @@ impl LogicalPlan {
             | LogicalPlan::Distinct(Distinct::All(input))
             | LogicalPlan::Sort(Sort { input, .. })
             | LogicalPlan::Limit(Limit { input, .. })
+            | LogicalPlan::Sample(Sample { input, .. })
             | LogicalPlan::Repartition(Repartition { input, .. })
             | LogicalPlan::Window(Window { input, .. }) => input.head_output_expr(),
             LogicalPlan::Join(Join {
@@ -563,6 +576,13 @@ impl LogicalPlan {
                     cross.left.head_output_expr()
                 }
             }
+            LogicalPlan::AsOfJoin(AsOfJoin { left, right, .. }) => {
+                if left.schema().fields().is_empty() {
+                    right.head_output_expr()
+                } else {
+                    left.head_output_expr()
+                }
+            }
             LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
                 static_term.head_output_expr()
             }
@@ -708,6 +728,15 @@ impl LogicalPlan {
                     schema: join_schema.into(),
                 }))
             }
+            LogicalPlan::AsOfJoin(AsOfJoin {
+                left,
+                right,
+                on,
+                match_condition,
+                join_type,
+                schema: _,
+            }) => AsOfJoin::try_new(left, right, on, match_condition, join_type)
+                .map(LogicalPlan::AsOfJoin),
             LogicalPlan::Subquery(_) => Ok(self),
             LogicalPlan::SubqueryAlias(SubqueryAlias {
                 input,
@@ -715,6 +744,7 @@ impl LogicalPlan {
                 schema: _,
             }) => SubqueryAlias::try_new(input, alias).map(LogicalPlan::SubqueryAlias),
             LogicalPlan::Limit(_) => Ok(self),
+            LogicalPlan::Sample(_) => Ok(self),
             LogicalPlan::Ddl(_) => Ok(self),
             LogicalPlan::Extension(Extension { node }) => {
                 // todo make an API that does not require cloning
@@ -941,6 +971,34 @@ impl LogicalPlan {
                 let right = inputs.swap_remove(0);
                 LogicalPlanBuilder::from(left).cross_join(right)?.build()
             }
+            LogicalPlan::AsOfJoin(AsOfJoin { on, join_type, .. }) => {
+                // The equijoin expressions are followed by the match condition
+                assert_eq!(expr.len(), on.len() + 1);
+                let match_condition = expr.pop().unwrap().unalias();
+                let new_on = expr
+                    .into_iter()
+                    .map(|equi_expr| match equi_expr.clone().unalias() {
+                        Expr::BinaryExpr(BinaryExpr {
+                            left,
+                            op: Operator::Eq,
+                            right,
+                        }) => Ok((*left, *right)),
+                        _ => internal_err!(
+                            "Expected a binary equality expression, actual:{equi_expr}"
+                        ),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let left = inputs.swap_remove(0);
+                let right = inputs.swap_remove(0);
+                AsOfJoin::try_new(
+                    Arc::new(left),
+                    Arc::new(right),
+                    new_on,
+                    match_condition,
+                    *join_type,
+                )
+                .map(LogicalPlan::AsOfJoin)
+            }
             LogicalPlan::Subquery(Subquery {
                 outer_ref_columns, ..
             }) => {
@@ -961,6 +1019,17 @@ impl LogicalPlan {
                     input: Arc::new(inputs.swap_remove(0)),
                 }))
             }
+            LogicalPlan::Sample(Sample {
+                method,
+                fraction,
+                seed,
+                ..
+            }) => Ok(LogicalPlan::Sample(Sample {
+                input: Arc::new(inputs.swap_remove(0)),
+                method: *method,
+                fraction: *fraction,
+                seed: *seed,
+            })),
             LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(CreateMemoryTable {
                 name,
                 if_not_exists,
@@ -988,6 +1057,34 @@ impl LogicalPlan {
                 or_replace: *or_replace,
                 definition: definition.clone(),
             }))),
+            LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                CreateMaterializedView {
+                    name,
+                    or_replace,
+                    target,
+                    definition,
+                    ..
+                },
+            )) => Ok(LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                CreateMaterializedView {
+                    input: Arc::new(inputs.swap_remove(0)),
+                    name: name.clone(),
+                    or_replace: *or_replace,
+                    target: target.clone(),
+                    definition: definition.clone(),
+                },
+            ))),
+            LogicalPlan::Ddl(DdlStatement::CacheTable(CacheTable {
+                name,
+                from_query,
+                schema,
+                ..
+            })) => Ok(LogicalPlan::Ddl(DdlStatement::CacheTable(CacheTable {
+                input: Arc::new(inputs.swap_remove(0)),
+                name: name.clone(),
+                from_query: *from_query,
+                schema: Arc::clone(schema),
+            }))),
             LogicalPlan::Extension(e) => Ok(LogicalPlan::Extension(Extension {
                 node: e.node.with_exprs_and_inputs(expr, inputs)?,
             })),
@@ -1232,6 +1329,8 @@ impl LogicalPlan {
                     _ => None,
                 }
             }
+            // every left row is joined to at most one right row
+            LogicalPlan::AsOfJoin(AsOfJoin { left, .. }) => left.max_rows(),
             LogicalPlan::Repartition(Repartition { input, .. }) => input.max_rows(),
             LogicalPlan::Union(Union { inputs, .. }) => inputs
                 .iter()
@@ -1250,6 +1349,7 @@ impl LogicalPlan {
             LogicalPlan::Subquery(_) => None,
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => input.max_rows(),
             LogicalPlan::Limit(Limit { fetch, .. }) => *fetch,
+            LogicalPlan::Sample(Sample { input, .. }) => input.max_rows(),
             LogicalPlan::Distinct(
                 Distinct::All(input) | Distinct::On(DistinctOn { input, .. }),
             ) => input.max_rows(),
@@ -1693,7 +1793,11 @@ impl LogicalPlan {
                         Ok(())
                     }
                     LogicalPlan::Dml(DmlStatement { table_name, op, .. }) => {
-                        write!(f, "Dml: op=[{op}] table=[{table_name}]")
+                        write!(f, "Dml: op=[{op}] table=[{table_name}]")?;
+                        if let WriteOp::Merge(merge) = op {
+                            write!(f, " {merge}")?;
+                        }
+                        Ok(())
                     }
                     LogicalPlan::Copy(CopyTo {
                         input: _,
@@ -1787,6 +1891,20 @@ impl LogicalPlan {
                     LogicalPlan::CrossJoin(_) => {
                         write!(f, "CrossJoin:")
                     }
+                    LogicalPlan::AsOfJoin(AsOfJoin {
+                        on: ref keys,
+                        match_condition,
+                        join_type,
+                        ..
+                    }) => {
+                        write!(f, "{join_type} AsOf Join: ")?;
+                        if !keys.is_empty() {
+                            let join_expr: Vec<String> =
+                                keys.iter().map(|(l, r)| format!("{l} = {r}")).collect();
+                            write!(f, "{} ", join_expr.join(", "))?;
+                        }
+                        write!(f, "MatchCondition: {match_condition}")
+                    }
                     LogicalPlan::Repartition(Repartition {
                         partitioning_scheme,
                         ..
@@ -1826,6 +1944,18 @@ impl LogicalPlan {
                             fetch.map_or_else(|| "None".to_string(), |x| x.to_string())
                         )
                     }
+                    LogicalPlan::Sample(Sample {
+                        ref method,
+                        ref fraction,
+                        ref seed,
+                        ..
+                    }) => {
+                        write!(
+                            f,
+                            "Sample: method={method}, fraction={fraction}, seed={}",
+                            seed.map_or_else(|| "None".to_string(), |x| x.to_string())
+                        )
+                    }
                     LogicalPlan::Subquery(Subquery { .. }) => {
                         write!(f, "Subquery:")
                     }
@@ -1865,7 +1995,8 @@ impl LogicalPlan {
                     LogicalPlan::Unnest(Unnest {
                         input: plan,
                         list_type_columns: list_col_indices,
-                        struct_type_columns: struct_col_indices, .. }) => {
+                        struct_type_columns: struct_col_indices,
+                        options, .. }) => {
                         let input_columns = plan.schema().columns();
                         let list_type_columns = list_col_indices
                             .iter()
@@ -1878,7 +2009,11 @@ impl LogicalPlan {
                         // get items from input_columns indexed by list_col_indices
                         write!(f, "Unnest: lists[{}] structs[{}]", 
                         expr_vec_fmt!(list_type_columns),
-                        expr_vec_fmt!(struct_type_columns))
+                        expr_vec_fmt!(struct_type_columns))?;
+                        if options.ordinality {
+                            write!(f, " with_ordinality")?;
+                        }
+                        Ok(())
                     }
                 }
             }
@@ -2530,6 +2665,82 @@ pub struct Limit {
     pub input: Arc<LogicalPlan>,
 }
 
+/// How the rows of a [`Sample`] are chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleMethod {
+    /// Each row is part of the sample independently of all other rows, with
+    /// the probability of the sample fraction
+    Bernoulli,
+    /// Blocks of rows, such as batches or Parquet row groups, are part of
+    /// the sample with the probability of the sample fraction. This is
+    /// cheaper than [`SampleMethod::Bernoulli`] as whole blocks can be
+    /// skipped, but rows of the same block are not sampled independently.
+    System,
+}
+
+impl Display for SampleMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleMethod::Bernoulli => write!(f, "BERNOULLI"),
+            SampleMethod::System => write!(f, "SYSTEM"),
+        }
+    }
+}
+
+/// Returns a random sample of the rows of its input, e.g. for
+/// `SELECT * FROM t TABLESAMPLE BERNOULLI(10) REPEATABLE(42)`
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// The input plan
+    pub input: Arc<LogicalPlan>,
+    /// How the rows of the sample are chosen
+    pub method: SampleMethod,
+    /// The fraction of the input rows to return, between 0 and 1
+    pub fraction: f64,
+    /// Seed of the random number generator, for repeatable samples
+    pub seed: Option<u64>,
+}
+
+impl Sample {
+    /// Create a new sample of `fraction` of the rows of `input`
+    pub fn try_new(
+        input: Arc<LogicalPlan>,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!("Sample fraction must be between 0 and 1, got {fraction}");
+        }
+        Ok(Self {
+            input,
+            method,
+            fraction,
+            seed,
+        })
+    }
+}
+
+impl PartialEq for Sample {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input
+            && self.method == other.method
+            && self.fraction.to_bits() == other.fraction.to_bits()
+            && self.seed == other.seed
+    }
+}
+
+impl Eq for Sample {}
+
+impl Hash for Sample {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.input.hash(state);
+        self.method.hash(state);
+        self.fraction.to_bits().hash(state);
+        self.seed.hash(state);
+    }
+}
+
 /// Removes duplicate rows from the input
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Distinct {
@@ -2896,6 +3107,66 @@ impl Join {
     }
 }
 
+/// Joins each row of the left input to the closest row of the right input
+/// that has the same join keys and satisfies `match_condition`, such as the
+/// latest quote at or before the time of a trade. This is used to implement
+/// SQL `ASOF JOIN`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AsOfJoin {
+    /// Left input
+    pub left: Arc<LogicalPlan>,
+    /// Right input
+    pub right: Arc<LogicalPlan>,
+    /// Equijoin clause expressed as pairs of (left, right) join expressions
+    pub on: Vec<(Expr, Expr)>,
+    /// Comparison of a left expression with a right expression, such as
+    /// `left.ts >= right.ts`. The closest right row that satisfies it is
+    /// joined, where closest means largest for `>` and `>=`, and smallest for
+    /// `<` and `<=`
+    pub match_condition: Expr,
+    /// Join type, either `Inner` or `Left`
+    pub join_type: JoinType,
+    /// The output schema, containing fields from the left and right inputs
+    pub schema: DFSchemaRef,
+}
+
+impl AsOfJoin {
+    /// Create a new as-of join of `left` and `right`
+    pub fn try_new(
+        left: Arc<LogicalPlan>,
+        right: Arc<LogicalPlan>,
+        on: Vec<(Expr, Expr)>,
+        match_condition: Expr,
+        join_type: JoinType,
+    ) -> Result<Self> {
+        if !matches!(join_type, JoinType::Inner | JoinType::Left) {
+            return not_impl_err!("ASOF JOIN does not support {join_type} joins");
+        }
+        let Expr::BinaryExpr(BinaryExpr { op, .. }) = &match_condition else {
+            return plan_err!(
+                "ASOF JOIN match condition must be a comparison, got {match_condition}"
+            );
+        };
+        if !matches!(
+            op,
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq
+        ) {
+            return plan_err!(
+                "ASOF JOIN match condition must use one of >, >=, < or <=, got {op}"
+            );
+        }
+        let schema = build_join_schema(left.schema(), right.schema(), &join_type)?;
+        Ok(Self {
+            left,
+            right,
+            on,
+            match_condition,
+            join_type,
+            schema: Arc::new(schema),
+        })
+    }
+}
+
 /// Subquery
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Subquery {
@@ -2967,6 +3238,17 @@ pub struct Unnest {
     pub options: UnnestOptions,
 }
 
+impl Unnest {
+    /// The position column appended when [`UnnestOptions::ordinality`] is set
+    pub fn ordinality_column(&self) -> Option<Column> {
+        if self.options.ordinality {
+            self.schema.columns().last().cloned()
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
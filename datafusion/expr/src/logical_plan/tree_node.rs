@@ -37,11 +37,12 @@
 //! * [`LogicalPlan::with_new_exprs`]: Create a new plan with different expressions
 //! * [`LogicalPlan::expressions`]: Return a copy of the plan's expressions
 use crate::{
-    dml::CopyTo, Aggregate, Analyze, CreateMemoryTable, CreateView, CrossJoin,
-    DdlStatement, Distinct, DistinctOn, DmlStatement, Explain, Expr, Extension, Filter,
-    Join, Limit, LogicalPlan, Partitioning, Prepare, Projection, RecursiveQuery,
-    Repartition, Sort, Subquery, SubqueryAlias, TableScan, Union, Unnest,
-    UserDefinedLogicalNode, Values, Window,
+    dml::CopyTo, Aggregate, Analyze, AsOfJoin, CacheTable, CreateMaterializedView,
+    CreateMemoryTable, CreateView, CrossJoin, DdlStatement, Distinct, DistinctOn,
+    DmlStatement, Explain, Expr, Extension, Filter, Join, Limit, LogicalPlan,
+    Partitioning, Prepare, Projection, RecursiveQuery, Repartition, Sample, Sort,
+    Subquery, SubqueryAlias, TableScan, Union, Unnest, UserDefinedLogicalNode, Values,
+    Window,
 };
 use std::sync::Arc;
 
@@ -175,8 +176,43 @@ impl TreeNode for LogicalPlan {
                     schema,
                 })
             }),
+            LogicalPlan::AsOfJoin(AsOfJoin {
+                left,
+                right,
+                on,
+                match_condition,
+                join_type,
+                schema,
+            }) => map_until_stop_and_collect!(
+                rewrite_arc(left, &mut f),
+                right,
+                rewrite_arc(right, &mut f)
+            )?
+            .update_data(|(left, right)| {
+                LogicalPlan::AsOfJoin(AsOfJoin {
+                    left,
+                    right,
+                    on,
+                    match_condition,
+                    join_type,
+                    schema,
+                })
+            }),
             LogicalPlan::Limit(Limit { skip, fetch, input }) => rewrite_arc(input, f)?
                 .update_data(|input| LogicalPlan::Limit(Limit { skip, fetch, input })),
+            LogicalPlan::Sample(Sample {
+                input,
+                method,
+                fraction,
+                seed,
+            }) => rewrite_arc(input, f)?.update_data(|input| {
+                LogicalPlan::Sample(Sample {
+                    input,
+                    method,
+                    fraction,
+                    seed,
+                })
+            }),
             LogicalPlan::Subquery(Subquery {
                 subquery,
                 outer_ref_columns,
@@ -308,8 +344,38 @@ impl TreeNode for LogicalPlan {
                             definition,
                         })
                     }),
+                    DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                        name,
+                        input,
+                        or_replace,
+                        target,
+                        definition,
+                    }) => rewrite_arc(input, f)?.update_data(|input| {
+                        DdlStatement::CreateMaterializedView(CreateMaterializedView {
+                            name,
+                            input,
+                            or_replace,
+                            target,
+                            definition,
+                        })
+                    }),
+                    DdlStatement::CacheTable(CacheTable {
+                        name,
+                        input,
+                        from_query,
+                        schema,
+                    }) => rewrite_arc(input, f)?.update_data(|input| {
+                        DdlStatement::CacheTable(CacheTable {
+                            name,
+                            input,
+                            from_query,
+                            schema,
+                        })
+                    }),
                     // no inputs in these statements
                     DdlStatement::CreateExternalTable(_)
+                    | DdlStatement::RefreshMaterializedView(_)
+                    | DdlStatement::UncacheTable(_)
                     | DdlStatement::CreateCatalogSchema(_)
                     | DdlStatement::CreateCatalog(_)
                     | DdlStatement::CreateIndex(_)
@@ -481,6 +547,16 @@ impl LogicalPlan {
                     .apply_until_stop(|e| f(&e))?
                     .visit_sibling(|| filter.iter().apply_until_stop(f))
             }
+            // The equijoin expressions `left-on = right-on` followed by the match condition
+            LogicalPlan::AsOfJoin(AsOfJoin {
+                on,
+                match_condition,
+                ..
+            }) => on
+                .iter()
+                .map(|(l, r)| Expr::eq(l.clone(), r.clone()))
+                .apply_until_stop(|e| f(&e))?
+                .visit_sibling(|| f(match_condition)),
             LogicalPlan::Sort(Sort { expr, .. }) => expr.iter().apply_until_stop(f),
             LogicalPlan::Extension(extension) => {
                 // would be nice to avoid this copy -- maybe can
@@ -515,6 +591,7 @@ impl LogicalPlan {
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Statement(_)
             | LogicalPlan::CrossJoin(_)
             | LogicalPlan::Analyze(_)
@@ -658,6 +735,30 @@ impl LogicalPlan {
                     null_equals_null,
                 })
             }),
+            LogicalPlan::AsOfJoin(AsOfJoin {
+                left,
+                right,
+                on,
+                match_condition,
+                join_type,
+                schema,
+            }) => map_until_stop_and_collect!(
+                on.into_iter().map_until_stop_and_collect(
+                    |on| map_until_stop_and_collect!(f(on.0), on.1, f(on.1))
+                ),
+                match_condition,
+                f(match_condition)
+            )?
+            .update_data(|(on, match_condition)| {
+                LogicalPlan::AsOfJoin(AsOfJoin {
+                    left,
+                    right,
+                    on,
+                    match_condition,
+                    join_type,
+                    schema,
+                })
+            }),
             LogicalPlan::Sort(Sort { expr, input, fetch }) => expr
                 .into_iter()
                 .map_until_stop_and_collect(f)?
@@ -727,6 +828,7 @@ impl LogicalPlan {
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Statement(_)
             | LogicalPlan::CrossJoin(_)
             | LogicalPlan::Analyze(_)
@@ -21,11 +21,13 @@ use std::sync::Arc;
 
 use arrow::datatypes::{DataType, Field, SchemaRef};
 use datafusion_common::{
-    config::ConfigOptions, file_options::file_type::FileType, not_impl_err, DFSchema,
-    Result, TableReference,
+    config::ConfigOptions, file_options::file_type::FileType, not_impl_err, plan_err,
+    DFSchema, Result, TableReference,
 };
 
-use crate::{AggregateUDF, Expr, GetFieldAccess, ScalarUDF, TableSource, WindowUDF};
+use crate::{
+    AggregateUDF, Expr, GetFieldAccess, LogicalPlan, ScalarUDF, TableSource, WindowUDF,
+};
 
 /// Provides the `SQL` query planner  meta-data about tables and
 /// functions referenced in SQL statements, without a direct dependency on other
@@ -47,6 +49,28 @@ pub trait ContextProvider {
         not_impl_err!("Table Functions are not supported")
     }
 
+    /// Getter for a table function whose arguments may be relations, such as
+    /// `my_func(TABLE(t), 3)` or `my_func((SELECT * FROM t), 3)`
+    ///
+    /// The default implementation calls [`Self::get_table_function_source`] if
+    /// all arguments are expressions and returns an error otherwise.
+    fn get_table_function_source_with_args(
+        &self,
+        name: &str,
+        args: Vec<TableFunctionArg>,
+    ) -> Result<Arc<dyn TableSource>> {
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                TableFunctionArg::Expr(expr) => Ok(expr),
+                TableFunctionArg::Table(_) => {
+                    plan_err!("Table function '{name}' does not accept table arguments")
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.get_table_function_source(name, args)
+    }
+
     /// This provides a worktable (an intermediate table that is used to store the results of a CTE during execution)
     /// We don't directly implement this in the logical plan's ['SqlToRel`]
     /// because the sql code needs access to a table that contains execution-related types that can't be a direct dependency
@@ -87,6 +111,33 @@ pub trait ContextProvider {
     fn udwf_names(&self) -> Vec<String>;
 }
 
+/// An argument of a table function call
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableFunctionArg {
+    /// A scalar expression, such as `3` in `my_func(TABLE(t), 3)`
+    Expr(Expr),
+    /// A relation, such as `TABLE(t)` or `(SELECT * FROM t)` in `my_func(TABLE(t), 3)`
+    Table(LogicalPlan),
+}
+
+impl TableFunctionArg {
+    /// Returns the expression if this argument is a scalar expression
+    pub fn as_expr(&self) -> Option<&Expr> {
+        match self {
+            Self::Expr(expr) => Some(expr),
+            Self::Table(_) => None,
+        }
+    }
+
+    /// Returns the logical plan if this argument is a relation
+    pub fn as_table(&self) -> Option<&LogicalPlan> {
+        match self {
+            Self::Expr(_) => None,
+            Self::Table(plan) => Some(plan),
+        }
+    }
+}
+
 /// This trait allows users to customize the behavior of the SQL planner
 pub trait ExprPlanner: Send + Sync {
     /// Plan the binary operation between two expressions, returns original
@@ -19,7 +19,8 @@
 
 use crate::expr::{
     AggregateFunction, Alias, Between, BinaryExpr, Case, Cast, GroupingSet, InList,
-    InSubquery, Like, Placeholder, ScalarFunction, Sort, TryCast, Unnest, WindowFunction,
+    InSubquery, Lambda, Like, Placeholder, ScalarFunction, Sort, TryCast, Unnest,
+    WindowFunction,
 };
 use crate::{Expr, ExprFunctionExt};
 
@@ -49,6 +50,7 @@ impl TreeNode for Expr {
             | Expr::Cast(Cast { expr, .. })
             | Expr::TryCast(TryCast { expr, .. })
             | Expr::Sort(Sort { expr, .. })
+            | Expr::Lambda(Lambda { body: expr, .. })
             | Expr::InSubquery(InSubquery{ expr, .. }) => vec![expr.as_ref()],
             Expr::GroupingSet(GroupingSet::Rollup(exprs))
             | Expr::GroupingSet(GroupingSet::Cube(exprs)) => exprs.iter().collect(),
@@ -66,6 +68,7 @@ impl TreeNode for Expr {
             | Expr::Exists {..}
             | Expr::ScalarSubquery(_)
             | Expr::Wildcard {..}
+            | Expr::LambdaVariable(_)
             | Expr::Placeholder (_) => vec![],
             Expr::BinaryExpr(BinaryExpr { left, right, .. }) => {
                 vec![left.as_ref(), right.as_ref()]
@@ -135,9 +138,12 @@ impl TreeNode for Expr {
             | Expr::Exists { .. }
             | Expr::ScalarSubquery(_)
             | Expr::ScalarVariable(_, _)
+            | Expr::LambdaVariable(_)
             | Expr::Literal(_) => Transformed::no(self),
             Expr::Unnest(Unnest { expr, .. }) => transform_box(expr, &mut f)?
                 .update_data(|be| Expr::Unnest(Unnest::new_boxed(be))),
+            Expr::Lambda(Lambda { params, body }) => transform_box(body, &mut f)?
+                .update_data(|body| Expr::Lambda(Lambda { params, body })),
             Expr::Alias(Alias {
                 expr,
                 relation,
@@ -23,6 +23,7 @@ use crate::sort_properties::{ExprProperties, SortProperties};
 use crate::{
     ColumnarValue, Expr, ReturnTypeFunction, ScalarFunctionImplementation, Signature,
 };
+use arrow::array::ArrayRef;
 use arrow::datatypes::DataType;
 use datafusion_common::{not_impl_err, ExprSchema, Result};
 use datafusion_expr_common::interval_arithmetic::Interval;
@@ -205,6 +206,28 @@ impl ScalarUDF {
         self.inner.invoke(args)
     }
 
+    /// Invoke the function on `args` and the lambda functions passed to it.
+    ///
+    /// See [`ScalarUDFImpl::invoke_with_lambdas`] for more details.
+    pub fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        self.inner.invoke_with_lambdas(args, lambdas)
+    }
+
+    /// Returns the parameter types of the lambda functions passed to this
+    /// function.
+    ///
+    /// See [`ScalarUDFImpl::lambda_parameter_types`] for more details.
+    pub fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        self.inner.lambda_parameter_types(arg_types)
+    }
+
     pub fn is_nullable(&self, args: &[Expr], schema: &dyn ExprSchema) -> bool {
         self.inner.is_nullable(args, schema)
     }
@@ -285,6 +308,17 @@ impl ScalarUDF {
     }
 }
 
+/// A compiled lambda function, passed to
+/// [`ScalarUDFImpl::invoke_with_lambdas`].
+///
+/// See [`Expr::Lambda`] for the logical representation.
+pub trait LambdaFunction: Debug + Send + Sync {
+    /// Evaluate the lambda body, where `args[i]` holds the values of the
+    /// `i`th lambda parameter. All arrays must have the same length, and
+    /// the result has that length as well.
+    fn invoke(&self, args: &[ArrayRef]) -> Result<ArrayRef>;
+}
+
 impl<F> From<F> for ScalarUDF
 where
     F: ScalarUDFImpl + Send + Sync + 'static,
@@ -457,6 +491,42 @@ pub trait ScalarUDFImpl: Debug + Send + Sync {
         )
     }
 
+    /// Invoke a higher-order function, i.e. a function that takes one or
+    /// more lambda functions as arguments, such as `array_transform(a, x -> x + 1)`.
+    ///
+    /// `args` holds the evaluated non-lambda arguments, in order, and
+    /// `lambdas` holds the lambda arguments, in order. A [`LambdaFunction`]
+    /// is evaluated on whole arrays at once, so implementations should pass
+    /// it all the values they need (e.g. the flattened values of a list
+    /// array) in a single call.
+    ///
+    /// This function is only called if the function is invoked with at
+    /// least one lambda argument, in which case [`Self::invoke`] is not called.
+    fn invoke_with_lambdas(
+        &self,
+        _args: &[ColumnarValue],
+        _lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        not_impl_err!("Function {} does not support lambda arguments", self.name())
+    }
+
+    /// Returns the types of the parameters of each lambda argument of this
+    /// function, given the types of the non-lambda arguments.
+    ///
+    /// For example `array_transform(a, x -> x + 1)` where `a` is a
+    /// `List(Int32)` returns `[[Int32]]`: one lambda, with a single
+    /// `Int32` parameter. These types are used to plan the lambda bodies.
+    ///
+    /// Defaults to a `NotImplemented` error, i.e. the function does not accept
+    /// lambdas. Functions that accept lambdas should report invalid arguments
+    /// with other errors, such as plan errors.
+    fn lambda_parameter_types(
+        &self,
+        _arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        not_impl_err!("Function {} does not support lambda arguments", self.name())
+    }
+
     /// Returns any aliases (alternate names) for this function.
     ///
     /// Aliases can be used to invoke the same function using different names.
@@ -672,6 +742,21 @@ impl ScalarUDFImpl for AliasedScalarUDFImpl {
         self.inner.invoke_no_args(number_rows)
     }
 
+    fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        self.inner.invoke_with_lambdas(args, lambdas)
+    }
+
+    fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        self.inner.lambda_parameter_types(arg_types)
+    }
+
     fn simplify(
         &self,
         args: Vec<Expr>,
@@ -277,6 +277,8 @@ pub fn expr_to_columns(expr: &Expr, accum: &mut HashSet<Column>) -> Result<()> {
             // new Expr types, they will check here as well
             Expr::Unnest(_)
             | Expr::ScalarVariable(_, _)
+            | Expr::Lambda(_)
+            | Expr::LambdaVariable(_)
             | Expr::Alias(_)
             | Expr::Literal(_)
             | Expr::BinaryExpr { .. }
@@ -0,0 +1,604 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ScalarUDFImpl`] definitions for higher-order array functions, which take
+//! a lambda function as argument: array_transform, array_filter, array_reduce,
+//! array_any and array_all.
+//!
+//! The lambda functions are evaluated once per batch on the flattened values
+//! of the list arrays, rather than once per list.
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, BooleanArray, BooleanBuilder, ListArray, UInt32Array,
+};
+use arrow::compute::{cast, concat, filter, nullif, prep_null_mask_filter, take};
+use arrow_buffer::OffsetBuffer;
+use arrow_schema::{DataType, Field};
+use datafusion_common::cast::as_list_array;
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::{
+    ColumnarValue, LambdaFunction, ScalarUDFImpl, Signature, Volatility,
+};
+use std::any::Any;
+use std::sync::Arc;
+
+make_udf_expr_and_func!(
+    ArrayTransform,
+    array_transform,
+    array lambda,
+    "returns an array with the result of a lambda function applied to each element of the array.",
+    array_transform_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayFilter,
+    array_filter,
+    array lambda,
+    "returns an array with the elements of the array for which a lambda function returns true.",
+    array_filter_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayReduce,
+    array_reduce,
+    array initial lambda,
+    "reduces the elements of the array to a single value, using a lambda function of the accumulator and the element.",
+    array_reduce_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayAny,
+    array_any,
+    array lambda,
+    "returns true if a lambda function returns true for any element of the array.",
+    array_any_udf
+);
+
+make_udf_expr_and_func!(
+    ArrayAll,
+    array_all,
+    array lambda,
+    "returns true if a lambda function returns true for all elements of the array.",
+    array_all_udf
+);
+
+#[derive(Debug)]
+pub(super) struct ArrayTransform {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayTransform {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec![String::from("list_transform")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayTransform {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_transform"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types, 2)?;
+        Ok(list_of(arg_types[1].clone()))
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        check_arg_count(self.name(), arg_types, 1)?;
+        Ok(vec![vec![element_type(self.name(), &arg_types[0])?]])
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        missing_lambda(self.name())
+    }
+
+    fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        invoke_higher_order(self.name(), args, lambdas, |args, lambda| {
+            let list = as_list_array(&args[0])?;
+            let (values, offsets) = flat_values(list);
+            let values = lambda.invoke(&[values])?;
+            Ok(Arc::new(ListArray::try_new(
+                Arc::new(Field::new_list_field(values.data_type().clone(), true)),
+                offsets,
+                values,
+                list.nulls().cloned(),
+            )?))
+        })
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayFilter {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayFilter {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec![String::from("list_filter")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayFilter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_filter"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types, 2)?;
+        check_predicate(self.name(), &arg_types[1])?;
+        Ok(list_of(element_type(self.name(), &arg_types[0])?))
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        check_arg_count(self.name(), arg_types, 1)?;
+        Ok(vec![vec![element_type(self.name(), &arg_types[0])?]])
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        missing_lambda(self.name())
+    }
+
+    fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        invoke_higher_order(self.name(), args, lambdas, |args, lambda| {
+            let list = as_list_array(&args[0])?;
+            let (values, offsets) = flat_values(list);
+            // elements for which the lambda returns null are filtered out
+            let predicate = prep_null_mask_filter(&invoke_predicate(lambda, &values)?);
+
+            let mut new_offsets = Vec::with_capacity(offsets.len());
+            new_offsets.push(0);
+            for window in offsets.windows(2) {
+                let (start, end) = (window[0] as usize, window[1] as usize);
+                let kept = predicate
+                    .values()
+                    .slice(start, end - start)
+                    .count_set_bits();
+                new_offsets.push(new_offsets[new_offsets.len() - 1] + kept as i32);
+            }
+            let values = filter(&values, &predicate)?;
+            Ok(Arc::new(ListArray::try_new(
+                Arc::new(Field::new_list_field(values.data_type().clone(), true)),
+                OffsetBuffer::new(new_offsets.into()),
+                values,
+                list.nulls().cloned(),
+            )?))
+        })
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayReduce {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl ArrayReduce {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+            aliases: vec![String::from("list_reduce")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayReduce {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_reduce"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// The result has the type of the initial value, the results of the
+    /// lambda function are cast to it.
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types, 3)?;
+        Ok(arg_types[1].clone())
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        check_arg_count(self.name(), arg_types, 2)?;
+        Ok(vec![vec![
+            arg_types[1].clone(),
+            element_type(self.name(), &arg_types[0])?,
+        ]])
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        missing_lambda(self.name())
+    }
+
+    fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        invoke_higher_order(self.name(), args, lambdas, |args, lambda| {
+            let list = as_list_array(&args[0])?;
+            let (values, offsets) = flat_values(list);
+            let acc_type = args[1].data_type().clone();
+            let list_len =
+                |row: u32| (offsets[row as usize + 1] - offsets[row as usize]) as u32;
+
+            // the k-th step of the reduction is evaluated at once for the
+            // lists with more than k elements, which are kept in `active` with
+            // their accumulators in `step_acc`, so that the lambda function is
+            // invoked once per element
+            let mut active = (0..list.len() as u32)
+                .filter(|row| list_len(*row) > 0)
+                .collect::<Vec<_>>();
+            let mut step_acc = take(&args[1], &UInt32Array::from(active.clone()), None)?;
+            // the results of the lists done so far, and for each row the index
+            // of its result in the initial values followed by these results
+            let mut done_acc = vec![];
+            let mut indices = (0..list.len() as u32).collect::<Vec<_>>();
+            let mut done_count = list.len() as u32;
+            let mut k = 0;
+            while !active.is_empty() {
+                let elements = active
+                    .iter()
+                    .map(|row| offsets[*row as usize] as u32 + k)
+                    .collect::<Vec<_>>();
+                let step_values = take(&values, &UInt32Array::from(elements), None)?;
+                let step = cast(&lambda.invoke(&[step_acc, step_values])?, &acc_type)?;
+                k += 1;
+
+                let (done, remaining): (Vec<u32>, Vec<u32>) = (0..active.len() as u32)
+                    .partition(|i| list_len(active[*i as usize]) == k);
+                if !done.is_empty() {
+                    for i in &done {
+                        indices[active[*i as usize] as usize] = done_count;
+                        done_count += 1;
+                    }
+                    done_acc.push(take(&step, &UInt32Array::from(done), None)?);
+                }
+                active = remaining.iter().map(|i| active[*i as usize]).collect();
+                step_acc = take(&step, &UInt32Array::from(remaining), None)?;
+            }
+
+            let acc = if done_acc.is_empty() {
+                Arc::clone(&args[1])
+            } else {
+                let mut parts = vec![args[1].as_ref()];
+                parts.extend(done_acc.iter().map(|acc| acc.as_ref()));
+                take(&concat(&parts)?, &UInt32Array::from(indices), None)?
+            };
+            Ok(nullif(&acc, &arrow::compute::is_null(list)?)?)
+        })
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayAny {
+    signature: Signature,
+}
+
+impl ArrayAny {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayAny {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_any"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types, 2)?;
+        check_predicate(self.name(), &arg_types[1])?;
+        Ok(DataType::Boolean)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        check_arg_count(self.name(), arg_types, 1)?;
+        Ok(vec![vec![element_type(self.name(), &arg_types[0])?]])
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        missing_lambda(self.name())
+    }
+
+    fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        invoke_higher_order(self.name(), args, lambdas, |args, lambda| {
+            quantify(as_list_array(&args[0])?, lambda, true)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ArrayAll {
+    signature: Signature,
+}
+
+impl ArrayAll {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for ArrayAll {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "array_all"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        check_arg_count(self.name(), arg_types, 2)?;
+        check_predicate(self.name(), &arg_types[1])?;
+        Ok(DataType::Boolean)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        coerce_list_arg(self.name(), arg_types)
+    }
+
+    fn lambda_parameter_types(
+        &self,
+        arg_types: &[DataType],
+    ) -> Result<Vec<Vec<DataType>>> {
+        check_arg_count(self.name(), arg_types, 1)?;
+        Ok(vec![vec![element_type(self.name(), &arg_types[0])?]])
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        missing_lambda(self.name())
+    }
+
+    fn invoke_with_lambdas(
+        &self,
+        args: &[ColumnarValue],
+        lambdas: &[Arc<dyn LambdaFunction>],
+    ) -> Result<ColumnarValue> {
+        invoke_higher_order(self.name(), args, lambdas, |args, lambda| {
+            quantify(as_list_array(&args[0])?, lambda, false)
+        })
+    }
+}
+
+/// Evaluates `array_any` (`any` is true) or `array_all` (`any` is false),
+/// following the semantics of `OR` and `AND` respectively: the result is null
+/// when it is not decided by the non null results of the lambda function.
+fn quantify(
+    list: &ListArray,
+    lambda: &Arc<dyn LambdaFunction>,
+    any: bool,
+) -> Result<ArrayRef> {
+    let (values, offsets) = flat_values(list);
+    let predicate = invoke_predicate(lambda, &values)?;
+
+    let mut builder = BooleanBuilder::with_capacity(list.len());
+    for (row, window) in offsets.windows(2).enumerate() {
+        if list.is_null(row) {
+            builder.append_null();
+            continue;
+        }
+        let mut seen_null = false;
+        let mut decided = false;
+        for i in window[0] as usize..window[1] as usize {
+            if predicate.is_null(i) {
+                seen_null = true;
+            } else if predicate.value(i) == any {
+                decided = true;
+                break;
+            }
+        }
+        if decided {
+            builder.append_value(any);
+        } else if seen_null {
+            builder.append_null();
+        } else {
+            builder.append_value(!any);
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Converts the arguments of a higher-order function to arrays and invokes
+/// `inner` with its single lambda function, returning a scalar if all the
+/// arguments are scalars.
+fn invoke_higher_order<F>(
+    name: &str,
+    args: &[ColumnarValue],
+    lambdas: &[Arc<dyn LambdaFunction>],
+    inner: F,
+) -> Result<ColumnarValue>
+where
+    F: Fn(&[ArrayRef], &Arc<dyn LambdaFunction>) -> Result<ArrayRef>,
+{
+    let [lambda] = lambdas else {
+        return exec_err!("{name} expects one lambda function, got {}", lambdas.len());
+    };
+    let is_scalar = args
+        .iter()
+        .all(|arg| matches!(arg, ColumnarValue::Scalar(_)));
+    let args = ColumnarValue::values_to_arrays(args)?;
+    let result = inner(&args, lambda)?;
+    if is_scalar {
+        let result = ScalarValue::try_from_array(&result, 0)?;
+        Ok(ColumnarValue::Scalar(result))
+    } else {
+        Ok(ColumnarValue::Array(result))
+    }
+}
+
+/// Returns the values of `list` that are referenced by its offsets, and the
+/// offsets rebased on these values
+fn flat_values(list: &ListArray) -> (ArrayRef, OffsetBuffer<i32>) {
+    let offsets = list.offsets();
+    let start = offsets[0];
+    let end = offsets[offsets.len() - 1];
+    let values = list.values().slice(start as usize, (end - start) as usize);
+    let offsets = offsets.iter().map(|o| o - start).collect::<Vec<_>>();
+    (values, OffsetBuffer::new(offsets.into()))
+}
+
+/// Evaluates a lambda function returning a boolean on `values`
+fn invoke_predicate(
+    lambda: &Arc<dyn LambdaFunction>,
+    values: &ArrayRef,
+) -> Result<BooleanArray> {
+    let predicate = lambda.invoke(&[Arc::clone(values)])?;
+    Ok(cast(&predicate, &DataType::Boolean)?.as_boolean().clone())
+}
+
+fn list_of(data_type: DataType) -> DataType {
+    DataType::List(Arc::new(Field::new_list_field(data_type, true)))
+}
+
+fn element_type(name: &str, data_type: &DataType) -> Result<DataType> {
+    match data_type {
+        DataType::List(field)
+        | DataType::LargeList(field)
+        | DataType::FixedSizeList(field, _) => Ok(field.data_type().clone()),
+        DataType::Null => Ok(DataType::Null),
+        _ => plan_err!("{name} does not support type '{data_type:?}'"),
+    }
+}
+
+/// All the list types are evaluated as [`DataType::List`]
+fn coerce_list_arg(name: &str, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+    let Some((list_type, rest)) = arg_types.split_first() else {
+        return plan_err!("{name} expects at least one argument");
+    };
+    let mut coerced = vec![list_of(element_type(name, list_type)?)];
+    coerced.extend_from_slice(rest);
+    Ok(coerced)
+}
+
+fn check_arg_count(name: &str, arg_types: &[DataType], expected: usize) -> Result<()> {
+    if arg_types.len() != expected {
+        return plan_err!(
+            "{name} expects {expected} arguments, got {}",
+            arg_types.len()
+        );
+    }
+    Ok(())
+}
+
+fn check_predicate(name: &str, data_type: &DataType) -> Result<()> {
+    match data_type {
+        DataType::Boolean | DataType::Null => Ok(()),
+        _ => plan_err!(
+            "The lambda function of {name} must return a boolean, got '{data_type:?}'"
+        ),
+    }
+}
+
+fn missing_lambda(name: &str) -> Result<ColumnarValue> {
+    exec_err!("{name} expects a lambda function as argument")
+}
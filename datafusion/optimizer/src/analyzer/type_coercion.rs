@@ -51,8 +51,9 @@ use datafusion_expr::type_coercion::{is_datetime, is_utf8_or_large_utf8};
 use datafusion_expr::utils::merge_schema;
 use datafusion_expr::{
     is_false, is_not_false, is_not_true, is_not_unknown, is_true, is_unknown, not,
-    AggregateUDF, Expr, ExprFunctionExt, ExprSchemable, Join, LogicalPlan, Operator,
-    Projection, ScalarUDF, Union, WindowFrame, WindowFrameBound, WindowFrameUnits,
+    AggregateUDF, AsOfJoin, Expr, ExprFunctionExt, ExprSchemable, Join, LogicalPlan,
+    Operator, Projection, ScalarUDF, Union, WindowFrame, WindowFrameBound,
+    WindowFrameUnits,
 };
 
 /// Performs type coercion by determining the schema
@@ -148,6 +149,7 @@ impl<'a> TypeCoercionRewriter<'a> {
     pub fn coerce_plan(&mut self, plan: LogicalPlan) -> Result<LogicalPlan> {
         match plan {
             LogicalPlan::Join(join) => self.coerce_join(join),
+            LogicalPlan::AsOfJoin(join) => self.coerce_asof_join(join),
             LogicalPlan::Union(union) => Self::coerce_union(union),
             _ => Ok(plan),
         }
@@ -182,6 +184,17 @@ impl<'a> TypeCoercionRewriter<'a> {
         Ok(LogicalPlan::Join(join))
     }
 
+    /// Coerce the equality expressions of an as-of join, like
+    /// [`TypeCoercionRewriter::coerce_join`]
+    pub fn coerce_asof_join(&mut self, mut join: AsOfJoin) -> Result<LogicalPlan> {
+        join.on = join
+            .on
+            .into_iter()
+            .map(|(lhs, rhs)| self.coerce_binary_op(lhs, Operator::Eq, rhs))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(LogicalPlan::AsOfJoin(join))
+    }
+
     /// Coerce the union’s inputs to a common schema compatible with all inputs.
     /// This occurs after wildcard expansion and the coercion of the input expressions.
     pub fn coerce_union(union_plan: Union) -> Result<LogicalPlan> {
@@ -499,6 +512,8 @@ impl<'a> TreeNodeRewriter for TypeCoercionRewriter<'a> {
             Expr::Alias(_)
             | Expr::Column(_)
             | Expr::ScalarVariable(_, _)
+            | Expr::Lambda(_)
+            | Expr::LambdaVariable(_)
             | Expr::Literal(_)
             | Expr::SimilarTo(_)
             | Expr::IsNotNull(_)
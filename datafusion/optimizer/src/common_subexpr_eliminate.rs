@@ -760,6 +760,7 @@ impl OptimizerRule for CommonSubexprEliminate {
             LogicalPlan::Aggregate(agg) => self.try_optimize_aggregate(agg, config)?,
             LogicalPlan::Join(_)
             | LogicalPlan::CrossJoin(_)
+            | LogicalPlan::AsOfJoin(_)
             | LogicalPlan::Repartition(_)
             | LogicalPlan::Union(_)
             | LogicalPlan::TableScan(_)
@@ -768,6 +769,7 @@ impl OptimizerRule for CommonSubexprEliminate {
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
@@ -1092,7 +1094,11 @@ impl<'n> TreeNodeVisitor<'n> for ExprIdentifierVisitor<'_, 'n> {
         let (down_index, sub_expr_id, sub_expr_is_valid) = self.pop_enter_mark();
 
         let expr_id = Identifier::new(expr, self.random_state).combine(sub_expr_id);
-        let is_valid = !expr.is_volatile_node() && sub_expr_is_valid;
+        // Lambdas can only be evaluated by the higher-order function they
+        // are passed to, so neither they nor their bodies can be extracted.
+        let is_valid = !matches!(expr, Expr::Lambda(_) | Expr::LambdaVariable(_))
+            && !expr.is_volatile_node()
+            && sub_expr_is_valid;
 
         self.id_array[down_index].0 = self.up_index;
         if is_valid && !self.expr_mask.ignores(expr) {
@@ -0,0 +1,276 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`MaterializedViewRewrite`] replaces the query of a materialized view with
+//! a scan of the view
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::{Transformed, TreeNodeRecursion};
+use datafusion_common::{Result, TableReference};
+use datafusion_expr::logical_plan::{LogicalPlan, Projection};
+use datafusion_expr::{Expr, LogicalPlanBuilder, TableSource};
+
+/// Optimizer rule that replaces subplans equal to the query of a
+/// materialized view with a scan of the view, so that the stored results
+/// are read instead of being computed again.
+///
+/// For example, given the materialized view
+///
+/// ```sql
+/// CREATE MATERIALIZED VIEW mv AS SELECT a, sum(b) FROM t GROUP BY a
+/// ```
+///
+/// the query `SELECT * FROM (SELECT a, sum(b) FROM t GROUP BY a) WHERE a > 1`
+/// is rewritten to read `mv`. The results may be stale if `t` was modified
+/// since `mv` was last refreshed, which is why the rule only runs when
+/// `datafusion.optimizer.enable_materialized_view_rewrite` is set.
+///
+/// A single rule serves all the materialized views of a session, which are
+/// added with [`Self::register_view`] and removed with
+/// [`Self::deregister_view`] when the views are created and dropped.
+///
+/// Subplans are compared to the query of the view after analysis, so the
+/// rule should run before any other optimizer rule.
+#[derive(Default)]
+pub struct MaterializedViewRewrite {
+    /// The registered views, by name
+    views: RwLock<HashMap<TableReference, MaterializedViewDefinition>>,
+}
+
+/// A materialized view known to [`MaterializedViewRewrite`]
+struct MaterializedViewDefinition {
+    /// The analyzed query of the view
+    definition: LogicalPlan,
+    /// The view itself
+    source: Arc<dyn TableSource>,
+}
+
+impl MaterializedViewRewrite {
+    /// Create a rule without any materialized view
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace `definition`, the analyzed query of the materialized view
+    /// `view`, with a scan of `source`. Replaces any view with the same name.
+    pub fn register_view(
+        &self,
+        view: TableReference,
+        definition: LogicalPlan,
+        source: Arc<dyn TableSource>,
+    ) {
+        let view_definition = MaterializedViewDefinition { definition, source };
+        self.views.write().unwrap().insert(view, view_definition);
+    }
+
+    /// Stop replacing the query of the materialized view `view`, returning
+    /// true if the view was registered
+    pub fn deregister_view(&self, view: &TableReference) -> bool {
+        self.views.write().unwrap().remove(view).is_some()
+    }
+
+    /// Stop replacing the queries of the materialized views of the schema
+    /// `schema` of the catalog `catalog`, which was dropped
+    pub fn deregister_schema_views(&self, catalog: &str, schema: &str) {
+        self.views.write().unwrap().retain(|view, _| {
+            view.catalog() != Some(catalog) || view.schema() != Some(schema)
+        });
+    }
+
+    /// Returns a scan of the view whose query is equal to `plan`, if any,
+    /// projected to the qualified column names of `plan`
+    fn view_scan(&self, plan: &LogicalPlan) -> Result<Option<LogicalPlan>> {
+        let views = self.views.read().unwrap();
+        // Compare the schemas first, which is much cheaper than comparing
+        // whole plans
+        let Some((view, view_definition)) = views.iter().find(|(_, view)| {
+            view.definition.schema() == plan.schema() && &view.definition == plan
+        }) else {
+            return Ok(None);
+        };
+
+        let scan = LogicalPlanBuilder::scan(
+            view.clone(),
+            Arc::clone(&view_definition.source),
+            None,
+        )?
+        .build()?;
+        // The parents of the replaced plan refer to its columns by their
+        // qualified names, which differ from the ones of the view
+        let exprs = scan
+            .schema()
+            .columns()
+            .into_iter()
+            .zip(plan.schema().iter())
+            .map(|(column, (qualifier, field))| {
+                Expr::Column(column).alias_qualified(qualifier.cloned(), field.name())
+            })
+            .collect();
+        let projection = Projection::try_new(exprs, Arc::new(scan))?;
+        Ok(Some(LogicalPlan::Projection(projection)))
+    }
+}
+
+impl OptimizerRule for MaterializedViewRewrite {
+    fn name(&self) -> &str {
+        "materialized_view_rewrite"
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.enable_materialized_view_rewrite
+            || self.views.read().unwrap().is_empty()
+        {
+            return Ok(Transformed::no(plan));
+        }
+
+        plan.transform_down_with_subqueries(|plan| match self.view_scan(&plan)? {
+            // The scan of the view must not be rewritten again
+            Some(scan) => Ok(Transformed::new(scan, true, TreeNodeRecursion::Jump)),
+            None => Ok(Transformed::no(plan)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use crate::Optimizer;
+
+    use chrono::{DateTime, Utc};
+    use datafusion_common::alias::AliasGenerator;
+    use datafusion_common::config::ConfigOptions;
+    use datafusion_expr::logical_plan::builder::LogicalTableSource;
+    use datafusion_expr::{col, lit};
+    use datafusion_functions_aggregate::expr_fn::sum;
+
+    /// [`OptimizerConfig`] with the rewrite of materialized views enabled
+    struct RewriteEnabled {
+        options: ConfigOptions,
+    }
+
+    impl RewriteEnabled {
+        fn new() -> Self {
+            let mut options = ConfigOptions::default();
+            options.optimizer.enable_materialized_view_rewrite = true;
+            Self { options }
+        }
+    }
+
+    impl OptimizerConfig for RewriteEnabled {
+        fn query_execution_start_time(&self) -> DateTime<Utc> {
+            Utc::now()
+        }
+
+        fn alias_generator(&self) -> Arc<AliasGenerator> {
+            Arc::new(AliasGenerator::new())
+        }
+
+        fn options(&self) -> &ConfigOptions {
+            &self.options
+        }
+    }
+
+    fn materialized_view_rewrite() -> Result<MaterializedViewRewrite> {
+        let definition = test_table_scan()?;
+        let definition = LogicalPlanBuilder::from(definition)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+        let source =
+            LogicalTableSource::new(Arc::new(definition.schema().as_arrow().clone()));
+        let rule = MaterializedViewRewrite::new();
+        rule.register_view(TableReference::bare("mv"), definition, Arc::new(source));
+        Ok(rule)
+    }
+
+    fn assert_optimized_plan_equal(
+        rule: MaterializedViewRewrite,
+        plan: LogicalPlan,
+        expected: &str,
+    ) -> Result<()> {
+        let optimizer = Optimizer::with_rules(vec![Arc::new(rule)]);
+        let optimized_plan =
+            optimizer.optimize(plan, &RewriteEnabled::new(), |_, _| {})?;
+        assert_eq!(format!("{optimized_plan}"), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_subplan() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .filter(col("a").gt(lit(1u32)))?
+            .build()?;
+
+        let expected = "\
+        Filter: test.a > UInt32(1)\
+        \n  Projection: mv.a AS test.a, mv.sum(test.b) AS sum(test.b)\
+        \n    TableScan: mv";
+        assert_optimized_plan_equal(materialized_view_rewrite()?, plan, expected)
+    }
+
+    #[test]
+    fn different_plan_not_rewritten() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("c"))])?
+            .build()?;
+
+        let expected = "\
+        Aggregate: groupBy=[[test.a]], aggr=[[sum(test.c)]]\
+        \n  TableScan: test";
+        assert_optimized_plan_equal(materialized_view_rewrite()?, plan, expected)
+    }
+
+    #[test]
+    fn deregistered_view_not_used() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+
+        let rule = materialized_view_rewrite()?;
+        assert!(rule.deregister_view(&TableReference::bare("mv")));
+        let expected = "\
+        Aggregate: groupBy=[[test.a]], aggr=[[sum(test.b)]]\
+        \n  TableScan: test";
+        assert_optimized_plan_equal(rule, plan, expected)
+    }
+
+    #[test]
+    fn rewrite_disabled_by_default() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .build()?;
+
+        // The stored results may be stale, so they are only read when enabled
+        let expected = "\
+        Aggregate: groupBy=[[test.a]], aggr=[[sum(test.b)]]\
+        \n  TableScan: test";
+        assert_optimized_plan_eq(Arc::new(materialized_view_rewrite()?), plan, expected)
+    }
+}
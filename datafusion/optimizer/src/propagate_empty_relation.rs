@@ -65,7 +65,8 @@ impl OptimizerRule for PropagateEmptyRelation {
             | LogicalPlan::Sort(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Repartition(_)
-            | LogicalPlan::Limit(_) => {
+            | LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_) => {
                 let empty = empty_child(&plan)?;
                 if let Some(empty_plan) = empty {
                     return Ok(Transformed::yes(empty_plan));
@@ -85,6 +86,20 @@ impl OptimizerRule for PropagateEmptyRelation {
                 Ok(Transformed::no(LogicalPlan::CrossJoin(join.clone())))
             }
 
+            LogicalPlan::AsOfJoin(ref join) => {
+                // every left row is returned at most once, with a match for inner joins
+                let (left_empty, right_empty) = binary_plan_children_is_empty(&plan)?;
+                if left_empty || (right_empty && join.join_type == JoinType::Inner) {
+                    return Ok(Transformed::yes(LogicalPlan::EmptyRelation(
+                        EmptyRelation {
+                            produce_one_row: false,
+                            schema: Arc::clone(&join.schema),
+                        },
+                    )));
+                }
+                Ok(Transformed::no(plan))
+            }
+
             LogicalPlan::Join(ref join) => {
                 // TODO: For Join, more join type need to be careful:
                 // For LeftOut/Full Join, if the right side is empty, the Join can be eliminated with a Projection with left side
@@ -585,6 +585,8 @@ impl<'a> ConstEvaluator<'a> {
             Expr::Alias(..)
             | Expr::AggregateFunction { .. }
             | Expr::ScalarVariable(_, _)
+            | Expr::Lambda(_)
+            | Expr::LambdaVariable(_)
             | Expr::Column(_)
             | Expr::OuterReferenceColumn(_, _)
             | Expr::Exists { .. }
@@ -0,0 +1,193 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Lambda function expression, passed to higher-order functions
+
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, RecordBatch, RecordBatchOptions};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+
+use crate::physical_expr::down_cast_any_ref;
+use crate::PhysicalExpr;
+use datafusion_common::{internal_err, Result};
+use datafusion_expr::{ColumnarValue, LambdaFunction};
+
+/// A lambda function such as `x -> x + 1`.
+///
+/// The body is planned against a schema made of the lambda parameters, in
+/// order, so a parameter is referenced by a [`Column`] with its position.
+///
+/// A `LambdaExpr` can not be evaluated on its own: it is only an argument of
+/// a [`ScalarFunctionExpr`], which invokes it as a [`LambdaFunction`] on the
+/// values it chooses. Its body is not one of its [`PhysicalExpr::children`],
+/// as it does not refer to the columns of the input.
+///
+/// [`Column`]: crate::expressions::Column
+/// [`ScalarFunctionExpr`]: crate::ScalarFunctionExpr
+#[derive(Debug, Clone, Hash)]
+pub struct LambdaExpr {
+    params: Vec<String>,
+    body: Arc<dyn PhysicalExpr>,
+    schema: SchemaRef,
+}
+
+impl LambdaExpr {
+    /// Create a new lambda expression, where `schema` holds the lambda
+    /// parameters `body` was planned against
+    pub fn new(
+        params: Vec<String>,
+        body: Arc<dyn PhysicalExpr>,
+        schema: SchemaRef,
+    ) -> Self {
+        Self {
+            params,
+            body,
+            schema,
+        }
+    }
+
+    /// The names of the lambda parameters
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    /// The body of the lambda
+    pub fn body(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.body
+    }
+}
+
+impl std::fmt::Display for LambdaExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.params.as_slice() {
+            [param] => write!(f, "{param} -> {}", self.body),
+            params => write!(f, "({}) -> {}", params.join(", "), self.body),
+        }
+    }
+}
+
+impl PhysicalExpr for LambdaExpr {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        self.body.data_type(&self.schema)
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn evaluate(&self, _batch: &RecordBatch) -> Result<ColumnarValue> {
+        internal_err!("LambdaExpr::evaluate() should not be called")
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn PhysicalExpr>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(self)
+    }
+
+    fn dyn_hash(&self, state: &mut dyn Hasher) {
+        let mut s = state;
+        self.hash(&mut s);
+    }
+}
+
+impl PartialEq<dyn Any> for LambdaExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| self.params == x.params && self.body.eq(&x.body))
+            .unwrap_or(false)
+    }
+}
+
+impl LambdaFunction for LambdaExpr {
+    fn invoke(&self, args: &[ArrayRef]) -> Result<ArrayRef> {
+        if args.len() != self.params.len() {
+            return internal_err!(
+                "Lambda with {} parameters invoked with {} arguments",
+                self.params.len(),
+                args.len()
+            );
+        }
+        let num_rows = args.first().map(|a| a.len()).unwrap_or_default();
+        // use the actual argument types, as unreferenced parameters are
+        // planned as nulls
+        let fields = self
+            .params
+            .iter()
+            .zip(args)
+            .map(|(name, arg)| Field::new(name, arg.data_type().clone(), true))
+            .collect::<Vec<_>>();
+        let batch = RecordBatch::try_new_with_options(
+            Arc::new(Schema::new(fields)),
+            args.to_vec(),
+            &RecordBatchOptions::new().with_row_count(Some(num_rows)),
+        )?;
+        self.body.evaluate(&batch)?.into_array(num_rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{binary, col, lit};
+
+    use arrow::array::{AsArray, Int64Array};
+    use arrow::datatypes::Int64Type;
+    use datafusion_expr::Operator;
+
+    #[test]
+    fn invoke_lambda() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("acc", DataType::Int64, true),
+            Field::new("x", DataType::Int64, true),
+        ]));
+        let body = binary(
+            col("acc", &schema)?,
+            Operator::Plus,
+            binary(col("x", &schema)?, Operator::Multiply, lit(2i64), &schema)?,
+            &schema,
+        )?;
+        let lambda = LambdaExpr::new(vec!["acc".into(), "x".into()], body, schema);
+        assert_eq!(lambda.to_string(), "(acc, x) -> acc@0 + x@1 * 2");
+        assert_eq!(lambda.data_type(&Schema::empty())?, DataType::Int64);
+
+        let acc: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        let x: ArrayRef = Arc::new(Int64Array::from(vec![10, 20, 30]));
+        let result = lambda.invoke(&[acc, x])?;
+        assert_eq!(
+            result.as_primitive::<Int64Type>(),
+            &Int64Array::from(vec![Some(21), None, Some(63)])
+        );
+
+        assert!(lambda.invoke(&[]).is_err());
+        Ok(())
+    }
+}
@@ -25,6 +25,7 @@ mod column;
 mod in_list;
 mod is_not_null;
 mod is_null;
+mod lambda;
 mod like;
 mod literal;
 mod negative;
@@ -50,6 +51,7 @@ pub use datafusion_expr::utils::format_state_name;
 pub use in_list::{in_list, InListExpr};
 pub use is_not_null::{is_not_null, IsNotNullExpr};
 pub use is_null::{is_null, IsNullExpr};
+pub use lambda::LambdaExpr;
 pub use like::{like, LikeExpr};
 pub use literal::{lit, Literal};
 pub use negative::{negative, NegativeExpr};
@@ -15,20 +15,24 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::scalar_function;
 use crate::{
-    expressions::{self, binary, like, Column, Literal},
+    expressions::{self, binary, like, Column, LambdaExpr, Literal},
     PhysicalExpr,
 };
 
-use arrow::datatypes::Schema;
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
 use datafusion_common::{
     exec_err, not_impl_err, plan_err, DFSchema, Result, ScalarValue, ToDFSchema,
 };
 use datafusion_expr::execution_props::ExecutionProps;
-use datafusion_expr::expr::{Alias, Cast, InList, ScalarFunction};
+use datafusion_expr::expr::{
+    Alias, Cast, InList, Lambda, LambdaVariable, ScalarFunction,
+};
 use datafusion_expr::var_provider::is_system_variables;
 use datafusion_expr::var_provider::VarType;
 use datafusion_expr::{
@@ -119,6 +123,46 @@ pub fn create_physical_expr(
             Ok(Arc::new(Column::new(&c.name, idx)))
         }
         Expr::Literal(value) => Ok(Arc::new(Literal::new(value.clone()))),
+        Expr::LambdaVariable(LambdaVariable { name, .. }) => {
+            // only valid in the body of a lambda, planned against its parameters
+            match input_dfschema.index_of_column_by_name(None, name) {
+                Some(idx) => Ok(Arc::new(Column::new(name, idx))),
+                None => plan_err!("Lambda variable {name} used outside of its lambda"),
+            }
+        }
+        Expr::Lambda(Lambda { params, body }) => {
+            // the body only refers to the lambda parameters, whose types are
+            // found on the variables referencing them
+            let mut param_types = HashMap::new();
+            body.apply(|expr| {
+                Ok(match expr {
+                    Expr::LambdaVariable(LambdaVariable { name, data_type }) => {
+                        param_types.insert(name.as_str(), data_type.clone());
+                        TreeNodeRecursion::Continue
+                    }
+                    // nested lambdas have their own parameters
+                    Expr::Lambda(_) => TreeNodeRecursion::Jump,
+                    _ => TreeNodeRecursion::Continue,
+                })
+            })?;
+            let fields = params
+                .iter()
+                .map(|name| {
+                    let data_type = param_types
+                        .get(name.as_str())
+                        .cloned()
+                        .unwrap_or(DataType::Null);
+                    Field::new(name, data_type, true)
+                })
+                .collect::<Vec<_>>();
+            let lambda_schema = Schema::new(fields).to_dfschema()?;
+            let body = create_physical_expr(body, &lambda_schema, execution_props)?;
+            Ok(Arc::new(LambdaExpr::new(
+                params.clone(),
+                body,
+                Arc::clone(lambda_schema.inner()),
+            )))
+        }
         Expr::ScalarVariable(_, variable_names) => {
             if is_system_variables(variable_names) {
                 match execution_props.get_var_provider(VarType::System) {
@@ -34,6 +34,7 @@ use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use crate::expressions::LambdaExpr;
 use crate::physical_expr::{down_cast_any_ref, physical_exprs_equal};
 use crate::PhysicalExpr;
 
@@ -43,7 +44,7 @@ use datafusion_common::{internal_err, DFSchema, Result};
 use datafusion_expr::interval_arithmetic::Interval;
 use datafusion_expr::sort_properties::ExprProperties;
 use datafusion_expr::type_coercion::functions::data_types_with_scalar_udf;
-use datafusion_expr::{expr_vec_fmt, ColumnarValue, Expr, ScalarUDF};
+use datafusion_expr::{expr_vec_fmt, ColumnarValue, Expr, LambdaFunction, ScalarUDF};
 
 /// Physical expression of a scalar function
 pub struct ScalarFunctionExpr {
@@ -133,16 +134,24 @@ impl PhysicalExpr for ScalarFunctionExpr {
     }
 
     fn evaluate(&self, batch: &RecordBatch) -> Result<ColumnarValue> {
-        let inputs = self
-            .args
-            .iter()
-            .map(|e| e.evaluate(batch))
-            .collect::<Result<Vec<_>>>()?;
+        // lambda arguments are not evaluated but passed to the function,
+        // which invokes them on the values of its choice
+        let mut inputs = Vec::with_capacity(self.args.len());
+        let mut lambdas: Vec<Arc<dyn LambdaFunction>> = vec![];
+        for arg in &self.args {
+            match arg.as_any().downcast_ref::<LambdaExpr>() {
+                Some(lambda) => lambdas.push(Arc::new(lambda.clone())),
+                None => inputs.push(arg.evaluate(batch)?),
+            }
+        }
 
         // evaluate the function
-        let output = match self.args.is_empty() {
-            true => self.fun.invoke_no_args(batch.num_rows()),
-            false => self.fun.invoke(&inputs),
+        let output = if !lambdas.is_empty() {
+            self.fun.invoke_with_lambdas(&inputs, &lambdas)
+        } else if self.args.is_empty() {
+            self.fun.invoke_no_args(batch.num_rows())
+        } else {
+            self.fun.invoke(&inputs)
         }?;
 
         if let ColumnarValue::Array(array) = &output {
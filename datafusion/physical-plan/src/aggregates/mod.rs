@@ -1598,6 +1598,16 @@ mod tests {
             // When spilling, the output rows metrics become partial output size + final output size
             // This is because final aggregation starts while partial aggregation is still emitting
             assert_eq!(8, output_rows);
+            assert!(metrics.spill_count().unwrap() > 0);
+            assert!(metrics.spilled_rows().unwrap() > 0);
+            let spill_file_bytes =
+                metrics.sum_by_name("spill_file_bytes").unwrap().as_usize();
+            assert!(spill_file_bytes > 0);
+            // the spill files are read back, except for the schema at their start
+            let spill_read_bytes =
+                metrics.sum_by_name("spill_read_bytes").unwrap().as_usize();
+            assert!(spill_read_bytes > 0);
+            assert!(spill_read_bytes <= spill_file_bytes);
         } else {
             assert_eq!(3, output_rows);
         }
@@ -31,13 +31,14 @@ use crate::common::IPCWriter;
 use crate::metrics::{BaselineMetrics, MetricBuilder, RecordOutput};
 use crate::sorts::sort::sort_batch;
 use crate::sorts::streaming_merge;
-use crate::spill::read_spill_as_stream;
+use crate::spill::{read_spill_as_stream_with_read_bytes, spill_write_options};
 use crate::stream::RecordBatchStreamAdapter;
 use crate::{aggregates, metrics, ExecutionPlan, PhysicalExpr};
 use crate::{RecordBatchStream, SendableRecordBatchStream};
 
 use arrow::array::*;
 use arrow::datatypes::SchemaRef;
+use arrow::ipc::writer::IpcWriteOptions;
 use arrow_schema::SortOptions;
 use datafusion_common::{internal_datafusion_err, DataFusionError, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
@@ -92,6 +93,13 @@ struct SpillState {
     /// GROUP BY expressions for merging spilled data
     merging_group_by: PhysicalGroupBy,
 
+    /// Options to write spill files with, e.g. their compression
+    spill_write_options: IpcWriteOptions,
+
+    /// The maximum disk space the spill files may use, if lower than the
+    /// limit of the `DiskManager`
+    max_temp_directory_size: Option<u64>,
+
     // ========================================================================
     // STATES:
     // Fields changes during execution. Can be buffer, or state flags that
@@ -103,6 +111,24 @@ struct SpillState {
 
     /// true when streaming merge is in progress
     is_stream_merging: bool,
+
+    // ========================================================================
+    // METRICS:
+    // ========================================================================
+    /// Number of spill files written
+    spill_count: metrics::Count,
+
+    /// Total in-memory size of the batches spilled to disk
+    spilled_bytes: metrics::Count,
+
+    /// Total number of rows spilled to disk
+    spilled_rows: metrics::Count,
+
+    /// Total size of the spill files written to disk, after compression
+    spill_file_bytes: metrics::Count,
+
+    /// Total number of bytes read back from the spill files
+    spill_read_bytes: metrics::Count,
 }
 
 /// Tracks if the aggregate should skip partial aggregations
@@ -511,6 +537,7 @@ impl GroupedHashAggregateStream {
 
         let exec_state = ExecutionState::ReadingInput;
 
+        let execution_options = &context.session_config().options().execution;
         let spill_state = SpillState {
             spills: vec![],
             spill_expr,
@@ -518,6 +545,17 @@ impl GroupedHashAggregateStream {
             is_stream_merging: false,
             merging_aggregate_arguments,
             merging_group_by: PhysicalGroupBy::new_single(agg_group_by.expr.clone()),
+            spill_write_options: spill_write_options(
+                execution_options.spill_compression,
+            )?,
+            max_temp_directory_size: execution_options.max_temp_directory_size,
+            spill_count: MetricBuilder::new(&agg.metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(&agg.metrics).spilled_bytes(partition),
+            spilled_rows: MetricBuilder::new(&agg.metrics).spilled_rows(partition),
+            spill_file_bytes: MetricBuilder::new(&agg.metrics)
+                .counter("spill_file_bytes", partition),
+            spill_read_bytes: MetricBuilder::new(&agg.metrics)
+                .counter("spill_read_bytes", partition),
         };
 
         // Skip aggregation is supported if:
@@ -904,8 +942,16 @@ impl GroupedHashAggregateStream {
     fn spill(&mut self) -> Result<()> {
         let emit = self.emit(EmitTo::All, true)?;
         let sorted = sort_batch(&emit, &self.spill_state.spill_expr, None)?;
-        let spillfile = self.runtime.disk_manager.create_tmp_file("HashAggSpill")?;
-        let mut writer = IPCWriter::new(spillfile.path(), &emit.schema())?;
+        let mut spillfile = self
+            .runtime
+            .disk_manager
+            .create_tmp_file("HashAggSpill")?
+            .with_max_temp_directory_size(self.spill_state.max_temp_directory_size);
+        let mut writer = IPCWriter::new_with_options(
+            spillfile.path(),
+            &emit.schema(),
+            self.spill_state.spill_write_options.clone(),
+        )?;
         // TODO: slice large `sorted` and write to multiple files in parallel
         let mut offset = 0;
         let total_rows = sorted.num_rows();
@@ -915,9 +961,20 @@ impl GroupedHashAggregateStream {
             let batch = sorted.slice(offset, length);
             offset += batch.num_rows();
             writer.write(&batch)?;
+            spillfile.update_disk_usage()?;
         }
 
         writer.finish()?;
+        spillfile.update_disk_usage()?;
+
+        self.spill_state.spill_count.add(1);
+        self.spill_state
+            .spilled_bytes
+            .add(sorted.get_array_memory_size());
+        self.spill_state.spilled_rows.add(total_rows);
+        self.spill_state
+            .spill_file_bytes
+            .add(spillfile.current_disk_usage() as usize);
         self.spill_state.spills.push(spillfile);
         Ok(())
     }
@@ -970,7 +1027,12 @@ impl GroupedHashAggregateStream {
             })),
         )));
         for spill in self.spill_state.spills.drain(..) {
-            let stream = read_spill_as_stream(spill, Arc::clone(&schema), 2)?;
+            let stream = read_spill_as_stream_with_read_bytes(
+                spill,
+                Arc::clone(&schema),
+                2,
+                self.spill_state.spill_read_bytes.clone(),
+            )?;
             streams.push(stream);
         }
         self.spill_state.is_stream_merging = true;
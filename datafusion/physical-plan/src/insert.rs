@@ -264,13 +264,14 @@ impl ExecutionPlan for DataSinkExec {
 /// | 6     |,
 /// +-------+,
 /// ```
-fn make_count_batch(count: u64) -> RecordBatch {
+pub fn make_count_batch(count: u64) -> RecordBatch {
     let array = Arc::new(UInt64Array::from(vec![count])) as ArrayRef;
 
     RecordBatch::try_from_iter_with_nullable(vec![("count", array, false)]).unwrap()
 }
 
-fn make_count_schema() -> SchemaRef {
+/// The schema of the batches created by [`make_count_batch`]
+pub fn make_count_schema() -> SchemaRef {
     // define a schema.
     Arc::new(Schema::new(vec![Field::new(
         "count",
@@ -0,0 +1,883 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the as-of join execution plan, which joins each row of the left
+//! input to the closest row of the right input.
+//! Both inputs are sorted by the join keys and the match condition, so the
+//! join is a single merge pass over them.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::Formatter;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::array::{new_null_array, Array, BooleanArray};
+use arrow::buffer::NullBuffer;
+use arrow::compute::{filter, interleave, SortOptions};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::{
+    internal_err, not_impl_err, plan_err, JoinSide, JoinType, Result, Statistics,
+};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::{PhysicalExprRef, PhysicalSortRequirement};
+use datafusion_physical_expr_common::sort_expr::LexRequirement;
+use futures::{ready, Stream, StreamExt};
+
+use crate::expressions::PhysicalSortExpr;
+use crate::joins::utils::{
+    build_join_schema, check_join_is_valid, symmetric_join_output_partitioning, JoinOn,
+    JoinOnRef,
+};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::{
+    execution_mode_from_children, DisplayAs, DisplayFormatType, Distribution,
+    ExecutionPlan, ExecutionPlanProperties, PlanProperties, RecordBatchStream,
+    SendableRecordBatchStream,
+};
+
+/// The condition `left <op> right` that selects the right row joined to a
+/// left row of an [`AsOfJoinExec`]
+#[derive(Debug, Clone)]
+pub struct AsOfMatchCondition {
+    /// Expression on the left input
+    pub left: PhysicalExprRef,
+    /// One of `>`, `>=`, `<` or `<=`
+    pub op: Operator,
+    /// Expression on the right input
+    pub right: PhysicalExprRef,
+}
+
+impl AsOfMatchCondition {
+    /// Create a new match condition `left <op> right`
+    pub fn new(left: PhysicalExprRef, op: Operator, right: PhysicalExprRef) -> Self {
+        Self { left, op, right }
+    }
+}
+
+/// Joins each row of the left input to the closest row of the right input
+/// with equal `on` keys that satisfies the match condition. For `>` and `>=`
+/// the closest row has the largest right value, e.g. the latest quote at or
+/// before the time of a trade, and for `<` and `<=` the smallest.
+///
+/// Both inputs must be sorted by the join keys followed by their side of the
+/// match condition, which lets the join consume them in a single pass while
+/// buffering at most one batch of the right input. The output preserves the
+/// order of the left input.
+///
+/// Rows with nulls in the join keys or the match condition never match.
+#[derive(Debug)]
+pub struct AsOfJoinExec {
+    /// Left input, all of its rows are probed
+    left: Arc<dyn ExecutionPlan>,
+    /// Right input, from which the matches are selected
+    right: Arc<dyn ExecutionPlan>,
+    /// Set of common columns used to join on
+    on: JoinOn,
+    /// Selects the closest right row
+    match_condition: AsOfMatchCondition,
+    /// How the join is performed, either `Inner` or `Left`
+    join_type: JoinType,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// The left SortExpr
+    left_sort_exprs: Vec<PhysicalSortExpr>,
+    /// The right SortExpr
+    right_sort_exprs: Vec<PhysicalSortExpr>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl AsOfJoinExec {
+    /// Tries to create a new [AsOfJoinExec].
+    /// # Error
+    /// This function errors when the join keys or the match condition do
+    /// not have the same types on both sides, or the join type or the
+    /// operator of the match condition are not supported.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        match_condition: AsOfMatchCondition,
+        join_type: JoinType,
+    ) -> Result<Self> {
+        if !matches!(join_type, JoinType::Inner | JoinType::Left) {
+            return not_impl_err!("AsOfJoinExec does not support JoinType::{join_type}");
+        }
+        if !matches!(
+            match_condition.op,
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq
+        ) {
+            return plan_err!(
+                "AsOfJoinExec match condition must use one of >, >=, < or <=, got {}",
+                match_condition.op
+            );
+        }
+
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &on)?;
+        // the join keys followed by the match condition
+        let sides = on
+            .iter()
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .chain([(
+                Arc::clone(&match_condition.left),
+                Arc::clone(&match_condition.right),
+            )])
+            .collect::<Vec<_>>();
+        for (l, r) in &sides {
+            let (left_type, right_type) =
+                (l.data_type(&left_schema)?, r.data_type(&right_schema)?);
+            if left_type != right_type {
+                return plan_err!(
+                    "AsOfJoinExec requires the same types on both sides, got {l} {left_type} and {r} {right_type}"
+                );
+            }
+        }
+
+        let (left_sort_exprs, right_sort_exprs) = sides
+            .into_iter()
+            .map(|(l, r)| {
+                let left = PhysicalSortExpr {
+                    expr: l,
+                    options: SortOptions::default(),
+                };
+                let right = PhysicalSortExpr {
+                    expr: r,
+                    options: SortOptions::default(),
+                };
+                (left, right)
+            })
+            .unzip();
+
+        let schema =
+            Arc::new(build_join_schema(&left_schema, &right_schema, &join_type).0);
+        let cache =
+            Self::compute_properties(&left, &right, Arc::clone(&schema), join_type, &on);
+        Ok(Self {
+            left,
+            right,
+            on,
+            match_condition,
+            join_type,
+            schema,
+            left_sort_exprs,
+            right_sort_exprs,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// Left input
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Right input
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Set of common columns used to join on
+    pub fn on(&self) -> &[(PhysicalExprRef, PhysicalExprRef)] {
+        &self.on
+    }
+
+    /// The condition that selects the closest right row
+    pub fn match_condition(&self) -> &AsOfMatchCondition {
+        &self.match_condition
+    }
+
+    /// How the join is performed
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        join_type: JoinType,
+        join_on: JoinOnRef,
+    ) -> PlanProperties {
+        // The output follows the order of the left input
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &join_type,
+            schema,
+            &[true, false],
+            Some(JoinSide::Left),
+            join_on,
+        );
+
+        let output_partitioning =
+            symmetric_join_output_partitioning(left, right, &join_type);
+
+        // Determine execution mode:
+        let mode = execution_mode_from_children([left, right]);
+
+        PlanProperties::new(eq_properties, output_partitioning, mode)
+    }
+}
+
+impl DisplayAs for AsOfJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| format!("({}, {})", c1, c2))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AsOfJoinExec: join_type={:?}, on=[{}], match_condition={} {} {}",
+                    self.join_type,
+                    on,
+                    self.match_condition.left,
+                    self.match_condition.op,
+                    self.match_condition.right
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsOfJoinExec {
+    fn name(&self) -> &'static str {
+        "AsOfJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        if self.on.is_empty() {
+            return vec![Distribution::SinglePartition, Distribution::SinglePartition];
+        }
+        let (left_expr, right_expr) = self
+            .on
+            .iter()
+            .map(|(l, r)| (Arc::clone(l), Arc::clone(r)))
+            .unzip();
+        vec![
+            Distribution::HashPartitioned(left_expr),
+            Distribution::HashPartitioned(right_expr),
+        ]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<LexRequirement>> {
+        vec![
+            Some(PhysicalSortRequirement::from_sort_exprs(
+                &self.left_sort_exprs,
+            )),
+            Some(PhysicalSortRequirement::from_sort_exprs(
+                &self.right_sort_exprs,
+            )),
+        ]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(AsOfJoinExec::try_new(
+                Arc::clone(left),
+                Arc::clone(right),
+                self.on.clone(),
+                self.match_condition.clone(),
+                self.join_type,
+            )?)),
+            _ => internal_err!("AsOfJoinExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left_partitions = self.left.output_partitioning().partition_count();
+        let right_partitions = self.right.output_partitioning().partition_count();
+        if left_partitions != right_partitions {
+            return internal_err!(
+                "Invalid AsOfJoinExec, partition count mismatch {left_partitions}!={right_partitions},\
+                 consider using RepartitionExec"
+            );
+        }
+
+        let (left_exprs, right_exprs): (Vec<_>, Vec<_>) = self
+            .left_sort_exprs
+            .iter()
+            .zip(&self.right_sort_exprs)
+            .map(|(l, r)| (Arc::clone(&l.expr), Arc::clone(&r.expr)))
+            .unzip();
+        let left_schema = self.left.schema();
+        let fields = left_exprs
+            .iter()
+            .map(|expr| {
+                Ok(SortField::new_with_options(
+                    expr.data_type(&left_schema)?,
+                    SortOptions::default(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Box::pin(AsOfJoinStream {
+            schema: Arc::clone(&self.schema),
+            left: self.left.execute(partition, Arc::clone(&context))?,
+            right: self.right.execute(partition, context)?,
+            left_exprs,
+            right_exprs,
+            converter: AsOfRowConverter::try_new(fields)?,
+            op: self.match_condition.op,
+            join_type: self.join_type,
+            current: None,
+            right_batch: None,
+            right_row: 0,
+            right_done: false,
+            candidate: None,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        // every left row is joined to at most one right row
+        let left = self.left.statistics()?;
+        let mut statistics = Statistics::new_unknown(&self.schema);
+        statistics.num_rows = match self.join_type {
+            JoinType::Left => left.num_rows,
+            _ => left.num_rows.to_inexact(),
+        };
+        Ok(statistics)
+    }
+}
+
+/// Converts the join keys and the match value of the rows of both inputs to
+/// the row format, which compares them in the order of the sorted inputs
+struct AsOfRowConverter {
+    /// Converts the join keys, if there are any
+    keys: Option<RowConverter>,
+    /// Converts the match value
+    value: RowConverter,
+}
+
+impl AsOfRowConverter {
+    /// Create a converter for `fields`, the join keys followed by the match value
+    fn try_new(mut fields: Vec<SortField>) -> Result<Self> {
+        let Some(value) = fields.pop() else {
+            return internal_err!("AsOfJoinExec requires a match condition");
+        };
+        let keys = if fields.is_empty() {
+            None
+        } else {
+            Some(RowConverter::new(fields)?)
+        };
+        Ok(Self {
+            keys,
+            value: RowConverter::new(vec![value])?,
+        })
+    }
+
+    /// Evaluates `exprs`, the join keys followed by the match value, on `batch`
+    fn convert(
+        &self,
+        batch: RecordBatch,
+        exprs: &[PhysicalExprRef],
+    ) -> Result<SortedBatch> {
+        let mut columns = exprs
+            .iter()
+            .map(|expr| expr.evaluate(&batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+        let nulls = columns.iter().fold(None, |nulls, column| {
+            NullBuffer::union(nulls.as_ref(), column.logical_nulls().as_ref())
+        });
+        let value = columns.split_off(columns.len() - 1);
+        let keys = match &self.keys {
+            Some(converter) => Some(converter.convert_columns(&columns)?),
+            None => None,
+        };
+        Ok(SortedBatch {
+            batch,
+            keys,
+            value: self.value.convert_columns(&value)?,
+            nulls,
+        })
+    }
+}
+
+/// A batch of either input, with its join keys and match values in the row
+/// format
+struct SortedBatch {
+    batch: RecordBatch,
+    /// The join keys of each row, if there are any
+    keys: Option<Rows>,
+    /// The match value of each row
+    value: Rows,
+    /// The rows with a null join key or match value, which never match
+    nulls: Option<NullBuffer>,
+}
+
+impl SortedBatch {
+    fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    /// Whether `row` can be matched
+    fn is_valid(&self, row: usize) -> bool {
+        self.nulls
+            .as_ref()
+            .map_or(true, |nulls| nulls.is_valid(row))
+    }
+
+    /// Compares the join keys and then the match value of `row` with
+    /// `other_row` of `other`
+    fn compare(&self, row: usize, other: &SortedBatch, other_row: usize) -> Ordering {
+        self.compare_keys(row, other, other_row)
+            .then_with(|| self.value.row(row).cmp(&other.value.row(other_row)))
+    }
+
+    /// Compares the join keys of `row` with `other_row` of `other`
+    fn compare_keys(
+        &self,
+        row: usize,
+        other: &SortedBatch,
+        other_row: usize,
+    ) -> Ordering {
+        match (&self.keys, &other.keys) {
+            (Some(keys), Some(other_keys)) => {
+                keys.row(row).cmp(&other_keys.row(other_row))
+            }
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// A row of the right input
+type RightRow = (Arc<SortedBatch>, usize);
+
+/// A left batch and the matches found for its rows so far
+struct LeftBatch {
+    batch: SortedBatch,
+    matches: Vec<Option<RightRow>>,
+}
+
+/// Merges the sorted left and right input streams of an [`AsOfJoinExec`]
+struct AsOfJoinStream {
+    schema: SchemaRef,
+    left: SendableRecordBatchStream,
+    right: SendableRecordBatchStream,
+    /// The join keys followed by the match value of the left input
+    left_exprs: Vec<PhysicalExprRef>,
+    /// The join keys followed by the match value of the right input
+    right_exprs: Vec<PhysicalExprRef>,
+    converter: AsOfRowConverter,
+    op: Operator,
+    join_type: JoinType,
+    /// The left batch being joined
+    current: Option<LeftBatch>,
+    /// The right batch of the next right row
+    right_batch: Option<Arc<SortedBatch>>,
+    /// The next right row in `right_batch`
+    right_row: usize,
+    /// Whether the right input is exhausted
+    right_done: bool,
+    /// The last right row that precedes the left rows, used by `>` and `>=`
+    candidate: Option<RightRow>,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl AsOfJoinStream {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            let mut current = match self.current.take() {
+                Some(current) => current,
+                None => match ready!(self.left.poll_next_unpin(cx)) {
+                    Some(Ok(batch)) => LeftBatch {
+                        matches: Vec::with_capacity(batch.num_rows()),
+                        batch: self.converter.convert(batch, &self.left_exprs)?,
+                    },
+                    Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    None => return Poll::Ready(None),
+                },
+            };
+
+            while current.matches.len() < current.batch.num_rows() {
+                let row = current.matches.len();
+                match self.poll_match(cx, &current.batch, row) {
+                    Poll::Ready(Ok(right_row)) => current.matches.push(right_row),
+                    Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    Poll::Pending => {
+                        self.current = Some(current);
+                        return Poll::Pending;
+                    }
+                }
+            }
+
+            let timer = self.baseline_metrics.elapsed_compute().timer();
+            let output = self.build_output(current)?;
+            timer.done();
+            if output.num_rows() > 0 {
+                return Poll::Ready(Some(Ok(output)));
+            }
+        }
+    }
+
+    /// Returns the next right row, or `None` if the right input is exhausted
+    fn poll_right_row(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<RightRow>>> {
+        loop {
+            if let Some(batch) = &self.right_batch {
+                if self.right_row < batch.num_rows() {
+                    return Poll::Ready(Ok(Some((Arc::clone(batch), self.right_row))));
+                }
+                self.right_batch = None;
+                self.right_row = 0;
+            }
+            if self.right_done {
+                return Poll::Ready(Ok(None));
+            }
+            match ready!(self.right.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => {
+                    let batch = self.converter.convert(batch, &self.right_exprs)?;
+                    self.right_batch = Some(Arc::new(batch));
+                }
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+                None => self.right_done = true,
+            }
+        }
+    }
+
+    /// Consumes the right rows that precede `row` of `left`, and returns the
+    /// right row joined to it
+    fn poll_match(
+        &mut self,
+        cx: &mut Context<'_>,
+        left: &SortedBatch,
+        row: usize,
+    ) -> Poll<Result<Option<RightRow>>> {
+        if !left.is_valid(row) {
+            return Poll::Ready(Ok(None));
+        }
+        let matches = |(right, right_row): &RightRow| {
+            right.is_valid(*right_row)
+                && right.compare_keys(*right_row, left, row) == Ordering::Equal
+        };
+        // `>=` and `<` match the right rows that are less or equal, `>` and
+        // `<=` the right rows that are less
+        let consume_equal = matches!(self.op, Operator::GtEq | Operator::Lt);
+        while let Some(right_row) = ready!(self.poll_right_row(cx))? {
+            let ordering = right_row.0.compare(right_row.1, left, row);
+            if ordering == Ordering::Greater || (ordering.is_eq() && !consume_equal) {
+                return Poll::Ready(Ok(match self.op {
+                    Operator::Lt | Operator::LtEq => Some(right_row).filter(matches),
+                    _ => self.candidate.clone().filter(matches),
+                }));
+            }
+            if right_row.0.is_valid(right_row.1) {
+                self.candidate = Some(right_row);
+            }
+            self.right_row += 1;
+        }
+        Poll::Ready(Ok(match self.op {
+            Operator::Lt | Operator::LtEq => None,
+            _ => self.candidate.clone().filter(matches),
+        }))
+    }
+
+    /// Joins the rows of a left batch to their matches
+    fn build_output(&self, left: LeftBatch) -> Result<RecordBatch> {
+        let LeftBatch { batch, matches } = left;
+        let mut left_columns = batch.batch.columns().to_vec();
+        let mut num_rows = batch.num_rows();
+        if self.join_type == JoinType::Inner {
+            let mask = matches
+                .iter()
+                .map(|m| Some(m.is_some()))
+                .collect::<BooleanArray>();
+            left_columns = left_columns
+                .iter()
+                .map(|column| filter(column, &mask))
+                .collect::<Result<_, _>>()?;
+            num_rows = mask.true_count();
+        }
+
+        // the rows of the right batches, after a row of nulls for the left
+        // rows without a match
+        let mut sources: Vec<Arc<SortedBatch>> = vec![];
+        let mut indices = Vec::with_capacity(num_rows);
+        for right_row in matches {
+            match right_row {
+                Some((right, row)) => {
+                    if !sources.last().is_some_and(|last| Arc::ptr_eq(last, &right)) {
+                        sources.push(right);
+                    }
+                    indices.push((sources.len(), row));
+                }
+                None if self.join_type == JoinType::Left => indices.push((0, 0)),
+                None => {}
+            }
+        }
+
+        let left_len = left_columns.len();
+        let mut columns = left_columns;
+        for (i, field) in self.schema.fields()[left_len..].iter().enumerate() {
+            let nulls = new_null_array(field.data_type(), 1);
+            let arrays = std::iter::once(nulls.as_ref())
+                .chain(sources.iter().map(|source| source.batch.column(i).as_ref()))
+                .collect::<Vec<&dyn Array>>();
+            columns.push(interleave(&arrays, &indices)?);
+        }
+
+        let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+        Ok(RecordBatch::try_new_with_options(
+            Arc::clone(&self.schema),
+            columns,
+            &options,
+        )?)
+    }
+}
+
+impl Stream for AsOfJoinStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_impl(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for AsOfJoinStream {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::expressions::Column;
+    use crate::memory::MemoryExec;
+    use crate::test::build_table_i32;
+
+    use datafusion_common::assert_batches_eq;
+
+    /// Builds a table of three columns whose rows are split into batches
+    /// of `batch_size` rows
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+        batch_size: usize,
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        let batches = (0..batch.num_rows())
+            .step_by(batch_size)
+            .map(|offset| batch.slice(offset, batch_size.min(batch.num_rows() - offset)))
+            .collect::<Vec<_>>();
+        Arc::new(MemoryExec::try_new(&[batches], schema, None).unwrap())
+    }
+
+    /// Trades `(a1, b1, c1)` and quotes `(a2, b2, c2)`, sorted by symbol `a`
+    /// and time `b`
+    fn trades_and_quotes(
+        batch_size: usize,
+    ) -> (Arc<dyn ExecutionPlan>, Arc<dyn ExecutionPlan>) {
+        let left = build_table(
+            ("a1", &vec![1, 1, 1, 2, 2]),
+            ("b1", &vec![1, 5, 10, 3, 7]),
+            ("c1", &vec![10, 20, 30, 40, 50]),
+            batch_size,
+        );
+        let right = build_table(
+            ("a2", &vec![1, 1, 2, 3]),
+            ("b2", &vec![2, 5, 4, 1]),
+            ("c2", &vec![100, 200, 300, 400]),
+            batch_size,
+        );
+        (left, right)
+    }
+
+    fn asof_join(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        op: Operator,
+        join_type: JoinType,
+    ) -> Result<AsOfJoinExec> {
+        let on = vec![(
+            Arc::new(Column::new_with_schema("a1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("a2", &right.schema())?) as _,
+        )];
+        let match_condition = AsOfMatchCondition::new(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?),
+            op,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?),
+        );
+        AsOfJoinExec::try_new(left, right, on, match_condition, join_type)
+    }
+
+    async fn join_collect(
+        op: Operator,
+        join_type: JoinType,
+        batch_size: usize,
+    ) -> Result<Vec<RecordBatch>> {
+        let (left, right) = trades_and_quotes(batch_size);
+        let join = asof_join(left, right, op, join_type)?;
+        common::collect(join.execute(0, Arc::new(TaskContext::default()))?).await
+    }
+
+    #[tokio::test]
+    async fn join_inner_gt_eq() -> Result<()> {
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 5  | 20 | 1  | 5  | 200 |",
+            "| 1  | 10 | 30 | 1  | 5  | 200 |",
+            "| 2  | 7  | 50 | 2  | 4  | 300 |",
+            "+----+----+----+----+----+-----+",
+        ];
+        for batch_size in [1, 2, 8192] {
+            let batches =
+                join_collect(Operator::GtEq, JoinType::Inner, batch_size).await?;
+            assert_batches_eq!(expected, &batches);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_left_gt() -> Result<()> {
+        let batches = join_collect(Operator::Gt, JoinType::Left, 2).await?;
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 1  | 10 |    |    |     |",
+            "| 1  | 5  | 20 | 1  | 2  | 100 |",
+            "| 1  | 10 | 30 | 1  | 5  | 200 |",
+            "| 2  | 3  | 40 |    |    |     |",
+            "| 2  | 7  | 50 | 2  | 4  | 300 |",
+            "+----+----+----+----+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_left_lt() -> Result<()> {
+        let batches = join_collect(Operator::Lt, JoinType::Left, 1).await?;
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 1  | 10 | 1  | 2  | 100 |",
+            "| 1  | 5  | 20 |    |    |     |",
+            "| 1  | 10 | 30 |    |    |     |",
+            "| 2  | 3  | 40 | 2  | 4  | 300 |",
+            "| 2  | 7  | 50 |    |    |     |",
+            "+----+----+----+----+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_inner_lt_eq() -> Result<()> {
+        let batches = join_collect(Operator::LtEq, JoinType::Inner, 8192).await?;
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 1  | 10 | 1  | 2  | 100 |",
+            "| 1  | 5  | 20 | 1  | 5  | 200 |",
+            "| 2  | 3  | 40 | 2  | 4  | 300 |",
+            "+----+----+----+----+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_join() {
+        let (left, right) = trades_and_quotes(8192);
+        let err = asof_join(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            Operator::GtEq,
+            JoinType::Full,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("does not support"), "{err}");
+
+        let err = asof_join(left, right, Operator::Eq, JoinType::Inner).unwrap_err();
+        assert!(err.to_string().contains("must use one of"), "{err}");
+    }
+
+    #[test]
+    fn required_input_ordering() -> Result<()> {
+        let (left, right) = trades_and_quotes(8192);
+        let join = asof_join(left, right, Operator::GtEq, JoinType::Inner)?;
+        let ordering = join.required_input_ordering();
+        let names = |requirement: &Option<LexRequirement>| {
+            requirement
+                .iter()
+                .flatten()
+                .map(|r| r.expr.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&ordering[0]), ["a1@0", "b1@1"]);
+        assert_eq!(names(&ordering[1]), ["a2@0", "b2@1"]);
+        assert!(join.maintains_input_order()[0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Runtime filters published by joins once their build side is known
+
+use std::sync::{Arc, OnceLock};
+
+use arrow::array::ArrayRef;
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::{Accumulator, Operator};
+use datafusion_functions_aggregate::min_max::{MaxAccumulator, MinAccumulator};
+use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
+use datafusion_physical_expr::PhysicalExpr;
+
+/// A filter on one join key of the probe side of a join, whose value only
+/// becomes known at execution time, after the build side has been collected.
+///
+/// The join publishes the range of build side key values with
+/// [`Self::update`]. Scans on the probe side that hold the same
+/// [`DynamicFilter`] can then use [`Self::predicate`] to skip data that
+/// cannot produce any match, e.g. `ParquetExec` uses it to prune row groups
+/// and pages before decoding them.
+///
+/// The filter is published at most once. Consumers that read it before the
+/// build side is done see no filter at all, which is always correct.
+#[derive(Debug, Default)]
+pub struct DynamicFilter {
+    /// `Some((min, max))` of the non-null build side keys, or `None` if
+    /// there are no such keys and so no probe side row can match
+    bounds: OnceLock<Option<(ScalarValue, ScalarValue)>>,
+}
+
+impl DynamicFilter {
+    /// Create a new, not yet published, filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish the filter from the build side values of the join key.
+    /// Null keys never match, and are ignored.
+    ///
+    /// Only the first call has an effect.
+    pub fn update(&self, keys: &ArrayRef) -> Result<()> {
+        if self.bounds.get().is_some() {
+            return Ok(());
+        }
+        let mut min = MinAccumulator::try_new(keys.data_type())?;
+        let mut max = MaxAccumulator::try_new(keys.data_type())?;
+        min.update_batch(&[Arc::clone(keys)])?;
+        max.update_batch(&[Arc::clone(keys)])?;
+        let (min, max) = (min.evaluate()?, max.evaluate()?);
+        let bounds = if min.is_null() || max.is_null() {
+            None
+        } else {
+            Some((min, max))
+        };
+        // a concurrent update computed the same bounds
+        let _ = self.bounds.set(bounds);
+        Ok(())
+    }
+
+    /// Returns true if the filter has been published
+    pub fn is_complete(&self) -> bool {
+        self.bounds.get().is_some()
+    }
+
+    /// Returns the published `(min, max)` of the build side keys, `Some(None)`
+    /// if the build side has no non-null keys, or `None` if the filter has not
+    /// been published yet
+    pub fn bounds(&self) -> Option<Option<&(ScalarValue, ScalarValue)>> {
+        self.bounds.get().map(Option::as_ref)
+    }
+
+    /// Returns the predicate `column >= min AND column <= max` that the probe
+    /// side `column` must satisfy to match any build side row, or `None` if
+    /// the filter has not been published yet.
+    pub fn predicate(
+        &self,
+        column: Arc<dyn PhysicalExpr>,
+    ) -> Option<Arc<dyn PhysicalExpr>> {
+        let predicate: Arc<dyn PhysicalExpr> = match self.bounds()? {
+            Some((min, max)) => {
+                let lower = BinaryExpr::new(
+                    Arc::clone(&column),
+                    Operator::GtEq,
+                    Arc::new(Literal::new(min.clone())),
+                );
+                let upper = BinaryExpr::new(
+                    column,
+                    Operator::LtEq,
+                    Arc::new(Literal::new(max.clone())),
+                );
+                Arc::new(BinaryExpr::new(
+                    Arc::new(lower),
+                    Operator::And,
+                    Arc::new(upper),
+                ))
+            }
+            None => Arc::new(Literal::new(ScalarValue::Boolean(Some(false)))),
+        };
+        Some(predicate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int32Array;
+    use arrow_schema::{DataType, Field, Schema};
+    use datafusion_physical_expr::expressions::col;
+
+    #[test]
+    fn publish_bounds() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let filter = DynamicFilter::new();
+        assert!(!filter.is_complete());
+        assert!(filter.predicate(col("a", &schema)?).is_none());
+
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![Some(7), None, Some(3)]));
+        filter.update(&keys)?;
+        assert_eq!(
+            filter.bounds(),
+            Some(Some(&(
+                ScalarValue::Int32(Some(3)),
+                ScalarValue::Int32(Some(7))
+            )))
+        );
+        let predicate = filter.predicate(col("a", &schema)?).unwrap();
+        assert_eq!(predicate.to_string(), "a@0 >= 3 AND a@0 <= 7");
+
+        // only the first update is published
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![100]));
+        filter.update(&keys)?;
+        assert_eq!(
+            filter.bounds(),
+            Some(Some(&(
+                ScalarValue::Int32(Some(3)),
+                ScalarValue::Int32(Some(7))
+            )))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn publish_no_keys() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let filter = DynamicFilter::new();
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![None, None]));
+        filter.update(&keys)?;
+        assert_eq!(filter.bounds(), Some(None));
+        let predicate = filter.predicate(col("a", &schema)?).unwrap();
+        assert_eq!(predicate.to_string(), "false");
+        Ok(())
+    }
+}
@@ -26,12 +26,12 @@ use std::{any::Any, vec};
 use super::utils::asymmetric_join_output_partitioning;
 use super::{
     utils::{OnceAsync, OnceFut},
-    PartitionMode,
+    DynamicFilter, PartitionMode,
 };
 use crate::ExecutionPlanProperties;
 use crate::{
     coalesce_partitions::CoalescePartitionsExec,
-    common::can_project,
+    common::{can_project, IPCWriter},
     execution_mode_from_children, handle_state,
     hash_utils::create_hashes,
     joins::utils::{
@@ -42,7 +42,9 @@ use crate::{
         BuildProbeJoinMetrics, ColumnIndex, JoinFilter, JoinHashMap, JoinHashMapOffset,
         JoinHashMapType, JoinOn, JoinOnRef, StatefulStreamResult,
     },
-    metrics::{ExecutionPlanMetricsSet, MetricsSet},
+    metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
+    spill::{read_spill_as_stream, spill_write_options},
+    stream::RecordBatchStreamAdapter,
     DisplayAs, DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
     Partitioning, PlanProperties, RecordBatchStream, SendableRecordBatchStream,
     Statistics,
@@ -54,6 +56,7 @@ use arrow::array::{
 use arrow::compute::kernels::cmp::{eq, not_distinct};
 use arrow::compute::{and, concat_batches, take, FilterBuilder};
 use arrow::datatypes::{Schema, SchemaRef};
+use arrow::ipc::writer::IpcWriteOptions;
 use arrow::record_batch::RecordBatch;
 use arrow::util::bit_util;
 use arrow_array::cast::downcast_array;
@@ -63,8 +66,9 @@ use datafusion_common::{
     internal_datafusion_err, internal_err, plan_err, project_schema, DataFusionError,
     JoinSide, JoinType, Result,
 };
+use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
-use datafusion_execution::TaskContext;
+use datafusion_execution::{DiskManager, TaskContext};
 use datafusion_physical_expr::equivalence::{
     join_equivalence_properties, ProjectionMapping,
 };
@@ -256,6 +260,17 @@ impl JoinLeftData {
 ///
 /// ```
 ///
+/// # Spilling
+///
+/// In [`PartitionMode::Partitioned`] mode, if the build side of a partition
+/// does not fit in memory, both sides of the partition are hash partitioned by
+/// join key and spilled to disk, and the resulting spill partitions are then
+/// joined one at a time (a "grace" hash join). The number of spill partitions
+/// is controlled by `datafusion.execution.hash_join_spill_partitions`.
+///
+/// Spilling requires a [`DiskManager`] and is not used when the probe side is
+/// ordered, as the output would no longer follow the order of the probe side.
+///
 /// # Example "Optimal" Plans
 ///
 /// The differences in the inputs means that for classic "Star Schema Query",
@@ -325,6 +340,9 @@ pub struct HashJoinExec {
     /// Otherwise, rows that have `null`s in the join columns will not be
     /// matched and thus will not appear in the output.
     pub null_equals_null: bool,
+    /// Runtime filters on the probe side keys, published once the build side
+    /// is collected, as `(index of the key in on, filter)`
+    dynamic_filters: Vec<(usize, Arc<DynamicFilter>)>,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
 }
@@ -387,10 +405,69 @@ impl HashJoinExec {
             projection,
             column_indices,
             null_equals_null,
+            dynamic_filters: vec![],
             cache,
         })
     }
 
+    /// Publish runtime filters on the probe side join keys once the build side
+    /// is collected, given as `(index of the key in on, filter)`.
+    ///
+    /// Only supported in [`PartitionMode::CollectLeft`] mode, where the build
+    /// side contains all the keys that any probe side row can match, and for
+    /// join types that do not output unmatched probe side rows.
+    pub fn with_dynamic_filters(
+        mut self,
+        dynamic_filters: Vec<(usize, Arc<DynamicFilter>)>,
+    ) -> Result<Self> {
+        if dynamic_filters.is_empty() {
+            self.dynamic_filters = dynamic_filters;
+            return Ok(self);
+        }
+        if self.mode != PartitionMode::CollectLeft {
+            return plan_err!(
+                "Dynamic filters are only supported in CollectLeft mode, got {:?}",
+                self.mode
+            );
+        }
+        if !Self::supports_dynamic_filters(self.join_type) {
+            return plan_err!(
+                "Dynamic filters are not supported for {} joins",
+                self.join_type
+            );
+        }
+        if let Some((index, _)) = dynamic_filters
+            .iter()
+            .find(|(index, _)| *index >= self.on.len())
+        {
+            return plan_err!(
+                "Dynamic filter refers to join key {index}, but the join only has {} keys",
+                self.on.len()
+            );
+        }
+        self.dynamic_filters = dynamic_filters;
+        Ok(self)
+    }
+
+    /// Runtime filters published on the probe side join keys
+    pub fn dynamic_filters(&self) -> &[(usize, Arc<DynamicFilter>)] {
+        &self.dynamic_filters
+    }
+
+    /// Returns true if probe side rows that match no build side row can be
+    /// discarded before the join for `join_type`, which is what dynamic
+    /// filters do
+    pub fn supports_dynamic_filters(join_type: JoinType) -> bool {
+        matches!(
+            join_type,
+            JoinType::Inner
+                | JoinType::Left
+                | JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::RightSemi
+        )
+    }
+
     /// left (build) side which gets hashed
     pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
         &self.left
@@ -682,16 +759,19 @@ impl ExecutionPlan for HashJoinExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(HashJoinExec::try_new(
-            Arc::clone(&children[0]),
-            Arc::clone(&children[1]),
-            self.on.clone(),
-            self.filter.clone(),
-            &self.join_type,
-            self.projection.clone(),
-            self.mode,
-            self.null_equals_null,
-        )?))
+        Ok(Arc::new(
+            HashJoinExec::try_new(
+                Arc::clone(&children[0]),
+                Arc::clone(&children[1]),
+                self.on.clone(),
+                self.filter.clone(),
+                &self.join_type,
+                self.projection.clone(),
+                self.mode,
+                self.null_equals_null,
+            )?
+            .with_dynamic_filters(self.dynamic_filters.clone())?,
+        ))
     }
 
     fn execute(
@@ -721,11 +801,35 @@ impl ExecutionPlan for HashJoinExec {
         }
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+        let batch_size = context.session_config().batch_size();
+
+        // update column indices to reflect the projection
+        let column_indices_after_projection = match &self.projection {
+            Some(projection) => projection
+                .iter()
+                .map(|i| self.column_indices[*i].clone())
+                .collect(),
+            None => self.column_indices.clone(),
+        };
+
+        let stream_builder = HashJoinStreamBuilder {
+            schema: self.schema(),
+            on_left: on_left.clone(),
+            on_right,
+            filter: self.filter.clone(),
+            join_type: self.join_type,
+            column_indices: column_indices_after_projection,
+            random_state: self.random_state.clone(),
+            null_equals_null: self.null_equals_null,
+            batch_size,
+            right_side_ordered: self.right.output_ordering().is_some(),
+        };
+
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.once(|| {
                 let reservation =
                     MemoryConsumer::new("HashJoinInput").register(context.memory_pool());
-                collect_left_input(
+                let left_data = collect_left_input(
                     None,
                     self.random_state.clone(),
                     Arc::clone(&self.left),
@@ -735,13 +839,53 @@ impl ExecutionPlan for HashJoinExec {
                     reservation,
                     need_produce_result_in_final(self.join_type),
                     self.right().output_partitioning().partition_count(),
-                )
+                );
+                let dynamic_filters = self.dynamic_filters.clone();
+                let on_left = on_left.clone();
+                async move {
+                    let left_data = left_data.await?;
+                    publish_dynamic_filters(
+                        &dynamic_filters,
+                        &on_left,
+                        left_data.batch(),
+                    )?;
+                    Ok(left_data)
+                }
             }),
             PartitionMode::Partitioned => {
                 let reservation =
                     MemoryConsumer::new(format!("HashJoinInput[{partition}]"))
                         .register(context.memory_pool());
 
+                let num_spill_partitions = context
+                    .session_config()
+                    .options()
+                    .execution
+                    .hash_join_spill_partitions;
+                // Spill partitions are joined one after another, which would
+                // not preserve the order of the probe side
+                if num_spill_partitions > 0
+                    && !stream_builder.right_side_ordered
+                    && context.runtime_env().disk_manager.tmp_files_enabled()
+                {
+                    let spill_metrics =
+                        HashJoinSpillMetrics::new(partition, &self.metrics);
+                    let schema = self.schema();
+                    let stream = futures::stream::once(spillable_join_partition(
+                        Arc::clone(&self.left),
+                        Arc::clone(&self.right),
+                        partition,
+                        context,
+                        stream_builder,
+                        join_metrics,
+                        spill_metrics,
+                        reservation,
+                        num_spill_partitions,
+                    ))
+                    .try_flatten();
+                    return Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)));
+                }
+
                 OnceFut::new(collect_left_input(
                     Some(partition),
                     self.random_state.clone(),
@@ -762,38 +906,15 @@ impl ExecutionPlan for HashJoinExec {
             }
         };
 
-        let batch_size = context.session_config().batch_size();
-
         // we have the batches and the hash map with their keys. We can how create a stream
         // over the right that uses this information to issue new batches.
         let right_stream = self.right.execute(partition, context)?;
 
-        // update column indices to reflect the projection
-        let column_indices_after_projection = match &self.projection {
-            Some(projection) => projection
-                .iter()
-                .map(|i| self.column_indices[*i].clone())
-                .collect(),
-            None => self.column_indices.clone(),
-        };
-
-        Ok(Box::pin(HashJoinStream {
-            schema: self.schema(),
-            on_left,
-            on_right,
-            filter: self.filter.clone(),
-            join_type: self.join_type,
-            right: right_stream,
-            column_indices: column_indices_after_projection,
-            random_state: self.random_state.clone(),
+        Ok(Box::pin(stream_builder.build(
+            left_fut,
+            right_stream,
             join_metrics,
-            null_equals_null: self.null_equals_null,
-            state: HashJoinStreamState::WaitBuildSide,
-            build_side: BuildSide::Initial(BuildSideInitialState { left_fut }),
-            batch_size,
-            hashes_buffer: vec![],
-            right_side_ordered: self.right.output_ordering().is_some(),
-        }))
+        )))
     }
 
     fn metrics(&self) -> Option<MetricsSet> {
@@ -825,6 +946,22 @@ impl ExecutionPlan for HashJoinExec {
     }
 }
 
+/// Publishes the runtime `dynamic_filters` from the collected build side
+/// `batch`, whose join keys are `on_left`
+fn publish_dynamic_filters(
+    dynamic_filters: &[(usize, Arc<DynamicFilter>)],
+    on_left: &[PhysicalExprRef],
+    batch: &RecordBatch,
+) -> Result<()> {
+    for (index, filter) in dynamic_filters {
+        let keys = on_left[*index]
+            .evaluate(batch)?
+            .into_array(batch.num_rows())?;
+        filter.update(&keys)?;
+    }
+    Ok(())
+}
+
 /// Reads the left (build) side of the input, buffering it in memory, to build a
 /// hash table (`LeftJoinData`)
 #[allow(clippy::too_many_arguments)]
@@ -856,7 +993,7 @@ async fn collect_left_input(
     // 1. creates a [JoinHashMap] of all batches from the stream
     // 2. stores the batches in a vector.
     let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = stream
+    let (batches, num_rows, metrics, reservation) = stream
         .try_fold(initial, |mut acc, batch| async {
             let batch_size = batch.get_array_memory_size();
             // Reserve memory for incoming batch
@@ -873,6 +1010,33 @@ async fn collect_left_input(
         })
         .await?;
 
+    build_left_data(
+        &schema,
+        &batches,
+        num_rows,
+        &random_state,
+        &on_left,
+        &metrics,
+        reservation,
+        with_visited_indices_bitmap,
+        probe_threads_count,
+    )
+}
+
+/// Builds the hash table (`LeftJoinData`) over already collected build side
+/// `batches`, whose memory is accounted for in `reservation`
+#[allow(clippy::too_many_arguments)]
+fn build_left_data(
+    schema: &SchemaRef,
+    batches: &[RecordBatch],
+    num_rows: usize,
+    random_state: &RandomState,
+    on_left: &[PhysicalExprRef],
+    metrics: &BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    with_visited_indices_bitmap: bool,
+    probe_threads_count: usize,
+) -> Result<JoinLeftData> {
     // Estimation of memory size, required for hashtable, prior to allocation.
     // Final result can be verified using `RawTable.allocation_info()`
     let fixed_size = std::mem::size_of::<JoinHashMap>();
@@ -892,11 +1056,11 @@ async fn collect_left_input(
         hashes_buffer.clear();
         hashes_buffer.resize(batch.num_rows(), 0);
         update_hash(
-            &on_left,
+            on_left,
             batch,
             &mut hashmap,
             offset,
-            &random_state,
+            random_state,
             &mut hashes_buffer,
             0,
             true,
@@ -904,7 +1068,7 @@ async fn collect_left_input(
         offset += batch.num_rows();
     }
     // Merge all batches into a single batch, so we can directly index into the arrays
-    let single_batch = concat_batches(&schema, batches_iter)?;
+    let single_batch = concat_batches(schema, batches_iter)?;
 
     // Reserve additional memory for visited indices bitmap and create shared builder
     let visited_indices_bitmap = if with_visited_indices_bitmap {
@@ -930,6 +1094,331 @@ async fn collect_left_input(
     Ok(data)
 }
 
+/// Spill metrics of a [`PartitionMode::Partitioned`] hash join
+#[derive(Clone)]
+struct HashJoinSpillMetrics {
+    /// Number of spill files written by the join
+    spill_count: Count,
+    /// Total bytes of build and probe side data spilled to disk
+    spilled_bytes: Count,
+    /// Total rows of build and probe side data spilled to disk
+    spilled_rows: Count,
+}
+
+impl HashJoinSpillMetrics {
+    fn new(partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+        Self {
+            spill_count: MetricBuilder::new(metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+        }
+    }
+}
+
+/// Executes one partition of a [`PartitionMode::Partitioned`] hash join,
+/// falling back to a grace hash join if its build side does not fit in memory.
+///
+/// The build side is first collected in memory as usual. If the memory pool
+/// refuses to grow the reservation, the collected batches and the rest of the
+/// build input are hash partitioned by join key into `num_spill_partitions`
+/// spill files, and so is the probe input. Rows with equal join keys always
+/// end up in the same spill partition, so each pair of build and probe spill
+/// files is then joined independently by a regular [`HashJoinStream`], one
+/// spill partition at a time.
+///
+/// Spill partitions are not split any further: a spill partition whose build
+/// side still does not fit in memory (e.g. due to heavily skewed join keys)
+/// fails with a resources exhausted error.
+#[allow(clippy::too_many_arguments)]
+async fn spillable_join_partition(
+    left: Arc<dyn ExecutionPlan>,
+    right: Arc<dyn ExecutionPlan>,
+    partition: usize,
+    context: Arc<TaskContext>,
+    stream_builder: HashJoinStreamBuilder,
+    join_metrics: BuildProbeJoinMetrics,
+    spill_metrics: HashJoinSpillMetrics,
+    mut reservation: MemoryReservation,
+    num_spill_partitions: usize,
+) -> Result<SendableRecordBatchStream> {
+    let left_schema = left.schema();
+    let right_schema = right.schema();
+    let with_visited_indices_bitmap =
+        need_produce_result_in_final(stream_builder.join_type);
+    let spill_reservation = reservation.new_empty();
+
+    let mut left_stream = left.execute(partition, Arc::clone(&context))?;
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    let mut overflow_batch = None;
+    while let Some(batch) = left_stream.next().await.transpose()? {
+        join_metrics.build_input_batches.add(1);
+        join_metrics.build_input_rows.add(batch.num_rows());
+
+        let batch_size = batch.get_array_memory_size();
+        match reservation.try_grow(batch_size) {
+            Ok(()) => {
+                join_metrics.build_mem_used.add(batch_size);
+                num_rows += batch.num_rows();
+                batches.push(batch);
+            }
+            Err(DataFusionError::ResourcesExhausted(_)) => {
+                overflow_batch = Some(batch);
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    if overflow_batch.is_none() {
+        let build_timer = join_metrics.build_time.timer();
+        let left_data = build_left_data(
+            &left_schema,
+            &batches,
+            num_rows,
+            &stream_builder.random_state,
+            &stream_builder.on_left,
+            &join_metrics,
+            reservation,
+            with_visited_indices_bitmap,
+            1,
+        );
+        build_timer.done();
+
+        match left_data {
+            Ok(left_data) => {
+                let right_stream = right.execute(partition, context)?;
+                return Ok(Box::pin(stream_builder.build(
+                    OnceFut::new(async move { Ok(left_data) }),
+                    right_stream,
+                    join_metrics,
+                )));
+            }
+            // Not enough memory left for the hash table, spill the collected
+            // batches instead. The reservation has been released on drop.
+            Err(DataFusionError::ResourcesExhausted(_)) => {}
+            Err(e) => return Err(e),
+        }
+    } else {
+        reservation.free();
+    }
+    join_metrics.build_mem_used.set(0);
+
+    let runtime = context.runtime_env();
+    let disk_manager = &runtime.disk_manager;
+    let execution_options = &context.session_config().options().execution;
+    let write_options = spill_write_options(execution_options.spill_compression)?;
+    let max_temp_directory_size = execution_options.max_temp_directory_size;
+
+    // Spill the build side
+    let mut build_spill = SpillPartitioner::try_new(
+        stream_builder.on_left.clone(),
+        &left_schema,
+        num_spill_partitions,
+        disk_manager,
+        &write_options,
+        max_temp_directory_size,
+        "HashJoinBuildSpill",
+    )?;
+    for batch in batches.iter().chain(overflow_batch.iter()) {
+        build_spill.write(batch)?;
+    }
+    drop(batches);
+    while let Some(batch) = left_stream.next().await.transpose()? {
+        join_metrics.build_input_batches.add(1);
+        join_metrics.build_input_rows.add(batch.num_rows());
+        build_spill.write(&batch)?;
+    }
+    let build_files = build_spill.finish(&spill_metrics)?;
+
+    // Spill the probe side
+    let mut right_stream = right.execute(partition, Arc::clone(&context))?;
+    let mut probe_spill = SpillPartitioner::try_new(
+        stream_builder.on_right.clone(),
+        &right_schema,
+        num_spill_partitions,
+        disk_manager,
+        &write_options,
+        max_temp_directory_size,
+        "HashJoinProbeSpill",
+    )?;
+    while let Some(batch) = right_stream.next().await.transpose()? {
+        probe_spill.write(&batch)?;
+    }
+    let probe_files = probe_spill.finish(&spill_metrics)?;
+
+    // Join the spill partitions one at a time
+    let schema = Arc::clone(&stream_builder.schema);
+    let streams = build_files.into_iter().zip(probe_files).map(
+        move |(build_file, probe_file)| -> Result<SendableRecordBatchStream> {
+            let left_fut = OnceFut::new(load_spilled_build_side(
+                build_file,
+                Arc::clone(&left_schema),
+                stream_builder.on_left.clone(),
+                stream_builder.random_state.clone(),
+                join_metrics.clone(),
+                spill_reservation.new_empty(),
+                with_visited_indices_bitmap,
+            ));
+            let right_stream =
+                read_spill_as_stream(probe_file, Arc::clone(&right_schema), 2)?;
+            Ok(Box::pin(stream_builder.build(
+                left_fut,
+                right_stream,
+                join_metrics.clone(),
+            )))
+        },
+    );
+
+    Ok(Box::pin(RecordBatchStreamAdapter::new(
+        schema,
+        futures::stream::iter(streams).try_flatten(),
+    )))
+}
+
+/// Reads the build side of a spill partition back into memory and builds its
+/// hash table (`LeftJoinData`)
+async fn load_spilled_build_side(
+    file: RefCountedTempFile,
+    schema: SchemaRef,
+    on_left: Vec<PhysicalExprRef>,
+    random_state: RandomState,
+    metrics: BuildProbeJoinMetrics,
+    mut reservation: MemoryReservation,
+    with_visited_indices_bitmap: bool,
+) -> Result<JoinLeftData> {
+    metrics.build_mem_used.set(0);
+    let mut stream = read_spill_as_stream(file, Arc::clone(&schema), 2)?;
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    while let Some(batch) = stream.next().await.transpose()? {
+        let batch_size = batch.get_array_memory_size();
+        reservation.try_grow(batch_size)?;
+        metrics.build_mem_used.add(batch_size);
+        num_rows += batch.num_rows();
+        batches.push(batch);
+    }
+
+    build_left_data(
+        &schema,
+        &batches,
+        num_rows,
+        &random_state,
+        &on_left,
+        &metrics,
+        reservation,
+        with_visited_indices_bitmap,
+        1,
+    )
+}
+
+/// Hash partitions record batches by join key into one spill file per
+/// partition, for joining the partitions separately afterwards
+struct SpillPartitioner {
+    /// Join key expressions to partition by
+    on: Vec<PhysicalExprRef>,
+    /// Random state used for hashing the join keys
+    random_state: RandomState,
+    /// Scratch space for computing hashes
+    hashes_buffer: Vec<u64>,
+    /// Spill files, one per partition
+    files: Vec<RefCountedTempFile>,
+    /// Writers of the spill files
+    writers: Vec<IPCWriter>,
+}
+
+impl SpillPartitioner {
+    fn try_new(
+        on: Vec<PhysicalExprRef>,
+        schema: &SchemaRef,
+        num_partitions: usize,
+        disk_manager: &DiskManager,
+        write_options: &IpcWriteOptions,
+        max_temp_directory_size: Option<u64>,
+        request_description: &str,
+    ) -> Result<Self> {
+        let files = (0..num_partitions)
+            .map(|_| {
+                Ok(disk_manager
+                    .create_tmp_file(request_description)?
+                    .with_max_temp_directory_size(max_temp_directory_size))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let writers = files
+            .iter()
+            .map(|file| {
+                IPCWriter::new_with_options(file.path(), schema, write_options.clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            on,
+            // Use seeds different from the ones of the join hash table, so
+            // rows of a single spill partition still spread over all buckets
+            random_state: RandomState::with_seeds(1, 2, 3, 4),
+            hashes_buffer: vec![],
+            files,
+            writers,
+        })
+    }
+
+    /// Splits `batch` by the hash of its join keys and writes the slices to
+    /// the spill files of the corresponding partitions
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let keys_values = self
+            .on
+            .iter()
+            .map(|c| c.evaluate(batch)?.into_array(batch.num_rows()))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
+
+        let num_partitions = self.writers.len() as u64;
+        let mut indices: Vec<Vec<u32>> = vec![vec![]; self.writers.len()];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % num_partitions) as usize].push(row as u32);
+        }
+
+        for ((writer, file), indices) in self
+            .writers
+            .iter_mut()
+            .zip(self.files.iter_mut())
+            .zip(indices)
+        {
+            if indices.is_empty() {
+                continue;
+            }
+            let indices = UInt32Array::from(indices);
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|c| take(c.as_ref(), &indices, None))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            writer.write(&RecordBatch::try_new(batch.schema(), columns)?)?;
+            file.update_disk_usage()?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes all spill files and returns them in partition order
+    fn finish(
+        mut self,
+        metrics: &HashJoinSpillMetrics,
+    ) -> Result<Vec<RefCountedTempFile>> {
+        for (writer, file) in self.writers.iter_mut().zip(self.files.iter_mut()) {
+            writer.finish()?;
+            file.update_disk_usage()?;
+            metrics.spill_count.add(1);
+            metrics.spilled_bytes.add(writer.num_bytes);
+            metrics.spilled_rows.add(writer.num_rows);
+        }
+        Ok(self.files)
+    }
+}
+
 /// Updates `hash_map` with new entries from `batch` evaluated against the expressions `on`
 /// using `offset` as a start value for `batch` row indices.
 ///
@@ -1125,6 +1614,60 @@ struct HashJoinStream {
     right_side_ordered: bool,
 }
 
+/// Creates [`HashJoinStream`]s sharing the same join parameters, for example
+/// one per spill partition of a [`PartitionMode::Partitioned`] hash join
+#[derive(Clone)]
+struct HashJoinStreamBuilder {
+    /// Output schema
+    schema: SchemaRef,
+    /// equijoin columns from the left (build side)
+    on_left: Vec<PhysicalExprRef>,
+    /// equijoin columns from the right (probe side)
+    on_right: Vec<PhysicalExprRef>,
+    /// optional join filter
+    filter: Option<JoinFilter>,
+    /// type of the join (left, right, semi, etc)
+    join_type: JoinType,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Random state used for hashing initialization
+    random_state: RandomState,
+    /// If null_equals_null is true, null == null else null != null
+    null_equals_null: bool,
+    /// Maximum output batch size
+    batch_size: usize,
+    /// Specifies whether the right side has an ordering to potentially preserve
+    right_side_ordered: bool,
+}
+
+impl HashJoinStreamBuilder {
+    /// Creates a stream joining `right` with the build side produced by `left_fut`
+    fn build(
+        &self,
+        left_fut: OnceFut<JoinLeftData>,
+        right: SendableRecordBatchStream,
+        join_metrics: BuildProbeJoinMetrics,
+    ) -> HashJoinStream {
+        HashJoinStream {
+            schema: Arc::clone(&self.schema),
+            on_left: self.on_left.clone(),
+            on_right: self.on_right.clone(),
+            filter: self.filter.clone(),
+            join_type: self.join_type,
+            right,
+            column_indices: self.column_indices.clone(),
+            random_state: self.random_state.clone(),
+            join_metrics,
+            null_equals_null: self.null_equals_null,
+            state: HashJoinStreamState::WaitBuildSide,
+            build_side: BuildSide::Initial(BuildSideInitialState { left_fut }),
+            batch_size: self.batch_size,
+            hashes_buffer: vec![],
+            right_side_ordered: self.right_side_ordered,
+        }
+    }
+}
+
 impl RecordBatchStream for HashJoinStream {
     fn schema(&self) -> SchemaRef {
         Arc::clone(&self.schema)
@@ -1559,8 +2102,9 @@ impl Stream for HashJoinStream {
 mod tests {
     use super::*;
     use crate::{
-        common, expressions::Column, memory::MemoryExec, repartition::RepartitionExec,
-        test::build_table_i32, test::exec::MockExec,
+        common, expressions::Column, joins::test_utils::compare_batches,
+        memory::MemoryExec, repartition::RepartitionExec, test::build_table_i32,
+        test::exec::MockExec,
     };
 
     use arrow::array::{Date32Array, Int32Array};
@@ -1803,6 +2347,92 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_publishes_dynamic_filters() -> Result<()> {
+        let task_ctx = prepare_task_ctx(8192);
+        let left = build_table(
+            ("a1", &vec![1, 2, 3]),
+            ("b1", &vec![4, 5, 5]),
+            ("c1", &vec![7, 8, 9]),
+        );
+        let right = build_table(
+            ("a2", &vec![10, 20, 30]),
+            ("b1", &vec![4, 5, 6]),
+            ("c2", &vec![70, 80, 90]),
+        );
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        )];
+
+        let filter = Arc::new(DynamicFilter::new());
+        let join = join(left, right, on, &JoinType::Inner, false)?
+            .with_dynamic_filters(vec![(0, Arc::clone(&filter))])?;
+        assert!(!filter.is_complete());
+
+        let batches = common::collect(join.execute(0, task_ctx)?).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+        assert_eq!(
+            filter.bounds(),
+            Some(Some(&(
+                ScalarValue::Int32(Some(4)),
+                ScalarValue::Int32(Some(5))
+            )))
+        );
+
+        // the filters survive rebuilding the join
+        let join = Arc::new(join);
+        let rebuilt = Arc::clone(&join)
+            .with_new_children(vec![Arc::clone(&join.left), Arc::clone(&join.right)])?;
+        let rebuilt = rebuilt.as_any().downcast_ref::<HashJoinExec>().unwrap();
+        assert_eq!(rebuilt.dynamic_filters().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_filters_unsupported() -> Result<()> {
+        let left = build_table(("a1", &vec![1]), ("b1", &vec![4]), ("c1", &vec![7]));
+        let right = build_table(("a2", &vec![10]), ("b1", &vec![4]), ("c2", &vec![70]));
+        let on: JoinOn = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b1", &right.schema())?) as _,
+        )];
+        let filters = vec![(0, Arc::new(DynamicFilter::new()))];
+
+        // unmatched probe side rows are part of the output
+        let err = join(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on.clone(),
+            &JoinType::Full,
+            false,
+        )?
+        .with_dynamic_filters(filters.clone())
+        .unwrap_err();
+        assert_contains!(err.to_string(), "not supported for Full joins");
+
+        // each partition only sees part of the build side
+        let err = HashJoinExec::try_new(
+            Arc::clone(&left),
+            Arc::clone(&right),
+            on.clone(),
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::Partitioned,
+            false,
+        )?
+        .with_dynamic_filters(filters.clone())
+        .unwrap_err();
+        assert_contains!(err.to_string(), "only supported in CollectLeft mode");
+
+        let err = join(left, right, on, &JoinType::Inner, false)?
+            .with_dynamic_filters(vec![(1, Arc::new(DynamicFilter::new()))])
+            .unwrap_err();
+        assert_contains!(err.to_string(), "refers to join key 1");
+        Ok(())
+    }
+
     #[apply(batch_sizes)]
     #[tokio::test]
     async fn partitioned_join_inner_one(batch_size: usize) -> Result<()> {
@@ -3879,7 +4509,9 @@ mod tests {
                     .with_memory_limit(100, 1.0)
                     .build()?,
             );
-            let session_config = SessionConfig::default().with_batch_size(50);
+            let session_config = SessionConfig::default()
+                .with_batch_size(50)
+                .set_usize("datafusion.execution.hash_join_spill_partitions", 0);
             let task_ctx = TaskContext::default()
                 .with_session_config(session_config)
                 .with_runtime(runtime);
@@ -3910,6 +4542,86 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn partitioned_join_spill() -> Result<()> {
+        let left_batches = (0..10)
+            .map(|i| {
+                let values = (i * 100..(i + 1) * 100).collect::<Vec<_>>();
+                build_table_i32(("a1", &values), ("b1", &values), ("c1", &values))
+            })
+            .collect::<Vec<_>>();
+        let left = Arc::new(MemoryExec::try_new(
+            &[left_batches.clone()],
+            left_batches[0].schema(),
+            None,
+        )?);
+        let right_values = (500..1500).step_by(2).collect::<Vec<_>>();
+        let right_batch = build_table_i32(
+            ("a2", &right_values),
+            ("b2", &right_values),
+            ("c2", &right_values),
+        );
+        let right = Arc::new(MemoryExec::try_new(
+            &[vec![right_batch.clone()]],
+            right_batch.schema(),
+            None,
+        )?);
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+        ];
+
+        for join_type in join_types {
+            let join = HashJoinExec::try_new(
+                Arc::clone(&left) as Arc<dyn ExecutionPlan>,
+                Arc::clone(&right) as Arc<dyn ExecutionPlan>,
+                on.clone(),
+                None,
+                &join_type,
+                None,
+                PartitionMode::Partitioned,
+                false,
+            )?;
+
+            // Expected output, joined without memory limit
+            let expected =
+                common::collect(join.execute(0, prepare_task_ctx(8192))?).await?;
+
+            // The whole build side does not fit within the memory limit, a
+            // single spill partition does
+            let runtime = Arc::new(
+                RuntimeEnvBuilder::new()
+                    .with_memory_limit(40_000, 1.0)
+                    .build()?,
+            );
+            let task_ctx = Arc::new(TaskContext::default().with_runtime(runtime));
+            let batches = common::collect(join.execute(0, task_ctx)?).await?;
+
+            compare_batches(&batches, &expected);
+            assert_eq!(
+                batches.iter().map(|b| b.num_rows()).sum::<usize>(),
+                expected.iter().map(|b| b.num_rows()).sum::<usize>()
+            );
+
+            let metrics = join.metrics().unwrap();
+            assert_eq!(metrics.spill_count(), Some(32));
+            assert_eq!(metrics.spilled_rows(), Some(1500));
+        }
+
+        Ok(())
+    }
+
     fn build_table_struct(
         struct_name: &str,
         field_name_and_values: (&str, &Vec<Option<i32>>),
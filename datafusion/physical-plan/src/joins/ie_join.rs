@@ -0,0 +1,934 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the IEJoin execution plan, which evaluates inner joins whose
+//! filter has two range conditions between the inputs, such as
+//! `a.start <= b.ts AND b.ts < a.end`, without comparing every pair of rows.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::task::Poll;
+
+use super::utils::asymmetric_join_output_partitioning;
+use crate::coalesce_partitions::CoalescePartitionsExec;
+use crate::joins::utils::{
+    apply_join_filter_to_indices, build_batch_from_indices, build_join_schema,
+    check_join_is_valid, estimate_join_statistics, BuildProbeJoinMetrics, ColumnIndex,
+    JoinFilter, OnceAsync, OnceFut,
+};
+use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::{
+    execution_mode_from_children, DisplayAs, DisplayFormatType, Distribution,
+    ExecutionMode, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    RecordBatchStream, SendableRecordBatchStream,
+};
+
+use arrow::array::{Array, ArrayRef, BooleanBufferBuilder, UInt32Array, UInt64Array};
+use arrow::compute::concat_batches;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
+use arrow::util::bit_iterator::BitIndexIterator;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{plan_err, JoinSide, JoinType, Result, Statistics};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column};
+use datafusion_physical_expr::utils::{collect_columns, split_conjunction};
+use datafusion_physical_expr::PhysicalExprRef;
+
+use futures::{ready, Stream, StreamExt, TryStreamExt};
+
+/// A range condition `left <op> right` of the filter of an [`IEJoinExec`]
+#[derive(Debug, Clone)]
+pub struct RangeCondition {
+    /// Expression on the left input
+    pub left: PhysicalExprRef,
+    /// One of `>`, `>=`, `<` or `<=`
+    pub op: Operator,
+    /// Expression on the right input
+    pub right: PhysicalExprRef,
+}
+
+impl RangeCondition {
+    /// Create a new range condition `left <op> right`
+    pub fn new(left: PhysicalExprRef, op: Operator, right: PhysicalExprRef) -> Self {
+        Self { left, op, right }
+    }
+}
+
+/// IEJoinExec evaluates inner joins without equijoin keys whose filter has at
+/// least two range conditions between the inputs, using the IEJoin algorithm
+/// of Khayyat et al. ("Lightning Fast and Space Efficient Inequality Joins").
+///
+/// Like [`NestedLoopJoinExec`], the left input is collected into a single
+/// batch shared by all partitions of the right input. The left rows are
+/// sorted by the value of the second range condition once, and each right
+/// batch is then joined as follows:
+///
+/// - the right rows are sorted by the value of the first range condition,
+///   giving each one a position in a bitmap
+/// - the left and right rows are merged in order of the second range
+///   condition, so that when a left row is reached exactly the right rows
+///   satisfying the second condition have been marked in the bitmap
+/// - the right rows satisfying the first condition form a contiguous range
+///   of the bitmap, found by binary search, whose marked rows are the
+///   matches of the left row
+///
+/// Rows with a null value in either condition never match. Any predicates
+/// of the filter beyond the two range conditions are evaluated on the
+/// matched pairs.
+///
+/// [`NestedLoopJoinExec`]: crate::joins::NestedLoopJoinExec
+#[derive(Debug)]
+pub struct IEJoinExec {
+    /// left side
+    left: Arc<dyn ExecutionPlan>,
+    /// right side
+    right: Arc<dyn ExecutionPlan>,
+    /// Filter of the join, containing the range conditions
+    filter: JoinFilter,
+    /// The two range conditions used to find matching rows
+    conditions: [RangeCondition; 2],
+    /// Whether the filter has predicates beyond `conditions`
+    residual: bool,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Build-side data
+    left_data: OnceAsync<IEJoinLeftData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl IEJoinExec {
+    /// Try to create a new [`IEJoinExec`] for an inner join with the given
+    /// filter, which must have at least two range conditions
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &[])?;
+
+        let mut conditions = Self::range_conditions(&filter)?.into_iter();
+        let (Some(first), Some(second)) = (conditions.next(), conditions.next()) else {
+            return plan_err!(
+                "IEJoinExec requires two range conditions between its inputs, got {}",
+                filter.expression()
+            );
+        };
+        let residual = split_conjunction(filter.expression()).len() > 2;
+
+        let (schema, column_indices) =
+            build_join_schema(&left_schema, &right_schema, &JoinType::Inner);
+        let schema = Arc::new(schema);
+        let cache = Self::compute_properties(&left, &right, Arc::clone(&schema));
+
+        Ok(IEJoinExec {
+            left,
+            right,
+            filter,
+            conditions: [first, second],
+            residual,
+            schema,
+            left_data: Default::default(),
+            column_indices,
+            metrics: Default::default(),
+            cache,
+        })
+    }
+
+    /// Returns the range conditions of `filter` comparing an expression of
+    /// the left input with an expression of the right input of the same
+    /// type, oriented with the left input first
+    pub fn range_conditions(filter: &JoinFilter) -> Result<Vec<RangeCondition>> {
+        let mut conditions = vec![];
+        for expr in split_conjunction(filter.expression()) {
+            let Some(binary) = expr.as_any().downcast_ref::<BinaryExpr>() else {
+                continue;
+            };
+            if !matches!(
+                binary.op(),
+                Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+            ) {
+                continue;
+            }
+            let (left, op, right) = match (
+                input_side(binary.left(), filter),
+                input_side(binary.right(), filter),
+            ) {
+                (Some(JoinSide::Left), Some(JoinSide::Right)) => {
+                    (binary.left(), *binary.op(), binary.right())
+                }
+                (Some(JoinSide::Right), Some(JoinSide::Left)) => {
+                    let Some(op) = binary.op().swap() else {
+                        continue;
+                    };
+                    (binary.right(), op, binary.left())
+                }
+                _ => continue,
+            };
+            if left.data_type(filter.schema())? != right.data_type(filter.schema())? {
+                continue;
+            }
+            conditions.push(RangeCondition::new(
+                to_input_expr(left, filter)?,
+                op,
+                to_input_expr(right, filter)?,
+            ));
+        }
+        Ok(conditions)
+    }
+
+    /// left side
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Filter of the join
+    pub fn filter(&self) -> &JoinFilter {
+        &self.filter
+    }
+
+    /// The two range conditions used to find matching rows
+    pub fn conditions(&self) -> &[RangeCondition; 2] {
+        &self.conditions
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+    ) -> PlanProperties {
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &JoinType::Inner,
+            schema,
+            &[false, false],
+            None,
+            // No on columns in IEJoin
+            &[],
+        );
+
+        let output_partitioning =
+            asymmetric_join_output_partitioning(left, right, &JoinType::Inner);
+
+        // Determine execution mode:
+        let mut mode = execution_mode_from_children([left, right]);
+        if mode.is_unbounded() {
+            mode = ExecutionMode::PipelineBreaking;
+        }
+
+        PlanProperties::new(eq_properties, output_partitioning, mode)
+    }
+}
+
+/// Returns the input all columns of `expr` refer to, if any
+fn input_side(expr: &PhysicalExprRef, filter: &JoinFilter) -> Option<JoinSide> {
+    let mut sides = collect_columns(expr)
+        .into_iter()
+        .map(|column| filter.column_indices()[column.index()].side);
+    let side = sides.next()?;
+    sides.all(|s| s == side).then_some(side)
+}
+
+/// Rewrites `expr` from the intermediate schema of `filter` to the schema of
+/// the input it refers to
+fn to_input_expr(expr: &PhysicalExprRef, filter: &JoinFilter) -> Result<PhysicalExprRef> {
+    Arc::clone(expr)
+        .transform(|e| match e.as_any().downcast_ref::<Column>() {
+            Some(column) => {
+                let index = filter.column_indices()[column.index()].index;
+                Ok(Transformed::yes(
+                    Arc::new(Column::new(column.name(), index)) as _,
+                ))
+            }
+            None => Ok(Transformed::no(e)),
+        })
+        .data()
+}
+
+impl DisplayAs for IEJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "IEJoinExec: join_type=Inner, filter={}",
+                    self.filter.expression()
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for IEJoinExec {
+    fn name(&self) -> &'static str {
+        "IEJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![
+            Distribution::SinglePartition,
+            Distribution::UnspecifiedDistribution,
+        ]
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.left, &self.right]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(IEJoinExec::try_new(
+            Arc::clone(&children[0]),
+            Arc::clone(&children[1]),
+            self.filter.clone(),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+
+        // Initialization reservation for load of left side
+        let load_reservation = MemoryConsumer::new(format!("IEJoinLoad[{partition}]"))
+            .register(context.memory_pool());
+
+        let left_data = self.left_data.once(|| {
+            collect_left_input(
+                Arc::clone(&self.left),
+                self.conditions.clone(),
+                Arc::clone(&context),
+                join_metrics.clone(),
+                load_reservation,
+            )
+        });
+        let right = self.right.execute(partition, context)?;
+
+        Ok(Box::pin(IEJoinStream {
+            schema: Arc::clone(&self.schema),
+            filter: self.residual.then(|| self.filter.clone()),
+            conditions: self.conditions.clone(),
+            right,
+            left_data,
+            column_indices: self.column_indices.clone(),
+            join_metrics,
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        estimate_join_statistics(
+            Arc::clone(&self.left),
+            Arc::clone(&self.right),
+            vec![],
+            &JoinType::Inner,
+            &self.schema,
+        )
+    }
+}
+
+/// Returns whether rows are visited in descending order of the value of the
+/// second range condition, so that the right rows satisfying `left <op> right`
+/// are visited before the left row
+fn visit_descending(op: Operator) -> bool {
+    matches!(op, Operator::Lt | Operator::LtEq)
+}
+
+/// Evaluates the range condition values of `batch`, returning them in the
+/// row format along with the rows without nulls in either value
+fn condition_values(
+    batch: &RecordBatch,
+    exprs: [&PhysicalExprRef; 2],
+    converters: &[RowConverter; 2],
+) -> Result<([Rows; 2], Vec<u32>)> {
+    let arrays = exprs
+        .iter()
+        .map(|expr| expr.evaluate(batch)?.into_array(batch.num_rows()))
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    let valid = (0..batch.num_rows() as u32)
+        .filter(|&i| arrays.iter().all(|a| a.is_valid(i as usize)))
+        .collect();
+    let values = [
+        converters[0].convert_columns(&arrays[0..1])?,
+        converters[1].convert_columns(&arrays[1..2])?,
+    ];
+    Ok((values, valid))
+}
+
+/// Sorts `indices` in the order rows are visited for the second range
+/// condition `op`
+fn sort_for_visit(indices: &mut [u32], values: &Rows, op: Operator) {
+    if visit_descending(op) {
+        indices.sort_unstable_by(|&a, &b| {
+            values.row(b as usize).cmp(&values.row(a as usize))
+        });
+    } else {
+        indices.sort_unstable_by(|&a, &b| {
+            values.row(a as usize).cmp(&values.row(b as usize))
+        });
+    }
+}
+
+/// Left (build-side) data
+struct IEJoinLeftData {
+    /// Build-side data collected to single batch
+    batch: RecordBatch,
+    /// Row converters for the values of the two range conditions, shared by
+    /// all probe threads so that left and right values are comparable
+    converters: [RowConverter; 2],
+    /// Values of the two range conditions for each left row
+    values: [Rows; 2],
+    /// Left rows without null values, in visiting order of the second range
+    /// condition
+    order: Vec<u32>,
+    /// Memory reservation for tracking batch and values
+    /// Cleared on `IEJoinLeftData` drop
+    #[allow(dead_code)]
+    reservation: MemoryReservation,
+}
+
+/// Asynchronously collect input into a single batch, and creates
+/// `IEJoinLeftData` from it
+async fn collect_left_input(
+    input: Arc<dyn ExecutionPlan>,
+    conditions: [RangeCondition; 2],
+    context: Arc<TaskContext>,
+    join_metrics: BuildProbeJoinMetrics,
+    reservation: MemoryReservation,
+) -> Result<IEJoinLeftData> {
+    let schema = input.schema();
+    let merge = if input.output_partitioning().partition_count() != 1 {
+        Arc::new(CoalescePartitionsExec::new(input))
+    } else {
+        input
+    };
+    let stream = merge.execute(0, context)?;
+
+    // Load all batches and count the rows
+    let (batches, metrics, mut reservation) = stream
+        .try_fold(
+            (Vec::new(), join_metrics, reservation),
+            |mut acc, batch| async {
+                let batch_size = batch.get_array_memory_size();
+                // Reserve memory for incoming batch
+                acc.2.try_grow(batch_size)?;
+                // Update metrics
+                acc.1.build_mem_used.add(batch_size);
+                acc.1.build_input_batches.add(1);
+                acc.1.build_input_rows.add(batch.num_rows());
+                // Push batch to output
+                acc.0.push(batch);
+                Ok(acc)
+            },
+        )
+        .await?;
+
+    let batch = concat_batches(&schema, &batches)?;
+
+    let converters = [
+        RowConverter::new(vec![SortField::new(conditions[0].left.data_type(&schema)?)])?,
+        RowConverter::new(vec![SortField::new(conditions[1].left.data_type(&schema)?)])?,
+    ];
+    let (values, mut order) = condition_values(
+        &batch,
+        [&conditions[0].left, &conditions[1].left],
+        &converters,
+    )?;
+    sort_for_visit(&mut order, &values[1], conditions[1].op);
+
+    let size =
+        values[0].size() + values[1].size() + order.len() * std::mem::size_of::<u32>();
+    reservation.try_grow(size)?;
+    metrics.build_mem_used.add(size);
+
+    Ok(IEJoinLeftData {
+        batch,
+        converters,
+        values,
+        order,
+        reservation,
+    })
+}
+
+/// A stream that issues [RecordBatch]es as they arrive from the right of the join.
+struct IEJoinStream {
+    /// Input schema
+    schema: Arc<Schema>,
+    /// Join filter, if it has predicates beyond the range conditions
+    filter: Option<JoinFilter>,
+    /// The two range conditions used to find matching rows
+    conditions: [RangeCondition; 2],
+    /// Right input
+    right: SendableRecordBatchStream,
+    /// Left data
+    left_data: OnceFut<IEJoinLeftData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Join execution metrics
+    join_metrics: BuildProbeJoinMetrics,
+}
+
+impl IEJoinStream {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        let build_timer = self.join_metrics.build_time.timer();
+        let left_data = match ready!(self.left_data.get_shared(cx)) {
+            Ok(data) => data,
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        };
+        build_timer.done();
+
+        self.right
+            .poll_next_unpin(cx)
+            .map(|maybe_batch| match maybe_batch {
+                Some(Ok(right_batch)) => {
+                    // Setting up timer & updating input metrics
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(right_batch.num_rows());
+                    let timer = self.join_metrics.join_time.timer();
+
+                    let result = self.join_right_batch(&left_data, &right_batch);
+
+                    // Recording time & updating output metrics
+                    if let Ok(batch) = &result {
+                        timer.done();
+                        self.join_metrics.output_batches.add(1);
+                        self.join_metrics.output_rows.add(batch.num_rows());
+                    }
+
+                    Some(result)
+                }
+                other => other,
+            })
+    }
+
+    fn join_right_batch(
+        &self,
+        left_data: &IEJoinLeftData,
+        right_batch: &RecordBatch,
+    ) -> Result<RecordBatch> {
+        let [first, second] = &self.conditions;
+        let (right_values, right_rows) = condition_values(
+            right_batch,
+            [&first.right, &second.right],
+            &left_data.converters,
+        )?;
+
+        // Position of each right row in the order of the first range condition
+        let mut by_first = right_rows.clone();
+        by_first.sort_unstable_by(|&a, &b| {
+            right_values[0]
+                .row(a as usize)
+                .cmp(&right_values[0].row(b as usize))
+        });
+        let mut positions = vec![0; right_batch.num_rows()];
+        for (position, &row) in by_first.iter().enumerate() {
+            positions[row as usize] = position;
+        }
+
+        let mut visit_order = right_rows;
+        sort_for_visit(&mut visit_order, &right_values[1], second.op);
+
+        let mut visited = BooleanBufferBuilder::new(by_first.len());
+        visited.append_n(by_first.len(), false);
+
+        let mut left_indices = vec![];
+        let mut right_indices = vec![];
+        let mut next_right = visit_order.iter().peekable();
+        for &left_row in &left_data.order {
+            let left_second = left_data.values[1].row(left_row as usize);
+            // Mark the right rows satisfying the second range condition
+            while let Some(&&right_row) = next_right.peek() {
+                let right_second = right_values[1].row(right_row as usize);
+                let ordering = if visit_descending(second.op) {
+                    left_second.cmp(&right_second)
+                } else {
+                    right_second.cmp(&left_second)
+                };
+                let visit = match ordering {
+                    Ordering::Less => true,
+                    Ordering::Equal => {
+                        matches!(second.op, Operator::LtEq | Operator::GtEq)
+                    }
+                    Ordering::Greater => false,
+                };
+                if !visit {
+                    break;
+                }
+                visited.set_bit(positions[right_row as usize], true);
+                next_right.next();
+            }
+
+            // The right rows satisfying the first range condition
+            let left_first = left_data.values[0].row(left_row as usize);
+            let lower = by_first
+                .partition_point(|&r| right_values[0].row(r as usize) < left_first);
+            let upper = by_first
+                .partition_point(|&r| right_values[0].row(r as usize) <= left_first);
+            let (start, end) = match first.op {
+                Operator::Gt => (0, lower),
+                Operator::GtEq => (0, upper),
+                Operator::Lt => (upper, by_first.len()),
+                _ => (lower, by_first.len()),
+            };
+            for position in BitIndexIterator::new(visited.as_slice(), start, end - start)
+            {
+                left_indices.push(left_row as u64);
+                right_indices.push(by_first[start + position]);
+            }
+        }
+
+        let left_indices = UInt64Array::from(left_indices);
+        let right_indices = UInt32Array::from(right_indices);
+        let (left_indices, right_indices) = match &self.filter {
+            Some(filter) => apply_join_filter_to_indices(
+                &left_data.batch,
+                right_batch,
+                left_indices,
+                right_indices,
+                filter,
+                JoinSide::Left,
+            )?,
+            None => (left_indices, right_indices),
+        };
+
+        build_batch_from_indices(
+            &self.schema,
+            &left_data.batch,
+            right_batch,
+            &left_indices,
+            &right_indices,
+            &self.column_indices,
+            JoinSide::Left,
+        )
+    }
+}
+
+impl Stream for IEJoinStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.poll_next_impl(cx)
+    }
+}
+
+impl RecordBatchStream for IEJoinStream {
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::joins::NestedLoopJoinExec;
+    use crate::{
+        common, memory::MemoryExec, repartition::RepartitionExec, test::build_table_i32,
+    };
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field};
+    use arrow::util::pretty::pretty_format_batches;
+    use datafusion_common::{assert_contains, ScalarValue};
+    use datafusion_physical_expr::expressions::Literal;
+    use datafusion_physical_expr::{Partitioning, PhysicalExpr};
+
+    const RANGE_OPS: [Operator; 4] =
+        [Operator::Lt, Operator::LtEq, Operator::Gt, Operator::GtEq];
+
+    fn build_left_table() -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(
+            ("a1", &vec![1, 3, 5, 7, 9, 3, 5]),
+            ("b1", &vec![10, 8, 6, 4, 2, 8, 3]),
+            ("c1", &vec![10, 20, 30, 40, 50, 60, 70]),
+        );
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    /// The right table, split in two batches, with nulls in both columns
+    /// compared by the range conditions
+    fn build_right_table() -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a2", DataType::Int32, true),
+            Field::new("b2", DataType::Int32, true),
+            Field::new("c2", DataType::Int32, false),
+        ]));
+        let batch = |a: Vec<Option<i32>>, b: Vec<Option<i32>>, c: Vec<i32>| {
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![
+                    Arc::new(Int32Array::from(a)),
+                    Arc::new(Int32Array::from(b)),
+                    Arc::new(Int32Array::from(c)),
+                ],
+            )
+            .unwrap()
+        };
+        let batches = vec![
+            batch(
+                vec![Some(2), Some(3), Some(6), None],
+                vec![Some(9), Some(8), Some(5), Some(5)],
+                vec![100, 200, 300, 400],
+            ),
+            batch(
+                vec![Some(9), Some(5), Some(0), Some(4)],
+                vec![Some(1), Some(4), None, Some(8)],
+                vec![500, 600, 700, 800],
+            ),
+        ];
+        Arc::new(MemoryExec::try_new(&[batches], schema, None).unwrap())
+    }
+
+    /// Builds the filter `a1 <first_op> a2 AND b2 <second_op> b1`, optionally
+    /// followed by `AND c1 != 30`
+    fn prepare_join_filter(
+        first_op: Operator,
+        second_op: Operator,
+        residual: bool,
+    ) -> JoinFilter {
+        let column_indices = vec![
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Right,
+            },
+            ColumnIndex {
+                index: 1,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 1,
+                side: JoinSide::Right,
+            },
+            ColumnIndex {
+                index: 2,
+                side: JoinSide::Left,
+            },
+        ];
+        let intermediate_schema = Schema::new(vec![
+            Field::new("a1", DataType::Int32, true),
+            Field::new("a2", DataType::Int32, true),
+            Field::new("b1", DataType::Int32, true),
+            Field::new("b2", DataType::Int32, true),
+            Field::new("c1", DataType::Int32, true),
+        ]);
+        let first = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("a1", 0)),
+            first_op,
+            Arc::new(Column::new("a2", 1)),
+        )) as Arc<dyn PhysicalExpr>;
+        // the second condition has the right input first
+        let second = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("b2", 3)),
+            second_op,
+            Arc::new(Column::new("b1", 2)),
+        )) as Arc<dyn PhysicalExpr>;
+        let mut expression = Arc::new(BinaryExpr::new(first, Operator::And, second))
+            as Arc<dyn PhysicalExpr>;
+        if residual {
+            let residual = Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("c1", 4)),
+                Operator::NotEq,
+                Arc::new(Literal::new(ScalarValue::Int32(Some(30)))),
+            ));
+            expression = Arc::new(BinaryExpr::new(expression, Operator::And, residual));
+        }
+
+        JoinFilter::new(expression, column_indices, intermediate_schema)
+    }
+
+    /// Executes `join` over all partitions of a round robin repartitioned
+    /// right input, returning the sorted lines of the formatted output
+    async fn join_collect(
+        join: impl FnOnce(
+            Arc<dyn ExecutionPlan>,
+            Arc<dyn ExecutionPlan>,
+        ) -> Result<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Vec<String>> {
+        let partition_count = 2;
+        let right = Arc::new(RepartitionExec::try_new(
+            build_right_table(),
+            Partitioning::RoundRobinBatch(partition_count),
+        )?) as Arc<dyn ExecutionPlan>;
+        let join = join(build_left_table(), right)?;
+
+        let task_ctx = Arc::new(TaskContext::default());
+        let mut batches = vec![];
+        for i in 0..partition_count {
+            let stream = join.execute(i, Arc::clone(&task_ctx))?;
+            batches.extend(common::collect(stream).await?);
+        }
+        let mut lines = pretty_format_batches(&batches)?
+            .to_string()
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        lines.sort();
+        Ok(lines)
+    }
+
+    #[tokio::test]
+    async fn join_matches_nested_loop_join() -> Result<()> {
+        for first_op in RANGE_OPS {
+            for second_op in RANGE_OPS {
+                for residual in [false, true] {
+                    let filter = prepare_join_filter(first_op, second_op, residual);
+                    let expected = join_collect(|left, right| {
+                        Ok(Arc::new(NestedLoopJoinExec::try_new(
+                            left,
+                            right,
+                            Some(filter.clone()),
+                            &JoinType::Inner,
+                        )?))
+                    })
+                    .await?;
+                    let actual = join_collect(|left, right| {
+                        Ok(Arc::new(IEJoinExec::try_new(left, right, filter)?))
+                    })
+                    .await?;
+                    assert_eq!(
+                        expected, actual,
+                        "a1 {first_op} a2 AND b2 {second_op} b1, residual: {residual}"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_inner_range() -> Result<()> {
+        let filter = prepare_join_filter(Operator::LtEq, Operator::Lt, false);
+        let actual = join_collect(|left, right| {
+            Ok(Arc::new(IEJoinExec::try_new(left, right, filter)?))
+        })
+        .await?;
+        // a1 <= a2 AND b2 < b1
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "+----+----+----+----+----+-----+",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 10 | 10 | 2  | 9  | 100 |",
+            "| 1  | 10 | 10 | 3  | 8  | 200 |",
+            "| 1  | 10 | 10 | 4  | 8  | 800 |",
+            "| 1  | 10 | 10 | 5  | 4  | 600 |",
+            "| 1  | 10 | 10 | 6  | 5  | 300 |",
+            "| 1  | 10 | 10 | 9  | 1  | 500 |",
+            "| 3  | 8  | 20 | 5  | 4  | 600 |",
+            "| 3  | 8  | 20 | 6  | 5  | 300 |",
+            "| 3  | 8  | 20 | 9  | 1  | 500 |",
+            "| 3  | 8  | 60 | 5  | 4  | 600 |",
+            "| 3  | 8  | 60 | 6  | 5  | 300 |",
+            "| 3  | 8  | 60 | 9  | 1  | 500 |",
+            "| 5  | 3  | 70 | 9  | 1  | 500 |",
+            "| 5  | 6  | 30 | 5  | 4  | 600 |",
+            "| 5  | 6  | 30 | 6  | 5  | 300 |",
+            "| 5  | 6  | 30 | 9  | 1  | 500 |",
+            "| 7  | 4  | 40 | 9  | 1  | 500 |",
+            "| 9  | 2  | 50 | 9  | 1  | 500 |",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+        ];
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn range_conditions() -> Result<()> {
+        let filter = prepare_join_filter(Operator::Lt, Operator::GtEq, true);
+        let conditions = IEJoinExec::range_conditions(&filter)?
+            .into_iter()
+            .map(|c| format!("{} {} {}", c.left, c.op, c.right))
+            .collect::<Vec<_>>();
+        // the second condition is swapped to have the left input first
+        assert_eq!(conditions, ["a1@0 < a2@0", "b1@1 <= b2@1"]);
+        Ok(())
+    }
+
+    #[test]
+    fn single_range_condition() {
+        let column_indices = vec![
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 0,
+                side: JoinSide::Right,
+            },
+        ];
+        let intermediate_schema = Schema::new(vec![
+            Field::new("a1", DataType::Int32, true),
+            Field::new("a2", DataType::Int32, true),
+        ]);
+        let expression = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("a1", 0)),
+            Operator::Lt,
+            Arc::new(Column::new("a2", 1)),
+        ));
+        let filter = JoinFilter::new(expression, column_indices, intermediate_schema);
+        let err = IEJoinExec::try_new(build_left_table(), build_right_table(), filter)
+            .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "IEJoinExec requires two range conditions between its inputs, got a1@0 < a2@1"
+        );
+    }
+}
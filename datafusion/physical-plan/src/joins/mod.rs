@@ -17,14 +17,20 @@
 
 //! DataFusion Join implementations
 
+pub use asof_join::{AsOfJoinExec, AsOfMatchCondition};
 pub use cross_join::CrossJoinExec;
+pub use dynamic_filter::DynamicFilter;
 pub use hash_join::HashJoinExec;
+pub use ie_join::{IEJoinExec, RangeCondition};
 pub use nested_loop_join::NestedLoopJoinExec;
 // Note: SortMergeJoin is not used in plans yet
 pub use sort_merge_join::SortMergeJoinExec;
 pub use symmetric_hash_join::SymmetricHashJoinExec;
+mod asof_join;
 mod cross_join;
+mod dynamic_filter;
 mod hash_join;
+mod ie_join;
 mod nested_loop_join;
 mod sort_merge_join;
 mod stream_join_utils;
@@ -37,6 +37,7 @@ use arrow::compute::{self, concat_batches, take, SortOptions};
 use arrow::datatypes::{DataType, SchemaRef, TimeUnit};
 use arrow::error::ArrowError;
 use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::IpcWriteOptions;
 use arrow_array::types::UInt64Type;
 use futures::{Stream, StreamExt};
 use hashbrown::HashSet;
@@ -59,7 +60,7 @@ use crate::joins::utils::{
     symmetric_join_output_partitioning, JoinFilter, JoinOn, JoinOnRef,
 };
 use crate::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
-use crate::spill::spill_record_batches;
+use crate::spill::{spill_record_batches, spill_write_options};
 use crate::{
     execution_mode_from_children, metrics, DisplayAs, DisplayFormatType, Distribution,
     ExecutionPlan, ExecutionPlanProperties, PhysicalExpr, PlanProperties,
@@ -368,6 +369,11 @@ impl ExecutionPlan for SortMergeJoinExec {
         let reservation = MemoryConsumer::new(format!("SMJStream[{partition}]"))
             .register(context.memory_pool());
 
+        let execution_options = &context.session_config().options().execution;
+        let spill_write_options =
+            spill_write_options(execution_options.spill_compression)?;
+        let max_temp_directory_size = execution_options.max_temp_directory_size;
+
         // create join stream
         Ok(Box::pin(SMJStream::try_new(
             Arc::clone(&self.schema),
@@ -383,6 +389,8 @@ impl ExecutionPlan for SortMergeJoinExec {
             SortMergeJoinMetrics::new(partition, &self.metrics),
             reservation,
             context.runtime_env(),
+            spill_write_options,
+            max_temp_directory_size,
         )?))
     }
 
@@ -593,7 +601,9 @@ struct BufferedBatch {
     pub join_arrays: Vec<ArrayRef>,
     /// Buffered joined index (null joining buffered)
     pub null_joined: Vec<usize>,
-    /// Size estimation used for reserving / releasing memory
+    /// Size estimation used for reserving / releasing memory.
+    /// Once the batch is spilled to disk, this only covers the parts
+    /// kept in memory, such as the join keys
     pub size_estimation: usize,
     /// The indices of buffered batch that failed the join filter.
     /// This is a map between buffered row index and a boolean value indicating whether all joined row
@@ -702,6 +712,11 @@ struct SMJStream {
     pub reservation: MemoryReservation,
     /// Runtime env
     pub runtime_env: Arc<RuntimeEnv>,
+    /// Options to write spill files with, e.g. their compression
+    pub spill_write_options: IpcWriteOptions,
+    /// The maximum disk space the spill files may use, if lower than the
+    /// limit of the `DiskManager`
+    pub max_temp_directory_size: Option<u64>,
 }
 
 impl RecordBatchStream for SMJStream {
@@ -822,6 +837,8 @@ impl SMJStream {
         join_metrics: SortMergeJoinMetrics,
         reservation: MemoryReservation,
         runtime_env: Arc<RuntimeEnv>,
+        spill_write_options: IpcWriteOptions,
+        max_temp_directory_size: Option<u64>,
     ) -> Result<Self> {
         let streamed_schema = streamed.schema();
         let buffered_schema = buffered.schema();
@@ -851,6 +868,8 @@ impl SMJStream {
             join_metrics,
             reservation,
             runtime_env,
+            spill_write_options,
+            max_temp_directory_size,
         })
     }
 
@@ -897,57 +916,80 @@ impl SMJStream {
     }
 
     fn free_reservation(&mut self, buffered_batch: BufferedBatch) -> Result<()> {
-        // Shrink memory usage for in-memory batches only
-        if buffered_batch.spill_file.is_none() && buffered_batch.batch.is_some() {
-            self.reservation
-                .try_shrink(buffered_batch.size_estimation)?;
-        }
+        self.reservation
+            .try_shrink(buffered_batch.size_estimation)?;
 
         Ok(())
     }
 
+    /// Reserves memory for a new buffered batch of the current key group.
+    ///
+    /// If the memory pool is exhausted, the in-memory batches already buffered
+    /// for the key group are spilled to disk first. If the new batch still
+    /// does not fit, it is spilled to disk as well. Spilled batches are read
+    /// back from disk one at a time whenever their rows are joined.
     fn allocate_reservation(&mut self, mut buffered_batch: BufferedBatch) -> Result<()> {
         match self.reservation.try_grow(buffered_batch.size_estimation) {
-            Ok(_) => {
-                self.join_metrics
-                    .peak_mem_used
-                    .set_max(self.reservation.size());
-                Ok(())
-            }
+            Ok(_) => {}
             Err(_) if self.runtime_env.disk_manager.tmp_files_enabled() => {
-                // spill buffered batch to disk
-                let spill_file = self
-                    .runtime_env
-                    .disk_manager
-                    .create_tmp_file("sort_merge_join_buffered_spill")?;
-
-                if let Some(batch) = buffered_batch.batch {
-                    spill_record_batches(
-                        vec![batch],
-                        spill_file.path().into(),
-                        Arc::clone(&self.buffered_schema),
-                    )?;
-                    buffered_batch.spill_file = Some(spill_file);
-                    buffered_batch.batch = None;
-
-                    // update metrics to register spill
-                    self.join_metrics.spill_count.add(1);
-                    self.join_metrics
-                        .spilled_bytes
-                        .add(buffered_batch.size_estimation);
-                    self.join_metrics.spilled_rows.add(buffered_batch.num_rows);
-                    Ok(())
-                } else {
-                    internal_err!("Buffered batch has empty body")
+                let mut batches = mem::take(&mut self.buffered_data.batches);
+                let spilled = batches
+                    .iter_mut()
+                    .filter(|batch| batch.spill_file.is_none())
+                    .try_for_each(|batch| self.spill_buffered_batch(batch));
+                self.buffered_data.batches = batches;
+                spilled?;
+
+                if self
+                    .reservation
+                    .try_grow(buffered_batch.size_estimation)
+                    .is_err()
+                {
+                    // Only the join keys of the spilled batch stay in memory
+                    self.reservation.grow(buffered_batch.size_estimation);
+                    self.spill_buffered_batch(&mut buffered_batch)?;
                 }
             }
-            Err(e) => exec_err!("{}. Disk spilling disabled.", e.message()),
-        }?;
+            Err(e) => return exec_err!("{}. Disk spilling disabled.", e.message()),
+        }
+        self.join_metrics
+            .peak_mem_used
+            .set_max(self.reservation.size());
 
         self.buffered_data.batches.push_back(buffered_batch);
         Ok(())
     }
 
+    /// Spills the record batch of an in-memory `buffered_batch` to disk and
+    /// releases its memory
+    fn spill_buffered_batch(&mut self, buffered_batch: &mut BufferedBatch) -> Result<()> {
+        let Some(batch) = buffered_batch.batch.take() else {
+            return internal_err!("Buffered batch has empty body");
+        };
+        let batch_size = batch.get_array_memory_size();
+
+        let mut spill_file = self
+            .runtime_env
+            .disk_manager
+            .create_tmp_file("sort_merge_join_buffered_spill")?
+            .with_max_temp_directory_size(self.max_temp_directory_size);
+        spill_record_batches(
+            vec![batch],
+            &mut spill_file,
+            Arc::clone(&self.buffered_schema),
+            self.spill_write_options.clone(),
+        )?;
+        buffered_batch.spill_file = Some(spill_file);
+        buffered_batch.size_estimation -= batch_size;
+        self.reservation.shrink(batch_size);
+
+        // update metrics to register spill
+        self.join_metrics.spill_count.add(1);
+        self.join_metrics.spilled_bytes.add(batch_size);
+        self.join_metrics.spilled_rows.add(buffered_batch.num_rows);
+        Ok(())
+    }
+
     /// Poll next buffered batches
     fn poll_buffered_batches(&mut self, cx: &mut Context) -> Poll<Option<Result<()>>> {
         loop {
@@ -1612,19 +1654,20 @@ fn get_buffered_columns_from_batch(
             .map_err(Into::<DataFusionError>::into)?),
         // If the batch was spilled to disk, less likely
         (Some(spill_file), None) => {
-            let mut buffered_cols: Vec<ArrayRef> =
-                Vec::with_capacity(buffered_indices.len());
-
             let file = BufReader::new(File::open(spill_file.path())?);
-            let reader = FileReader::try_new(file, None)?;
-
-            for batch in reader {
-                batch?.columns().iter().for_each(|column| {
-                    buffered_cols.extend(take(column, &buffered_indices, None))
-                });
-            }
+            let mut reader = FileReader::try_new(file, None)?;
+            let Some(batch) = reader.next().transpose()? else {
+                return internal_err!(
+                    "Spill file {:?} of buffered batch is empty",
+                    spill_file.path()
+                );
+            };
 
-            Ok(buffered_cols)
+            Ok(batch
+                .columns()
+                .iter()
+                .map(|column| take(column, &buffered_indices, None))
+                .collect::<Result<Vec<_>, ArrowError>>()?)
         }
         // Invalid combination
         (spill, batch) => internal_err!("Unexpected buffered batch spill status. Spill exists: {}. In-memory exists: {}", spill.is_some(), batch.is_some()),
@@ -3221,6 +3264,105 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn spill_skewed_key_group() -> Result<()> {
+        // A single join key spanning all buffered batches, followed by a
+        // second key group which must still be joined after the spill
+        let left_batches = (0..3)
+            .map(|i| {
+                build_table_i32(
+                    ("a1", &vec![i, i + 10]),
+                    ("b1", &vec![1, 1]),
+                    ("c1", &vec![i * 2, i * 3]),
+                )
+            })
+            .chain(std::iter::once(build_table_i32(
+                ("a1", &vec![100]),
+                ("b1", &vec![2]),
+                ("c1", &vec![200]),
+            )))
+            .collect::<Vec<_>>();
+        let right_batches = (0..10)
+            .map(|i| {
+                build_table_i32(
+                    ("a2", &vec![i, i + 1, i + 2, i + 3]),
+                    ("b2", &vec![1, 1, 1, 1]),
+                    ("c2", &vec![i * 10, i * 20, i * 30, i * 40]),
+                )
+            })
+            .chain(std::iter::once(build_table_i32(
+                ("a2", &vec![100, 101]),
+                ("b2", &vec![2, 3]),
+                ("c2", &vec![1000, 1010]),
+            )))
+            .collect::<Vec<_>>();
+        let left = build_table_from_batches(left_batches);
+        let right = build_table_from_batches(right_batches);
+        let on = vec![(
+            Arc::new(Column::new_with_schema("b1", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("b2", &right.schema())?) as _,
+        )];
+        let sort_options = vec![SortOptions::default(); on.len()];
+
+        let join_types = [
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+        ];
+
+        for join_type in &join_types {
+            let runtime = Arc::new(
+                RuntimeEnvBuilder::new()
+                    .with_memory_limit(1000, 1.0)
+                    .with_disk_manager(DiskManagerConfig::NewOs)
+                    .build()?,
+            );
+            let task_ctx = Arc::new(
+                TaskContext::default()
+                    .with_session_config(SessionConfig::default().with_batch_size(4))
+                    .with_runtime(Arc::clone(&runtime)),
+            );
+            let join = join_with_options(
+                Arc::clone(&left),
+                Arc::clone(&right),
+                on.clone(),
+                *join_type,
+                sort_options.clone(),
+                false,
+            )?;
+            let stream = join.execute(0, task_ctx)?;
+            let spilled_join_result = common::collect(stream).await?;
+
+            let metrics = join.metrics().unwrap();
+            assert!(metrics.spill_count().unwrap() > 0, "{join_type}");
+            assert!(metrics.spilled_rows().unwrap() > 0, "{join_type}");
+            assert_eq!(runtime.memory_pool.reserved(), 0, "{join_type}");
+
+            let task_ctx_no_spill = Arc::new(
+                TaskContext::default()
+                    .with_session_config(SessionConfig::default().with_batch_size(4)),
+            );
+            let join = join_with_options(
+                Arc::clone(&left),
+                Arc::clone(&right),
+                on.clone(),
+                *join_type,
+                sort_options.clone(),
+                false,
+            )?;
+            let stream = join.execute(0, task_ctx_no_spill)?;
+            let no_spilled_join_result = common::collect(stream).await?;
+            assert_eq!(join.metrics().unwrap().spill_count(), Some(0));
+
+            assert_eq!(spilled_join_result, no_spilled_join_result, "{join_type}");
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn left_semi_join_filtered_mask() -> Result<()> {
         assert_eq!(
@@ -0,0 +1,355 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the TABLESAMPLE plan
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::{
+    DisplayAs, ExecutionPlanProperties, PlanProperties, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use crate::{DisplayFormatType, ExecutionPlan};
+
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::stats::Precision;
+use datafusion_common::{internal_err, plan_err, Result};
+use datafusion_execution::TaskContext;
+use datafusion_expr::SampleMethod;
+
+use futures::stream::{Stream, StreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Returns a random sample of the rows of its input, see [`SampleMethod`].
+///
+/// Each partition is sampled independently. With a seed, the same input
+/// batches always produce the same sample.
+#[derive(Debug)]
+pub struct SampleExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// How the rows of the sample are chosen
+    method: SampleMethod,
+    /// The fraction of the input rows to return, between 0 and 1
+    fraction: f64,
+    /// Seed of the random number generator
+    seed: Option<u64>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+impl SampleExec {
+    /// Create a new SampleExec
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        method: SampleMethod,
+        fraction: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return plan_err!("Sample fraction must be between 0 and 1, got {fraction}");
+        }
+        let cache = Self::compute_properties(&input);
+        Ok(Self {
+            input,
+            method,
+            fraction,
+            seed,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// How the rows of the sample are chosen
+    pub fn method(&self) -> SampleMethod {
+        self.method
+    }
+
+    /// The fraction of the input rows to return
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Seed of the random number generator
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(input: &Arc<dyn ExecutionPlan>) -> PlanProperties {
+        PlanProperties::new(
+            input.equivalence_properties().clone(), // Equivalence Properties
+            input.output_partitioning().clone(),    // Output Partitioning
+            input.execution_mode(),                 // Execution Mode
+        )
+    }
+}
+
+impl DisplayAs for SampleExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "SampleExec: method={}, fraction={}, seed={}",
+                    self.method,
+                    self.fraction,
+                    self.seed
+                        .map_or_else(|| "None".to_string(), |x| x.to_string())
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SampleExec {
+    fn name(&self) -> &'static str {
+        "SampleExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        vec![&self.input]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        // Sampling is cheap, and repartitioning the input would make a seeded
+        // sample depend on how batches are distributed at runtime
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match children.len() {
+            1 => Ok(Arc::new(SampleExec::try_new(
+                Arc::clone(&children[0]),
+                self.method,
+                self.fraction,
+                self.seed,
+            )?)),
+            _ => internal_err!("SampleExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed.wrapping_add(partition as u64)),
+            None => StdRng::from_entropy(),
+        };
+        Ok(Box::pin(SampleStream {
+            input: self.input.execute(partition, context)?,
+            method: self.method,
+            fraction: self.fraction,
+            rng,
+            baseline_metrics,
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        let stats = self.input.statistics()?;
+        let fraction = self.fraction;
+        let scale =
+            |n: Precision<usize>| n.map(|n| (n as f64 * fraction).ceil() as usize);
+        Ok(Statistics {
+            num_rows: scale(stats.num_rows),
+            total_byte_size: scale(stats.total_byte_size),
+            column_statistics: stats.column_statistics,
+        }
+        .to_inexact())
+    }
+}
+
+/// Samples the batches of its input
+struct SampleStream {
+    input: SendableRecordBatchStream,
+    method: SampleMethod,
+    fraction: f64,
+    rng: StdRng,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl SampleStream {
+    /// Returns the sampled rows of `batch`, or `None` if no row is sampled
+    fn sample(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        // records time on drop
+        let _timer = self.baseline_metrics.elapsed_compute().timer();
+        let batch = match self.method {
+            SampleMethod::Bernoulli => {
+                let fraction = self.fraction;
+                let mask: BooleanArray = (0..batch.num_rows())
+                    .map(|_| Some(self.rng.gen_bool(fraction)))
+                    .collect();
+                filter_record_batch(&batch, &mask)?
+            }
+            SampleMethod::System => {
+                if !self.rng.gen_bool(self.fraction) {
+                    return Ok(None);
+                }
+                batch
+            }
+        };
+        Ok((batch.num_rows() > 0).then_some(batch))
+    }
+}
+
+impl Stream for SampleStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = loop {
+            match self.input.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(batch))) => match self.sample(batch) {
+                    Ok(Some(batch)) => break Poll::Ready(Some(Ok(batch))),
+                    // continue to poll input stream
+                    Ok(None) => {}
+                    Err(e) => break Poll::Ready(Some(Err(e))),
+                },
+                other => break other,
+            }
+        };
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for SampleStream {
+    /// Get the schema
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::collect;
+    use crate::memory::MemoryExec;
+    use crate::test::build_table_i32;
+
+    fn input(batches: usize) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(
+            ("a", &(0..1000).collect()),
+            ("b", &(0..1000).collect()),
+            ("c", &(0..1000).collect()),
+        );
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch; batches]], schema, None).unwrap())
+    }
+
+    async fn sampled_rows(sample: SampleExec) -> Result<Vec<i32>> {
+        let batches =
+            collect(sample.execute(0, Arc::new(TaskContext::default()))?).await?;
+        Ok(batches
+            .iter()
+            .flat_map(|batch| {
+                arrow::array::as_primitive_array::<arrow::datatypes::Int32Type>(
+                    batch.column(0),
+                )
+                .values()
+                .to_vec()
+            })
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn bernoulli_sample() -> Result<()> {
+        let sample =
+            SampleExec::try_new(input(1), SampleMethod::Bernoulli, 0.1, Some(42))?;
+        let rows = sampled_rows(sample).await?;
+        assert!(rows.len() > 50 && rows.len() < 150, "{} rows", rows.len());
+        // the input order is preserved
+        assert!(rows.windows(2).all(|w| w[0] < w[1]));
+
+        // the same seed returns the same sample
+        let sample =
+            SampleExec::try_new(input(1), SampleMethod::Bernoulli, 0.1, Some(42))?;
+        assert_eq!(sampled_rows(sample).await?, rows);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn system_sample() -> Result<()> {
+        let sample = SampleExec::try_new(input(20), SampleMethod::System, 0.5, Some(7))?;
+        let rows = sampled_rows(sample).await?;
+        // whole batches are returned
+        assert_eq!(rows.len() % 1000, 0);
+        assert!(rows.len() < 20_000);
+
+        let sample = SampleExec::try_new(input(20), SampleMethod::System, 0.0, None)?;
+        assert!(sampled_rows(sample).await?.is_empty());
+        let sample = SampleExec::try_new(input(2), SampleMethod::System, 1.0, None)?;
+        assert_eq!(sampled_rows(sample).await?.len(), 2000);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_fraction() {
+        let err = SampleExec::try_new(input(1), SampleMethod::Bernoulli, 1.5, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("between 0 and 1"), "{err}");
+    }
+
+    #[test]
+    fn sample_statistics() -> Result<()> {
+        let sample = SampleExec::try_new(input(2), SampleMethod::Bernoulli, 0.25, None)?;
+        assert_eq!(sample.statistics()?.num_rows, Precision::Inexact(500));
+        Ok(())
+    }
+}
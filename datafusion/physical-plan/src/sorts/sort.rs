@@ -31,7 +31,9 @@ use crate::metrics::{
     BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
 };
 use crate::sorts::streaming_merge::streaming_merge;
-use crate::spill::{read_spill_as_stream, spill_record_batches};
+use crate::spill::{
+    read_spill_as_stream_with_read_bytes, spill_record_batches, spill_write_options,
+};
 use crate::stream::RecordBatchStreamAdapter;
 use crate::topk::TopK;
 use crate::{
@@ -42,6 +44,7 @@ use crate::{
 
 use arrow::compute::{concat_batches, lexsort_to_indices, take, SortColumn};
 use arrow::datatypes::SchemaRef;
+use arrow::ipc::writer::IpcWriteOptions;
 use arrow::record_batch::RecordBatch;
 use arrow::row::{RowConverter, SortField};
 use arrow_array::{Array, RecordBatchOptions, UInt32Array};
@@ -69,6 +72,12 @@ struct ExternalSorterMetrics {
 
     /// total spilled rows during the execution of the operator
     spilled_rows: Count,
+
+    /// total size of the spill files written to disk, after compression
+    spill_file_bytes: Count,
+
+    /// total number of bytes read back from the spill files
+    spill_read_bytes: Count,
 }
 
 impl ExternalSorterMetrics {
@@ -78,6 +87,10 @@ impl ExternalSorterMetrics {
             spill_count: MetricBuilder::new(metrics).spill_count(partition),
             spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
             spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+            spill_file_bytes: MetricBuilder::new(metrics)
+                .counter("spill_file_bytes", partition),
+            spill_read_bytes: MetricBuilder::new(metrics)
+                .counter("spill_read_bytes", partition),
         }
     }
 }
@@ -233,6 +246,11 @@ struct ExternalSorter {
     /// the data will be concatenated and sorted in place rather than
     /// sort/merged.
     sort_in_place_threshold_bytes: usize,
+    /// Options to write spill files with, e.g. their compression
+    spill_write_options: IpcWriteOptions,
+    /// The maximum disk space the spill files may use, if lower than the
+    /// limit of the `DiskManager`
+    max_temp_directory_size: Option<u64>,
 }
 
 impl ExternalSorter {
@@ -247,6 +265,8 @@ impl ExternalSorter {
         fetch: Option<usize>,
         sort_spill_reservation_bytes: usize,
         sort_in_place_threshold_bytes: usize,
+        spill_write_options: IpcWriteOptions,
+        max_temp_directory_size: Option<u64>,
         metrics: &ExecutionPlanMetricsSet,
         runtime: Arc<RuntimeEnv>,
     ) -> Self {
@@ -273,6 +293,8 @@ impl ExternalSorter {
             batch_size,
             sort_spill_reservation_bytes,
             sort_in_place_threshold_bytes,
+            spill_write_options,
+            max_temp_directory_size,
         }
     }
 
@@ -337,7 +359,12 @@ impl ExternalSorter {
                 if !spill.path().exists() {
                     return internal_err!("Spill file {:?} does not exist", spill.path());
                 }
-                let stream = read_spill_as_stream(spill, Arc::clone(&self.schema), 2)?;
+                let stream = read_spill_as_stream_with_read_bytes(
+                    spill,
+                    Arc::clone(&self.schema),
+                    2,
+                    self.metrics.spill_read_bytes.clone(),
+                )?;
                 streams.push(stream);
             }
 
@@ -393,17 +420,25 @@ impl ExternalSorter {
 
         self.in_mem_sort().await?;
 
-        let spill_file = self.runtime.disk_manager.create_tmp_file("Sorting")?;
+        let mut spill_file = self
+            .runtime
+            .disk_manager
+            .create_tmp_file("Sorting")?
+            .with_max_temp_directory_size(self.max_temp_directory_size);
         let batches = std::mem::take(&mut self.in_mem_batches);
         let spilled_rows = spill_record_batches(
             batches,
-            spill_file.path().into(),
+            &mut spill_file,
             Arc::clone(&self.schema),
+            self.spill_write_options.clone(),
         )?;
         let used = self.reservation.free();
         self.metrics.spill_count.add(1);
         self.metrics.spilled_bytes.add(used);
         self.metrics.spilled_rows.add(spilled_rows);
+        self.metrics
+            .spill_file_bytes
+            .add(spill_file.current_disk_usage() as usize);
         self.spills.push(spill_file);
         Ok(used)
     }
@@ -938,6 +973,8 @@ impl ExecutionPlan for SortExec {
                     self.fetch,
                     execution_options.sort_spill_reservation_bytes,
                     execution_options.sort_in_place_threshold_bytes,
+                    spill_write_options(execution_options.spill_compression)?,
+                    execution_options.max_temp_directory_size,
                     &self.metrics_set,
                     context.runtime_env(),
                 );
@@ -1187,6 +1224,12 @@ mod tests {
         assert_eq!(metrics.spill_count().unwrap(), 4);
         assert_eq!(metrics.spilled_bytes().unwrap(), 38784);
         assert_eq!(metrics.spilled_rows().unwrap(), 9600);
+        let spill_file_bytes = metrics.sum_by_name("spill_file_bytes").unwrap();
+        assert!(spill_file_bytes.as_usize() > 0);
+        // the spill files are read back, except for the schema at their start
+        let spill_read_bytes = metrics.sum_by_name("spill_read_bytes").unwrap();
+        assert!(spill_read_bytes.as_usize() > spill_file_bytes.as_usize() / 2);
+        assert!(spill_read_bytes.as_usize() <= spill_file_bytes.as_usize());
 
         let columns = result[0].columns();
 
@@ -1203,6 +1246,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_spill_compressed() -> Result<()> {
+        let mut spill_file_bytes = vec![];
+        for compression in ["uncompressed", "lz4_frame", "zstd"] {
+            let session_config = SessionConfig::new()
+                .set_str("datafusion.execution.spill_compression", compression);
+            let sort_spill_reservation_bytes = session_config
+                .options()
+                .execution
+                .sort_spill_reservation_bytes;
+            let runtime = Arc::new(
+                RuntimeEnvBuilder::new()
+                    .with_memory_limit(sort_spill_reservation_bytes + 12288, 1.0)
+                    .build()?,
+            );
+            let task_ctx = Arc::new(
+                TaskContext::default()
+                    .with_session_config(session_config)
+                    .with_runtime(runtime),
+            );
+
+            let input = test::scan_partitioned(100);
+            let schema = input.schema();
+            let sort_exec = Arc::new(SortExec::new(
+                vec![PhysicalSortExpr {
+                    expr: col("i", &schema)?,
+                    options: SortOptions::default(),
+                }],
+                Arc::new(CoalescePartitionsExec::new(input)),
+            ));
+
+            let result = collect(
+                Arc::clone(&sort_exec) as Arc<dyn ExecutionPlan>,
+                Arc::clone(&task_ctx),
+            )
+            .await?;
+            assert_eq!(result.iter().map(|b| b.num_rows()).sum::<usize>(), 10000);
+
+            let metrics = sort_exec.metrics().unwrap();
+            assert_eq!(metrics.spill_count().unwrap(), 4);
+            spill_file_bytes
+                .push(metrics.sum_by_name("spill_file_bytes").unwrap().as_usize());
+        }
+
+        assert!(
+            spill_file_bytes[1] < spill_file_bytes[0],
+            "{spill_file_bytes:?}"
+        );
+        assert!(
+            spill_file_bytes[2] < spill_file_bytes[0],
+            "{spill_file_bytes:?}"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sort_fetch_memory_calculation() -> Result<()> {
         // This test mirrors down the size from the example above.
@@ -18,29 +18,44 @@
 //! Defines the spilling functions
 
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use arrow::datatypes::SchemaRef;
 use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::IpcWriteOptions;
 use arrow::record_batch::RecordBatch;
+use arrow_ipc::CompressionType;
 use log::debug;
 use tokio::sync::mpsc::Sender;
 
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{exec_datafusion_err, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::human_readable_size;
 use datafusion_execution::SendableRecordBatchStream;
 
 use crate::common::IPCWriter;
+use crate::metrics::Count;
 use crate::stream::RecordBatchReceiverStream;
 
+/// Returns the [`IpcWriteOptions`] to write spill files with, given the
+/// `datafusion.execution.spill_compression` setting
+pub fn spill_write_options(compression: SpillCompression) -> Result<IpcWriteOptions> {
+    let compression = match compression {
+        SpillCompression::Uncompressed => None,
+        SpillCompression::Lz4Frame => Some(CompressionType::LZ4_FRAME),
+        SpillCompression::Zstd => Some(CompressionType::ZSTD),
+    };
+    Ok(IpcWriteOptions::default().try_with_compression(compression)?)
+}
+
 /// Read spilled batches from the disk
 ///
 /// `path` - temp file
 /// `schema` - batches schema, should be the same across batches
 /// `buffer` - internal buffer of capacity batches
-pub(crate) fn read_spill_as_stream(
+pub fn read_spill_as_stream(
     path: RefCountedTempFile,
     schema: SchemaRef,
     buffer: usize,
@@ -53,19 +68,46 @@ pub(crate) fn read_spill_as_stream(
     Ok(builder.build())
 }
 
-/// Spills in-memory `batches` to disk.
+/// Read spilled batches from the disk like [`read_spill_as_stream`], adding
+/// the number of bytes read from the file to `read_bytes`
+pub fn read_spill_as_stream_with_read_bytes(
+    path: RefCountedTempFile,
+    schema: SchemaRef,
+    buffer: usize,
+    read_bytes: Count,
+) -> Result<SendableRecordBatchStream> {
+    let mut builder = RecordBatchReceiverStream::builder(schema, buffer);
+    let sender = builder.tx();
+
+    builder.spawn_blocking(move || {
+        let file = BufReader::new(File::open(path.path())?);
+        read_spill_from(sender, CountingReader::new(file, read_bytes))
+    });
+
+    Ok(builder.build())
+}
+
+/// Spills in-memory `batches` to `spill_file`, using `write_options` to
+/// e.g. compress the spill file.
+///
+/// The disk usage of the file is updated after each batch, failing once the
+/// maximum temporary directory size is exceeded.
 ///
 /// Returns total number of the rows spilled to disk.
-pub(crate) fn spill_record_batches(
+pub fn spill_record_batches(
     batches: Vec<RecordBatch>,
-    path: PathBuf,
+    spill_file: &mut RefCountedTempFile,
     schema: SchemaRef,
+    write_options: IpcWriteOptions,
 ) -> Result<usize> {
-    let mut writer = IPCWriter::new(path.as_ref(), schema.as_ref())?;
+    let mut writer =
+        IPCWriter::new_with_options(spill_file.path(), schema.as_ref(), write_options)?;
     for batch in batches {
         writer.write(&batch)?;
+        spill_file.update_disk_usage()?;
     }
     writer.finish()?;
+    spill_file.update_disk_usage()?;
     debug!(
         "Spilled {} batches of total {} rows to disk, memory released {}",
         writer.num_batches,
@@ -75,9 +117,18 @@ pub(crate) fn spill_record_batches(
     Ok(writer.num_rows)
 }
 
-fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
+/// Read the batches spilled to `path` and send them to `sender`
+pub fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
     let file = BufReader::new(File::open(path)?);
-    let reader = FileReader::try_new(file, None)?;
+    read_spill_from(sender, file)
+}
+
+/// Read the batches spilled to `reader` and send them to `sender`
+fn read_spill_from(
+    sender: Sender<Result<RecordBatch>>,
+    reader: impl Read + Seek,
+) -> Result<()> {
+    let reader = FileReader::try_new(reader, None)?;
     for batch in reader {
         sender
             .blocking_send(batch.map_err(Into::into))
@@ -86,6 +137,33 @@ fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// A reader adding the number of bytes read from its inner reader to a
+/// [`Count`]
+struct CountingReader<R> {
+    inner: R,
+    read_bytes: Count,
+}
+
+impl<R> CountingReader<R> {
+    fn new(inner: R, read_bytes: Count) -> Self {
+        Self { inner, read_bytes }
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.read_bytes.add(read);
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
 /// Spill the `RecordBatch` to disk as smaller batches
 /// split by `batch_size_rows`
 /// Return `total_rows` what is spilled
@@ -112,8 +190,13 @@ pub fn spill_record_batch_by_size(
 
 #[cfg(test)]
 mod tests {
-    use crate::spill::{spill_record_batch_by_size, spill_record_batches};
+    use crate::metrics::Count;
+    use crate::spill::{
+        read_spill_as_stream, read_spill_as_stream_with_read_bytes,
+        spill_record_batch_by_size, spill_record_batches, spill_write_options,
+    };
     use crate::test::build_table_i32;
+    use datafusion_common::config::SpillCompression;
     use datafusion_common::Result;
     use datafusion_execution::disk_manager::DiskManagerConfig;
     use datafusion_execution::DiskManager;
@@ -137,13 +220,14 @@ mod tests {
 
         let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
 
-        let spill_file = disk_manager.create_tmp_file("Test Spill")?;
+        let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
         let schema = batch1.schema();
         let num_rows = batch1.num_rows() + batch2.num_rows();
         let cnt = spill_record_batches(
             vec![batch1, batch2],
-            spill_file.path().into(),
+            &mut spill_file,
             Arc::clone(&schema),
+            spill_write_options(SpillCompression::Uncompressed)?,
         );
         assert_eq!(cnt.unwrap(), num_rows);
 
@@ -183,4 +267,117 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_compressed_batch_spill_and_read() -> Result<()> {
+        let batch = build_table_i32(
+            ("a2", &vec![0; 1000]),
+            ("b2", &(0..1000).collect::<Vec<_>>()),
+            ("c2", &vec![7; 1000]),
+        );
+        let schema = batch.schema();
+
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
+        let mut file_sizes = vec![];
+        for compression in [
+            SpillCompression::Uncompressed,
+            SpillCompression::Lz4Frame,
+            SpillCompression::Zstd,
+        ] {
+            let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
+            let num_rows = spill_record_batches(
+                vec![batch.clone()],
+                &mut spill_file,
+                Arc::clone(&schema),
+                spill_write_options(compression)?,
+            )?;
+            assert_eq!(num_rows, 1000);
+            file_sizes.push(spill_file.current_disk_usage());
+
+            let stream = read_spill_as_stream(spill_file, Arc::clone(&schema), 2)?;
+            let batches = crate::common::collect(stream).await?;
+            assert_eq!(batches, vec![batch.clone()]);
+        }
+
+        // compressed spill files are smaller than uncompressed ones
+        assert!(file_sizes[1] < file_sizes[0], "{file_sizes:?}");
+        assert!(file_sizes[2] < file_sizes[0], "{file_sizes:?}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spill_read_bytes() -> Result<()> {
+        let batch = build_table_i32(
+            ("a2", &(0..1000).collect::<Vec<_>>()),
+            ("b2", &(0..1000).collect::<Vec<_>>()),
+            ("c2", &(0..1000).collect::<Vec<_>>()),
+        );
+        let schema = batch.schema();
+
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
+        let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
+        spill_record_batches(
+            vec![batch.clone(), batch.clone()],
+            &mut spill_file,
+            Arc::clone(&schema),
+            spill_write_options(SpillCompression::Uncompressed)?,
+        )?;
+        let file_bytes = spill_file.current_disk_usage() as usize;
+
+        // nothing is read until the stream is polled
+        let read_bytes = Count::new();
+        let stream = read_spill_as_stream_with_read_bytes(
+            spill_file,
+            Arc::clone(&schema),
+            2,
+            read_bytes.clone(),
+        )?;
+        assert_eq!(read_bytes.value(), 0);
+
+        // the batches make up most of the file, the schema at its start is
+        // not read again
+        let batches = crate::common::collect(stream).await?;
+        assert_eq!(batches, vec![batch.clone(), batch]);
+        assert!(
+            read_bytes.value() > 2 * 3 * 1000 * 4,
+            "{}",
+            read_bytes.value()
+        );
+        assert!(read_bytes.value() <= file_bytes, "{}", read_bytes.value());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spill_exceeds_max_temp_directory_size() -> Result<()> {
+        let batch = build_table_i32(
+            ("a2", &(0..1000).collect::<Vec<_>>()),
+            ("b2", &(0..1000).collect::<Vec<_>>()),
+            ("c2", &(0..1000).collect::<Vec<_>>()),
+        );
+        let schema = batch.schema();
+
+        let disk_manager = DiskManager::try_new(DiskManagerConfig::NewOs)?;
+        disk_manager.set_max_temp_directory_size(20_000);
+        let mut spill_file = disk_manager.create_tmp_file("Test Spill")?;
+
+        // fails while writing the batches, not once the file is complete
+        let err = spill_record_batches(
+            vec![batch; 10],
+            &mut spill_file,
+            Arc::clone(&schema),
+            spill_write_options(SpillCompression::Uncompressed)?,
+        )
+        .unwrap_err();
+        assert!(
+            err.strip_backtrace().contains(
+                "The used disk space during the spilling process has exceeded the allowable limit of 20000 bytes"
+            ),
+            "{err}"
+        );
+        assert!(spill_file.current_disk_usage() < 40_000);
+
+        Ok(())
+    }
 }
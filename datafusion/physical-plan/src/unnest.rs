@@ -37,7 +37,7 @@ use arrow::compute::{cast, is_not_null, kernels, sum};
 use arrow::datatypes::{DataType, Int64Type, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use arrow_array::{Int64Array, Scalar, StructArray};
-use arrow_ord::cmp::lt;
+use arrow_ord::cmp::{eq, lt};
 use datafusion_common::{
     exec_datafusion_err, exec_err, internal_err, Result, UnnestOptions,
 };
@@ -345,6 +345,9 @@ fn flatten_struct_cols(
 /// - For struct columns: We will expand the struct columns into multiple subfield columns.
 ///
 /// For columns that don't need to be unnested, repeat their values until reaching the longest length.
+///
+/// If [`UnnestOptions::ordinality`] is set, a column with the 1-based position of every output
+/// row within the rows produced for the same input row is appended.
 fn build_batch(
     batch: &RecordBatch,
     schema: &SchemaRef,
@@ -353,7 +356,14 @@ fn build_batch(
     options: &UnnestOptions,
 ) -> Result<RecordBatch> {
     let transformed = match list_type_columns.len() {
-        0 => flatten_struct_cols(batch.columns(), schema, struct_column_indices),
+        0 => {
+            let mut columns = batch.columns().to_vec();
+            if options.ordinality {
+                // every input row produces exactly one output row
+                columns.push(Arc::new(Int64Array::from_value(1, batch.num_rows())));
+            }
+            flatten_struct_cols(&columns, schema, struct_column_indices)
+        }
         _ => {
             let list_arrays: Vec<ArrayRef> = list_type_columns
                 .iter()
@@ -389,11 +399,30 @@ fn build_batch(
             let take_indicies = create_take_indicies(unnested_length, total_length);
 
             // vertical expansion because of list unnest
-            let ret = flatten_list_cols_from_indices(
+            let mut ret = flatten_list_cols_from_indices(
                 batch,
                 &unnested_array_map,
                 &take_indicies,
             )?;
+            if options.ordinality {
+                // Rows produced for preserved null or empty lists have no position
+                let value_length =
+                    if options.preserve_nulls || options.preserve_empty_lists {
+                        Some(find_longest_length(
+                            &list_arrays,
+                            &UnnestOptions::new().with_preserve_nulls(false),
+                        )?)
+                    } else {
+                        None
+                    };
+                ret.push(Arc::new(create_ordinality_array(
+                    unnested_length,
+                    value_length
+                        .as_ref()
+                        .map(|length| length.as_primitive::<Int64Type>()),
+                    total_length,
+                )));
+            }
             flatten_struct_cols(&ret, schema, struct_column_indices)
         }
     };
@@ -432,12 +461,21 @@ fn find_longest_length(
     } else {
         Scalar::new(Int64Array::from_value(0, 1))
     };
+    let empty_length = Scalar::new(Int64Array::from_value(0, 1));
+    let preserved_empty_length = Scalar::new(Int64Array::from_value(1, 1));
     let list_lengths: Vec<ArrayRef> = list_arrays
         .iter()
         .map(|list_array| {
             let mut length_array = length(list_array)?;
             // Make sure length arrays have the same type. Int64 is the most general one.
             length_array = cast(&length_array, &DataType::Int64)?;
+            if options.preserve_empty_lists {
+                length_array = zip(
+                    &eq(&length_array, &empty_length)?,
+                    &preserved_empty_length,
+                    &length_array,
+                )?;
+            }
             length_array =
                 zip(&is_not_null(&length_array)?, &length_array, &null_length)?;
             Ok(length_array)
@@ -609,6 +647,45 @@ fn create_take_indicies(
     builder.finish()
 }
 
+/// Creates the 1-based positions of the unnested values within the rows produced for
+/// the same input row, according to the length array.
+///
+/// If `value_length_array` is given, rows produced for input rows without any values
+/// (preserved null or empty lists) get a null position.
+///
+/// If the length array looks like this:
+///
+/// ```ignore
+/// [2, 3, 1]
+/// ```
+/// Then `create_ordinality_array` will return an array like this
+///
+/// ```ignore
+/// [1, 2, 1, 2, 3, 1]
+/// ```
+///
+fn create_ordinality_array(
+    length_array: &PrimitiveArray<Int64Type>,
+    value_length_array: Option<&PrimitiveArray<Int64Type>>,
+    capacity: usize,
+) -> PrimitiveArray<Int64Type> {
+    // `find_longest_length()` guarantees this.
+    debug_assert!(
+        length_array.null_count() == 0,
+        "length array should not contain nulls"
+    );
+    let mut builder = PrimitiveArray::<Int64Type>::builder(capacity);
+    for (row, repeat) in length_array.values().iter().enumerate() {
+        match value_length_array {
+            Some(value_length) if value_length.value(row) == 0 => {
+                (0..*repeat).for_each(|_| builder.append_null())
+            }
+            _ => (1..=*repeat).for_each(|position| builder.append_value(position)),
+        }
+    }
+    builder.finish()
+}
+
 /// Create the final batch given the unnested column arrays and a `indices` array
 /// that is used by the take kernel to copy values.
 ///
@@ -796,7 +873,10 @@ mod tests {
         preserve_nulls: bool,
         expected: Vec<i64>,
     ) -> datafusion_common::Result<()> {
-        let options = UnnestOptions { preserve_nulls };
+        let options = UnnestOptions {
+            preserve_nulls,
+            ..Default::default()
+        };
         let longest_length = find_longest_length(list_arrays, &options)?;
         let expected_array = Int64Array::from(expected);
         assert_eq!(
@@ -841,6 +921,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_longest_list_length_preserve_empty_lists() -> datafusion_common::Result<()> {
+        //  [A, B, C], [], NULL, [D], NULL, [NULL, F]
+        let list_array = Arc::new(make_generic_array::<i32>()) as ArrayRef;
+        for (preserve_nulls, expected) in [
+            (false, vec![3, 1, 0, 1, 0, 2]),
+            (true, vec![3, 1, 1, 1, 1, 2]),
+        ] {
+            let options = UnnestOptions::new()
+                .with_preserve_nulls(preserve_nulls)
+                .with_preserve_empty_lists(true);
+            let longest_length =
+                find_longest_length(&[Arc::clone(&list_array)], &options)?;
+            assert_eq!(
+                longest_length.as_primitive::<Int64Type>(),
+                &Int64Array::from(expected)
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_create_take_indicies() -> datafusion_common::Result<()> {
         let length_array = Int64Array::from(vec![2, 3, 1]);
@@ -849,4 +950,59 @@ mod tests {
         assert_eq!(take_indicies, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_create_ordinality_array() -> datafusion_common::Result<()> {
+        let length_array = Int64Array::from(vec![2, 3, 0, 1]);
+        let ordinality = create_ordinality_array(&length_array, None, 6);
+        let expected = Int64Array::from(vec![1, 2, 1, 2, 3, 1]);
+        assert_eq!(ordinality, expected);
+
+        // preserved null or empty lists have no position
+        let length_array = Int64Array::from(vec![2, 1, 1]);
+        let value_length_array = Int64Array::from(vec![2, 0, 1]);
+        let ordinality =
+            create_ordinality_array(&length_array, Some(&value_length_array), 4);
+        let expected = Int64Array::from(vec![Some(1), Some(2), None, Some(1)]);
+        assert_eq!(ordinality, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_batch_with_ordinality() -> datafusion_common::Result<()> {
+        let list_array = Arc::new(make_generic_array::<i32>()) as ArrayRef;
+        let names = Arc::new(StringArray::from(vec!["a", "b", "c", "d", "e", "f"]));
+        let batch = RecordBatch::try_from_iter(vec![
+            ("list", list_array),
+            ("name", names as ArrayRef),
+        ])?;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("list", DataType::Utf8, true),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("ordinality", DataType::Int64, false),
+        ]));
+        let options = UnnestOptions::new()
+            .with_preserve_nulls(false)
+            .with_ordinality(true);
+        let result = build_batch(&batch, &schema, &[0], &HashSet::new(), &options)?;
+
+        // [A, B, C], [], NULL, [D], NULL, [NULL, F]
+        let expected = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec![
+                    Some("A"),
+                    Some("B"),
+                    Some("C"),
+                    Some("D"),
+                    None,
+                    Some("F"),
+                ])),
+                Arc::new(StringArray::from(vec!["a", "a", "a", "d", "f", "f"])),
+                Arc::new(Int64Array::from(vec![1, 2, 3, 1, 1, 2])),
+            ],
+        )?;
+        assert_eq!(result, expected);
+        Ok(())
+    }
 }
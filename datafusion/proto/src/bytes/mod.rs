@@ -24,7 +24,8 @@ use crate::physical_plan::{
     AsExecutionPlan, DefaultPhysicalExtensionCodec, PhysicalExtensionCodec,
 };
 use crate::protobuf;
-use datafusion_common::{plan_datafusion_err, Result};
+use datafusion_common::tree_node::TreeNode;
+use datafusion_common::{not_impl_err, plan_datafusion_err, Result};
 use datafusion_expr::{
     create_udaf, create_udf, create_udwf, AggregateUDF, Expr, LogicalPlan, Volatility,
     WindowUDF,
@@ -86,8 +87,23 @@ pub trait Serializeable: Sized {
     ) -> Result<Self>;
 }
 
+/// Subquery expressions are only serialized as part of a logical plan (see
+/// [`logical_plan_to_bytes`]), as decoding their plans requires a
+/// [`SessionContext`].
 impl Serializeable for Expr {
     fn to_bytes(&self) -> Result<Bytes> {
+        let has_subquery = self.exists(|expr| {
+            Ok(matches!(
+                expr,
+                Expr::ScalarSubquery(_) | Expr::Exists(_) | Expr::InSubquery(_)
+            ))
+        })?;
+        if has_subquery {
+            return not_impl_err!(
+                "Serializing subquery expressions outside of a logical plan, use logical_plan_to_bytes instead"
+            );
+        }
+
         let mut buffer = BytesMut::new();
         let extension_codec = DefaultLogicalExtensionCodec {};
         let protobuf: protobuf::LogicalExprNode = serialize_expr(self, &extension_codec)
@@ -18,6 +18,7 @@
 use std::sync::Arc;
 
 use datafusion::execution::registry::FunctionRegistry;
+use datafusion::prelude::SessionContext;
 use datafusion_common::{
     exec_datafusion_err, internal_err, plan_datafusion_err, Result, ScalarValue,
     TableReference, UnnestOptions,
@@ -27,7 +28,7 @@ use datafusion_expr::expr::{Unnest, WildcardOptions};
 use datafusion_expr::ExprFunctionExt;
 use datafusion_expr::{
     expr::{self, InList, Sort, WindowFunction},
-    logical_plan::{PlanType, StringifiedPlan},
+    logical_plan::{PlanType, StringifiedPlan, Subquery},
     Between, BinaryExpr, BuiltInWindowFunction, Case, Cast, Expr, GroupingSet,
     GroupingSet::GroupingSets,
     JoinConstraint, JoinType, Like, Operator, TryCast, WindowFrame, WindowFrameBound,
@@ -50,12 +51,14 @@ use crate::protobuf::{
     OptimizedPhysicalPlanType, PlaceholderNode, RollupNode,
 };
 
-use super::LogicalExtensionCodec;
+use super::{AsLogicalPlan, LogicalExtensionCodec};
 
 impl From<&protobuf::UnnestOptions> for UnnestOptions {
     fn from(opts: &protobuf::UnnestOptions) -> Self {
         Self {
             preserve_nulls: opts.preserve_nulls,
+            ordinality: opts.ordinality,
+            preserve_empty_lists: opts.preserve_empty_lists,
         }
     }
 }
@@ -224,10 +227,33 @@ impl From<protobuf::JoinConstraint> for JoinConstraint {
     }
 }
 
+/// Parse a `protobuf::LogicalExprNode`.
+///
+/// Subquery expressions can only be decoded as part of a logical plan, see
+/// [`AsLogicalPlan::try_into_logical_plan`].
 pub fn parse_expr(
     proto: &protobuf::LogicalExprNode,
     registry: &dyn FunctionRegistry,
     codec: &dyn LogicalExtensionCodec,
+) -> Result<Expr, Error> {
+    parse_expr_impl(proto, registry, None, codec)
+}
+
+/// Parse a `protobuf::LogicalExprNode` of a logical plan, using `ctx` to
+/// decode the plans of any subquery expressions.
+pub(crate) fn parse_plan_expr(
+    proto: &protobuf::LogicalExprNode,
+    ctx: &SessionContext,
+    codec: &dyn LogicalExtensionCodec,
+) -> Result<Expr, Error> {
+    parse_expr_impl(proto, ctx, Some(ctx), codec)
+}
+
+fn parse_expr_impl(
+    proto: &protobuf::LogicalExprNode,
+    registry: &dyn FunctionRegistry,
+    ctx: Option<&SessionContext>,
+    codec: &dyn LogicalExtensionCodec,
 ) -> Result<Expr, Error> {
     use protobuf::{logical_expr_node::ExprType, window_expr_node};
 
@@ -239,7 +265,7 @@ pub fn parse_expr(
     match expr_type {
         ExprType::BinaryExpr(binary_expr) => {
             let op = from_proto_binary_op(&binary_expr.op)?;
-            let operands = parse_exprs(&binary_expr.operands, registry, codec)?;
+            let operands = parse_exprs_impl(&binary_expr.operands, registry, ctx, codec)?;
 
             if operands.len() < 2 {
                 return Err(proto_error(
@@ -266,8 +292,9 @@ pub fn parse_expr(
                 .window_function
                 .as_ref()
                 .ok_or_else(|| Error::required("window_function"))?;
-            let partition_by = parse_exprs(&expr.partition_by, registry, codec)?;
-            let mut order_by = parse_exprs(&expr.order_by, registry, codec)?;
+            let partition_by =
+                parse_exprs_impl(&expr.partition_by, registry, ctx, codec)?;
+            let mut order_by = parse_exprs_impl(&expr.order_by, registry, ctx, codec)?;
             let window_frame = expr
                 .window_frame
                 .as_ref()
@@ -290,7 +317,7 @@ pub fn parse_expr(
                         .into();
 
                     let args =
-                        parse_optional_expr(expr.expr.as_deref(), registry, codec)?
+                        parse_optional_expr(expr.expr.as_deref(), registry, ctx, codec)?
                             .map(|e| vec![e])
                             .unwrap_or_else(Vec::new);
 
@@ -313,7 +340,7 @@ pub fn parse_expr(
                     };
 
                     let args =
-                        parse_optional_expr(expr.expr.as_deref(), registry, codec)?
+                        parse_optional_expr(expr.expr.as_deref(), registry, ctx, codec)?
                             .map(|e| vec![e])
                             .unwrap_or_else(Vec::new);
                     Expr::WindowFunction(WindowFunction::new(
@@ -333,7 +360,7 @@ pub fn parse_expr(
                     };
 
                     let args =
-                        parse_optional_expr(expr.expr.as_deref(), registry, codec)?
+                        parse_optional_expr(expr.expr.as_deref(), registry, ctx, codec)?
                             .map(|e| vec![e])
                             .unwrap_or_else(Vec::new);
                     Expr::WindowFunction(WindowFunction::new(
@@ -349,7 +376,7 @@ pub fn parse_expr(
             }
         }
         ExprType::Alias(alias) => Ok(Expr::Alias(Alias::new(
-            parse_required_expr(alias.expr.as_deref(), registry, "expr", codec)?,
+            parse_required_expr(alias.expr.as_deref(), registry, ctx, "expr", codec)?,
             alias
                 .relation
                 .first()
@@ -360,55 +387,69 @@ pub fn parse_expr(
         ExprType::IsNullExpr(is_null) => Ok(Expr::IsNull(Box::new(parse_required_expr(
             is_null.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
-        ExprType::IsNotNullExpr(is_not_null) => Ok(Expr::IsNotNull(Box::new(
-            parse_required_expr(is_not_null.expr.as_deref(), registry, "expr", codec)?,
-        ))),
+        ExprType::IsNotNullExpr(is_not_null) => {
+            Ok(Expr::IsNotNull(Box::new(parse_required_expr(
+                is_not_null.expr.as_deref(),
+                registry,
+                ctx,
+                "expr",
+                codec,
+            )?)))
+        }
         ExprType::NotExpr(not) => Ok(Expr::Not(Box::new(parse_required_expr(
             not.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
         ExprType::IsTrue(msg) => Ok(Expr::IsTrue(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
         ExprType::IsFalse(msg) => Ok(Expr::IsFalse(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
         ExprType::IsUnknown(msg) => Ok(Expr::IsUnknown(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
         ExprType::IsNotTrue(msg) => Ok(Expr::IsNotTrue(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
         ExprType::IsNotFalse(msg) => Ok(Expr::IsNotFalse(Box::new(parse_required_expr(
             msg.expr.as_deref(),
             registry,
+            ctx,
             "expr",
             codec,
         )?))),
         ExprType::IsNotUnknown(msg) => Ok(Expr::IsNotUnknown(Box::new(
-            parse_required_expr(msg.expr.as_deref(), registry, "expr", codec)?,
+            parse_required_expr(msg.expr.as_deref(), registry, ctx, "expr", codec)?,
         ))),
         ExprType::Between(between) => Ok(Expr::Between(Between::new(
             Box::new(parse_required_expr(
                 between.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
@@ -416,12 +457,14 @@ pub fn parse_expr(
             Box::new(parse_required_expr(
                 between.low.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
             Box::new(parse_required_expr(
                 between.high.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
@@ -431,12 +474,14 @@ pub fn parse_expr(
             Box::new(parse_required_expr(
                 like.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
             Box::new(parse_required_expr(
                 like.pattern.as_deref(),
                 registry,
+                ctx,
                 "pattern",
                 codec,
             )?),
@@ -448,12 +493,14 @@ pub fn parse_expr(
             Box::new(parse_required_expr(
                 like.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
             Box::new(parse_required_expr(
                 like.pattern.as_deref(),
                 registry,
+                ctx,
                 "pattern",
                 codec,
             )?),
@@ -465,12 +512,14 @@ pub fn parse_expr(
             Box::new(parse_required_expr(
                 like.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
             Box::new(parse_required_expr(
                 like.pattern.as_deref(),
                 registry,
+                ctx,
                 "pattern",
                 codec,
             )?),
@@ -485,12 +534,14 @@ pub fn parse_expr(
                     let when_expr = parse_required_expr(
                         e.when_expr.as_ref(),
                         registry,
+                        ctx,
                         "when_expr",
                         codec,
                     )?;
                     let then_expr = parse_required_expr(
                         e.then_expr.as_ref(),
                         registry,
+                        ctx,
                         "then_expr",
                         codec,
                     )?;
@@ -498,9 +549,10 @@ pub fn parse_expr(
                 })
                 .collect::<Result<Vec<(Box<Expr>, Box<Expr>)>, Error>>()?;
             Ok(Expr::Case(Case::new(
-                parse_optional_expr(case.expr.as_deref(), registry, codec)?.map(Box::new),
+                parse_optional_expr(case.expr.as_deref(), registry, ctx, codec)?
+                    .map(Box::new),
                 when_then_expr,
-                parse_optional_expr(case.else_expr.as_deref(), registry, codec)?
+                parse_optional_expr(case.else_expr.as_deref(), registry, ctx, codec)?
                     .map(Box::new),
             )))
         }
@@ -508,6 +560,7 @@ pub fn parse_expr(
             let expr = Box::new(parse_required_expr(
                 cast.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?);
@@ -518,6 +571,7 @@ pub fn parse_expr(
             let expr = Box::new(parse_required_expr(
                 cast.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?);
@@ -528,6 +582,7 @@ pub fn parse_expr(
             Box::new(parse_required_expr(
                 sort.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
@@ -535,10 +590,10 @@ pub fn parse_expr(
             sort.nulls_first,
         ))),
         ExprType::Negative(negative) => Ok(Expr::Negative(Box::new(
-            parse_required_expr(negative.expr.as_deref(), registry, "expr", codec)?,
+            parse_required_expr(negative.expr.as_deref(), registry, ctx, "expr", codec)?,
         ))),
         ExprType::Unnest(unnest) => {
-            let mut exprs = parse_exprs(&unnest.exprs, registry, codec)?;
+            let mut exprs = parse_exprs_impl(&unnest.exprs, registry, ctx, codec)?;
             if exprs.len() != 1 {
                 return Err(proto_error("Unnest must have exactly one expression"));
             }
@@ -548,10 +603,11 @@ pub fn parse_expr(
             Box::new(parse_required_expr(
                 in_list.expr.as_deref(),
                 registry,
+                ctx,
                 "expr",
                 codec,
             )?),
-            parse_exprs(&in_list.list, registry, codec)?,
+            parse_exprs_impl(&in_list.list, registry, ctx, codec)?,
             in_list.negated,
         ))),
         ExprType::Wildcard(protobuf::Wildcard { qualifier }) => {
@@ -572,7 +628,7 @@ pub fn parse_expr(
             };
             Ok(Expr::ScalarFunction(expr::ScalarFunction::new_udf(
                 scalar_fn,
-                parse_exprs(args, registry, codec)?,
+                parse_exprs_impl(args, registry, ctx, codec)?,
             )))
         }
         ExprType::AggregateUdfExpr(pb) => {
@@ -583,12 +639,13 @@ pub fn parse_expr(
 
             Ok(Expr::AggregateFunction(expr::AggregateFunction::new_udf(
                 agg_fn,
-                parse_exprs(&pb.args, registry, codec)?,
+                parse_exprs_impl(&pb.args, registry, ctx, codec)?,
                 pb.distinct,
-                parse_optional_expr(pb.filter.as_deref(), registry, codec)?.map(Box::new),
+                parse_optional_expr(pb.filter.as_deref(), registry, ctx, codec)?
+                    .map(Box::new),
                 match pb.order_by.len() {
                     0 => None,
-                    _ => Some(parse_exprs(&pb.order_by, registry, codec)?),
+                    _ => Some(parse_exprs_impl(&pb.order_by, registry, ctx, codec)?),
                 },
                 None,
             )))
@@ -597,15 +654,17 @@ pub fn parse_expr(
         ExprType::GroupingSet(GroupingSetNode { expr }) => {
             Ok(Expr::GroupingSet(GroupingSets(
                 expr.iter()
-                    .map(|expr_list| parse_exprs(&expr_list.expr, registry, codec))
+                    .map(|expr_list| {
+                        parse_exprs_impl(&expr_list.expr, registry, ctx, codec)
+                    })
                     .collect::<Result<Vec<_>, Error>>()?,
             )))
         }
         ExprType::Cube(CubeNode { expr }) => Ok(Expr::GroupingSet(GroupingSet::Cube(
-            parse_exprs(expr, registry, codec)?,
+            parse_exprs_impl(expr, registry, ctx, codec)?,
         ))),
         ExprType::Rollup(RollupNode { expr }) => Ok(Expr::GroupingSet(
-            GroupingSet::Rollup(parse_exprs(expr, registry, codec)?),
+            GroupingSet::Rollup(parse_exprs_impl(expr, registry, ctx, codec)?),
         )),
         ExprType::Placeholder(PlaceholderNode { id, data_type }) => match data_type {
             None => Ok(Expr::Placeholder(Placeholder::new(id.clone(), None))),
@@ -614,6 +673,46 @@ pub fn parse_expr(
                 Some(data_type.try_into()?),
             ))),
         },
+        ExprType::ScalarSubquery(subquery) => Ok(Expr::ScalarSubquery(parse_subquery(
+            subquery,
+            required_context(ctx)?,
+            codec,
+        )?)),
+        ExprType::Exists(exists) => {
+            let subquery = exists
+                .subquery
+                .as_deref()
+                .ok_or_else(|| Error::required("subquery"))?;
+            Ok(Expr::Exists(expr::Exists::new(
+                parse_subquery(subquery, required_context(ctx)?, codec)?,
+                exists.negated,
+            )))
+        }
+        ExprType::InSubquery(in_subquery) => {
+            let subquery = in_subquery
+                .subquery
+                .as_deref()
+                .ok_or_else(|| Error::required("subquery"))?;
+            Ok(Expr::InSubquery(expr::InSubquery::new(
+                Box::new(parse_required_expr(
+                    in_subquery.expr.as_deref(),
+                    registry,
+                    ctx,
+                    "expr",
+                    codec,
+                )?),
+                parse_subquery(subquery, required_context(ctx)?, codec)?,
+                in_subquery.negated,
+            )))
+        }
+        ExprType::OuterReferenceColumn(protobuf::OuterReferenceColumnNode {
+            data_type,
+            column,
+        }) => {
+            let data_type = data_type.as_ref().required("data_type")?;
+            let column = column.as_ref().ok_or_else(|| Error::required("column"))?;
+            Ok(Expr::OuterReferenceColumn(data_type, column.into()))
+        }
     }
 }
 
@@ -623,18 +722,69 @@ pub fn parse_exprs<'a, I>(
     registry: &dyn FunctionRegistry,
     codec: &dyn LogicalExtensionCodec,
 ) -> Result<Vec<Expr>, Error>
+where
+    I: IntoIterator<Item = &'a protobuf::LogicalExprNode>,
+{
+    parse_exprs_impl(protos, registry, None, codec)
+}
+
+/// Parse a vector of `protobuf::LogicalExprNode`s of a logical plan, see
+/// [`parse_plan_expr`].
+pub(crate) fn parse_plan_exprs<'a, I>(
+    protos: I,
+    ctx: &SessionContext,
+    codec: &dyn LogicalExtensionCodec,
+) -> Result<Vec<Expr>, Error>
+where
+    I: IntoIterator<Item = &'a protobuf::LogicalExprNode>,
+{
+    parse_exprs_impl(protos, ctx, Some(ctx), codec)
+}
+
+fn parse_exprs_impl<'a, I>(
+    protos: I,
+    registry: &dyn FunctionRegistry,
+    ctx: Option<&SessionContext>,
+    codec: &dyn LogicalExtensionCodec,
+) -> Result<Vec<Expr>, Error>
 where
     I: IntoIterator<Item = &'a protobuf::LogicalExprNode>,
 {
     let res = protos
         .into_iter()
         .map(|elem| {
-            parse_expr(elem, registry, codec).map_err(|e| plan_datafusion_err!("{}", e))
+            parse_expr_impl(elem, registry, ctx, codec)
+                .map_err(|e| plan_datafusion_err!("{}", e))
         })
         .collect::<Result<Vec<_>>>()?;
     Ok(res)
 }
 
+/// Parse a `protobuf::SubqueryNode`, decoding its plan with `ctx`.
+pub(crate) fn parse_subquery(
+    proto: &protobuf::SubqueryNode,
+    ctx: &SessionContext,
+    codec: &dyn LogicalExtensionCodec,
+) -> Result<Subquery, Error> {
+    let plan = proto
+        .subquery
+        .as_deref()
+        .ok_or_else(|| Error::required("subquery"))?
+        .try_into_logical_plan(ctx, codec)?;
+    Ok(Subquery {
+        subquery: Arc::new(plan),
+        outer_ref_columns: parse_plan_exprs(&proto.outer_ref_columns, ctx, codec)?,
+    })
+}
+
+fn required_context(ctx: Option<&SessionContext>) -> Result<&SessionContext, Error> {
+    ctx.ok_or_else(|| {
+        proto_error(
+            "Subquery expressions can only be deserialized as part of a logical plan",
+        )
+    })
+}
+
 /// Parse an optional escape_char for Like, ILike, SimilarTo
 fn parse_escape_char(s: &str) -> Result<Option<char>> {
     match s.len() {
@@ -682,10 +832,11 @@ pub fn from_proto_binary_op(op: &str) -> Result<Operator, Error> {
 fn parse_optional_expr(
     p: Option<&protobuf::LogicalExprNode>,
     registry: &dyn FunctionRegistry,
+    ctx: Option<&SessionContext>,
     codec: &dyn LogicalExtensionCodec,
 ) -> Result<Option<Expr>, Error> {
     match p {
-        Some(expr) => parse_expr(expr, registry, codec).map(Some),
+        Some(expr) => parse_expr_impl(expr, registry, ctx, codec).map(Some),
         None => Ok(None),
     }
 }
@@ -693,11 +844,12 @@ fn parse_optional_expr(
 fn parse_required_expr(
     p: Option<&protobuf::LogicalExprNode>,
     registry: &dyn FunctionRegistry,
+    ctx: Option<&SessionContext>,
     field: impl Into<String>,
     codec: &dyn LogicalExtensionCodec,
 ) -> Result<Expr, Error> {
     match p {
-        Some(expr) => parse_expr(expr, registry, codec),
+        Some(expr) => parse_expr_impl(expr, registry, ctx, codec),
         None => Err(Error::required(field)),
     }
 }
@@ -272,7 +272,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     values
                         .values_list
                         .chunks_exact(n_cols)
-                        .map(|r| from_proto::parse_exprs(r, ctx, extension_codec))
+                        .map(|r| from_proto::parse_plan_exprs(r, ctx, extension_codec))
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(|e| e.into())
                 }?;
@@ -282,7 +282,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let input: LogicalPlan =
                     into_logical_plan!(projection.input, ctx, extension_codec)?;
                 let expr: Vec<Expr> =
-                    from_proto::parse_exprs(&projection.expr, ctx, extension_codec)?;
+                    from_proto::parse_plan_exprs(&projection.expr, ctx, extension_codec)?;
 
                 let new_proj = project(input, expr)?;
                 match projection.optional_alias.as_ref() {
@@ -303,7 +303,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let expr: Expr = selection
                     .expr
                     .as_ref()
-                    .map(|expr| from_proto::parse_expr(expr, ctx, extension_codec))
+                    .map(|expr| from_proto::parse_plan_expr(expr, ctx, extension_codec))
                     .transpose()?
                     .ok_or_else(|| {
                         DataFusionError::Internal("expression required".to_string())
@@ -314,17 +314,26 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlanType::Window(window) => {
                 let input: LogicalPlan =
                     into_logical_plan!(window.input, ctx, extension_codec)?;
-                let window_expr =
-                    from_proto::parse_exprs(&window.window_expr, ctx, extension_codec)?;
+                let window_expr = from_proto::parse_plan_exprs(
+                    &window.window_expr,
+                    ctx,
+                    extension_codec,
+                )?;
                 LogicalPlanBuilder::from(input).window(window_expr)?.build()
             }
             LogicalPlanType::Aggregate(aggregate) => {
                 let input: LogicalPlan =
                     into_logical_plan!(aggregate.input, ctx, extension_codec)?;
-                let group_expr =
-                    from_proto::parse_exprs(&aggregate.group_expr, ctx, extension_codec)?;
-                let aggr_expr =
-                    from_proto::parse_exprs(&aggregate.aggr_expr, ctx, extension_codec)?;
+                let group_expr = from_proto::parse_plan_exprs(
+                    &aggregate.group_expr,
+                    ctx,
+                    extension_codec,
+                )?;
+                let aggr_expr = from_proto::parse_plan_exprs(
+                    &aggregate.aggr_expr,
+                    ctx,
+                    extension_codec,
+                )?;
                 LogicalPlanBuilder::from(input)
                     .aggregate(group_expr, aggr_expr)?
                     .build()
@@ -343,11 +352,11 @@ impl AsLogicalPlan for LogicalPlanNode {
                 }
 
                 let filters =
-                    from_proto::parse_exprs(&scan.filters, ctx, extension_codec)?;
+                    from_proto::parse_plan_exprs(&scan.filters, ctx, extension_codec)?;
 
                 let mut all_sort_orders = vec![];
                 for order in &scan.file_sort_order {
-                    all_sort_orders.push(from_proto::parse_exprs(
+                    all_sort_orders.push(from_proto::parse_plan_exprs(
                         &order.logical_expr_nodes,
                         ctx,
                         extension_codec,
@@ -453,7 +462,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 }
 
                 let filters =
-                    from_proto::parse_exprs(&scan.filters, ctx, extension_codec)?;
+                    from_proto::parse_plan_exprs(&scan.filters, ctx, extension_codec)?;
 
                 let table_name =
                     from_table_reference(scan.table_name.as_ref(), "CustomScan")?;
@@ -477,7 +486,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let input: LogicalPlan =
                     into_logical_plan!(sort.input, ctx, extension_codec)?;
                 let sort_expr: Vec<Expr> =
-                    from_proto::parse_exprs(&sort.expr, ctx, extension_codec)?;
+                    from_proto::parse_plan_exprs(&sort.expr, ctx, extension_codec)?;
                 LogicalPlanBuilder::from(input).sort(sort_expr)?.build()
             }
             LogicalPlanType::Repartition(repartition) => {
@@ -496,12 +505,19 @@ impl AsLogicalPlan for LogicalPlanNode {
                         hash_expr: pb_hash_expr,
                         partition_count,
                     }) => Partitioning::Hash(
-                        from_proto::parse_exprs(pb_hash_expr, ctx, extension_codec)?,
+                        from_proto::parse_plan_exprs(pb_hash_expr, ctx, extension_codec)?,
                         *partition_count as usize,
                     ),
                     PartitionMethod::RoundRobin(partition_count) => {
                         Partitioning::RoundRobinBatch(*partition_count as usize)
                     }
+                    PartitionMethod::DistributeBy(distribute_by) => {
+                        Partitioning::DistributeBy(from_proto::parse_plan_exprs(
+                            &distribute_by.logical_expr_nodes,
+                            ctx,
+                            extension_codec,
+                        )?)
+                    }
                 };
 
                 LogicalPlanBuilder::from(input)
@@ -536,7 +552,7 @@ impl AsLogicalPlan for LogicalPlanNode {
 
                 let mut order_exprs = vec![];
                 for expr in &create_extern_table.order_exprs {
-                    order_exprs.push(from_proto::parse_exprs(
+                    order_exprs.push(from_proto::parse_plan_exprs(
                         &expr.logical_expr_nodes,
                         ctx,
                         extension_codec,
@@ -546,7 +562,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let mut column_defaults =
                     HashMap::with_capacity(create_extern_table.column_defaults.len());
                 for (col_name, expr) in &create_extern_table.column_defaults {
-                    let expr = from_proto::parse_expr(expr, ctx, extension_codec)?;
+                    let expr = from_proto::parse_plan_expr(expr, ctx, extension_codec)?;
                     column_defaults.insert(col_name.clone(), expr);
                 }
 
@@ -635,6 +651,9 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .explain(explain.verbose, false)?
                     .build()
             }
+            LogicalPlanType::Subquery(subquery) => Ok(LogicalPlan::Subquery(
+                from_proto::parse_subquery(subquery, ctx, extension_codec)?,
+            )),
             LogicalPlanType::SubqueryAlias(aliased_relation) => {
                 let input: LogicalPlan =
                     into_logical_plan!(aliased_relation.input, ctx, extension_codec)?;
@@ -658,10 +677,16 @@ impl AsLogicalPlan for LogicalPlanNode {
                 LogicalPlanBuilder::from(input).limit(skip, fetch)?.build()
             }
             LogicalPlanType::Join(join) => {
-                let left_keys: Vec<Expr> =
-                    from_proto::parse_exprs(&join.left_join_key, ctx, extension_codec)?;
-                let right_keys: Vec<Expr> =
-                    from_proto::parse_exprs(&join.right_join_key, ctx, extension_codec)?;
+                let left_keys: Vec<Expr> = from_proto::parse_plan_exprs(
+                    &join.left_join_key,
+                    ctx,
+                    extension_codec,
+                )?;
+                let right_keys: Vec<Expr> = from_proto::parse_plan_exprs(
+                    &join.right_join_key,
+                    ctx,
+                    extension_codec,
+                )?;
                 let join_type =
                     protobuf::JoinType::try_from(join.join_type).map_err(|_| {
                         proto_error(format!(
@@ -681,7 +706,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let filter: Option<Expr> = join
                     .filter
                     .as_ref()
-                    .map(|expr| from_proto::parse_expr(expr, ctx, extension_codec))
+                    .map(|expr| from_proto::parse_plan_expr(expr, ctx, extension_codec))
                     .map_or(Ok(None), |v| v.map(Some))?;
 
                 let builder = LogicalPlanBuilder::from(into_logical_plan!(
@@ -763,16 +788,19 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlanType::DistinctOn(distinct_on) => {
                 let input: LogicalPlan =
                     into_logical_plan!(distinct_on.input, ctx, extension_codec)?;
-                let on_expr =
-                    from_proto::parse_exprs(&distinct_on.on_expr, ctx, extension_codec)?;
-                let select_expr = from_proto::parse_exprs(
+                let on_expr = from_proto::parse_plan_exprs(
+                    &distinct_on.on_expr,
+                    ctx,
+                    extension_codec,
+                )?;
+                let select_expr = from_proto::parse_plan_exprs(
                     &distinct_on.select_expr,
                     ctx,
                     extension_codec,
                 )?;
                 let sort_expr = match distinct_on.sort_expr.len() {
                     0 => None,
-                    _ => Some(from_proto::parse_exprs(
+                    _ => Some(from_proto::parse_plan_exprs(
                         &distinct_on.sort_expr,
                         ctx,
                         extension_codec,
@@ -1078,10 +1106,10 @@ impl AsLogicalPlan for LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Selection(Box::new(
                         protobuf::SelectionNode {
                             input: Some(Box::new(input)),
-                            expr: Some(serialize_expr(
+                            expr: Some(Box::new(serialize_expr(
                                 &filter.predicate,
                                 extension_codec,
-                            )?),
+                            )?)),
                         },
                     ))),
                 })
@@ -1201,7 +1229,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     join_constraint.to_owned().into();
                 let filter = filter
                     .as_ref()
-                    .map(|e| serialize_expr(e, extension_codec))
+                    .map(|e| serialize_expr(e, extension_codec).map(Box::new))
                     .map_or(Ok(None), |v| v.map(Some))?;
                 Ok(protobuf::LogicalPlanNode {
                     logical_plan_type: Some(LogicalPlanType::Join(Box::new(
@@ -1218,9 +1246,11 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
-            LogicalPlan::Subquery(_) => {
-                not_impl_err!("LogicalPlan serde is not yet implemented for subqueries")
-            }
+            LogicalPlan::Subquery(subquery) => Ok(protobuf::LogicalPlanNode {
+                logical_plan_type: Some(LogicalPlanType::Subquery(Box::new(
+                    to_proto::serialize_subquery(subquery, extension_codec)?,
+                ))),
+            }),
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, alias, .. }) => {
                 let input: protobuf::LogicalPlanNode =
                     protobuf::LogicalPlanNode::try_from_logical_plan(
@@ -1295,8 +1325,10 @@ impl AsLogicalPlan for LogicalPlanNode {
                     Partitioning::RoundRobinBatch(partition_count) => {
                         PartitionMethod::RoundRobin(*partition_count as u64)
                     }
-                    Partitioning::DistributeBy(_) => {
-                        return not_impl_err!("DistributeBy")
+                    Partitioning::DistributeBy(exprs) => {
+                        PartitionMethod::DistributeBy(protobuf::LogicalExprNodeCollection {
+                            logical_expr_nodes: serialize_exprs(exprs, extension_codec)?,
+                        })
                     }
                 };
 
@@ -1560,6 +1592,18 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for CreateMemoryTable",
             )),
+            LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for CreateMaterializedView",
+            )),
+            LogicalPlan::Ddl(DdlStatement::RefreshMaterializedView(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for RefreshMaterializedView",
+            )),
+            LogicalPlan::Ddl(DdlStatement::CacheTable(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for CacheTable",
+            )),
+            LogicalPlan::Ddl(DdlStatement::UncacheTable(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for UncacheTable",
+            )),
             LogicalPlan::Ddl(DdlStatement::CreateIndex(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for CreateIndex",
             )),
@@ -1626,6 +1670,12 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::RecursiveQuery(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for RecursiveQuery",
             )),
+            LogicalPlan::Sample(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for Sample",
+            )),
+            LogicalPlan::AsOfJoin(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for AsOfJoin",
+            )),
         }
     }
 }
@@ -26,8 +26,8 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::{
     logical_plan::PlanType, logical_plan::StringifiedPlan, BuiltInWindowFunction, Expr,
-    JoinConstraint, JoinType, TryCast, WindowFrame, WindowFrameBound, WindowFrameUnits,
-    WindowFunctionDefinition,
+    JoinConstraint, JoinType, Subquery, TryCast, WindowFrame, WindowFrameBound,
+    WindowFrameUnits, WindowFunctionDefinition,
 };
 
 use crate::protobuf::{
@@ -43,12 +43,14 @@ use crate::protobuf::{
     ToProtoError as Error,
 };
 
-use super::LogicalExtensionCodec;
+use super::{AsLogicalPlan, LogicalExtensionCodec};
 
 impl From<&UnnestOptions> for protobuf::UnnestOptions {
     fn from(opts: &UnnestOptions) -> Self {
         Self {
             preserve_nulls: opts.preserve_nulls,
+            ordinality: opts.ordinality,
+            preserve_empty_lists: opts.preserve_empty_lists,
         }
     }
 }
@@ -394,6 +396,11 @@ pub fn serialize_expr(
                 "Proto serialization error: Scalar Variable not supported".to_string(),
             ))
         }
+        Expr::Lambda(_) | Expr::LambdaVariable(_) => {
+            return Err(Error::General(
+                "Proto serialization error: Lambda not supported".to_string(),
+            ))
+        }
         Expr::ScalarFunction(ScalarFunction { func, args }) => {
             let mut buf = Vec::new();
             let _ = codec.try_encode_udf(func, &mut buf);
@@ -586,14 +593,42 @@ pub fn serialize_expr(
                 qualifier: qualifier.to_owned().map(|x| x.into()),
             })),
         },
-        Expr::ScalarSubquery(_)
-        | Expr::InSubquery(_)
-        | Expr::Exists { .. }
-        | Expr::OuterReferenceColumn { .. } => {
-            // we would need to add logical plan operators to datafusion.proto to support this
-            // see discussion in https://github.com/apache/datafusion/issues/2565
-            return Err(Error::General("Proto serialization error: Expr::ScalarSubquery(_) | Expr::InSubquery(_) | Expr::Exists { .. } | Exp:OuterReferenceColumn not supported".to_string()));
+        Expr::ScalarSubquery(subquery) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::ScalarSubquery(Box::new(serialize_subquery(
+                subquery, codec,
+            )?))),
+        },
+        Expr::Exists(expr::Exists { subquery, negated }) => {
+            let expr = Box::new(protobuf::ExistsNode {
+                subquery: Some(Box::new(serialize_subquery(subquery, codec)?)),
+                negated: *negated,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::Exists(expr)),
+            }
         }
+        Expr::InSubquery(expr::InSubquery {
+            expr,
+            subquery,
+            negated,
+        }) => {
+            let expr = Box::new(protobuf::InSubqueryNode {
+                expr: Some(Box::new(serialize_expr(expr.as_ref(), codec)?)),
+                subquery: Some(Box::new(serialize_subquery(subquery, codec)?)),
+                negated: *negated,
+            });
+            protobuf::LogicalExprNode {
+                expr_type: Some(ExprType::InSubquery(expr)),
+            }
+        }
+        Expr::OuterReferenceColumn(data_type, column) => protobuf::LogicalExprNode {
+            expr_type: Some(ExprType::OuterReferenceColumn(
+                protobuf::OuterReferenceColumnNode {
+                    data_type: Some(data_type.try_into()?),
+                    column: Some(column.into()),
+                },
+            )),
+        },
         Expr::GroupingSet(GroupingSet::Cube(exprs)) => protobuf::LogicalExprNode {
             expr_type: Some(ExprType::Cube(CubeNode {
                 expr: serialize_exprs(exprs, codec)?,
@@ -635,6 +670,20 @@ pub fn serialize_expr(
     Ok(expr_node)
 }
 
+/// Serialize a [`Subquery`], including its plan.
+pub(crate) fn serialize_subquery(
+    subquery: &Subquery,
+    codec: &dyn LogicalExtensionCodec,
+) -> Result<protobuf::SubqueryNode, Error> {
+    let plan =
+        protobuf::LogicalPlanNode::try_from_logical_plan(&subquery.subquery, codec)
+            .map_err(|e| Error::General(e.to_string()))?;
+    Ok(protobuf::SubqueryNode {
+        subquery: Some(Box::new(plan)),
+        outer_ref_columns: serialize_exprs(&subquery.outer_ref_columns, codec)?,
+    })
+}
+
 impl From<TableReference> for protobuf::TableReference {
     fn from(t: TableReference) -> Self {
         use protobuf::table_reference::TableReferenceEnum;
@@ -48,7 +48,8 @@ use datafusion::physical_plan::filter::FilterExec;
 use datafusion::physical_plan::insert::DataSinkExec;
 use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use datafusion::physical_plan::joins::{
-    CrossJoinExec, NestedLoopJoinExec, StreamJoinPartitionMode, SymmetricHashJoinExec,
+    CrossJoinExec, IEJoinExec, NestedLoopJoinExec, StreamJoinPartitionMode,
+    SymmetricHashJoinExec,
 };
 use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
 use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
@@ -981,6 +982,49 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                     &join_type.into(),
                 )?))
             }
+            PhysicalPlanType::IeJoin(join) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan(&join.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> =
+                    into_physical_plan(&join.right, registry, runtime, extension_codec)?;
+                let f = join
+                    .filter
+                    .as_ref()
+                    .ok_or_else(|| proto_error("Missing IEJoinExecNode filter"))?;
+                let schema = f
+                    .schema
+                    .as_ref()
+                    .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
+                    .try_into()?;
+                let expression = parse_physical_expr(
+                    f.expression.as_ref().ok_or_else(|| {
+                        proto_error("Unexpected empty filter expression")
+                    })?,
+                    registry,
+                    &schema,
+                    extension_codec,
+                )?;
+                let column_indices = f
+                    .column_indices
+                    .iter()
+                    .map(|i| {
+                        let side = protobuf::JoinSide::try_from(i.side).map_err(|_| {
+                            proto_error(format!(
+                                "Received an IEJoinExecNode message with JoinSide in Filter {}",
+                                i.side
+                            ))
+                        })?;
+
+                        Ok(ColumnIndex {
+                            index: i.index as usize,
+                            side: side.into(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let filter = JoinFilter::new(expression, column_indices, schema);
+
+                Ok(Arc::new(IEJoinExec::try_new(left, right, filter)?))
+            }
             PhysicalPlanType::Analyze(analyze) => {
                 let input: Arc<dyn ExecutionPlan> = into_physical_plan(
                     &analyze.input,
@@ -1797,6 +1841,47 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
             });
         }
 
+        if let Some(exec) = plan.downcast_ref::<IEJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+
+            let f = exec.filter();
+            let expression =
+                serialize_physical_expr(f.expression().to_owned(), extension_codec)?;
+            let column_indices = f
+                .column_indices()
+                .iter()
+                .map(|i| {
+                    let side: protobuf::JoinSide = i.side.to_owned().into();
+                    protobuf::ColumnIndex {
+                        index: i.index as u32,
+                        side: side.into(),
+                    }
+                })
+                .collect();
+            let filter = protobuf::JoinFilter {
+                expression: Some(expression),
+                column_indices,
+                schema: Some(f.schema().try_into()?),
+            };
+
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::IeJoin(Box::new(
+                    protobuf::IeJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        filter: Some(filter),
+                    },
+                ))),
+            });
+        }
+
         if let Some(exec) = plan.downcast_ref::<WindowAggExec>() {
             let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
                 exec.input().to_owned(),
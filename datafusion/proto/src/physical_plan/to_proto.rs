@@ -598,6 +598,10 @@ pub fn serialize_file_scan_config(
                 physical_sort_expr_nodes: e,
             })
             .collect::<Vec<_>>(),
+        sample: conf.sample.map(|sample| protobuf::FileSample {
+            fraction: sample.fraction,
+            seed: sample.seed,
+        }),
     })
 }
 
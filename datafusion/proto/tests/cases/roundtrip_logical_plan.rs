@@ -940,6 +940,48 @@ async fn roundtrip_logical_plan_with_view_scan() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn roundtrip_logical_plan_with_subqueries() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_csv("t1", "tests/testdata/test.csv", CsvReadOptions::default())
+        .await?;
+
+    let queries = [
+        "SELECT a, (SELECT max(b) FROM t1) AS max_b FROM t1",
+        "SELECT a FROM t1 WHERE b > (SELECT avg(t2.b) FROM t1 AS t2 WHERE t2.a = t1.a)",
+        "SELECT a FROM t1 WHERE EXISTS (SELECT 1 FROM t1 AS t2 WHERE t2.a = t1.a)",
+        "SELECT a FROM t1 WHERE NOT EXISTS (SELECT 1 FROM t1 AS t2 WHERE t2.b = t1.a)",
+        "SELECT a FROM t1 WHERE a IN (SELECT b FROM t1)",
+        "SELECT a FROM t1 WHERE a NOT IN (SELECT t2.b FROM t1 AS t2 WHERE t2.a = t1.b)",
+    ];
+    for query in queries {
+        // the optimizer decorrelates subqueries, so round trip the plans as planned
+        let plan = ctx.sql(query).await?.into_unoptimized_plan();
+        let bytes = logical_plan_to_bytes(&plan)?;
+        let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn roundtrip_logical_plan_distribute_by() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_csv("t1", "tests/testdata/test.csv", CsvReadOptions::default())
+        .await?;
+
+    let plan = ctx
+        .sql("SELECT a, b FROM t1 DISTRIBUTE BY a")
+        .await?
+        .into_optimized_plan()?;
+    let bytes = logical_plan_to_bytes(&plan)?;
+    let logical_round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+    assert_eq!(format!("{plan:?}"), format!("{logical_round_trip:?}"));
+
+    Ok(())
+}
+
 pub mod proto {
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct TopKPlanProto {
@@ -1975,6 +2017,14 @@ fn roundtrip_unnest() {
     roundtrip_expr_test(test_expr, ctx);
 }
 
+#[test]
+fn roundtrip_outer_reference_column() {
+    let test_expr = out_ref_col(DataType::Int64, "t1.a");
+
+    let ctx = SessionContext::new();
+    roundtrip_expr_test(test_expr, ctx);
+}
+
 #[test]
 fn roundtrip_wildcard() {
     let test_expr = Expr::Wildcard {
@@ -41,8 +41,8 @@ use datafusion::datasource::file_format::parquet::ParquetSink;
 use datafusion::datasource::listing::{ListingTableUrl, PartitionedFile};
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{
-    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileScanConfig,
-    FileSinkConfig, ParquetExec,
+    wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileSample,
+    FileScanConfig, FileSinkConfig, ParquetExec,
 };
 use datafusion::execution::FunctionRegistry;
 use datafusion::functions_aggregate::sum::sum_udaf;
@@ -61,8 +61,9 @@ use datafusion::physical_plan::expressions::{
 };
 use datafusion::physical_plan::filter::FilterExec;
 use datafusion::physical_plan::insert::DataSinkExec;
+use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use datafusion::physical_plan::joins::{
-    HashJoinExec, NestedLoopJoinExec, PartitionMode, StreamJoinPartitionMode,
+    HashJoinExec, IEJoinExec, NestedLoopJoinExec, PartitionMode, StreamJoinPartitionMode,
 };
 use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion::physical_plan::placeholder_row::PlaceholderRowExec;
@@ -82,7 +83,7 @@ use datafusion_common::file_options::csv_writer::CsvWriterOptions;
 use datafusion_common::file_options::json_writer::JsonWriterOptions;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::stats::Precision;
-use datafusion_common::{internal_err, not_impl_err, DataFusionError, Result};
+use datafusion_common::{internal_err, not_impl_err, DataFusionError, JoinSide, Result};
 use datafusion_expr::{
     Accumulator, AccumulatorFactoryFunction, AggregateUDF, ColumnarValue, ScalarUDF,
     Signature, SimpleAggregateUDF, WindowFrame, WindowFrameBound,
@@ -261,6 +262,63 @@ fn roundtrip_nested_loop_join() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn roundtrip_ie_join() -> Result<()> {
+    let schema_left = Arc::new(Schema::new(vec![
+        Field::new("a1", DataType::Int64, false),
+        Field::new("b1", DataType::Int64, false),
+    ]));
+    let schema_right = Arc::new(Schema::new(vec![
+        Field::new("a2", DataType::Int64, false),
+        Field::new("b2", DataType::Int64, false),
+    ]));
+    let column_indices = vec![
+        ColumnIndex {
+            index: 0,
+            side: JoinSide::Left,
+        },
+        ColumnIndex {
+            index: 0,
+            side: JoinSide::Right,
+        },
+        ColumnIndex {
+            index: 1,
+            side: JoinSide::Left,
+        },
+        ColumnIndex {
+            index: 1,
+            side: JoinSide::Right,
+        },
+    ];
+    let intermediate_schema = Schema::new(vec![
+        Field::new("a1", DataType::Int64, false),
+        Field::new("a2", DataType::Int64, false),
+        Field::new("b1", DataType::Int64, false),
+        Field::new("b2", DataType::Int64, false),
+    ]);
+    // a1 < a2 AND b1 > b2
+    let expression = Arc::new(BinaryExpr::new(
+        Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("a1", 0)),
+            Operator::Lt,
+            Arc::new(Column::new("a2", 1)),
+        )),
+        Operator::And,
+        Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("b1", 2)),
+            Operator::Gt,
+            Arc::new(Column::new("b2", 3)),
+        )),
+    ));
+    let filter = JoinFilter::new(expression, column_indices, intermediate_schema);
+
+    roundtrip_test(Arc::new(IEJoinExec::try_new(
+        Arc::new(EmptyExec::new(schema_left)),
+        Arc::new(EmptyExec::new(schema_right)),
+        filter,
+    )?))
+}
+
 #[test]
 fn roundtrip_window() -> Result<()> {
     let field_a = Field::new("a", DataType::Int64, false);
@@ -669,6 +727,7 @@ fn roundtrip_parquet_exec_with_pruning_predicate() -> Result<()> {
         limit: None,
         table_partition_cols: vec![],
         output_ordering: vec![],
+        sample: None,
     };
 
     let predicate = Arc::new(BinaryExpr::new(
@@ -704,11 +763,37 @@ async fn roundtrip_parquet_exec_with_table_partition_cols() -> Result<()> {
             false,
         )],
         output_ordering: vec![],
+        sample: None,
     };
 
     roundtrip_test(ParquetExec::builder(scan_config).build_arc())
 }
 
+#[test]
+fn roundtrip_parquet_exec_with_sample() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("col", DataType::Utf8, false)]));
+    let scan_config = FileScanConfig::new(ObjectStoreUrl::local_filesystem(), schema)
+        .with_file(PartitionedFile::new(
+            "/path/to/file.parquet".to_string(),
+            1024,
+        ))
+        .with_sample(Some(FileSample::new(0.25, Some(42))));
+
+    let ctx = SessionContext::new();
+    let codec = DefaultPhysicalExtensionCodec {};
+    let plan = roundtrip_test_and_return(
+        ParquetExec::builder(scan_config).build_arc(),
+        &ctx,
+        &codec,
+    )?;
+    let parquet = plan.as_any().downcast_ref::<ParquetExec>().unwrap();
+    assert_eq!(
+        parquet.base_config().sample,
+        Some(FileSample::new(0.25, Some(42)))
+    );
+    Ok(())
+}
+
 #[test]
 fn roundtrip_parquet_exec_with_custom_predicate_expr() -> Result<()> {
     let scan_config = FileScanConfig {
@@ -733,6 +818,7 @@ fn roundtrip_parquet_exec_with_custom_predicate_expr() -> Result<()> {
         limit: None,
         table_partition_cols: vec![],
         output_ordering: vec![],
+        sample: None,
     };
 
     #[derive(Debug, Hash, Clone)]
@@ -22,7 +22,7 @@ use arrow::datatypes::DataType;
 
 use datafusion::execution::FunctionRegistry;
 use datafusion::prelude::SessionContext;
-use datafusion_expr::{col, create_udf, lit, ColumnarValue};
+use datafusion_expr::{col, create_udf, exists, lit, ColumnarValue, LogicalPlanBuilder};
 use datafusion_expr::{Expr, Volatility};
 use datafusion_functions::string;
 use datafusion_proto::bytes::Serializeable;
@@ -98,6 +98,19 @@ fn udf_roundtrip_without_registry() {
     Expr::from_bytes(&bytes).unwrap();
 }
 
+#[test]
+fn subquery_expr_to_bytes() {
+    let subquery = LogicalPlanBuilder::empty(false).build().unwrap();
+    let expr = col("a").and(exists(Arc::new(subquery)));
+
+    let err = expr.to_bytes().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Serializing subquery expressions outside of a logical plan"),
+        "{err}"
+    );
+}
+
 fn roundtrip_expr(expr: &Expr) -> Expr {
     let bytes = expr.to_bytes().unwrap();
     Expr::from_bytes(&bytes).unwrap()
@@ -19,8 +19,8 @@ use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
 use arrow_schema::DataType;
 use datafusion_common::{
-    internal_datafusion_err, not_impl_err, plan_datafusion_err, plan_err, DFSchema,
-    Dependency, Result,
+    internal_datafusion_err, internal_err, not_impl_err, plan_datafusion_err, plan_err,
+    DFSchema, DataFusionError, Dependency, Result,
 };
 use datafusion_expr::expr::WildcardOptions;
 use datafusion_expr::planner::PlannerResult;
@@ -28,14 +28,16 @@ use datafusion_expr::{
     expr, Expr, ExprFunctionExt, ExprSchemable, WindowFrame, WindowFunctionDefinition,
 };
 use datafusion_expr::{
-    expr::{ScalarFunction, Unnest},
-    BuiltInWindowFunction,
+    expr::{Lambda, ScalarFunction, Unnest},
+    BuiltInWindowFunction, ScalarUDF,
 };
 use sqlparser::ast::{
-    DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction, FunctionArg,
-    FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList, FunctionArguments,
-    NullTreatment, ObjectName, OrderByExpr, WindowType,
+    BinaryOperator, DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction,
+    FunctionArg, FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList,
+    FunctionArguments, LambdaFunction as SQLLambdaFunction, NullTreatment, ObjectName,
+    OneOrManyWithParens, OrderByExpr, WindowType,
 };
+use std::collections::HashMap;
 use strum::IntoEnumIterator;
 
 /// Suggest a valid function based on an invalid input function name
@@ -80,6 +82,63 @@ fn find_closest_match(candidates: Vec<String>, target: &str) -> String {
         .expect("No candidates provided.") // Panic if `candidates` argument is empty
 }
 
+/// Returns the lambda function passed as `arg`, if any
+fn lambda_arg(arg: &FunctionArg) -> Option<&SQLLambdaFunction> {
+    match arg {
+        FunctionArg::Named {
+            arg: FunctionArgExpr::Expr(SQLExpr::Lambda(lambda)),
+            ..
+        }
+        | FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Lambda(lambda))) => {
+            Some(lambda)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the lambda function `x -> body` or `(x, y) -> body` passed as
+/// `arg` in dialects that don't parse lambda functions, such as the generic
+/// dialect, where it is parsed as the binary operator `->`
+fn arrow_lambda_arg(arg: &FunctionArg) -> Option<SQLLambdaFunction> {
+    let (FunctionArg::Named {
+        arg: FunctionArgExpr::Expr(expr),
+        ..
+    }
+    | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))) = arg
+    else {
+        return None;
+    };
+    let SQLExpr::BinaryOp {
+        left,
+        op: BinaryOperator::Arrow,
+        right,
+    } = expr
+    else {
+        return None;
+    };
+    let params = match left.as_ref() {
+        SQLExpr::Identifier(param) => OneOrManyWithParens::One(param.clone()),
+        SQLExpr::Nested(param) => match param.as_ref() {
+            SQLExpr::Identifier(param) => OneOrManyWithParens::Many(vec![param.clone()]),
+            _ => return None,
+        },
+        SQLExpr::Tuple(params) => OneOrManyWithParens::Many(
+            params
+                .iter()
+                .map(|param| match param {
+                    SQLExpr::Identifier(param) => Some(param.clone()),
+                    _ => None,
+                })
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    };
+    Some(SQLLambdaFunction {
+        params,
+        body: right.clone(),
+    })
+}
+
 /// Arguments to for a function call extracted from the SQL AST
 #[derive(Debug)]
 struct FunctionArgs {
@@ -239,7 +298,19 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         // user-defined function (UDF) should have precedence
         if let Some(fm) = self.context_provider.get_function_meta(&name) {
-            let args = self.function_args_to_expr(args, schema, planner_context)?;
+            let args = if args
+                .iter()
+                .any(|arg| lambda_arg(arg).is_some() || arrow_lambda_arg(arg).is_some())
+            {
+                self.higher_order_function_args_to_expr(
+                    &fm,
+                    args,
+                    schema,
+                    planner_context,
+                )?
+            } else {
+                self.function_args_to_expr(args, schema, planner_context)?
+            };
             return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(fm, args)));
         }
 
@@ -448,6 +519,146 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .collect::<Result<Vec<Expr>>>()
     }
 
+    /// Plans the arguments of a higher-order function, some of which are
+    /// lambda functions.
+    ///
+    /// The other arguments are planned first, as their types determine the
+    /// types of the lambda parameters (see [`ScalarUDF::lambda_parameter_types`]).
+    ///
+    /// Arguments parsed as the binary operator `->` (see [`arrow_lambda_arg`])
+    /// are only planned as lambda functions if `func` accepts them, as `->`
+    /// is also used to access JSON values.
+    fn higher_order_function_args_to_expr(
+        &self,
+        func: &ScalarUDF,
+        args: Vec<FunctionArg>,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Vec<Expr>> {
+        let mut exprs = Vec::with_capacity(args.len());
+        let mut lambdas = vec![];
+        let mut arrow_args = vec![];
+        for arg in args {
+            if let Some(lambda) = lambda_arg(&arg) {
+                lambdas.push(lambda.clone());
+                exprs.push(None);
+            } else if let Some(lambda) = arrow_lambda_arg(&arg) {
+                lambdas.push(lambda);
+                arrow_args.push(arg);
+                exprs.push(None);
+            } else {
+                exprs.push(Some(self.sql_fn_arg_to_logical_expr(
+                    arg,
+                    schema,
+                    planner_context,
+                )?));
+            }
+        }
+
+        let arg_types = exprs
+            .iter()
+            .flatten()
+            .map(|e| e.get_type(schema))
+            .collect::<Result<Vec<_>>>()?;
+        let param_types = match func.lambda_parameter_types(&arg_types) {
+            Ok(param_types) => param_types,
+            // Only `->` operators were passed to a function without lambdas
+            Err(e)
+                if matches!(e.find_root(), DataFusionError::NotImplemented(_))
+                    && arrow_args.len() == lambdas.len() =>
+            {
+                let mut arrow_args = arrow_args.into_iter();
+                return exprs
+                    .into_iter()
+                    .map(|expr| match (expr, arrow_args.next()) {
+                        (Some(expr), _) => Ok(expr),
+                        (None, Some(arg)) => {
+                            self.sql_fn_arg_to_logical_expr(arg, schema, planner_context)
+                        }
+                        (None, None) => internal_err!("Missing operator argument"),
+                    })
+                    .collect();
+            }
+            Err(e) => return Err(e),
+        };
+        if param_types.len() != lambdas.len() {
+            return plan_err!(
+                "Function {} expects {} lambda arguments, got {}",
+                func.name(),
+                param_types.len(),
+                lambdas.len()
+            );
+        }
+
+        let mut lambdas = lambdas
+            .into_iter()
+            .zip(param_types)
+            .map(|(lambda, types)| {
+                self.sql_lambda_to_expr(lambda, types, planner_context)
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter();
+        exprs
+            .into_iter()
+            .map(|expr| expr.or_else(|| lambdas.next()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| internal_datafusion_err!("Missing planned lambda argument"))
+    }
+
+    /// Plans a lambda function whose parameters have the types `param_types`.
+    ///
+    /// The body of the lambda can only reference the lambda parameters: the
+    /// columns of the input are not captured, so the body is planned against
+    /// an empty schema.
+    fn sql_lambda_to_expr(
+        &self,
+        lambda: SQLLambdaFunction,
+        param_types: Vec<DataType>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let params = match lambda.params {
+            OneOrManyWithParens::One(param) => vec![param],
+            OneOrManyWithParens::Many(params) => params,
+        }
+        .into_iter()
+        .map(|param| self.ident_normalizer.normalize(param))
+        .collect::<Vec<_>>();
+        if params.len() != param_types.len() {
+            return plan_err!(
+                "Lambda function expects {} parameters, got {}",
+                param_types.len(),
+                params.len()
+            );
+        }
+        let lambda_parameters = params
+            .iter()
+            .cloned()
+            .zip(param_types)
+            .collect::<HashMap<_, _>>();
+        if lambda_parameters.len() != params.len() {
+            return plan_err!("Duplicate lambda parameter in {params:?}");
+        }
+
+        let outer_lambda_parameters =
+            planner_context.set_lambda_parameters(lambda_parameters);
+        let outer_query_schema = planner_context.set_outer_query_schema(None);
+        let body = self.sql_expr_to_logical_expr(
+            *lambda.body,
+            &DFSchema::empty(),
+            planner_context,
+        );
+        planner_context.set_outer_query_schema(outer_query_schema);
+        planner_context.set_lambda_parameters(outer_lambda_parameters);
+        let body = body?;
+
+        if let Some(column) = body.column_refs().into_iter().next() {
+            return plan_err!(
+                "Lambda function body can only reference the lambda parameters, found {column}"
+            );
+        }
+        Ok(Expr::Lambda(Lambda::new(params, body)))
+    }
+
     pub(crate) fn check_unnest_arg(arg: &Expr, schema: &DFSchema) -> Result<()> {
         // Check argument type, array types are supported
         match arg.get_type(schema)? {
@@ -22,6 +22,7 @@ use datafusion_common::{
     internal_err, not_impl_err, plan_datafusion_err, Column, DFSchema, DataFusionError,
     Result, TableReference,
 };
+use datafusion_expr::expr::LambdaVariable;
 use datafusion_expr::planner::PlannerResult;
 use datafusion_expr::{Case, Expr};
 
@@ -52,6 +53,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             // identifier. (e.g. it is "foo.bar" not foo.bar)
             let normalize_ident = self.ident_normalizer.normalize(id);
 
+            // Check for a parameter of the lambda being planned
+            if let Some(data_type) =
+                planner_context.lambda_parameter_type(normalize_ident.as_str())
+            {
+                return Ok(Expr::LambdaVariable(LambdaVariable::new(
+                    normalize_ident,
+                    data_type.clone(),
+                )));
+            }
+
             // Check for qualified field with unqualified name
             if let Ok((qualifier, _)) =
                 schema.qualified_field_with_unqualified_name(normalize_ident.as_str())
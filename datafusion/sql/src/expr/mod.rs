@@ -670,6 +670,9 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 options: WildcardOptions::default(),
             }),
             SQLExpr::Tuple(values) => self.parse_tuple(schema, planner_context, values),
+            SQLExpr::Lambda(_) => plan_err!(
+                "Lambda functions are only supported as arguments of higher-order functions"
+            ),
             _ => not_impl_err!("Unsupported ast node in sqltorel: {sql:?}"),
         }
     }
@@ -214,6 +214,25 @@ impl fmt::Display for CreateExternalTable {
     }
 }
 
+/// DataFusion extension DDL for `REFRESH MATERIALIZED VIEW`
+///
+/// # Syntax:
+///
+/// ```text
+/// REFRESH MATERIALIZED VIEW <view_name>
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshMaterializedViewStatement {
+    /// View name
+    pub name: ObjectName,
+}
+
+impl fmt::Display for RefreshMaterializedViewStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REFRESH MATERIALIZED VIEW {}", self.name)
+    }
+}
+
 /// DataFusion SQL Statement.
 ///
 /// This can either be a [`Statement`] from [`sqlparser`] from a
@@ -231,6 +250,8 @@ pub enum Statement {
     CopyTo(CopyToStatement),
     /// EXPLAIN for extensions
     Explain(ExplainStatement),
+    /// Extension: `REFRESH MATERIALIZED VIEW`
+    RefreshMaterializedView(RefreshMaterializedViewStatement),
 }
 
 impl fmt::Display for Statement {
@@ -240,6 +261,7 @@ impl fmt::Display for Statement {
             Statement::CreateExternalTable(stmt) => write!(f, "{stmt}"),
             Statement::CopyTo(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
+            Statement::RefreshMaterializedView(stmt) => write!(f, "{stmt}"),
         }
     }
 }
@@ -280,7 +302,7 @@ impl<'a> DFParser<'a> {
         dialect: &'a dyn Dialect,
     ) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = rewrite_table_sample(&tokenizer.tokenize()?)?;
 
         Ok(DFParser {
             parser: Parser::new(dialect).with_tokens(tokens),
@@ -358,6 +380,11 @@ impl<'a> DFParser<'a> {
                         self.parser.next_token(); // EXPLAIN
                         self.parse_explain()
                     }
+                    // REFRESH is not a keyword of sqlparser
+                    Keyword::NoKeyword if w.value.eq_ignore_ascii_case("refresh") => {
+                        self.parser.next_token(); // REFRESH
+                        self.parse_refresh()
+                    }
                     _ => {
                         // use sqlparser-rs parser
                         Ok(Statement::Statement(Box::from(
@@ -537,6 +564,16 @@ impl<'a> DFParser<'a> {
         }))
     }
 
+    /// Parse a SQL `REFRESH MATERIALIZED VIEW` statement
+    pub fn parse_refresh(&mut self) -> Result<Statement, ParserError> {
+        self.parser
+            .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+        let name = self.parser.parse_object_name(false)?;
+        Ok(Statement::RefreshMaterializedView(
+            RefreshMaterializedViewStatement { name },
+        ))
+    }
+
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
@@ -863,6 +900,107 @@ impl<'a> DFParser<'a> {
     }
 }
 
+/// Rewrites `TABLESAMPLE <method>(<args>) [REPEATABLE(<seed>)]`, which
+/// [`Parser`] does not support, into the table hint
+/// `WITH (TABLESAMPLE(<method>(<args>) [, REPEATABLE(<seed>)]))` that the
+/// SQL planner turns into a sample of the table. Arguments other than
+/// literals are rejected, the planner checks the values of the literals.
+///
+/// The hint itself is rejected when written in the query, so that the planner
+/// only sees hints produced by this rewrite.
+fn rewrite_table_sample(tokens: &[Token]) -> Result<Vec<Token>, ParserError> {
+    let is_keyword = |token: &Token, keyword: Keyword| matches!(token, Token::Word(w) if w.keyword == keyword);
+    // index of the first non whitespace token at or after `i`
+    let next = |i: usize| {
+        (i..tokens.len()).find(|&i| !matches!(tokens[i], Token::Whitespace(_)))
+    };
+    // end of the call `<name>(...)` that starts at `i`, if there is one
+    let call_end = |i: usize| {
+        if !matches!(tokens[i], Token::Word(_)) {
+            return None;
+        }
+        let open = next(i + 1).filter(|&open| tokens[open] == Token::LParen)?;
+        let mut depth = 0;
+        for (j, token) in tokens.iter().enumerate().skip(open) {
+            match token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth == 1 => return Some(j + 1),
+                Token::RParen => depth -= 1,
+                _ => {}
+            }
+        }
+        None
+    };
+    // checks that the argument of the call starting at `i` and ending before
+    // `end` is a literal
+    let literal_arg = |i: usize, end: usize| {
+        let Some(open) = next(i + 1) else {
+            return Ok(());
+        };
+        let arg = &tokens[open + 1..end - 1];
+        let arg_tokens = arg
+            .iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)))
+            .collect::<Vec<_>>();
+        match arg_tokens.as_slice() {
+            [Token::Number(_, _) | Token::SingleQuotedString(_)]
+            | [Token::Minus | Token::Plus, Token::Number(_, _)] => Ok(()),
+            _ => {
+                let arg = arg.iter().map(ToString::to_string).collect::<String>();
+                parser_err!(format!(
+                    "TABLESAMPLE arguments must be literals, found: {}",
+                    arg.trim()
+                ))
+            }
+        }
+    };
+
+    let mut rewritten = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        let hint = next(i + 1)
+            .filter(|&open| {
+                is_keyword(&tokens[i], Keyword::WITH) && tokens[open] == Token::LParen
+            })
+            .and_then(|open| next(open + 1));
+        if hint.is_some_and(|hint| is_keyword(&tokens[hint], Keyword::TABLESAMPLE)) {
+            return parser_err!(
+                "Unsupported table hint TABLESAMPLE, use TABLESAMPLE <method>(<percent>) after the table instead"
+            );
+        }
+
+        let method = next(i + 1)
+            .filter(|_| is_keyword(&tokens[i], Keyword::TABLESAMPLE))
+            .and_then(|method| Some((method, call_end(method)?)));
+        let Some((method, method_end)) = method else {
+            rewritten.push(tokens[i].clone());
+            i += 1;
+            continue;
+        };
+        literal_arg(method, method_end)?;
+
+        rewritten.extend([
+            Token::make_keyword("WITH"),
+            Token::LParen,
+            tokens[i].clone(),
+            Token::LParen,
+        ]);
+        rewritten.extend_from_slice(&tokens[method..method_end]);
+        i = method_end;
+        let repeatable = next(i)
+            .filter(|&r| is_keyword(&tokens[r], Keyword::REPEATABLE))
+            .and_then(|r| Some((r, call_end(r)?)));
+        if let Some((repeatable, repeatable_end)) = repeatable {
+            literal_arg(repeatable, repeatable_end)?;
+            rewritten.push(Token::Comma);
+            rewritten.extend_from_slice(&tokens[repeatable..repeatable_end]);
+            i = repeatable_end;
+        }
+        rewritten.extend([Token::RParen, Token::RParen]);
+    }
+    Ok(rewritten)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1512,10 +1650,58 @@ mod tests {
 
     // For error cases, see: `copy.slt`
 
+    #[test]
+    fn refresh_materialized_view() -> Result<(), ParserError> {
+        let sql = "REFRESH MATERIALIZED VIEW s.mv";
+        let expected =
+            Statement::RefreshMaterializedView(RefreshMaterializedViewStatement {
+                name: ObjectName(vec![Ident::new("s"), Ident::new("mv")]),
+            });
+        assert_eq!(verified_stmt(sql), expected);
+
+        expect_parse_ok(
+            "refresh materialized view mv",
+            Statement::RefreshMaterializedView(RefreshMaterializedViewStatement {
+                name: ObjectName(vec![Ident::new("mv")]),
+            }),
+        )?;
+
+        expect_parse_error("REFRESH VIEW mv", "Expected: MATERIALIZED, found: VIEW");
+        Ok(())
+    }
+
     fn object_name(name: &str) -> CopyToSource {
         CopyToSource::Relation(ObjectName(vec![Ident::new(name)]))
     }
 
+    #[test]
+    fn table_sample() {
+        one_statement_parses_to(
+            "SELECT * FROM t AS x TABLESAMPLE BERNOULLI (10) REPEATABLE (42) WHERE a > 1",
+            "SELECT * FROM t AS x WITH (TABLESAMPLE(BERNOULLI(10), REPEATABLE(42))) WHERE a > 1",
+        );
+        one_statement_parses_to(
+            "SELECT * FROM t TABLESAMPLE SYSTEM(1.5) REPEATABLE(-1) JOIN s ON t.a = s.a",
+            "SELECT * FROM t WITH (TABLESAMPLE(SYSTEM(1.5), REPEATABLE(-1))) JOIN s ON t.a = s.a",
+        );
+        // not a sample
+        verified_stmt("SELECT tablesample FROM t");
+
+        expect_parse_error(
+            "SELECT * FROM t TABLESAMPLE SYSTEM(1.5 + 2)",
+            "TABLESAMPLE arguments must be literals, found: 1.5 + 2",
+        );
+        expect_parse_error(
+            "SELECT * FROM t TABLESAMPLE BERNOULLI(10) REPEATABLE(a)",
+            "TABLESAMPLE arguments must be literals, found: a",
+        );
+        // the hint the clause is rewritten to can't be written directly
+        expect_parse_error(
+            "SELECT * FROM t WITH (TABLESAMPLE(SYSTEM(10)))",
+            "Unsupported table hint TABLESAMPLE, use TABLESAMPLE <method>(<percent>) after the table instead",
+        );
+    }
+
     // Based on  sqlparser-rs
     // https://github.com/sqlparser-rs/sqlparser-rs/blob/ae3b5844c839072c235965fe0d1bddc473dced87/src/test_utils.rs#L104-L116
 
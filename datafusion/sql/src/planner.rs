@@ -138,6 +138,8 @@ pub struct PlannerContext {
     /// The joined schemas of all FROM clauses planned so far. When planning LATERAL
     /// FROM clauses, this should become a suffix of the `outer_query_schema`.
     outer_from_schema: Option<DFSchemaRef>,
+    /// Types of the parameters of the lambda function whose body is being planned
+    lambda_parameters: HashMap<String, DataType>,
 }
 
 impl Default for PlannerContext {
@@ -154,6 +156,7 @@ impl PlannerContext {
             ctes: HashMap::new(),
             outer_query_schema: None,
             outer_from_schema: None,
+            lambda_parameters: HashMap::new(),
         }
     }
 
@@ -204,6 +207,22 @@ impl PlannerContext {
         Ok(())
     }
 
+    /// Return the type of the lambda parameter `name`, if a lambda body with
+    /// such a parameter is being planned
+    pub fn lambda_parameter_type(&self, name: &str) -> Option<&DataType> {
+        self.lambda_parameters.get(name)
+    }
+
+    /// sets the parameters of the lambda whose body is being planned,
+    /// returning the existing ones
+    pub fn set_lambda_parameters(
+        &mut self,
+        mut parameters: HashMap<String, DataType>,
+    ) -> HashMap<String, DataType> {
+        std::mem::swap(&mut self.lambda_parameters, &mut parameters);
+        parameters
+    }
+
     /// Return the types of parameters (`$1`, `$2`, etc) if known
     pub fn prepare_param_data_types(&self) -> &[DataType] {
         &self.prepare_param_data_types
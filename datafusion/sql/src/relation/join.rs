@@ -16,9 +16,13 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{not_impl_err, Column, Result};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableFactor, TableWithJoins};
+use datafusion_common::{not_impl_err, plan_err, Column, Result};
+use datafusion_expr::expr::BinaryExpr;
+use datafusion_expr::utils::split_conjunction;
+use datafusion_expr::{Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Operator};
+use sqlparser::ast::{
+    Expr as SQLExpr, Join, JoinConstraint, JoinOperator, TableFactor, TableWithJoins,
+};
 use std::collections::HashSet;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -94,6 +98,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.parse_join(left, right, constraint, JoinType::Full, planner_context)
             }
             JoinOperator::CrossJoin => self.parse_cross_join(left, right),
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => self.parse_asof_join(
+                left,
+                right,
+                match_condition,
+                constraint,
+                planner_context,
+            ),
             other => not_impl_err!("Unsupported JOIN operator {other:?}"),
         }
     }
@@ -106,6 +120,61 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         LogicalPlanBuilder::from(left).cross_join(right)?.build()
     }
 
+    /// Plans `left ASOF JOIN right MATCH_CONDITION (<match_condition>) [ON <keys>]`.
+    ///
+    /// Like Snowflake, the left rows without a match are returned with nulls,
+    /// see [`LogicalPlanBuilder::asof_join`]
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        match_condition: SQLExpr,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let join_schema = left.schema().join(right.schema())?;
+        let match_condition =
+            self.sql_to_expr(match_condition, &join_schema, planner_context)?;
+        let (mut left_keys, mut right_keys) = (vec![], vec![]);
+        match constraint {
+            JoinConstraint::On(sql_expr) => {
+                let expr = self.sql_to_expr(sql_expr, &join_schema, planner_context)?;
+                for predicate in split_conjunction(&expr) {
+                    let Expr::BinaryExpr(BinaryExpr {
+                        left: l,
+                        op: Operator::Eq,
+                        right: r,
+                    }) = predicate
+                    else {
+                        return plan_err!(
+                            "ASOF JOIN ON clause only supports equalities of columns, got {predicate}"
+                        );
+                    };
+                    let (Expr::Column(l), Expr::Column(r)) = (l.as_ref(), r.as_ref())
+                    else {
+                        return plan_err!(
+                            "ASOF JOIN ON clause only supports equalities of columns, got {predicate}"
+                        );
+                    };
+                    left_keys.push(l.clone());
+                    right_keys.push(r.clone());
+                }
+            }
+            JoinConstraint::None => {}
+            JoinConstraint::Using(_) | JoinConstraint::Natural => {
+                return not_impl_err!("ASOF JOIN only supports an ON clause")
+            }
+        }
+        LogicalPlanBuilder::from(left)
+            .asof_join(
+                right,
+                JoinType::Left,
+                (left_keys, right_keys),
+                match_condition,
+            )?
+            .build()
+    }
+
     fn parse_join(
         &self,
         left: LogicalPlan,
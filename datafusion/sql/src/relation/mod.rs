@@ -19,14 +19,23 @@ use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
+use arrow_schema::DataType;
 use datafusion_common::tree_node::{Transformed, TreeNode};
-use datafusion_common::{not_impl_err, plan_err, DFSchema, Result, TableReference};
+use datafusion_common::{
+    internal_err, not_impl_err, plan_err, DFSchema, Result, ScalarValue, TableReference,
+    UnnestOptions,
+};
 use datafusion_expr::builder::subquery_alias;
+use datafusion_expr::planner::TableFunctionArg;
 use datafusion_expr::{expr::Unnest, Expr, LogicalPlan, LogicalPlanBuilder};
-use datafusion_expr::{Subquery, SubqueryAlias};
-use sqlparser::ast::{FunctionArg, FunctionArgExpr, TableFactor};
+use datafusion_expr::{SampleMethod, Subquery, SubqueryAlias};
+use sqlparser::ast::{
+    Expr as SQLExpr, FunctionArg, FunctionArgExpr, FunctionArguments, ObjectName,
+    TableFactor,
+};
 
 mod join;
+mod pivot;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Create a `LogicalPlan` that scans the named relation
@@ -37,58 +46,35 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     ) -> Result<LogicalPlan> {
         let (plan, alias) = match relation {
             TableFactor::Table {
-                name, alias, args, ..
+                name,
+                alias,
+                args,
+                with_hints,
+                ..
             } => {
-                if let Some(func_args) = args {
+                let plan = if let Some(func_args) = args {
                     let tbl_func_name = name.0.first().unwrap().value.to_string();
                     let args = func_args
                         .args
                         .into_iter()
-                        .flat_map(|arg| {
-                            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg
-                            {
-                                self.sql_expr_to_logical_expr(
-                                    expr,
-                                    &DFSchema::empty(),
-                                    planner_context,
-                                )
-                            } else {
-                                plan_err!("Unsupported function argument type: {:?}", arg)
-                            }
-                        })
-                        .collect::<Vec<_>>();
+                        .map(|arg| self.sql_to_table_function_arg(arg, planner_context))
+                        .collect::<Result<Vec<_>>>()?;
                     let provider = self
                         .context_provider
-                        .get_table_function_source(&tbl_func_name, args)?;
-                    let plan = LogicalPlanBuilder::scan(
+                        .get_table_function_source_with_args(&tbl_func_name, args)?;
+                    LogicalPlanBuilder::scan(
                         TableReference::Bare {
                             table: "tmp_table".into(),
                         },
                         provider,
                         None,
                     )?
-                    .build()?;
-                    (plan, alias)
+                    .build()?
                 } else {
-                    // normalize name and alias
-                    let table_ref = self.object_name_to_table_reference(name)?;
-                    let table_name = table_ref.to_string();
-                    let cte = planner_context.get_cte(&table_name);
-                    (
-                        match (
-                            cte,
-                            self.context_provider.get_table_source(table_ref.clone()),
-                        ) {
-                            (Some(cte_plan), _) => Ok(cte_plan.clone()),
-                            (_, Ok(provider)) => {
-                                LogicalPlanBuilder::scan(table_ref, provider, None)?
-                                    .build()
-                            }
-                            (None, Err(e)) => Err(e),
-                        }?,
-                        alias,
-                    )
-                }
+                    self.plan_table_name(name, planner_context)?
+                };
+                let plan = self.apply_table_sample(plan, with_hints, planner_context)?;
+                (plan, alias)
             }
             TableFactor::Derived {
                 subquery, alias, ..
@@ -110,10 +96,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 with_offset_alias: None,
                 with_ordinality,
             } => {
-                if with_ordinality {
-                    return not_impl_err!("UNNEST with ordinality is not supported yet");
-                }
-
                 // Unnest table factor has empty input
                 let schema = DFSchema::empty();
                 let input = LogicalPlanBuilder::empty(true).build()?;
@@ -134,7 +116,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 if unnest_exprs.is_empty() {
                     return plan_err!("UNNEST must have at least one argument");
                 }
-                let logical_plan = self.try_process_unnest(input, unnest_exprs)?;
+                // Set preserve_nulls to false to ensure compatibility with DuckDB and PostgreSQL
+                let unnest_options = UnnestOptions::new()
+                    .with_preserve_nulls(false)
+                    .with_ordinality(with_ordinality);
+                let logical_plan = self.try_process_unnest_with_options(
+                    input,
+                    unnest_exprs,
+                    unnest_options,
+                )?;
                 (logical_plan, alias)
             }
             TableFactor::UNNEST { .. } => {
@@ -142,6 +132,37 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     "UNNEST table factor with offset is not supported yet"
                 );
             }
+            TableFactor::Pivot {
+                table,
+                aggregate_functions,
+                value_column,
+                value_source,
+                default_on_null,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan = self.plan_pivot(
+                    input,
+                    aggregate_functions,
+                    value_column,
+                    value_source,
+                    default_on_null,
+                    planner_context,
+                )?;
+                (plan, alias)
+            }
+            TableFactor::Unpivot {
+                table,
+                value,
+                name,
+                columns,
+                alias,
+            } => {
+                let input = self.create_relation(*table, planner_context)?;
+                let plan =
+                    self.plan_unpivot(input, value, name, columns, planner_context)?;
+                (plan, alias)
+            }
             // @todo Support TableFactory::TableFunction?
             _ => {
                 return not_impl_err!(
@@ -158,6 +179,159 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
+    /// Samples `plan` if the table `hints` contain a `TABLESAMPLE`, which the
+    /// parser passes on as the hint
+    /// `TABLESAMPLE(<method>(<percent>) [, REPEATABLE(<seed>)])`
+    fn apply_table_sample(
+        &self,
+        plan: LogicalPlan,
+        hints: Vec<SQLExpr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let Some((_, sample_args)) = hints
+            .into_iter()
+            .filter_map(function_call)
+            .find(|(name, _)| name == "TABLESAMPLE")
+        else {
+            return Ok(plan);
+        };
+
+        let mut sample_args = sample_args.into_iter();
+        let Some((method, method_args)) = sample_args.next().and_then(function_call)
+        else {
+            return plan_err!("TABLESAMPLE requires a sampling method");
+        };
+        let method = match method.as_str() {
+            "BERNOULLI" => SampleMethod::Bernoulli,
+            "SYSTEM" => SampleMethod::System,
+            _ => return not_impl_err!("Unsupported TABLESAMPLE method {method}"),
+        };
+        let [percent] = <[SQLExpr; 1]>::try_from(method_args).or_else(|_| {
+            plan_err!("TABLESAMPLE {method} requires exactly one argument")
+        })?;
+        let percent = match self
+            .sample_argument(percent, planner_context)?
+            .cast_to(&DataType::Float64)?
+        {
+            ScalarValue::Float64(Some(percent)) => percent,
+            _ => return plan_err!("TABLESAMPLE percentage must not be NULL"),
+        };
+        if !(0.0..=100.0).contains(&percent) {
+            return plan_err!(
+                "TABLESAMPLE percentage must be between 0 and 100, got {percent}"
+            );
+        }
+
+        let seed = match sample_args.next().and_then(function_call) {
+            Some((name, seed_args)) if name == "REPEATABLE" => {
+                let [seed] = <[SQLExpr; 1]>::try_from(seed_args)
+                    .or_else(|_| plan_err!("REPEATABLE requires exactly one argument"))?;
+                match self
+                    .sample_argument(seed, planner_context)?
+                    .cast_to(&DataType::UInt64)
+                {
+                    Ok(ScalarValue::UInt64(Some(seed))) => Some(seed),
+                    _ => {
+                        return plan_err!(
+                            "REPEATABLE seed must be a non negative integer"
+                        )
+                    }
+                }
+            }
+            None => None,
+            Some((name, _)) => {
+                return plan_err!("Unexpected {name} in TABLESAMPLE");
+            }
+        };
+
+        LogicalPlanBuilder::from(plan)
+            .sample(method, percent / 100.0, seed)?
+            .build()
+    }
+
+    /// Returns the value of an argument of `TABLESAMPLE`, which the parser
+    /// only accepts as a literal
+    fn sample_argument(
+        &self,
+        arg: SQLExpr,
+        planner_context: &mut PlannerContext,
+    ) -> Result<ScalarValue> {
+        match self.sql_expr_to_logical_expr(arg, &DFSchema::empty(), planner_context)? {
+            Expr::Literal(value) => Ok(value),
+            expr => internal_err!("Expected a literal TABLESAMPLE argument, got {expr}"),
+        }
+    }
+
+    /// Plans a scan of the named table or common table expression
+    fn plan_table_name(
+        &self,
+        name: ObjectName,
+        planner_context: &PlannerContext,
+    ) -> Result<LogicalPlan> {
+        // normalize name and alias
+        let table_ref = self.object_name_to_table_reference(name)?;
+        let table_name = table_ref.to_string();
+        let cte = planner_context.get_cte(&table_name);
+        match (
+            cte,
+            self.context_provider.get_table_source(table_ref.clone()),
+        ) {
+            (Some(cte_plan), _) => Ok(cte_plan.clone()),
+            (_, Ok(provider)) => {
+                LogicalPlanBuilder::scan(table_ref, provider, None)?.build()
+            }
+            (None, Err(e)) => Err(e),
+        }
+    }
+
+    /// Converts an argument of a table function call. Besides expressions, table
+    /// functions accept relations, written as `TABLE(t)` or as a subquery
+    /// `(SELECT ...)`.
+    fn sql_to_table_function_arg(
+        &self,
+        arg: FunctionArg,
+        planner_context: &mut PlannerContext,
+    ) -> Result<TableFunctionArg> {
+        match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Subquery(query))) => {
+                let plan = self.query_to_plan(*query, planner_context)?;
+                Ok(TableFunctionArg::Table(plan))
+            }
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Function(function)))
+                if matches!(
+                    function.name.0.as_slice(),
+                    [ident] if ident.value.eq_ignore_ascii_case("table")
+                ) =>
+            {
+                let table_name = match function.args {
+                    FunctionArguments::List(list) if list.args.len() == 1 => {
+                        match list.args.into_iter().next() {
+                            Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                                SQLExpr::Identifier(ident),
+                            ))) => ObjectName(vec![ident]),
+                            Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                                SQLExpr::CompoundIdentifier(idents),
+                            ))) => ObjectName(idents),
+                            _ => return plan_err!("TABLE argument must be a table name"),
+                        }
+                    }
+                    _ => return plan_err!("TABLE argument must be a table name"),
+                };
+                let plan = self.plan_table_name(table_name, planner_context)?;
+                Ok(TableFunctionArg::Table(plan))
+            }
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                let expr = self.sql_expr_to_logical_expr(
+                    expr,
+                    &DFSchema::empty(),
+                    planner_context,
+                )?;
+                Ok(TableFunctionArg::Expr(expr))
+            }
+            _ => plan_err!("Unsupported function argument type: {:?}", arg),
+        }
+    }
+
     pub(crate) fn create_relation_subquery(
         &self,
         subquery: TableFactor,
@@ -232,3 +406,23 @@ fn optimize_subquery_sort(plan: LogicalPlan) -> Result<Transformed<LogicalPlan>>
     });
     new_plan
 }
+
+/// Returns the upper case name and the arguments of `expr`, if it is a call
+/// of a function with only unnamed arguments
+fn function_call(expr: SQLExpr) -> Option<(String, Vec<SQLExpr>)> {
+    let SQLExpr::Function(function) = expr else {
+        return None;
+    };
+    let FunctionArguments::List(list) = function.args else {
+        return None;
+    };
+    let args = list
+        .args
+        .into_iter()
+        .map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => Some(arg),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((function.name.to_string().to_uppercase(), args))
+}
@@ -0,0 +1,176 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use datafusion_common::{not_impl_err, plan_err, DFSchema, Result};
+use datafusion_expr::expr::AggregateFunction;
+use datafusion_expr::{and, when, Expr, LogicalPlan, LogicalPlanBuilder};
+use sqlparser::ast::{Expr as SQLExpr, ExprWithAlias, Ident, PivotValueSource};
+
+impl<'a, S: ContextProvider> SqlToRel<'a, S> {
+    /// Plans `input PIVOT(<aggregates> FOR <column> IN (<values>))` as an
+    /// aggregation, grouped by the columns of `input` that are neither the
+    /// pivot column nor aggregated, with one aggregate for each value that only
+    /// sees the rows of that value.
+    ///
+    /// The output column of each value is named after the value, or
+    /// `<value>_<aggregate>` if there are several aggregates.
+    pub(super) fn plan_pivot(
+        &self,
+        input: LogicalPlan,
+        aggregate_functions: Vec<ExprWithAlias>,
+        value_column: Vec<Ident>,
+        value_source: PivotValueSource,
+        default_on_null: Option<SQLExpr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let PivotValueSource::List(values) = value_source else {
+            return not_impl_err!(
+                "PIVOT is only supported with a list of values, got {value_source}"
+            );
+        };
+        let schema = Arc::clone(input.schema());
+
+        let pivot_expr = match <[Ident; 1]>::try_from(value_column) {
+            Ok([ident]) => SQLExpr::Identifier(ident),
+            Err(idents) => SQLExpr::CompoundIdentifier(idents),
+        };
+        let pivot_expr = self.sql_to_expr(pivot_expr, &schema, planner_context)?;
+        let Expr::Column(pivot_column) = &pivot_expr else {
+            return plan_err!("PIVOT requires a column, got {pivot_expr}");
+        };
+
+        let mut aggregates = vec![];
+        for ExprWithAlias { expr, alias } in aggregate_functions {
+            let name = match alias {
+                Some(alias) => self.ident_normalizer.normalize(alias),
+                None => expr.to_string(),
+            };
+            match self.sql_to_expr(expr, &schema, planner_context)? {
+                Expr::AggregateFunction(aggregate) => aggregates.push((name, aggregate)),
+                expr => {
+                    return plan_err!("PIVOT requires aggregate functions, got {expr}")
+                }
+            }
+        }
+
+        // the other columns identify the rows of the output
+        let mut pivoted_columns = HashSet::from([pivot_column.clone()]);
+        for (_, aggregate) in &aggregates {
+            let aggregate = Expr::AggregateFunction(aggregate.clone());
+            pivoted_columns.extend(aggregate.column_refs().into_iter().cloned());
+        }
+        let group_expr = schema
+            .columns()
+            .into_iter()
+            .filter(|column| !pivoted_columns.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut aggr_expr = vec![];
+        let mut pivot_names = HashSet::new();
+        for ExprWithAlias { expr, alias } in values {
+            let sql = expr.to_string();
+            let value = self.sql_to_expr(expr, &DFSchema::empty(), planner_context)?;
+            let value_name = match (alias, &value) {
+                (Some(alias), _) => self.ident_normalizer.normalize(alias),
+                (None, Expr::Literal(value)) => value.to_string(),
+                (None, _) => sql,
+            };
+            for (aggregate_name, aggregate) in &aggregates {
+                let name = if aggregates.len() == 1 {
+                    value_name.clone()
+                } else {
+                    format!("{value_name}_{aggregate_name}")
+                };
+                let is_value = pivot_expr.clone().eq(value.clone());
+                let filter = match &aggregate.filter {
+                    Some(filter) => and(filter.as_ref().clone(), is_value),
+                    None => is_value,
+                };
+                let aggregate = AggregateFunction {
+                    filter: Some(Box::new(filter)),
+                    ..aggregate.clone()
+                };
+                aggr_expr.push(Expr::AggregateFunction(aggregate).alias(&name));
+                pivot_names.insert(name);
+            }
+        }
+
+        let plan = LogicalPlanBuilder::from(input).aggregate(group_expr, aggr_expr)?;
+        let Some(default_on_null) = default_on_null else {
+            return plan.build();
+        };
+        let default_on_null =
+            self.sql_to_expr(default_on_null, &DFSchema::empty(), planner_context)?;
+        let exprs = plan
+            .schema()
+            .columns()
+            .into_iter()
+            .map(|column| {
+                if !pivot_names.contains(&column.name) {
+                    return Ok(Expr::Column(column));
+                }
+                let name = column.name.clone();
+                let expr = Expr::Column(column);
+                Ok(when(expr.clone().is_null(), default_on_null.clone())
+                    .otherwise(expr)?
+                    .alias(name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        plan.project(exprs)?.build()
+    }
+
+    /// Plans `input UNPIVOT(<value> FOR <name> IN (<columns>))`, which turns
+    /// each of `columns` into a row with the column name in `name` and its
+    /// value in `value`, see [`LogicalPlanBuilder::unpivot`]
+    pub(super) fn plan_unpivot(
+        &self,
+        input: LogicalPlan,
+        value: Ident,
+        name: Ident,
+        columns: Vec<Ident>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let schema = Arc::clone(input.schema());
+        let columns = columns
+            .into_iter()
+            .map(|ident| {
+                let column_name = self.ident_normalizer.normalize(ident.clone());
+                match self.sql_to_expr(
+                    SQLExpr::Identifier(ident),
+                    &schema,
+                    planner_context,
+                )? {
+                    Expr::Column(column) => Ok((column_name, vec![column])),
+                    expr => plan_err!("UNPIVOT requires columns, got {expr}"),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        LogicalPlanBuilder::from(input)
+            .unpivot(
+                vec![self.ident_normalizer.normalize(value)],
+                self.ident_normalizer.normalize(name),
+                columns,
+                false,
+            )?
+            .build()
+    }
+}
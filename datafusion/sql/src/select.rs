@@ -26,9 +26,10 @@ use crate::utils::{
     resolve_columns, resolve_positions_to_exprs, transform_bottom_unnests,
 };
 
+use arrow_schema::DataType;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
 use datafusion_common::UnnestOptions;
-use datafusion_common::{not_impl_err, plan_err, DataFusionError, Result};
+use datafusion_common::{not_impl_err, plan_err, Column, DataFusionError, Result};
 use datafusion_expr::expr::{Alias, PlannedReplaceSelectItem, WildcardOptions};
 use datafusion_expr::expr_rewriter::{
     normalize_col, normalize_col_with_schemas_and_ambiguity_check, normalize_cols,
@@ -37,11 +38,12 @@ use datafusion_expr::utils::{
     expr_as_column_expr, expr_to_columns, find_aggregate_exprs, find_window_exprs,
 };
 use datafusion_expr::{
-    qualified_wildcard_with_options, wildcard_with_options, Aggregate, Expr, Filter,
-    GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
+    lit, qualified_wildcard_with_options, wildcard_with_options, Aggregate, Expr,
+    ExprSchemable, Filter, GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
 };
 use sqlparser::ast::{
-    Distinct, Expr as SQLExpr, GroupByExpr, NamedWindowExpr, OrderByExpr,
+    Distinct, Expr as SQLExpr, FunctionArg, FunctionArgExpr, FunctionArgumentList,
+    FunctionArguments, GroupByExpr, LateralView, NamedWindowExpr, OrderByExpr,
     WildcardAdditionalOptions, WindowType,
 };
 use sqlparser::ast::{NamedWindowDefinition, Select, SelectItem, TableWithJoins};
@@ -58,12 +60,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         if !select.cluster_by.is_empty() {
             return not_impl_err!("CLUSTER BY");
         }
-        if !select.lateral_views.is_empty() {
-            return not_impl_err!("LATERAL VIEWS");
-        }
-        if select.qualify.is_some() {
-            return not_impl_err!("QUALIFY");
-        }
         if select.top.is_some() {
             return not_impl_err!("TOP");
         }
@@ -75,6 +71,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let plan = self.plan_from_tables(select.from, planner_context)?;
         let empty_from = matches!(plan, LogicalPlan::EmptyRelation(_));
 
+        // process `lateral view` clauses, whose columns can be used by the `where` clause
+        let plan =
+            self.plan_lateral_views(plan, select.lateral_views, planner_context)?;
+
         // process `where` clause
         let base_plan = self.plan_selection(select.selection, plan, planner_context)?;
 
@@ -139,6 +139,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
             .transpose()?;
 
+        // Optionally the QUALIFY expression, which may also refer to aliased
+        // columns, for example:
+        //
+        //   SELECT c1, ROW_NUMBER() OVER (PARTITION BY c1 ORDER BY c2) AS rn FROM t QUALIFY rn = 1;
+        //
+        let qualify_expr_opt = select
+            .qualify
+            .map::<Result<Expr>, _>(|qualify_expr| {
+                let qualify_expr = self.sql_expr_to_logical_expr(
+                    qualify_expr,
+                    &combined_schema,
+                    planner_context,
+                )?;
+                let qualify_expr = resolve_aliases_to_exprs(qualify_expr, &alias_map)?;
+                normalize_col(qualify_expr, &projected_plan)
+            })
+            .transpose()?;
+
         // The outer expressions we will search through for
         // aggregates. Aggregates may be sourced from the SELECT...
         let mut aggr_expr_haystack = select_exprs.clone();
@@ -146,6 +164,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         if let Some(having_expr) = &having_expr_opt {
             aggr_expr_haystack.push(having_expr.clone());
         }
+        // ... or from the QUALIFY.
+        if let Some(qualify_expr) = &qualify_expr_opt {
+            aggr_expr_haystack.push(qualify_expr.clone());
+        }
 
         // All of the aggregate expressions (deduplicated).
         let aggr_exprs = find_aggregate_exprs(&aggr_expr_haystack);
@@ -194,21 +216,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         };
 
         // process group by, aggregation or having
-        let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
-            .is_empty()
-            || !aggr_exprs.is_empty()
-        {
+        let (
+            plan,
+            mut select_exprs_post_aggr,
+            having_expr_post_aggr,
+            qualify_expr_post_aggr,
+        ) = if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
             self.aggregate(
                 &base_plan,
                 &select_exprs,
                 having_expr_opt.as_ref(),
+                qualify_expr_opt.as_ref(),
                 &group_by_exprs,
                 &aggr_exprs,
             )?
         } else {
             match having_expr_opt {
                 Some(having_expr) => return plan_err!("HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"),
-                None => (base_plan.clone(), select_exprs.clone(), having_expr_opt)
+                None => (base_plan.clone(), select_exprs.clone(), having_expr_opt, qualify_expr_opt)
             }
         };
 
@@ -220,8 +245,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
-        // process window function
-        let window_func_exprs = find_window_exprs(&select_exprs_post_aggr);
+        // process window function, either in the projection or in the QUALIFY clause
+        let mut window_expr_haystack = select_exprs_post_aggr.clone();
+        if let Some(qualify_expr) = &qualify_expr_post_aggr {
+            window_expr_haystack.push(qualify_expr.clone());
+        }
+        let window_func_exprs = find_window_exprs(&window_expr_haystack);
 
         let plan = if window_func_exprs.is_empty() {
             plan
@@ -237,6 +266,21 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
+        // process qualify clause, which filters the result of the window functions
+        let plan = if let Some(qualify_expr) = qualify_expr_post_aggr {
+            if window_func_exprs.is_empty() {
+                return plan_err!(
+                    "QUALIFY clause requires a window function in the SELECT list or in the QUALIFY clause"
+                );
+            }
+            let qualify_expr = rebase_expr(&qualify_expr, &window_func_exprs, &plan)?;
+            LogicalPlanBuilder::from(plan)
+                .filter(qualify_expr)?
+                .build()?
+        } else {
+            plan
+        };
+
         // try process unnest expression or do the final projection
         let plan = self.try_process_unnest(plan, select_exprs_post_aggr)?;
 
@@ -291,17 +335,179 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         self.order_by(plan, order_by_rex)
     }
 
+    /// Plans Hive style `LATERAL VIEW [OUTER] explode(expr)` and
+    /// `LATERAL VIEW [OUTER] posexplode(expr)` clauses on top of `plan`.
+    fn plan_lateral_views(
+        &self,
+        plan: LogicalPlan,
+        lateral_views: Vec<LateralView>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        lateral_views
+            .into_iter()
+            .try_fold(plan, |plan, lateral_view| {
+                self.plan_lateral_view(plan, lateral_view, planner_context)
+            })
+    }
+
+    /// Converts a single lateral view into Projection/Unnest/Projection.
+    ///
+    /// Every input row is repeated for each element of the array returned by the
+    /// generator function. `posexplode` also produces the 0-based position of the
+    /// element. With `OUTER`, rows whose array is null or empty are kept once with
+    /// null generated columns.
+    fn plan_lateral_view(
+        &self,
+        input: LogicalPlan,
+        lateral_view: LateralView,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let LateralView {
+            lateral_view,
+            lateral_view_name,
+            lateral_col_alias,
+            outer,
+        } = lateral_view;
+
+        let function = match lateral_view {
+            SQLExpr::Function(function) => function,
+            other => {
+                return not_impl_err!("Unsupported LATERAL VIEW expression: {other}")
+            }
+        };
+        let with_position = match function.name.0.as_slice() {
+            [ident] if ident.value.eq_ignore_ascii_case("explode") => false,
+            [ident] if ident.value.eq_ignore_ascii_case("posexplode") => true,
+            _ => {
+                return not_impl_err!(
+                    "Unsupported LATERAL VIEW function: {}",
+                    function.name
+                )
+            }
+        };
+        let arg = match function.args {
+            FunctionArguments::List(FunctionArgumentList {
+                duplicate_treatment: None,
+                args,
+                clauses,
+            }) if args.len() == 1 && clauses.is_empty() => {
+                match args.into_iter().next() {
+                    Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))) => arg,
+                    _ => {
+                        return plan_err!(
+                            "{} expects a single unnamed argument",
+                            function.name
+                        )
+                    }
+                }
+            }
+            _ => return plan_err!("{} expects exactly one argument", function.name),
+        };
+
+        let arg = self.sql_expr_to_logical_expr(arg, input.schema(), planner_context)?;
+        match arg.get_type(input.schema())? {
+            DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::FixedSizeList(_, _) => {}
+            DataType::Map(_, _) => {
+                return not_impl_err!("{} of a map is not supported yet", function.name)
+            }
+            other => {
+                return plan_err!(
+                    "{} expects an array argument, got {other}",
+                    function.name
+                )
+            }
+        }
+
+        let view_name = self.object_name_to_table_reference(lateral_view_name)?;
+        let placeholder = Column::from_name(format!("__lateral_view_{view_name}"));
+        let input_columns = input
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut inner_projection_exprs = input_columns.clone();
+        inner_projection_exprs.push(arg.alias(placeholder.name.clone()));
+        let unnest_options = UnnestOptions::new()
+            .with_preserve_nulls(outer)
+            .with_preserve_empty_lists(outer)
+            .with_ordinality(with_position);
+        let unnest = LogicalPlanBuilder::from(input)
+            .project(inner_projection_exprs)?
+            .unnest_column_with_options(placeholder.clone(), unnest_options)?
+            .build()?;
+
+        let mut generated_exprs = vec![];
+        if let LogicalPlan::Unnest(unnest) = &unnest {
+            if let Some(ordinality) = unnest.ordinality_column() {
+                // posexplode positions are 0-based
+                generated_exprs.push(Expr::Column(ordinality) - lit(1i64));
+            }
+        }
+        generated_exprs.push(Expr::Column(placeholder));
+
+        let generated_names = if lateral_col_alias.is_empty() {
+            let default_names: &[&str] = if with_position {
+                &["pos", "col"]
+            } else {
+                &["col"]
+            };
+            default_names.iter().map(|name| name.to_string()).collect()
+        } else if lateral_col_alias.len() == generated_exprs.len() {
+            lateral_col_alias
+                .into_iter()
+                .map(|ident| self.ident_normalizer.normalize(ident))
+                .collect::<Vec<_>>()
+        } else {
+            return plan_err!(
+                "LATERAL VIEW {view_name} produces {} columns but {} column aliases were given",
+                generated_exprs.len(),
+                lateral_col_alias.len()
+            );
+        };
+
+        let mut outer_projection_exprs = input_columns;
+        outer_projection_exprs.extend(
+            generated_exprs
+                .into_iter()
+                .zip(generated_names)
+                .map(|(expr, name)| expr.alias_qualified(Some(view_name.clone()), name)),
+        );
+        LogicalPlanBuilder::from(unnest)
+            .project(outer_projection_exprs)?
+            .build()
+    }
+
     /// Try converting Expr(Unnest(Expr)) to Projection/Unnest/Projection
     pub(super) fn try_process_unnest(
         &self,
         input: LogicalPlan,
         select_exprs: Vec<Expr>,
+    ) -> Result<LogicalPlan> {
+        // Set preserve_nulls to false to ensure compatibility with DuckDB and PostgreSQL
+        let unnest_options = UnnestOptions::new().with_preserve_nulls(false);
+        self.try_process_unnest_with_options(input, select_exprs, unnest_options)
+    }
+
+    /// Same as [`Self::try_process_unnest`], but unnests with the given options.
+    ///
+    /// If [`UnnestOptions::ordinality`] is set, the position column produced by
+    /// the unnest is appended to the final projection as `ordinality`.
+    pub(super) fn try_process_unnest_with_options(
+        &self,
+        input: LogicalPlan,
+        select_exprs: Vec<Expr>,
+        unnest_options: UnnestOptions,
     ) -> Result<LogicalPlan> {
         // Try process group by unnest
         let input = self.try_process_aggregate_unnest(input)?;
 
         let mut intermediate_plan = input;
         let mut intermediate_select_exprs = select_exprs;
+        let mut ordinality_column = None;
         // Each expr in select_exprs can contains multiple unnest stage
         // The transformation happen bottom up, one at a time for each iteration
         // Only exaust the loop if no more unnest transformation is found
@@ -333,17 +539,26 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 }
                 break;
             } else {
+                if i > 0 && unnest_options.ordinality {
+                    return not_impl_err!(
+                        "UNNEST with ordinality is not supported for nested unnest"
+                    );
+                }
                 let columns = unnest_columns.into_iter().map(|col| col.into()).collect();
-                // Set preserve_nulls to false to ensure compatibility with DuckDB and PostgreSQL
-                let unnest_options = UnnestOptions::new().with_preserve_nulls(false);
                 let plan = LogicalPlanBuilder::from(intermediate_plan)
                     .project(inner_projection_exprs)?
-                    .unnest_columns_with_options(columns, unnest_options)?
+                    .unnest_columns_with_options(columns, unnest_options.clone())?
                     .build()?;
+                if let LogicalPlan::Unnest(unnest) = &plan {
+                    ordinality_column = unnest.ordinality_column();
+                }
                 intermediate_plan = plan;
                 intermediate_select_exprs = outer_projection_exprs;
             }
         }
+        if let Some(column) = ordinality_column {
+            intermediate_select_exprs.push(Expr::Column(column).alias("ordinality"));
+        }
         LogicalPlanBuilder::from(intermediate_plan)
             .project(intermediate_select_exprs)?
             .build()
@@ -700,9 +915,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         input: &LogicalPlan,
         select_exprs: &[Expr],
         having_expr_opt: Option<&Expr>,
+        qualify_expr_opt: Option<&Expr>,
         group_by_exprs: &[Expr],
         aggr_exprs: &[Expr],
-    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>)> {
+    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>, Option<Expr>)> {
         // create the aggregate plan
         let plan = LogicalPlanBuilder::from(input.clone())
             .aggregate(group_by_exprs.to_vec(), aggr_exprs.to_vec())?
@@ -719,7 +935,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         // the aggregate plan.
 
         // combine the original grouping and aggregate expressions into one list (note that
-        // we do not add the "having" expression since that is not part of the projection)
+        // we do not add the "having" and "qualify" expressions since they are not part of
+        // the projection)
         let mut aggr_projection_exprs = vec![];
         for expr in group_by_exprs {
             match expr {
@@ -783,7 +1000,29 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             None
         };
 
-        Ok((plan, select_exprs_post_aggr, having_expr_post_aggr))
+        // Rewrite the QUALIFY expression to use the columns produced by the
+        // aggregation.
+        let qualify_expr_post_aggr = if let Some(qualify_expr) = qualify_expr_opt {
+            let qualify_expr_post_aggr =
+                rebase_expr(qualify_expr, &aggr_projection_exprs, input)?;
+
+            check_columns_satisfy_exprs(
+                &column_exprs_post_aggr,
+                &[qualify_expr_post_aggr.clone()],
+                "QUALIFY clause references non-aggregate values",
+            )?;
+
+            Some(qualify_expr_post_aggr)
+        } else {
+            None
+        };
+
+        Ok((
+            plan,
+            select_exprs_post_aggr,
+            having_expr_post_aggr,
+            qualify_expr_post_aggr,
+        ))
     }
 }
 
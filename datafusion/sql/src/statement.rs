@@ -22,7 +22,7 @@ use std::sync::Arc;
 
 use crate::parser::{
     CopyToSource, CopyToStatement, CreateExternalTable, DFParser, ExplainStatement,
-    LexOrdering, Statement as DFStatement,
+    LexOrdering, RefreshMaterializedViewStatement, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
@@ -43,14 +43,16 @@ use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, Analyze, CacheTable, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
-    CreateIndex as PlanCreateIndex, CreateMemoryTable, CreateView, DescribeTable,
-    DmlStatement, DropCatalogSchema, DropFunction, DropTable, DropView, EmptyRelation,
-    Explain, Expr, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder,
-    OperateFunctionArg, PlanType, Prepare, SetVariable, Statement as PlanStatement,
-    ToStringifiedPlan, TransactionAccessMode, TransactionConclusion, TransactionEnd,
-    TransactionIsolationLevel, TransactionStart, Volatility, WriteOp,
+    CreateIndex as PlanCreateIndex, CreateMaterializedView, CreateMemoryTable,
+    CreateView, DescribeTable, DmlStatement, DropCatalogSchema, DropFunction, DropTable,
+    DropView, EmptyRelation, Explain, Expr, ExprSchemable, Filter, LogicalPlan,
+    LogicalPlanBuilder, MergeAction, MergeClause, MergeClauseKind, MergeOp,
+    OperateFunctionArg, PlanType, Prepare, RefreshMaterializedView, SetVariable,
+    Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
+    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
+    UncacheTable, Volatility, WriteOp,
 };
 use sqlparser::ast;
 use sqlparser::ast::{
@@ -169,6 +171,9 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 analyze,
                 statement,
             }) => self.explain_to_plan(verbose, analyze, *statement),
+            DFStatement::RefreshMaterializedView(s) => {
+                self.refresh_materialized_view_to_plan(s)
+            }
         }
     }
 
@@ -316,10 +321,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             Statement::CreateView {
                 or_replace,
+                materialized,
                 name,
                 columns,
                 query,
                 options: CreateTableOptions::None,
+                to,
                 ..
             } => {
                 let columns = columns
@@ -338,6 +345,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 let mut plan = self.query_to_plan(*query, &mut PlannerContext::new())?;
                 plan = self.apply_expr_alias(plan, columns)?;
 
+                if materialized {
+                    let target = to
+                        .map(|to| self.object_name_to_table_reference(to))
+                        .transpose()?;
+                    return Ok(LogicalPlan::Ddl(DdlStatement::CreateMaterializedView(
+                        CreateMaterializedView {
+                            name: self.object_name_to_table_reference(name)?,
+                            input: Arc::new(plan),
+                            or_replace,
+                            target,
+                            definition: sql,
+                        },
+                    )));
+                }
+                if to.is_some() {
+                    return plan_err!("Only materialized views can have a target table");
+                }
+
                 Ok(LogicalPlan::Ddl(DdlStatement::CreateView(CreateView {
                     name: self.object_name_to_table_reference(name)?,
                     input: Arc::new(plan),
@@ -575,6 +600,14 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.delete_to_plan(table_name, selection)
             }
 
+            Statement::Merge {
+                into: _,
+                table,
+                source,
+                on,
+                clauses,
+            } => self.merge_to_plan(table, source, *on, clauses),
+
             Statement::StartTransaction {
                 modes,
                 begin: false,
@@ -805,6 +838,47 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     },
                 )))
             }
+            Statement::Cache {
+                table_flag,
+                table_name,
+                options,
+                query,
+                ..
+            } => {
+                if let Some(table_flag) = table_flag {
+                    return not_impl_err!("CACHE {table_flag} TABLE is not supported");
+                }
+                if !options.is_empty() {
+                    return not_impl_err!("Options are not supported for CACHE TABLE");
+                }
+                let name = self.object_name_to_table_reference(table_name)?;
+                let (input, from_query) = match query {
+                    Some(query) => (self.query_to_plan(query, planner_context)?, true),
+                    None => {
+                        // Do a table lookup to verify the table exists
+                        let table_source =
+                            self.context_provider.get_table_source(name.clone())?;
+                        let plan =
+                            LogicalPlanBuilder::scan(name.clone(), table_source, None)?
+                                .build()?;
+                        (plan, false)
+                    }
+                };
+                Ok(LogicalPlan::Ddl(DdlStatement::CacheTable(CacheTable {
+                    name,
+                    input: Arc::new(input),
+                    from_query,
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                })))
+            }
+            Statement::UNCache {
+                table_name,
+                if_exists,
+            } => Ok(LogicalPlan::Ddl(DdlStatement::UncacheTable(UncacheTable {
+                name: self.object_name_to_table_reference(table_name)?,
+                if_exists,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            }))),
             _ => {
                 not_impl_err!("Unsupported SQL statement: {sql:?}")
             }
@@ -1086,6 +1160,20 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(options_map)
     }
 
+    /// Generate a plan for REFRESH MATERIALIZED VIEW
+    fn refresh_materialized_view_to_plan(
+        &self,
+        statement: RefreshMaterializedViewStatement,
+    ) -> Result<LogicalPlan> {
+        let name = self.object_name_to_table_reference(statement.name)?;
+        Ok(LogicalPlan::Ddl(DdlStatement::RefreshMaterializedView(
+            RefreshMaterializedView {
+                name,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            },
+        )))
+    }
+
     /// Generate a plan for EXPLAIN ... that will print out a plan
     ///
     /// Note this is the sqlparser explain statement, not the
@@ -1394,6 +1482,163 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(plan)
     }
 
+    fn merge_to_plan(
+        &self,
+        table: TableFactor,
+        source: TableFactor,
+        on: SQLExpr,
+        clauses: Vec<ast::MergeClause>,
+    ) -> Result<LogicalPlan> {
+        let (table_name, table_alias) = match table {
+            TableFactor::Table { name, alias, .. } => (name, alias),
+            _ => plan_err!("Cannot merge into non-table relation!")?,
+        };
+
+        // Do a table lookup to verify the table exists
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let table_source = self.context_provider.get_table_source(table_name.clone())?;
+        // If the target table has an alias, use it to qualify the column names
+        let qualifier = match table_alias {
+            Some(alias) => {
+                TableReference::bare(self.ident_normalizer.normalize(alias.name))
+            }
+            None => table_name.clone(),
+        };
+        let table_schema = Arc::new(DFSchema::try_from_qualified_schema(
+            qualifier,
+            &table_source.schema(),
+        )?);
+
+        let mut planner_context = PlannerContext::new();
+        let source = self.create_relation(source, &mut planner_context)?;
+        let schema = table_schema.join(source.schema())?;
+
+        let on = self.sql_to_expr(on, &schema, &mut planner_context)?;
+        let clauses = clauses
+            .into_iter()
+            .map(|clause| {
+                let kind = match clause.clause_kind {
+                    ast::MergeClauseKind::Matched => MergeClauseKind::Matched,
+                    ast::MergeClauseKind::NotMatched
+                    | ast::MergeClauseKind::NotMatchedByTarget => {
+                        MergeClauseKind::NotMatched
+                    }
+                    ast::MergeClauseKind::NotMatchedBySource => {
+                        MergeClauseKind::NotMatchedBySource
+                    }
+                };
+                let predicate = clause
+                    .predicate
+                    .map(|predicate| {
+                        self.sql_to_expr(predicate, &schema, &mut planner_context)
+                    })
+                    .transpose()?;
+                let action = match clause.action {
+                    ast::MergeAction::Update { assignments } => {
+                        let assignments = assignments
+                            .into_iter()
+                            .map(|assign| {
+                                let cols = match &assign.target {
+                                    AssignmentTarget::ColumnName(cols) => cols,
+                                    _ => plan_err!("Tuples are not supported")?,
+                                };
+                                let col_name = cols.0.iter().last().ok_or_else(|| {
+                                    plan_datafusion_err!("Empty column id")
+                                })?;
+                                let col_name =
+                                    self.ident_normalizer.normalize(col_name.clone());
+                                // Validate that the assignment target column exists
+                                let field = table_schema
+                                    .field_with_unqualified_name(&col_name)?;
+                                let value = self
+                                    .sql_to_expr(
+                                        assign.value,
+                                        &schema,
+                                        &mut planner_context,
+                                    )?
+                                    .cast_to(field.data_type(), &schema)?;
+                                Ok((col_name, value))
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        MergeAction::Update(assignments)
+                    }
+                    ast::MergeAction::Delete => MergeAction::Delete,
+                    ast::MergeAction::Insert(insert) => {
+                        let ast::MergeInsertKind::Values(values) = insert.kind else {
+                            return not_impl_err!("MERGE INSERT ROW is not supported");
+                        };
+                        let [row] = values.rows.as_slice() else {
+                            return plan_err!(
+                                "MERGE INSERT requires exactly one row of values"
+                            );
+                        };
+                        let columns = if insert.columns.is_empty() {
+                            table_schema
+                                .fields()
+                                .iter()
+                                .map(|f| f.name().clone())
+                                .collect()
+                        } else {
+                            insert
+                                .columns
+                                .into_iter()
+                                .map(|c| self.ident_normalizer.normalize(c))
+                                .collect::<Vec<_>>()
+                        };
+                        if columns.len() != row.len() {
+                            plan_err!("Column count doesn't match insert query!")?;
+                        }
+                        let mut values = HashMap::new();
+                        for (column, value) in columns.into_iter().zip(row) {
+                            table_schema.field_with_unqualified_name(&column)?;
+                            if values.contains_key(&column) {
+                                return schema_err!(
+                                    SchemaError::DuplicateUnqualifiedField {
+                                        name: column,
+                                    }
+                                );
+                            }
+                            values.insert(column, value.clone());
+                        }
+                        let values = table_schema
+                            .fields()
+                            .iter()
+                            .map(|field| {
+                                let value = match values.remove(field.name()) {
+                                    Some(value) => self.sql_to_expr(
+                                        value,
+                                        &schema,
+                                        &mut planner_context,
+                                    )?,
+                                    // The value is not specified. Fill in the default value for the column.
+                                    None => table_source
+                                        .get_column_default(field.name())
+                                        .cloned()
+                                        .unwrap_or(Expr::Literal(ScalarValue::Null)),
+                                };
+                                value.cast_to(field.data_type(), &schema)
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        MergeAction::Insert(values)
+                    }
+                };
+                Ok(MergeClause {
+                    kind,
+                    predicate,
+                    action,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let plan = LogicalPlan::Dml(DmlStatement::new(
+            table_name,
+            table_schema,
+            WriteOp::Merge(MergeOp { on, clauses }),
+            Arc::new(source),
+        ));
+        Ok(plan)
+    }
+
     fn insert_to_plan(
         &self,
         table_name: ObjectName,
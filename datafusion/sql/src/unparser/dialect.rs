@@ -15,15 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use arrow_schema::TimeUnit;
+use datafusion_common::{not_impl_err, Result, ScalarValue};
+use datafusion_expr::Expr;
 use regex::Regex;
 use sqlparser::{
-    ast::{self, Ident, ObjectName, TimezoneInfo},
+    ast::{self, CharacterLength, Ident, ObjectName, TimezoneInfo},
     keywords::ALL_KEYWORDS,
 };
 
+use super::{expr::function_to_sql, Unparser};
+
 /// `Dialect` to use for Unparsing
 ///
 /// The default dialect tries to avoid quoting identifiers unless necessary (e.g. `a` instead of `"a"`)
@@ -42,6 +46,18 @@ pub trait Dialect: Send + Sync {
         true
     }
 
+    /// Does the dialect use `SELECT TOP n` rather than `LIMIT n` to limit the result?
+    /// E.g. MS SQL Server does not support LIMIT
+    fn use_top_for_limit(&self) -> bool {
+        false
+    }
+
+    /// Does the dialect support `QUALIFY` to filter on the results of window functions?
+    /// E.g. BigQuery, Snowflake and DuckDB
+    fn supports_qualify(&self) -> bool {
+        false
+    }
+
     /// Does the dialect use TIMESTAMP to represent Date64 rather than DATETIME?
     /// E.g. Trino, Athena and Dremio does not have DATETIME data type
     fn use_timestamp_for_date64(&self) -> bool {
@@ -96,6 +112,18 @@ pub trait Dialect: Send + Sync {
 
         ast::DataType::Timestamp(None, tz_info)
     }
+
+    /// Allows the dialect to override the unparsing of a scalar function call,
+    /// e.g. to rename the function or to rewrite it into a different expression.
+    /// Returns `None` to unparse the call as a regular function call
+    fn scalar_function_to_sql_overrides(
+        &self,
+        _unparser: &Unparser,
+        _func_name: &str,
+        _args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        Ok(None)
+    }
 }
 
 /// `IntervalStyle` to use for unparsing
@@ -106,11 +134,14 @@ pub trait Dialect: Send + Sync {
 /// compatible with arrow display format, as well as duckdb
 /// sql standard format is '1-2' for year-month, or '1 10:10:10.123456' for day-time
 /// <https://www.contrib.andrew.cmu.edu/~shadow/sql/sql1992.txt>
+/// dateadd: SQL Server has no interval literals, so intervals added to or subtracted
+/// from dates are unparsed as `DATEADD(MONTH, 1, date)`
 #[derive(Clone, Copy)]
 pub enum IntervalStyle {
     PostgresVerbose,
     SQLStandard,
     MySQL,
+    DateAdd,
 }
 
 /// Datetime subfield extraction style for unparsing
@@ -198,6 +229,18 @@ impl Dialect for MySqlDialect {
     ) -> ast::DataType {
         ast::DataType::Datetime(None)
     }
+
+    fn scalar_function_to_sql_overrides(
+        &self,
+        unparser: &Unparser,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        match func_name {
+            "regexp_like" => regexp_like_to_sql(unparser, args),
+            _ => Ok(None),
+        }
+    }
 }
 
 pub struct SqliteDialect {}
@@ -206,11 +249,252 @@ impl Dialect for SqliteDialect {
     fn identifier_quote_style(&self, _: &str) -> Option<char> {
         Some('`')
     }
+
+    fn scalar_function_to_sql_overrides(
+        &self,
+        unparser: &Unparser,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        match func_name {
+            "character_length" => {
+                unparser.scalar_function_to_sql("LENGTH", args).map(Some)
+            }
+            "regexp_like" => regexp_like_to_sql(unparser, args),
+            _ => Ok(None),
+        }
+    }
+}
+
+pub struct BigQueryDialect {}
+
+impl Dialect for BigQueryDialect {
+    fn identifier_quote_style(&self, _: &str) -> Option<char> {
+        Some('`')
+    }
+
+    fn supports_qualify(&self) -> bool {
+        true
+    }
+
+    fn interval_style(&self) -> IntervalStyle {
+        IntervalStyle::MySQL
+    }
+
+    fn float64_ast_dtype(&self) -> sqlparser::ast::DataType {
+        sqlparser::ast::DataType::Float64
+    }
+
+    fn utf8_cast_dtype(&self) -> ast::DataType {
+        ast::DataType::String(None)
+    }
+
+    fn large_utf8_cast_dtype(&self) -> ast::DataType {
+        ast::DataType::String(None)
+    }
+
+    fn date_field_extract_style(&self) -> DateFieldExtractStyle {
+        DateFieldExtractStyle::Extract
+    }
+
+    fn int64_cast_dtype(&self) -> ast::DataType {
+        ast::DataType::Int64
+    }
+
+    /// BigQuery's TIMESTAMP is an absolute point in time, a timestamp without a
+    /// time zone is a DATETIME
+    fn timestamp_cast_dtype(
+        &self,
+        _time_unit: &TimeUnit,
+        tz: &Option<Arc<str>>,
+    ) -> ast::DataType {
+        match tz {
+            Some(_) => ast::DataType::Timestamp(None, TimezoneInfo::None),
+            None => ast::DataType::Datetime(None),
+        }
+    }
+
+    fn scalar_function_to_sql_overrides(
+        &self,
+        unparser: &Unparser,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        match func_name {
+            "character_length" => {
+                unparser.scalar_function_to_sql("LENGTH", args).map(Some)
+            }
+            "regexp_like" if args.len() == 2 => unparser
+                .scalar_function_to_sql("REGEXP_CONTAINS", args)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+pub struct SnowflakeDialect {}
+
+impl Dialect for SnowflakeDialect {
+    /// Quoted identifiers are case sensitive in Snowflake, so only quote when necessary
+    fn identifier_quote_style(&self, identifier: &str) -> Option<char> {
+        DefaultDialect {}.identifier_quote_style(identifier)
+    }
+
+    fn supports_qualify(&self) -> bool {
+        true
+    }
+
+    fn date_field_extract_style(&self) -> DateFieldExtractStyle {
+        DateFieldExtractStyle::Extract
+    }
+
+    fn timestamp_cast_dtype(
+        &self,
+        _time_unit: &TimeUnit,
+        tz: &Option<Arc<str>>,
+    ) -> ast::DataType {
+        let name = match tz {
+            Some(_) => "TIMESTAMP_TZ",
+            None => "TIMESTAMP_NTZ",
+        };
+        ast::DataType::Custom(ObjectName(vec![Ident::new(name)]), vec![])
+    }
+
+    fn scalar_function_to_sql_overrides(
+        &self,
+        unparser: &Unparser,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        match func_name {
+            "character_length" => {
+                unparser.scalar_function_to_sql("LENGTH", args).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+pub struct DuckDBDialect {}
+
+impl Dialect for DuckDBDialect {
+    fn identifier_quote_style(&self, _: &str) -> Option<char> {
+        Some('"')
+    }
+
+    fn supports_qualify(&self) -> bool {
+        true
+    }
+}
+
+pub struct MsSqlDialect {}
+
+impl Dialect for MsSqlDialect {
+    fn identifier_quote_style(&self, _: &str) -> Option<char> {
+        Some('[')
+    }
+
+    fn supports_nulls_first_in_sort(&self) -> bool {
+        false
+    }
+
+    fn use_top_for_limit(&self) -> bool {
+        true
+    }
+
+    fn interval_style(&self) -> IntervalStyle {
+        IntervalStyle::DateAdd
+    }
+
+    fn float64_ast_dtype(&self) -> sqlparser::ast::DataType {
+        sqlparser::ast::DataType::Float(None)
+    }
+
+    fn utf8_cast_dtype(&self) -> ast::DataType {
+        ast::DataType::Nvarchar(Some(CharacterLength::Max))
+    }
+
+    fn large_utf8_cast_dtype(&self) -> ast::DataType {
+        ast::DataType::Nvarchar(Some(CharacterLength::Max))
+    }
+
+    fn timestamp_cast_dtype(
+        &self,
+        _time_unit: &TimeUnit,
+        tz: &Option<Arc<str>>,
+    ) -> ast::DataType {
+        let name = match tz {
+            Some(_) => "DATETIMEOFFSET",
+            None => "DATETIME2",
+        };
+        ast::DataType::Custom(ObjectName(vec![Ident::new(name)]), vec![])
+    }
+
+    fn scalar_function_to_sql_overrides(
+        &self,
+        unparser: &Unparser,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        match func_name {
+            "character_length" => unparser.scalar_function_to_sql("LEN", args).map(Some),
+            "date_part" => datepart_to_sql(unparser, args).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Unparse `date_part('<field>', expr)` as `DATEPART(<datepart>, expr)`, which
+/// requires the field to be a literal
+fn datepart_to_sql(unparser: &Unparser, args: &[Expr]) -> Result<ast::Expr> {
+    let [Expr::Literal(ScalarValue::Utf8(Some(field))), expr] = args else {
+        return not_impl_err!("DATEPART requires a literal date part");
+    };
+    let datepart = match field.to_lowercase().as_str() {
+        "year" => "YEAR",
+        "quarter" => "QUARTER",
+        "month" => "MONTH",
+        "week" => "WEEK",
+        "day" => "DAY",
+        "doy" => "DAYOFYEAR",
+        "dow" => "WEEKDAY",
+        "hour" => "HOUR",
+        "minute" => "MINUTE",
+        "second" => "SECOND",
+        "millisecond" => "MILLISECOND",
+        "microsecond" => "MICROSECOND",
+        "nanosecond" => "NANOSECOND",
+        _ => return not_impl_err!("Unsupported date part {field} for DATEPART"),
+    };
+    Ok(function_to_sql(
+        "DATEPART",
+        vec![
+            ast::Expr::Identifier(Ident::new(datepart)),
+            unparser.expr_to_sql(expr)?,
+        ],
+    ))
+}
+
+/// Unparse `regexp_like(str, pattern)` as `str REGEXP pattern`, calls with flags
+/// are left as is
+fn regexp_like_to_sql(unparser: &Unparser, args: &[Expr]) -> Result<Option<ast::Expr>> {
+    let [expr, pattern] = args else {
+        return Ok(None);
+    };
+
+    Ok(Some(ast::Expr::RLike {
+        negated: false,
+        expr: Box::new(unparser.expr_to_sql(expr)?),
+        pattern: Box::new(unparser.expr_to_sql(pattern)?),
+        regexp: true,
+    }))
 }
 
 pub struct CustomDialect {
     identifier_quote_style: Option<char>,
     supports_nulls_first_in_sort: bool,
+    use_top_for_limit: bool,
+    supports_qualify: bool,
     use_timestamp_for_date64: bool,
     interval_style: IntervalStyle,
     float64_ast_dtype: sqlparser::ast::DataType,
@@ -220,6 +504,7 @@ pub struct CustomDialect {
     int64_cast_dtype: ast::DataType,
     timestamp_cast_dtype: ast::DataType,
     timestamp_tz_cast_dtype: ast::DataType,
+    scalar_function_renames: HashMap<String, String>,
 }
 
 impl Default for CustomDialect {
@@ -227,6 +512,8 @@ impl Default for CustomDialect {
         Self {
             identifier_quote_style: None,
             supports_nulls_first_in_sort: true,
+            use_top_for_limit: false,
+            supports_qualify: false,
             use_timestamp_for_date64: false,
             interval_style: IntervalStyle::SQLStandard,
             float64_ast_dtype: sqlparser::ast::DataType::Double,
@@ -239,6 +526,7 @@ impl Default for CustomDialect {
                 None,
                 TimezoneInfo::WithTimeZone,
             ),
+            scalar_function_renames: HashMap::new(),
         }
     }
 }
@@ -263,6 +551,14 @@ impl Dialect for CustomDialect {
         self.supports_nulls_first_in_sort
     }
 
+    fn use_top_for_limit(&self) -> bool {
+        self.use_top_for_limit
+    }
+
+    fn supports_qualify(&self) -> bool {
+        self.supports_qualify
+    }
+
     fn use_timestamp_for_date64(&self) -> bool {
         self.use_timestamp_for_date64
     }
@@ -302,6 +598,18 @@ impl Dialect for CustomDialect {
             self.timestamp_cast_dtype.clone()
         }
     }
+
+    fn scalar_function_to_sql_overrides(
+        &self,
+        unparser: &Unparser,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Option<ast::Expr>> {
+        match self.scalar_function_renames.get(func_name) {
+            Some(new_name) => unparser.scalar_function_to_sql(new_name, args).map(Some),
+            None => Ok(None),
+        }
+    }
 }
 
 /// `CustomDialectBuilder` to build `CustomDialect` using builder pattern
@@ -321,6 +629,8 @@ impl Dialect for CustomDialect {
 pub struct CustomDialectBuilder {
     identifier_quote_style: Option<char>,
     supports_nulls_first_in_sort: bool,
+    use_top_for_limit: bool,
+    supports_qualify: bool,
     use_timestamp_for_date64: bool,
     interval_style: IntervalStyle,
     float64_ast_dtype: sqlparser::ast::DataType,
@@ -330,6 +640,7 @@ pub struct CustomDialectBuilder {
     int64_cast_dtype: ast::DataType,
     timestamp_cast_dtype: ast::DataType,
     timestamp_tz_cast_dtype: ast::DataType,
+    scalar_function_renames: HashMap<String, String>,
 }
 
 impl Default for CustomDialectBuilder {
@@ -343,6 +654,8 @@ impl CustomDialectBuilder {
         Self {
             identifier_quote_style: None,
             supports_nulls_first_in_sort: true,
+            use_top_for_limit: false,
+            supports_qualify: false,
             use_timestamp_for_date64: false,
             interval_style: IntervalStyle::PostgresVerbose,
             float64_ast_dtype: sqlparser::ast::DataType::Double,
@@ -355,6 +668,7 @@ impl CustomDialectBuilder {
                 None,
                 TimezoneInfo::WithTimeZone,
             ),
+            scalar_function_renames: HashMap::new(),
         }
     }
 
@@ -362,6 +676,8 @@ impl CustomDialectBuilder {
         CustomDialect {
             identifier_quote_style: self.identifier_quote_style,
             supports_nulls_first_in_sort: self.supports_nulls_first_in_sort,
+            use_top_for_limit: self.use_top_for_limit,
+            supports_qualify: self.supports_qualify,
             use_timestamp_for_date64: self.use_timestamp_for_date64,
             interval_style: self.interval_style,
             float64_ast_dtype: self.float64_ast_dtype,
@@ -371,6 +687,7 @@ impl CustomDialectBuilder {
             int64_cast_dtype: self.int64_cast_dtype,
             timestamp_cast_dtype: self.timestamp_cast_dtype,
             timestamp_tz_cast_dtype: self.timestamp_tz_cast_dtype,
+            scalar_function_renames: self.scalar_function_renames,
         }
     }
 
@@ -389,6 +706,18 @@ impl CustomDialectBuilder {
         self
     }
 
+    /// Customize the dialect to use `SELECT TOP n` rather than `LIMIT n`
+    pub fn with_use_top_for_limit(mut self, use_top_for_limit: bool) -> Self {
+        self.use_top_for_limit = use_top_for_limit;
+        self
+    }
+
+    /// Customize the dialect to support `QUALIFY` for filters on window functions
+    pub fn with_supports_qualify(mut self, supports_qualify: bool) -> Self {
+        self.supports_qualify = supports_qualify;
+        self
+    }
+
     /// Customize the dialect to uses TIMESTAMP when casting Date64 rather than DATETIME
     pub fn with_use_timestamp_for_date64(
         mut self,
@@ -453,4 +782,15 @@ impl CustomDialectBuilder {
         self.timestamp_tz_cast_dtype = timestamp_tz_cast_dtype;
         self
     }
+
+    /// Customize the dialect to unparse calls to the scalar function `func_name` as `new_name`
+    pub fn with_scalar_function_rename(
+        mut self,
+        func_name: impl Into<String>,
+        new_name: impl Into<String>,
+    ) -> Self {
+        self.scalar_function_renames
+            .insert(func_name.into(), new_name.into());
+        self
+    }
 }
@@ -42,7 +42,10 @@ use datafusion_common::{
     ScalarValue,
 };
 use datafusion_expr::{
-    expr::{Alias, Exists, InList, ScalarFunction, Sort, WindowFunction},
+    expr::{
+        Alias, Exists, InList, Lambda, LambdaVariable, ScalarFunction, Sort,
+        WindowFunction,
+    },
     Between, BinaryExpr, Case, Cast, Expr, GroupingSet, Like, Operator, TryCast,
 };
 
@@ -149,47 +152,20 @@ impl Unparser<'_> {
             Expr::ScalarFunction(ScalarFunction { func, args }) => {
                 let func_name = func.name();
 
+                if let Some(expr) = self
+                    .dialect
+                    .scalar_function_to_sql_overrides(self, func_name, args)?
+                {
+                    return Ok(expr);
+                }
+
                 if let Some(expr) =
                     self.scalar_function_to_sql_overrides(func_name, func, args)
                 {
                     return Ok(expr);
                 }
 
-                let args = args
-                    .iter()
-                    .map(|e| {
-                        if matches!(
-                            e,
-                            Expr::Wildcard {
-                                qualifier: None,
-                                ..
-                            }
-                        ) {
-                            Ok(FunctionArg::Unnamed(ast::FunctionArgExpr::Wildcard))
-                        } else {
-                            self.expr_to_sql_inner(e).map(|e| {
-                                FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(e))
-                            })
-                        }
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                Ok(ast::Expr::Function(Function {
-                    name: ast::ObjectName(vec![Ident {
-                        value: func_name.to_string(),
-                        quote_style: None,
-                    }]),
-                    args: ast::FunctionArguments::List(ast::FunctionArgumentList {
-                        duplicate_treatment: None,
-                        args,
-                        clauses: vec![],
-                    }),
-                    filter: None,
-                    null_treatment: None,
-                    over: None,
-                    within_group: vec![],
-                    parameters: ast::FunctionArguments::None,
-                }))
+                self.scalar_function_to_sql(func_name, args)
             }
             Expr::Between(Between {
                 expr,
@@ -209,6 +185,11 @@ impl Unparser<'_> {
             }
             Expr::Column(col) => self.col_to_sql(col),
             Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                if let IntervalStyle::DateAdd = self.dialect.interval_style() {
+                    if let Some(expr) = self.dateadd_to_sql(left, op, right)? {
+                        return Ok(expr);
+                    }
+                }
                 let l = self.expr_to_sql_inner(left.as_ref())?;
                 let r = self.expr_to_sql_inner(right.as_ref())?;
                 let op = self.op_to_sql(op)?;
@@ -530,6 +511,24 @@ impl Unparser<'_> {
                 Ok(ast::Expr::Value(ast::Value::Placeholder(p.id.to_string())))
             }
             Expr::OuterReferenceColumn(_, col) => self.col_to_sql(col),
+            Expr::Lambda(Lambda { params, body }) => {
+                let mut params = params
+                    .iter()
+                    .map(|p| self.new_ident_quoted_if_needs(p.to_string()))
+                    .collect::<Vec<_>>();
+                let params = if params.len() == 1 {
+                    ast::OneOrManyWithParens::One(params.remove(0))
+                } else {
+                    ast::OneOrManyWithParens::Many(params)
+                };
+                Ok(ast::Expr::Lambda(ast::LambdaFunction {
+                    params,
+                    body: Box::new(self.expr_to_sql_inner(body)?),
+                }))
+            }
+            Expr::LambdaVariable(LambdaVariable { name, .. }) => Ok(
+                ast::Expr::Identifier(self.new_ident_quoted_if_needs(name.to_string())),
+            ),
             Expr::Unnest(_) => not_impl_err!("Unsupported Expr conversion: {expr:?}"),
         }
     }
@@ -566,6 +565,49 @@ impl Unparser<'_> {
         }
     }
 
+    /// Convert a call to the scalar function `func_name` to its SQL representation,
+    /// without applying any dialect specific overrides
+    pub fn scalar_function_to_sql(
+        &self,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<ast::Expr> {
+        let args = args
+            .iter()
+            .map(|e| {
+                if matches!(
+                    e,
+                    Expr::Wildcard {
+                        qualifier: None,
+                        ..
+                    }
+                ) {
+                    Ok(FunctionArg::Unnamed(ast::FunctionArgExpr::Wildcard))
+                } else {
+                    self.expr_to_sql_inner(e)
+                        .map(|e| FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(e)))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ast::Expr::Function(Function {
+            name: ast::ObjectName(vec![Ident {
+                value: func_name.to_string(),
+                quote_style: None,
+            }]),
+            args: ast::FunctionArguments::List(ast::FunctionArgumentList {
+                duplicate_treatment: None,
+                args,
+                clauses: vec![],
+            }),
+            filter: None,
+            null_treatment: None,
+            over: None,
+            within_group: vec![],
+            parameters: ast::FunctionArguments::None,
+        }))
+    }
+
     fn scalar_function_to_sql_overrides(
         &self,
         func_name: &str,
@@ -1165,6 +1207,80 @@ impl Unparser<'_> {
         }
     }
 
+    /// Unparses `<date> + <interval>`, `<interval> + <date>` and `<date> - <interval>`
+    /// as nested `DATEADD(<datepart>, <number>, <date>)` calls for
+    /// `IntervalStyle::DateAdd`, adding the months first, then the days and then
+    /// the remaining time like DataFusion does. Returns `None` for other expressions
+    fn dateadd_to_sql(
+        &self,
+        left: &Expr,
+        op: &Operator,
+        right: &Expr,
+    ) -> Result<Option<ast::Expr>> {
+        let interval = |expr: &Expr| match expr {
+            Expr::Literal(v) if matches!(v.data_type(), DataType::Interval(_)) => {
+                Some(v.clone())
+            }
+            _ => None,
+        };
+        let (date, interval, sign) = match (op, interval(left), interval(right)) {
+            (Operator::Plus, None, Some(interval)) => (left, interval, 1),
+            (Operator::Plus, Some(interval), None) => (right, interval, 1),
+            (Operator::Minus, None, Some(interval)) => (left, interval, -1),
+            _ => return Ok(None),
+        };
+        let (months, days, nanoseconds) = match interval {
+            ScalarValue::IntervalYearMonth(Some(v)) => (v, 0, 0),
+            ScalarValue::IntervalDayTime(Some(v)) => {
+                (0, v.days, v.milliseconds as i64 * 1_000_000)
+            }
+            ScalarValue::IntervalMonthDayNano(Some(v)) => {
+                (v.months, v.days, v.nanoseconds)
+            }
+            _ => {
+                return not_impl_err!(
+                    "Unsupported ScalarValue for Interval conversion: {interval:?}"
+                )
+            }
+        };
+        // the coarsest unit that represents the time exactly
+        let (time_unit, unit_nanoseconds) = [
+            ("HOUR", 3_600_000_000_000),
+            ("MINUTE", 60_000_000_000),
+            ("SECOND", 1_000_000_000),
+            ("MILLISECOND", 1_000_000),
+            ("MICROSECOND", 1_000),
+        ]
+        .into_iter()
+        .find(|(_, unit_nanoseconds)| nanoseconds % unit_nanoseconds == 0)
+        .unwrap_or(("NANOSECOND", 1));
+
+        let mut expr = self.expr_to_sql_inner(date)?;
+        for (datepart, number) in [
+            ("MONTH", months as i64),
+            ("DAY", days as i64),
+            (time_unit, nanoseconds / unit_nanoseconds),
+        ] {
+            if number == 0 {
+                continue;
+            }
+            let Ok(number) = i32::try_from(sign * number) else {
+                return not_impl_err!(
+                    "Unsupported Interval scalar exceeding the range of DATEADD for IntervalStyle::DateAdd"
+                );
+            };
+            expr = function_to_sql(
+                "DATEADD",
+                vec![
+                    ast::Expr::Identifier(Ident::new(datepart)),
+                    ast::Expr::Value(ast::Value::Number(number.to_string(), false)),
+                    expr,
+                ],
+            );
+        }
+        Ok(Some(expr))
+    }
+
     /// MySQL requires INTERVAL sql to be in the format: INTERVAL 1 YEAR + INTERVAL 1 MONTH + INTERVAL 1 DAY etc
     /// `<https://dev.mysql.com/doc/refman/8.4/en/expressions.html#temporal-intervals>`
     /// Interval sequence can't be wrapped in brackets - (INTERVAL 1 YEAR + INTERVAL 1 MONTH ...) so we need to generate
@@ -1390,6 +1506,9 @@ impl Unparser<'_> {
                     "Unsupported ScalarValue for Interval conversion: {v:?}"
                 ),
             },
+            IntervalStyle::DateAdd => not_impl_err!(
+                "Intervals are only supported when added to or subtracted from a date for IntervalStyle::DateAdd"
+            ),
             IntervalStyle::MySQL => match v {
                 ScalarValue::IntervalYearMonth(Some(v)) => {
                     self.interval_to_mysql_expr(*v, 0, 0)
@@ -1512,6 +1631,26 @@ impl Unparser<'_> {
     }
 }
 
+/// Builds the call `<func_name>(<args>)`
+pub(super) fn function_to_sql(func_name: &str, args: Vec<ast::Expr>) -> ast::Expr {
+    ast::Expr::Function(Function {
+        name: ast::ObjectName(vec![Ident::new(func_name)]),
+        args: ast::FunctionArguments::List(ast::FunctionArgumentList {
+            duplicate_treatment: None,
+            args: args
+                .into_iter()
+                .map(|arg| FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(arg)))
+                .collect(),
+            clauses: vec![],
+        }),
+        filter: None,
+        null_treatment: None,
+        over: None,
+        within_group: vec![],
+        parameters: ast::FunctionArguments::None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, Sub};
@@ -1534,7 +1673,10 @@ mod tests {
     use datafusion_functions_aggregate::expr_fn::sum;
     use datafusion_functions_window::row_number::row_number_udwf;
 
-    use crate::unparser::dialect::{CustomDialect, CustomDialectBuilder};
+    use crate::unparser::dialect::{
+        BigQueryDialect, CustomDialect, CustomDialectBuilder, DefaultDialect, Dialect,
+        MsSqlDialect, MySqlDialect,
+    };
 
     use super::*;
 
@@ -2205,6 +2347,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_interval_dateadd_to_expr() -> Result<()> {
+        let tests = [
+            (
+                col("a").add(interval_month_day_nano_lit("1 YEAR 2 DAY")),
+                "DATEADD(DAY, 2, DATEADD(MONTH, 12, [a]))",
+            ),
+            (
+                interval_datetime_lit("1.5 HOUR").add(col("a")),
+                "DATEADD(MINUTE, 90, [a])",
+            ),
+            (
+                col("a").sub(interval_month_day_nano_lit("1 DAY 1 MILLISECOND")),
+                "DATEADD(MILLISECOND, -1, DATEADD(DAY, -1, [a]))",
+            ),
+        ];
+        let unparser = Unparser::new(&MsSqlDialect {});
+        for (expr, expected) in tests {
+            let actual = format!("{}", unparser.expr_to_sql(&expr)?);
+            assert_eq!(actual, expected);
+        }
+
+        let err = unparser
+            .expr_to_sql(&interval_month_day_nano_lit("1 DAY"))
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "This feature is not implemented: Intervals are only supported when added to or subtracted from a date for IntervalStyle::DateAdd"
+        );
+        Ok(())
+    }
+
     #[test]
     fn custom_dialect_use_char_for_utf8_cast() -> Result<()> {
         let default_dialect = CustomDialectBuilder::default().build();
@@ -2349,4 +2523,65 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn dialect_scalar_function_to_sql_overrides() -> Result<()> {
+        let character_length =
+            datafusion_functions::unicode::expr_fn::character_length(col("a"));
+        let regexp_like =
+            datafusion_functions::regex::expr_fn::regexp_like(col("a"), lit("^b"), None);
+        let regexp_like_with_flags = datafusion_functions::regex::expr_fn::regexp_like(
+            col("a"),
+            lit("^b"),
+            Some(lit("i")),
+        );
+        let date_part = ScalarUDF::new_from_impl(
+            datafusion_functions::datetime::date_part::DatePartFunc::new(),
+        )
+        .call(vec![lit("YEAR"), col("a")]);
+
+        let tests: Vec<(&dyn Dialect, &Expr, &str)> = vec![
+            (&DefaultDialect {}, &character_length, "character_length(a)"),
+            (&MsSqlDialect {}, &character_length, "LEN([a])"),
+            (&BigQueryDialect {}, &character_length, "LENGTH(`a`)"),
+            (&DefaultDialect {}, &regexp_like, "regexp_like(a, '^b')"),
+            (&MySqlDialect {}, &regexp_like, "`a` REGEXP '^b'"),
+            (
+                &MySqlDialect {},
+                &regexp_like_with_flags,
+                "regexp_like(`a`, '^b', 'i')",
+            ),
+            (
+                &BigQueryDialect {},
+                &regexp_like,
+                "REGEXP_CONTAINS(`a`, '^b')",
+            ),
+            (&BigQueryDialect {}, &date_part, "EXTRACT(YEAR FROM `a`)"),
+            (&MsSqlDialect {}, &date_part, "DATEPART(YEAR, [a])"),
+        ];
+
+        for (dialect, expr, expected) in tests {
+            let unparser = Unparser::new(dialect);
+            let actual = format!("{}", unparser.expr_to_sql(expr)?);
+
+            assert_eq!(actual, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn custom_dialect_with_scalar_function_rename() -> Result<()> {
+        let dialect = CustomDialectBuilder::new()
+            .with_scalar_function_rename("character_length", "CHAR_LENGTH")
+            .build();
+        let unparser = Unparser::new(&dialect);
+        let expr = datafusion_functions::unicode::expr_fn::character_length(col("a"));
+        let ast = unparser.expr_to_sql(&expr)?;
+
+        let actual = format!("{}", ast);
+        let expected = "CHAR_LENGTH(a)";
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
 }
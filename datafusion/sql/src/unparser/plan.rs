@@ -110,6 +110,8 @@ impl Unparser<'_> {
             | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::RecursiveQuery(_)
+            | LogicalPlan::Sample(_)
+            | LogicalPlan::AsOfJoin(_)
             | LogicalPlan::Unnest(_) => not_impl_err!("Unsupported plan: {plan:?}"),
         }
     }
@@ -270,15 +272,27 @@ impl Unparser<'_> {
                 self.select_to_sql_recursively(p.input.as_ref(), query, select, relation)
             }
             LogicalPlan::Filter(filter) => {
-                if let Some(AggVariant::Aggregate(agg)) =
-                    find_agg_node_within_select(plan, None, select.already_projected())
+                match find_agg_node_within_select(plan, None, select.already_projected())
                 {
-                    let unprojected = unproject_agg_exprs(&filter.predicate, agg)?;
-                    let filter_expr = self.expr_to_sql(&unprojected)?;
-                    select.having(Some(filter_expr));
-                } else {
-                    let filter_expr = self.expr_to_sql(&filter.predicate)?;
-                    select.selection(Some(filter_expr));
+                    Some(AggVariant::Aggregate(agg)) => {
+                        let unprojected = unproject_agg_exprs(&filter.predicate, agg)?;
+                        let filter_expr = self.expr_to_sql(&unprojected)?;
+                        select.having(Some(filter_expr));
+                    }
+                    // Filters on the output of window functions are only expressible
+                    // inline for dialects supporting QUALIFY
+                    Some(AggVariant::Window(windows))
+                        if self.dialect.supports_qualify() =>
+                    {
+                        let unprojected =
+                            unproject_window_exprs(&filter.predicate, &windows)?;
+                        let filter_expr = self.expr_to_sql(&unprojected)?;
+                        select.qualify(Some(filter_expr));
+                    }
+                    _ => {
+                        let filter_expr = self.expr_to_sql(&filter.predicate)?;
+                        select.selection(Some(filter_expr));
+                    }
                 }
 
                 self.select_to_sql_recursively(
@@ -299,10 +313,28 @@ impl Unparser<'_> {
                             "Limit operator only valid in a statement context."
                         );
                     };
-                    query.limit(Some(ast::Expr::Value(ast::Value::Number(
-                        fetch.to_string(),
-                        false,
-                    ))));
+                    if self.dialect.use_top_for_limit() {
+                        // TOP is part of the SELECT clause, so it can't limit a set operation
+                        let mut input = limit.input.as_ref();
+                        if let LogicalPlan::Sort(sort) = input {
+                            input = sort.input.as_ref();
+                        }
+                        if matches!(input, LogicalPlan::Union(_)) {
+                            return not_impl_err!(
+                                "Unsupported LIMIT on UNION for dialects using TOP"
+                            );
+                        }
+                        select.top(Some(ast::Top {
+                            with_ties: false,
+                            percent: false,
+                            quantity: Some(ast::TopQuantity::Constant(fetch as u64)),
+                        }));
+                    } else {
+                        query.limit(Some(ast::Expr::Value(ast::Value::Number(
+                            fetch.to_string(),
+                            false,
+                        ))));
+                    }
                 }
 
                 self.select_to_sql_recursively(
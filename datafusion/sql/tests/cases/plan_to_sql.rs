@@ -24,8 +24,10 @@ use datafusion_expr::test::function_stub::{count_udaf, max_udaf, min_udaf, sum_u
 use datafusion_expr::{col, table_scan};
 use datafusion_sql::planner::{ContextProvider, PlannerContext, SqlToRel};
 use datafusion_sql::unparser::dialect::{
-    DefaultDialect as UnparserDefaultDialect, Dialect as UnparserDialect,
-    MySqlDialect as UnparserMySqlDialect,
+    BigQueryDialect as UnparserBigQueryDialect, DefaultDialect as UnparserDefaultDialect,
+    Dialect as UnparserDialect, DuckDBDialect as UnparserDuckDBDialect,
+    MsSqlDialect as UnparserMsSqlDialect, MySqlDialect as UnparserMySqlDialect,
+    SnowflakeDialect as UnparserSnowflakeDialect,
 };
 use datafusion_sql::unparser::{expr_to_sql, plan_to_sql, Unparser};
 
@@ -238,6 +240,60 @@ fn roundtrip_statement_with_dialect() -> Result<()> {
             parser_dialect: Box::new(GenericDialect {}),
             unparser_dialect: Box::new(UnparserDefaultDialect {}),
         },
+        TestStatementWithDialect {
+            sql: "select ta.j1_id from j1 ta order by j1_id limit 10;",
+            expected: r#"SELECT TOP 10 [ta].[j1_id] FROM [j1] AS [ta] ORDER BY [ta].[j1_id] ASC"#,
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserMsSqlDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: "SELECT CAST(j1_id AS BIGINT), CAST(j1_string AS VARCHAR) FROM j1",
+            expected: "SELECT CAST(`j1`.`j1_id` AS INT64), CAST(`j1`.`j1_string` AS STRING) FROM `j1`",
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserBigQueryDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: "SELECT j1_id, max(j1_id) OVER (PARTITION BY j1_string) AS m FROM j1 QUALIFY m > 10",
+            expected: "SELECT `j1`.`j1_id`, max(`j1`.`j1_id`) OVER (PARTITION BY `j1`.`j1_string` ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS `m` FROM `j1` QUALIFY (max(`j1`.`j1_id`) OVER (PARTITION BY `j1`.`j1_string` ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) > 10)",
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserBigQueryDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: r#"SELECT j1_id AS "Id 1" FROM j1"#,
+            expected: r#"SELECT j1.j1_id AS "Id 1" FROM j1"#,
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserSnowflakeDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: "SELECT CAST(j1_id AS BIGINT), CAST(j1_string AS TIMESTAMP) FROM j1",
+            expected: "SELECT CAST(j1.j1_id AS BIGINT), CAST(j1.j1_string AS TIMESTAMP_NTZ) FROM j1",
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserSnowflakeDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: "SELECT j1_id, max(j1_id) OVER (PARTITION BY j1_string) AS m FROM j1 QUALIFY m > 10",
+            expected: "SELECT j1.j1_id, max(j1.j1_id) OVER (PARTITION BY j1.j1_string ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS m FROM j1 QUALIFY (max(j1.j1_id) OVER (PARTITION BY j1.j1_string ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) > 10)",
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserSnowflakeDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: r#"SELECT j1_id AS "Id 1" FROM j1"#,
+            expected: r#"SELECT "j1"."j1_id" AS "Id 1" FROM "j1""#,
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserDuckDBDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: "SELECT CAST(j1_id AS BIGINT), CAST(j1_string AS TIMESTAMP) FROM j1",
+            expected: r#"SELECT CAST("j1"."j1_id" AS BIGINT), CAST("j1"."j1_string" AS TIMESTAMP) FROM "j1""#,
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserDuckDBDialect {}),
+        },
+        TestStatementWithDialect {
+            sql: "SELECT j1_id, max(j1_id) OVER (PARTITION BY j1_string) AS m FROM j1 QUALIFY m > 10",
+            expected: r#"SELECT "j1"."j1_id", max("j1"."j1_id") OVER (PARTITION BY "j1"."j1_string" ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS "m" FROM "j1" QUALIFY (max("j1"."j1_id") OVER (PARTITION BY "j1"."j1_string" ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) > 10)"#,
+            parser_dialect: Box::new(GenericDialect {}),
+            unparser_dialect: Box::new(UnparserDuckDBDialect {}),
+        },
         TestStatementWithDialect {
             sql: "SELECT j1_id FROM j1
                   UNION ALL
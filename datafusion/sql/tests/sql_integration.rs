@@ -409,6 +409,31 @@ fn plan_create_table_check_constraint() {
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_cache_table() {
+    let sql = "cache table person";
+    let plan = r#"
+CacheTable: Bare { table: "person" }
+  TableScan: person
+    "#
+    .trim();
+    quick_test(sql, plan);
+
+    let sql = "cache table adults as select id from person where age >= 18";
+    let plan = r#"
+CacheTable: Bare { table: "adults" }
+  Projection: person.id
+    Filter: person.age >= Int64(18)
+      TableScan: person
+    "#
+    .trim();
+    quick_test(sql, plan);
+
+    let sql = "uncache table if exists person";
+    let plan = "UncacheTable: Bare { table: \"person\" } if not exist:=true";
+    quick_test(sql, plan);
+}
+
 #[test]
 fn plan_start_transaction() {
     let sql = "start transaction";
@@ -620,6 +645,53 @@ Dml: op=[Delete] table=[person]
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_merge() {
+    let sql = "merge into lineitem l using orders o on l.l_item_id = o.order_id \
+        when matched and o.qty > 10 then delete \
+        when matched then update set price = o.price \
+        when not matched by source then delete \
+        when not matched then insert (l_item_id, price) values (o.order_id, o.price)";
+    let plan = r#"
+Dml: op=[Merge] table=[lineitem] on=[l.l_item_id = o.order_id] WHEN MATCHED AND o.qty > Int64(10) THEN DELETE WHEN MATCHED THEN UPDATE SET price = o.price WHEN NOT MATCHED BY SOURCE THEN DELETE WHEN NOT MATCHED THEN INSERT VALUES (o.order_id, CAST(NULL AS Utf8), o.price)
+  SubqueryAlias: o
+    TableScan: orders
+    "#
+    .trim();
+    quick_test(sql, plan);
+}
+
+#[rstest]
+#[case::missing_assignment_target(
+    "merge into lineitem using orders on l_item_id = order_id when matched then update set doesnotexist = 1"
+)]
+#[case::missing_insert_column(
+    "merge into lineitem using orders on l_item_id = order_id when not matched then insert (doesnotexist) values (1)"
+)]
+#[case::missing_on_expression(
+    "merge into lineitem using orders on doesnotexist = order_id when matched then delete"
+)]
+#[test]
+fn merge_column_does_not_exist(#[case] sql: &str) {
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_field_not_found(err, "doesnotexist");
+}
+
+#[rstest]
+#[case::column_count(
+    "merge into lineitem using orders on l_item_id = order_id when not matched then insert (l_item_id) values (order_id, price)",
+    "Error during planning: Column count doesn't match insert query!"
+)]
+#[case::multiple_rows(
+    "merge into lineitem using orders on l_item_id = order_id when not matched then insert values (1, 'a', 1.0), (2, 'b', 2.0)",
+    "Error during planning: MERGE INSERT requires exactly one row of values"
+)]
+#[test]
+fn plan_merge_error(#[case] sql: &str, #[case] expected: &str) {
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(err.strip_backtrace(), expected);
+}
+
 #[test]
 fn select_column_does_not_exist() {
     let sql = "SELECT doesnotexist FROM person";
@@ -970,6 +1042,148 @@ fn using_join_multiple_keys_multiple_joins() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_table_sample() {
+    let sql = "SELECT id FROM person AS p TABLESAMPLE BERNOULLI(10) REPEATABLE(42) WHERE age > 20";
+    let expected = "Projection: p.id\
+                        \n  Filter: p.age > Int64(20)\
+                        \n    SubqueryAlias: p\
+                        \n      Sample: method=BERNOULLI, fraction=0.1, seed=42\
+                        \n        TableScan: person";
+    quick_test(sql, expected);
+
+    let sql = "SELECT id FROM person TABLESAMPLE SYSTEM(50)";
+    let expected = "Projection: person.id\
+                        \n  Sample: method=SYSTEM, fraction=0.5, seed=None\
+                        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_table_sample_invalid() {
+    let err = logical_plan("SELECT id FROM person TABLESAMPLE BERNOULLI(110)")
+        .expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: TABLESAMPLE percentage must be between 0 and 100, got 110",
+        err.strip_backtrace()
+    );
+
+    let err = logical_plan("SELECT id FROM person TABLESAMPLE RESERVOIR(10)")
+        .expect_err("query should have failed");
+    assert_eq!(
+        "This feature is not implemented: Unsupported TABLESAMPLE method RESERVOIR",
+        err.strip_backtrace()
+    );
+
+    let err = logical_plan("SELECT id FROM person TABLESAMPLE SYSTEM(age)")
+        .expect_err("query should have failed");
+    assert_eq!(
+        "SQL error: ParserError(\"TABLESAMPLE arguments must be literals, found: age\")",
+        err.strip_backtrace()
+    );
+}
+
+#[test]
+fn select_pivot() {
+    let sql = "SELECT * FROM (SELECT id, state, salary FROM person) \
+               PIVOT(sum(salary) FOR state IN ('CO', 'TX' AS texas)) AS p";
+    let expected = "Projection: *\
+                        \n  SubqueryAlias: p\
+                        \n    Aggregate: groupBy=[[person.id]], aggr=[[sum(person.salary) FILTER (WHERE person.state = Utf8(\"CO\")) AS CO, sum(person.salary) FILTER (WHERE person.state = Utf8(\"TX\")) AS texas]]\
+                        \n      Projection: person.id, person.state, person.salary\
+                        \n        TableScan: person";
+    quick_test(sql, expected);
+
+    // several aggregates, with a default for missing values
+    let sql = "SELECT * FROM (SELECT id, state, salary FROM person) \
+               PIVOT(sum(salary) AS total, count(*) AS n FOR state IN ('CO') DEFAULT ON NULL (0))";
+    let expected = "Projection: *\
+                        \n  Projection: person.id, CASE WHEN CO_total IS NULL THEN Int64(0) ELSE CO_total END AS CO_total, CASE WHEN CO_n IS NULL THEN Int64(0) ELSE CO_n END AS CO_n\
+                        \n    Aggregate: groupBy=[[person.id]], aggr=[[sum(person.salary) FILTER (WHERE person.state = Utf8(\"CO\")) AS CO_total, count(*) FILTER (WHERE person.state = Utf8(\"CO\")) AS CO_n]]\
+                        \n      Projection: person.id, person.state, person.salary\
+                        \n        TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_pivot_unsupported() {
+    let sql = "SELECT * FROM person PIVOT(sum(salary) FOR state IN (ANY))";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "This feature is not implemented: PIVOT is only supported with a list of values, got ANY",
+        err.strip_backtrace()
+    );
+
+    let sql = "SELECT * FROM person PIVOT(abs(salary) FOR state IN ('CO'))";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: PIVOT requires aggregate functions, got abs(person.salary)",
+        err.strip_backtrace()
+    );
+}
+
+#[test]
+fn select_unpivot() {
+    let sql = "SELECT * FROM (SELECT id, first_name, last_name FROM person) \
+               UNPIVOT(name FOR kind IN (first_name, last_name))";
+    let expected = "Projection: *\
+                        \n  Union\
+                        \n    Projection: person.id, Utf8(\"first_name\") AS kind, person.first_name AS name\
+                        \n      Filter: person.first_name IS NOT NULL\
+                        \n        Projection: person.id, person.first_name, person.last_name\
+                        \n          TableScan: person\
+                        \n    Projection: person.id, Utf8(\"last_name\") AS kind, person.last_name AS name\
+                        \n      Filter: person.last_name IS NOT NULL\
+                        \n        Projection: person.id, person.first_name, person.last_name\
+                        \n          TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_asof_join() {
+    let sql = "SELECT p.id, o.order_id FROM person p \
+               ASOF JOIN orders o MATCH_CONDITION (p.age >= o.qty) \
+               ON p.id = o.customer_id";
+    let expected = "Projection: p.id, o.order_id\
+                        \n  Left AsOf Join: p.id = o.customer_id MatchCondition: p.age >= o.qty\
+                        \n    SubqueryAlias: p\
+                        \n      TableScan: person\
+                        \n    SubqueryAlias: o\
+                        \n      TableScan: orders";
+    quick_test(sql, expected);
+
+    // the match condition is oriented with the left input first
+    let sql = "SELECT p.id, o.order_id FROM person p \
+               ASOF JOIN orders o MATCH_CONDITION (o.qty < p.age)";
+    let expected = "Projection: p.id, o.order_id\
+                        \n  Left AsOf Join: MatchCondition: p.age > o.qty\
+                        \n    SubqueryAlias: p\
+                        \n      TableScan: person\
+                        \n    SubqueryAlias: o\
+                        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_asof_join_unsupported() {
+    let sql = "SELECT * FROM person p \
+               ASOF JOIN orders o MATCH_CONDITION (p.age >= o.qty) \
+               ON p.id > o.customer_id";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: ASOF JOIN ON clause only supports equalities of columns, got p.id > o.customer_id",
+        err.strip_backtrace()
+    );
+
+    let sql = "SELECT * FROM person p \
+               ASOF JOIN orders o MATCH_CONDITION (p.age = o.qty)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: ASOF JOIN match condition must use one of >, >=, < or <=, got =",
+        err.strip_backtrace()
+    );
+}
+
 #[test]
 fn select_with_having() {
     let sql = "SELECT id, age
@@ -2240,6 +2454,51 @@ fn over_partition_by() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn over_partition_by_qualify_alias() {
+    let sql = "SELECT order_id, MAX(qty) OVER (PARTITION BY order_id) AS max_qty from orders QUALIFY max_qty > 10";
+    let expected = "\
+        Projection: orders.order_id, max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS max_qty\
+        \n  Filter: max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING > Int64(10)\
+        \n    WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_window_not_in_projection() {
+    let sql = "SELECT order_id FROM orders WHERE qty > 1 QUALIFY MAX(qty) OVER (PARTITION BY order_id) = qty";
+    let expected = "\
+        Projection: orders.order_id\
+        \n  Filter: max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = orders.qty\
+        \n    WindowAggr: windowExpr=[[max(orders.qty) PARTITION BY [orders.order_id] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      Filter: orders.qty > Int64(1)\
+        \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_with_aggregate() {
+    let sql = "SELECT customer_id, SUM(qty) AS total FROM orders GROUP BY customer_id QUALIFY MAX(SUM(qty)) OVER () = total";
+    let expected = "\
+        Projection: orders.customer_id, sum(orders.qty) AS total\
+        \n  Filter: max(sum(orders.qty)) ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING = sum(orders.qty)\
+        \n    WindowAggr: windowExpr=[[max(sum(orders.qty)) ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n      Aggregate: groupBy=[[orders.customer_id]], aggr=[[sum(orders.qty)]]\
+        \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn qualify_without_window_function() {
+    let sql = "SELECT order_id FROM orders QUALIFY order_id > 1";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: QUALIFY clause requires a window function in the SELECT list or in the QUALIFY clause",
+        err.strip_backtrace()
+    );
+}
+
 /// psql result
 /// ```text
 ///                                     QUERY PLAN
@@ -3526,13 +3785,9 @@ fn test_select_distinct_order_by() {
     "SELECT customer_name, sum(order_total) as total_order_amount FROM orders CLUSTER BY customer_name",
     "This feature is not implemented: CLUSTER BY"
 )]
-#[case::select_lateral_view_unsupported(
-    "SELECT id, number FROM person LATERAL VIEW explode(numbers) exploded_table AS number",
-    "This feature is not implemented: LATERAL VIEWS"
-)]
-#[case::select_qualify_unsupported(
-    "SELECT i, p, o FROM person QUALIFY ROW_NUMBER() OVER (PARTITION BY p ORDER BY o) = 1",
-    "This feature is not implemented: QUALIFY"
+#[case::select_lateral_view_unsupported_function(
+    "SELECT id, number FROM person LATERAL VIEW inline(numbers) exploded_table AS number",
+    "This feature is not implemented: Unsupported LATERAL VIEW function: inline"
 )]
 #[case::select_top_unsupported(
     "SELECT TOP (5) * FROM person",
@@ -3548,6 +3803,61 @@ fn test_select_unsupported_syntax_errors(#[case] sql: &str, #[case] error: &str)
     assert_eq!(err.strip_backtrace(), error)
 }
 
+#[test]
+fn select_lateral_view_explode() {
+    let sql = "SELECT array_col, x FROM unnest_table LATERAL VIEW explode(array_col) t AS x WHERE x > 1";
+    let expected = "Projection: unnest_table.array_col, t.x\
+                        \n  Filter: t.x > Int64(1)\
+                        \n    Projection: unnest_table.array_col, unnest_table.struct_col, __lateral_view_t AS x\
+                        \n      Unnest: lists[__lateral_view_t] structs[]\
+                        \n        Projection: unnest_table.array_col, unnest_table.struct_col, unnest_table.array_col AS __lateral_view_t\
+                        \n          TableScan: unnest_table";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_lateral_view_outer_posexplode() {
+    let sql = "SELECT * FROM unnest_table LATERAL VIEW OUTER posexplode(array_col) t";
+    let expected = "Projection: *\
+                        \n  Projection: unnest_table.array_col, unnest_table.struct_col, __unnest_ordinality - Int64(1) AS pos, __lateral_view_t AS col\
+                        \n    Unnest: lists[__lateral_view_t] structs[] with_ordinality\
+                        \n      Projection: unnest_table.array_col, unnest_table.struct_col, unnest_table.array_col AS __lateral_view_t\
+                        \n        TableScan: unnest_table";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_lateral_view_posexplode_with_ordinality_column() {
+    // an input column named `ordinality` is not mistaken for the position
+    let sql = "SELECT * FROM (SELECT array_col, 1 AS ordinality FROM unnest_table) u \
+               LATERAL VIEW posexplode(array_col) t";
+    let expected = "Projection: *\
+                        \n  Projection: u.array_col, u.ordinality, __unnest_ordinality - Int64(1) AS pos, __lateral_view_t AS col\
+                        \n    Unnest: lists[__lateral_view_t] structs[] with_ordinality\
+                        \n      Projection: u.array_col, u.ordinality, u.array_col AS __lateral_view_t\
+                        \n        SubqueryAlias: u\
+                        \n          Projection: unnest_table.array_col, Int64(1) AS ordinality\
+                        \n            TableScan: unnest_table";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_lateral_view_errors() {
+    let sql = "SELECT * FROM person LATERAL VIEW explode(id) t";
+    let err = logical_plan(sql).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: explode expects an array argument, got UInt32"
+    );
+
+    let sql = "SELECT * FROM unnest_table LATERAL VIEW posexplode(array_col) t AS x";
+    let err = logical_plan(sql).unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Error during planning: LATERAL VIEW t produces 2 columns but 1 column aliases were given"
+    );
+}
+
 #[test]
 fn select_order_by_with_cast() {
     let sql =
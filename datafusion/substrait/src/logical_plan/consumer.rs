@@ -26,6 +26,13 @@ use datafusion::common::{
     not_impl_err, plan_datafusion_err, substrait_datafusion_err, substrait_err, DFSchema,
     DFSchemaRef,
 };
+use datafusion::datasource::file_format::arrow::ArrowFormat;
+use datafusion::datasource::file_format::parquet::ParquetFormat;
+use datafusion::datasource::file_format::FileFormat;
+use datafusion::datasource::listing::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+};
+use datafusion::datasource::provider_as_source;
 use datafusion::execution::FunctionRegistry;
 use datafusion::logical_expr::expr::{Exists, InSubquery, Sort};
 
@@ -76,11 +83,16 @@ use substrait::proto::expression::literal::{
 };
 use substrait::proto::expression::subquery::SubqueryType;
 use substrait::proto::expression::{self, FieldReference, Literal, ScalarFunction};
-use substrait::proto::read_rel::local_files::file_or_files::PathType::UriFile;
+use substrait::proto::read_rel::local_files::file_or_files::FileFormat as SubstraitFileFormat;
+use substrait::proto::read_rel::local_files::file_or_files::PathType::{
+    UriFile, UriFolder, UriPath, UriPathGlob,
+};
+use substrait::proto::read_rel::LocalFiles;
 use substrait::proto::{
     aggregate_function::AggregationInvocation,
     expression::{
-        field_reference::ReferenceType::DirectReference, literal::LiteralType,
+        field_reference::ReferenceType::DirectReference,
+        field_reference::RootType::OuterReference, literal::LiteralType,
         reference_segment::ReferenceType::StructField,
         window_function::bound as SubstraitBound,
         window_function::bound::Kind as BoundKind, window_function::Bound,
@@ -148,7 +160,7 @@ pub fn substrait_fun_name(name: &str) -> &str {
     name
 }
 
-fn split_eq_and_noneq_join_predicate_with_nulls_equality(
+pub(crate) fn split_eq_and_noneq_join_predicate_with_nulls_equality(
     filter: &Expr,
 ) -> (Vec<(Column, Column)>, bool, Option<Expr>) {
     let exprs = split_conjunction(filter);
@@ -200,10 +212,9 @@ pub async fn from_substrait_plan(
     plan: &Plan,
 ) -> Result<LogicalPlan> {
     // Register function extension
+    // Type variations are resolved through the variation references of the
+    // types themselves, so their declarations do not need to be checked here
     let extensions = Extensions::try_from(&plan.extensions)?;
-    if !extensions.type_variations.is_empty() {
-        return not_impl_err!("Type variation extensions are not supported");
-    }
 
     // Parse relations
     match plan.relations.len() {
@@ -211,10 +222,10 @@ pub async fn from_substrait_plan(
             match plan.relations[0].rel_type.as_ref() {
                 Some(rt) => match rt {
                     plan_rel::RelType::Rel(rel) => {
-                        Ok(from_substrait_rel(ctx, rel, &extensions).await?)
+                        Ok(from_substrait_rel(ctx, rel, &[], &extensions).await?)
                     },
                     plan_rel::RelType::Root(root) => {
-                        let plan = from_substrait_rel(ctx, root.input.as_ref().unwrap(), &extensions).await?;
+                        let plan = from_substrait_rel(ctx, root.input.as_ref().unwrap(), &[], &extensions).await?;
                         if root.names.is_empty() {
                             // Backwards compatibility for plans missing names
                             return Ok(plan);
@@ -402,20 +413,26 @@ fn make_renamed_schema(
 pub async fn from_substrait_rel(
     ctx: &SessionContext,
     rel: &Rel,
+    outer_schemas: &[DFSchemaRef],
     extensions: &Extensions,
 ) -> Result<LogicalPlan> {
     match &rel.rel_type {
         Some(RelType::Project(p)) => {
             if let Some(input) = p.input.as_ref() {
                 let mut input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, outer_schemas, extensions).await?,
                 );
                 let mut names: HashSet<String> = HashSet::new();
                 let mut exprs: Vec<Expr> = vec![];
                 for e in &p.expressions {
-                    let x =
-                        from_substrait_rex(ctx, e, input.clone().schema(), extensions)
-                            .await?;
+                    let x = from_substrait_rex(
+                        ctx,
+                        e,
+                        input.clone().schema(),
+                        outer_schemas,
+                        extensions,
+                    )
+                    .await?;
                     // if the expression is WindowFunction, wrap in a Window relation
                     if let Expr::WindowFunction(_) = &x {
                         // Adding the same expression here and in the project below
@@ -447,12 +464,17 @@ pub async fn from_substrait_rel(
         Some(RelType::Filter(filter)) => {
             if let Some(input) = filter.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, outer_schemas, extensions).await?,
                 );
                 if let Some(condition) = filter.condition.as_ref() {
-                    let expr =
-                        from_substrait_rex(ctx, condition, input.schema(), extensions)
-                            .await?;
+                    let expr = from_substrait_rex(
+                        ctx,
+                        condition,
+                        input.schema(),
+                        outer_schemas,
+                        extensions,
+                    )
+                    .await?;
                     input.filter(expr)?.build()
                 } else {
                     not_impl_err!("Filter without an condition is not valid")
@@ -464,7 +486,7 @@ pub async fn from_substrait_rel(
         Some(RelType::Fetch(fetch)) => {
             if let Some(input) = fetch.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, outer_schemas, extensions).await?,
                 );
                 let offset = fetch.offset as usize;
                 // Since protobuf can't directly distinguish `None` vs `0` `None` is encoded as `MAX`
@@ -481,11 +503,16 @@ pub async fn from_substrait_rel(
         Some(RelType::Sort(sort)) => {
             if let Some(input) = sort.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, outer_schemas, extensions).await?,
                 );
-                let sorts =
-                    from_substrait_sorts(ctx, &sort.sorts, input.schema(), extensions)
-                        .await?;
+                let sorts = from_substrait_sorts(
+                    ctx,
+                    &sort.sorts,
+                    input.schema(),
+                    outer_schemas,
+                    extensions,
+                )
+                .await?;
                 input.sort(sorts)?.build()
             } else {
                 not_impl_err!("Sort without an input is not valid")
@@ -494,7 +521,7 @@ pub async fn from_substrait_rel(
         Some(RelType::Aggregate(agg)) => {
             if let Some(input) = agg.input.as_ref() {
                 let input = LogicalPlanBuilder::from(
-                    from_substrait_rel(ctx, input, extensions).await?,
+                    from_substrait_rel(ctx, input, outer_schemas, extensions).await?,
                 );
                 let mut group_expr = vec![];
                 let mut aggr_expr = vec![];
@@ -502,9 +529,14 @@ pub async fn from_substrait_rel(
                 match agg.groupings.len() {
                     1 => {
                         for e in &agg.groupings[0].grouping_expressions {
-                            let x =
-                                from_substrait_rex(ctx, e, input.schema(), extensions)
-                                    .await?;
+                            let x = from_substrait_rex(
+                                ctx,
+                                e,
+                                input.schema(),
+                                outer_schemas,
+                                extensions,
+                            )
+                            .await?;
                             group_expr.push(x);
                         }
                     }
@@ -517,6 +549,7 @@ pub async fn from_substrait_rel(
                                     ctx,
                                     e,
                                     input.schema(),
+                                    outer_schemas,
                                     extensions,
                                 )
                                 .await?;
@@ -537,8 +570,14 @@ pub async fn from_substrait_rel(
                 for m in &agg.measures {
                     let filter = match &m.filter {
                         Some(fil) => Some(Box::new(
-                            from_substrait_rex(ctx, fil, input.schema(), extensions)
-                                .await?,
+                            from_substrait_rex(
+                                ctx,
+                                fil,
+                                input.schema(),
+                                outer_schemas,
+                                extensions,
+                            )
+                            .await?,
                         )),
                         None => None,
                     };
@@ -561,6 +600,7 @@ pub async fn from_substrait_rel(
                                 ctx,
                                 f,
                                 input.schema(),
+                                outer_schemas,
                                 extensions,
                                 filter,
                                 // TODO: Add parsing of order_by also
@@ -588,10 +628,22 @@ pub async fn from_substrait_rel(
             }
 
             let left: LogicalPlanBuilder = LogicalPlanBuilder::from(
-                from_substrait_rel(ctx, join.left.as_ref().unwrap(), extensions).await?,
+                from_substrait_rel(
+                    ctx,
+                    join.left.as_ref().unwrap(),
+                    outer_schemas,
+                    extensions,
+                )
+                .await?,
             );
             let right = LogicalPlanBuilder::from(
-                from_substrait_rel(ctx, join.right.as_ref().unwrap(), extensions).await?,
+                from_substrait_rel(
+                    ctx,
+                    join.right.as_ref().unwrap(),
+                    outer_schemas,
+                    extensions,
+                )
+                .await?,
             );
             let (left, right) = requalify_sides_if_needed(left, right)?;
 
@@ -604,8 +656,14 @@ pub async fn from_substrait_rel(
             // Otherwise, build join with only the filter, without join keys
             match &join.expression.as_ref() {
                 Some(expr) => {
-                    let on = from_substrait_rex(ctx, expr, &in_join_schema, extensions)
-                        .await?;
+                    let on = from_substrait_rex(
+                        ctx,
+                        expr,
+                        &in_join_schema,
+                        outer_schemas,
+                        extensions,
+                    )
+                    .await?;
                     // The join expression can contain both equal and non-equal ops.
                     // As of datafusion 31.0.0, the equal and non equal join conditions are in separate fields.
                     // So we extract each part as follows:
@@ -629,11 +687,22 @@ pub async fn from_substrait_rel(
         }
         Some(RelType::Cross(cross)) => {
             let left = LogicalPlanBuilder::from(
-                from_substrait_rel(ctx, cross.left.as_ref().unwrap(), extensions).await?,
+                from_substrait_rel(
+                    ctx,
+                    cross.left.as_ref().unwrap(),
+                    outer_schemas,
+                    extensions,
+                )
+                .await?,
             );
             let right = LogicalPlanBuilder::from(
-                from_substrait_rel(ctx, cross.right.as_ref().unwrap(), extensions)
-                    .await?,
+                from_substrait_rel(
+                    ctx,
+                    cross.right.as_ref().unwrap(),
+                    outer_schemas,
+                    extensions,
+                )
+                .await?,
             );
             let (left, right) = requalify_sides_if_needed(left, right)?;
             left.cross_join(right.build()?)?.build()
@@ -707,38 +776,27 @@ pub async fn from_substrait_rel(
                 Ok(LogicalPlan::Values(Values { schema, values }))
             }
             Some(ReadType::LocalFiles(lf)) => {
-                fn extract_filename(name: &str) -> Option<String> {
-                    let corrected_url =
-                        if name.starts_with("file://") && !name.starts_with("file:///") {
-                            name.replacen("file://", "file:///", 1)
-                        } else {
-                            name.to_string()
-                        };
-
-                    Url::parse(&corrected_url).ok().and_then(|url| {
-                        let path = url.path();
-                        std::path::Path::new(path)
-                            .file_name()
-                            .map(|filename| filename.to_string_lossy().to_string())
-                    })
-                }
-
                 // we could use the file name to check the original table provider
-                // TODO: currently does not support multiple local files
-                let filename: Option<String> =
-                    lf.items.first().and_then(|x| match x.path_type.as_ref() {
-                        Some(UriFile(name)) => extract_filename(name),
-                        _ => None,
-                    });
-
-                if lf.items.len() > 1 || filename.is_none() {
-                    return not_impl_err!("Only single file reads are supported");
+                if let [item] = lf.items.as_slice() {
+                    if let Some(UriFile(name)) = item.path_type.as_ref() {
+                        if let Some(name) = extract_filename(name) {
+                            let table_reference =
+                                TableReference::Bare { table: name.into() };
+                            if ctx.table_exist(table_reference.clone())? {
+                                let t = ctx.table(table_reference).await?;
+                                let t = t.into_optimized_plan()?;
+                                return extract_projection(t, &read.projection);
+                            }
+                        }
+                    }
                 }
-                let name = filename.unwrap();
-                // directly use unwrap here since we could determine it is a valid one
-                let table_reference = TableReference::Bare { table: name.into() };
-                let t = ctx.table(table_reference).await?;
-                let t = t.into_optimized_plan()?;
+
+                // otherwise read the files directly, with the schema of the read
+                let Some(base_schema) = read.base_schema.as_ref() else {
+                    return substrait_err!("LocalFiles read requires a base schema");
+                };
+                let schema = from_substrait_named_struct(base_schema, extensions)?;
+                let t = from_substrait_local_files(lf, schema.as_ref())?;
                 extract_projection(t, &read.projection)
             }
             _ => not_impl_err!("Unsupported ReadType: {:?}", &read.as_ref().read_type),
@@ -748,11 +806,19 @@ pub async fn from_substrait_rel(
                 set_rel::SetOp::UnionAll => {
                     if !set.inputs.is_empty() {
                         let mut union_builder = Ok(LogicalPlanBuilder::from(
-                            from_substrait_rel(ctx, &set.inputs[0], extensions).await?,
+                            from_substrait_rel(
+                                ctx,
+                                &set.inputs[0],
+                                outer_schemas,
+                                extensions,
+                            )
+                            .await?,
                         ));
                         for input in &set.inputs[1..] {
-                            union_builder = union_builder?
-                                .union(from_substrait_rel(ctx, input, extensions).await?);
+                            union_builder = union_builder?.union(
+                                from_substrait_rel(ctx, input, outer_schemas, extensions)
+                                    .await?,
+                            );
                         }
                         union_builder?.build()
                     } else {
@@ -786,7 +852,8 @@ pub async fn from_substrait_rel(
                     "ExtensionSingleRel doesn't contains input rel. Try use ExtensionLeafRel instead"
                 );
             };
-            let input_plan = from_substrait_rel(ctx, input_rel, extensions).await?;
+            let input_plan =
+                from_substrait_rel(ctx, input_rel, outer_schemas, extensions).await?;
             let plan =
                 plan.with_exprs_and_inputs(plan.expressions(), vec![input_plan])?;
             Ok(LogicalPlan::Extension(Extension { node: plan }))
@@ -801,7 +868,8 @@ pub async fn from_substrait_rel(
                 .deserialize_logical_plan(&ext_detail.type_url, &ext_detail.value)?;
             let mut inputs = Vec::with_capacity(extension.inputs.len());
             for input in &extension.inputs {
-                let input_plan = from_substrait_rel(ctx, input, extensions).await?;
+                let input_plan =
+                    from_substrait_rel(ctx, input, outer_schemas, extensions).await?;
                 inputs.push(input_plan);
             }
             let plan = plan.with_exprs_and_inputs(plan.expressions(), inputs)?;
@@ -811,7 +879,9 @@ pub async fn from_substrait_rel(
             let Some(input) = exchange.input.as_ref() else {
                 return substrait_err!("Unexpected empty input in ExchangeRel");
             };
-            let input = Arc::new(from_substrait_rel(ctx, input, extensions).await?);
+            let input = Arc::new(
+                from_substrait_rel(ctx, input, outer_schemas, extensions).await?,
+            );
 
             let Some(exchange_kind) = &exchange.exchange_kind else {
                 return substrait_err!("Unexpected empty input in ExchangeRel");
@@ -823,8 +893,11 @@ pub async fn from_substrait_rel(
                     let mut partition_columns = vec![];
                     let input_schema = input.schema();
                     for field_ref in &scatter_fields.fields {
-                        let column =
-                            from_substrait_field_reference(field_ref, input_schema)?;
+                        let column = from_substrait_field_reference(
+                            field_ref,
+                            input_schema,
+                            outer_schemas,
+                        )?;
                         partition_columns.push(column);
                     }
                     Partitioning::Hash(
@@ -878,7 +951,7 @@ fn requalify_sides_if_needed(
     }
 }
 
-fn from_substrait_jointype(join_type: i32) -> Result<JoinType> {
+pub(crate) fn from_substrait_jointype(join_type: i32) -> Result<JoinType> {
     if let Ok(substrait_join_type) = join_rel::JoinType::try_from(join_type) {
         match substrait_join_type {
             join_rel::JoinType::Inner => Ok(JoinType::Inner),
@@ -894,18 +967,97 @@ fn from_substrait_jointype(join_type: i32) -> Result<JoinType> {
     }
 }
 
+fn extract_filename(name: &str) -> Option<String> {
+    Url::parse(&correct_file_url(name)).ok().and_then(|url| {
+        let path = url.path();
+        std::path::Path::new(path)
+            .file_name()
+            .map(|filename| filename.to_string_lossy().to_string())
+    })
+}
+
+/// Turn `file://path` into `file:///path`, as the former reads `path` as a host
+fn correct_file_url(name: &str) -> String {
+    if name.starts_with("file://") && !name.starts_with("file:///") {
+        name.replacen("file://", "file:///", 1)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Build a scan of the files of a LocalFiles read, named after its first file
+fn from_substrait_local_files(lf: &LocalFiles, schema: &DFSchema) -> Result<LogicalPlan> {
+    let mut table_paths = vec![];
+    let mut file_format: Option<Arc<dyn FileFormat>> = None;
+    for item in &lf.items {
+        let path = match item.path_type.as_ref() {
+            Some(UriFile(path) | UriPath(path) | UriPathGlob(path) | UriFolder(path)) => {
+                correct_file_url(path)
+            }
+            None => return substrait_err!("LocalFiles item without a path"),
+        };
+        table_paths.push(ListingTableUrl::parse(path)?);
+
+        let item_format: Arc<dyn FileFormat> = match item.file_format.as_ref() {
+            Some(SubstraitFileFormat::Parquet(_)) => Arc::new(ParquetFormat::default()),
+            Some(SubstraitFileFormat::Arrow(_)) => Arc::new(ArrowFormat),
+            other => {
+                return not_impl_err!("Unsupported file format in LocalFiles: {other:?}")
+            }
+        };
+        match &file_format {
+            Some(format) if format.get_ext() != item_format.get_ext() => {
+                return not_impl_err!("LocalFiles read of files with different formats");
+            }
+            Some(_) => {}
+            None => file_format = Some(item_format),
+        }
+    }
+    let (Some(first), Some(file_format)) = (lf.items.first(), file_format) else {
+        return substrait_err!("LocalFiles read without files");
+    };
+    let Some(name) = first
+        .path_type
+        .as_ref()
+        .and_then(|path_type| match path_type {
+            UriFile(path) | UriPath(path) | UriPathGlob(path) | UriFolder(path) => {
+                extract_filename(path)
+            }
+        })
+    else {
+        return substrait_err!("Cannot name the table of a LocalFiles read");
+    };
+
+    let config = ListingTableConfig::new_with_multi_paths(table_paths)
+        .with_listing_options(ListingOptions::new(file_format))
+        .with_schema(Arc::new(schema.as_arrow().clone()));
+    let table = ListingTable::try_new(config)?;
+    LogicalPlanBuilder::scan(
+        TableReference::Bare { table: name.into() },
+        provider_as_source(Arc::new(table)),
+        None,
+    )?
+    .build()
+}
+
 /// Convert Substrait Sorts to DataFusion Exprs
 pub async fn from_substrait_sorts(
     ctx: &SessionContext,
     substrait_sorts: &Vec<SortField>,
     input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
     extensions: &Extensions,
 ) -> Result<Vec<Expr>> {
     let mut sorts: Vec<Expr> = vec![];
     for s in substrait_sorts {
-        let expr =
-            from_substrait_rex(ctx, s.expr.as_ref().unwrap(), input_schema, extensions)
-                .await?;
+        let expr = from_substrait_rex(
+            ctx,
+            s.expr.as_ref().unwrap(),
+            input_schema,
+            outer_schemas,
+            extensions,
+        )
+        .await?;
         let asc_nullfirst = match &s.sort_kind {
             Some(k) => match k {
                 Direction(d) => {
@@ -949,11 +1101,14 @@ pub async fn from_substrait_rex_vec(
     ctx: &SessionContext,
     exprs: &Vec<Expression>,
     input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
     extensions: &Extensions,
 ) -> Result<Vec<Expr>> {
     let mut expressions: Vec<Expr> = vec![];
     for expr in exprs {
-        let expression = from_substrait_rex(ctx, expr, input_schema, extensions).await?;
+        let expression =
+            from_substrait_rex(ctx, expr, input_schema, outer_schemas, extensions)
+                .await?;
         expressions.push(expression);
     }
     Ok(expressions)
@@ -964,15 +1119,26 @@ pub async fn from_substrait_func_args(
     ctx: &SessionContext,
     arguments: &Vec<FunctionArgument>,
     input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
     extensions: &Extensions,
 ) -> Result<Vec<Expr>> {
     let mut args: Vec<Expr> = vec![];
     for arg in arguments {
         let arg_expr = match &arg.arg_type {
             Some(ArgType::Value(e)) => {
-                from_substrait_rex(ctx, e, input_schema, extensions).await
+                from_substrait_rex(ctx, e, input_schema, outer_schemas, extensions).await
             }
-            _ => not_impl_err!("Function argument non-Value type not supported"),
+            // enum arguments, such as the component of `extract`, are passed to
+            // DataFusion functions as string literals
+            Some(ArgType::Enum(name)) => {
+                Ok(Expr::Literal(ScalarValue::Utf8(Some(name.to_owned()))))
+            }
+            // type arguments are passed as a null of that type
+            Some(ArgType::Type(t)) => {
+                let data_type = from_substrait_type_without_names(t, extensions)?;
+                Ok(Expr::Literal(ScalarValue::try_from(&data_type)?))
+            }
+            None => substrait_err!("Function argument without a type"),
         };
         args.push(arg_expr?);
     }
@@ -984,13 +1150,20 @@ pub async fn from_substrait_agg_func(
     ctx: &SessionContext,
     f: &AggregateFunction,
     input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
     extensions: &Extensions,
     filter: Option<Box<Expr>>,
     order_by: Option<Vec<Expr>>,
     distinct: bool,
 ) -> Result<Arc<Expr>> {
-    let args =
-        from_substrait_func_args(ctx, &f.arguments, input_schema, extensions).await?;
+    let args = from_substrait_func_args(
+        ctx,
+        &f.arguments,
+        input_schema,
+        outer_schemas,
+        extensions,
+    )
+    .await?;
 
     let Some(function_name) = extensions.functions.get(&f.function_reference) else {
         return plan_err!(
@@ -1027,6 +1200,7 @@ pub async fn from_substrait_rex(
     ctx: &SessionContext,
     e: &Expression,
     input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
     extensions: &Extensions,
 ) -> Result<Expr> {
     match &e.rex_type {
@@ -1035,22 +1209,31 @@ pub async fn from_substrait_rex(
             let substrait_list = s.options.as_ref();
             Ok(Expr::InList(InList {
                 expr: Box::new(
-                    from_substrait_rex(ctx, substrait_expr, input_schema, extensions)
-                        .await?,
+                    from_substrait_rex(
+                        ctx,
+                        substrait_expr,
+                        input_schema,
+                        outer_schemas,
+                        extensions,
+                    )
+                    .await?,
                 ),
                 list: from_substrait_rex_vec(
                     ctx,
                     substrait_list,
                     input_schema,
+                    outer_schemas,
                     extensions,
                 )
                 .await?,
                 negated: false,
             }))
         }
-        Some(RexType::Selection(field_ref)) => {
-            Ok(from_substrait_field_reference(field_ref, input_schema)?)
-        }
+        Some(RexType::Selection(field_ref)) => Ok(from_substrait_field_reference(
+            field_ref,
+            input_schema,
+            outer_schemas,
+        )?),
         Some(RexType::IfThen(if_then)) => {
             // Parse `ifs`
             // If the first element does not have a `then` part, then we can assume it's a base expression
@@ -1065,6 +1248,7 @@ pub async fn from_substrait_rex(
                                 ctx,
                                 if_expr.r#if.as_ref().unwrap(),
                                 input_schema,
+                                outer_schemas,
                                 extensions,
                             )
                             .await?,
@@ -1078,6 +1262,7 @@ pub async fn from_substrait_rex(
                             ctx,
                             if_expr.r#if.as_ref().unwrap(),
                             input_schema,
+                            outer_schemas,
                             extensions,
                         )
                         .await?,
@@ -1087,6 +1272,7 @@ pub async fn from_substrait_rex(
                             ctx,
                             if_expr.then.as_ref().unwrap(),
                             input_schema,
+                            outer_schemas,
                             extensions,
                         )
                         .await?,
@@ -1096,7 +1282,8 @@ pub async fn from_substrait_rex(
             // Parse `else`
             let else_expr = match &if_then.r#else {
                 Some(e) => Some(Box::new(
-                    from_substrait_rex(ctx, e, input_schema, extensions).await?,
+                    from_substrait_rex(ctx, e, input_schema, outer_schemas, extensions)
+                        .await?,
                 )),
                 None => None,
             };
@@ -1113,11 +1300,21 @@ pub async fn from_substrait_rex(
                     f.function_reference
                 );
             };
-            let fn_name = substrait_fun_name(fn_name);
+            let fn_name = match substrait_fun_name(fn_name) {
+                // the `extract` function of the Substrait standard extensions,
+                // whose component is an enum argument
+                "extract" => "date_part",
+                fn_name => fn_name,
+            };
 
-            let args =
-                from_substrait_func_args(ctx, &f.arguments, input_schema, extensions)
-                    .await?;
+            let args = from_substrait_func_args(
+                ctx,
+                &f.arguments,
+                input_schema,
+                outer_schemas,
+                extensions,
+            )
+            .await?;
 
             // try to first match the requested function into registered udfs, then built-in ops
             // and finally built-in expressions
@@ -1151,7 +1348,9 @@ pub async fn from_substrait_rex(
 
                 Ok(combined_expr)
             } else if let Some(builder) = BuiltinExprBuilder::try_from_name(fn_name) {
-                builder.build(ctx, f, input_schema, extensions).await
+                builder
+                    .build(ctx, f, input_schema, outer_schemas, extensions)
+                    .await
             } else {
                 not_impl_err!("Unsupported function name: {fn_name:?}")
             }
@@ -1167,6 +1366,7 @@ pub async fn from_substrait_rex(
                         ctx,
                         cast.as_ref().input.as_ref().unwrap().as_ref(),
                         input_schema,
+                        outer_schemas,
                         extensions,
                     )
                     .await?,
@@ -1200,9 +1400,14 @@ pub async fn from_substrait_rex(
                 )
             }?;
 
-            let order_by =
-                from_substrait_sorts(ctx, &window.sorts, input_schema, extensions)
-                    .await?;
+            let order_by = from_substrait_sorts(
+                ctx,
+                &window.sorts,
+                input_schema,
+                outer_schemas,
+                extensions,
+            )
+            .await?;
 
             let bound_units =
                 match BoundsType::try_from(window.bounds_type).map_err(|e| {
@@ -1227,6 +1432,7 @@ pub async fn from_substrait_rex(
                     ctx,
                     &window.arguments,
                     input_schema,
+                    outer_schemas,
                     extensions,
                 )
                 .await?,
@@ -1234,6 +1440,7 @@ pub async fn from_substrait_rex(
                     ctx,
                     &window.partitions,
                     input_schema,
+                    outer_schemas,
                     extensions,
                 )
                 .await?,
@@ -1255,9 +1462,14 @@ pub async fn from_substrait_rex(
                         let needle_expr = &in_predicate.needles[0];
                         let haystack_expr = &in_predicate.haystack;
                         if let Some(haystack_expr) = haystack_expr {
-                            let haystack_expr =
-                                from_substrait_rel(ctx, haystack_expr, extensions)
-                                    .await?;
+                            let haystack_expr = from_substrait_subquery_rel(
+                                ctx,
+                                haystack_expr,
+                                input_schema,
+                                outer_schemas,
+                                extensions,
+                            )
+                            .await?;
                             let outer_refs = haystack_expr.all_out_ref_exprs();
                             Ok(Expr::InSubquery(InSubquery {
                                 expr: Box::new(
@@ -1265,6 +1477,7 @@ pub async fn from_substrait_rex(
                                         ctx,
                                         needle_expr,
                                         input_schema,
+                                        outer_schemas,
                                         extensions,
                                     )
                                     .await?,
@@ -1281,9 +1494,11 @@ pub async fn from_substrait_rex(
                     }
                 }
                 SubqueryType::Scalar(query) => {
-                    let plan = from_substrait_rel(
+                    let plan = from_substrait_subquery_rel(
                         ctx,
                         &(query.input.clone()).unwrap_or_default(),
+                        input_schema,
+                        outer_schemas,
                         extensions,
                     )
                     .await?;
@@ -1298,9 +1513,11 @@ pub async fn from_substrait_rex(
                         // exist
                         PredicateOp::Exists => {
                             let relation = &predicate.tuples;
-                            let plan = from_substrait_rel(
+                            let plan = from_substrait_subquery_rel(
                                 ctx,
                                 &relation.clone().unwrap_or_default(),
+                                input_schema,
+                                outer_schemas,
                                 extensions,
                             )
                             .await?;
@@ -2237,9 +2454,24 @@ fn from_substrait_null(
     }
 }
 
+/// Convert the relation of a subquery of an expression over `input_schema`,
+/// which is the innermost schema its outer references can refer to
+async fn from_substrait_subquery_rel(
+    ctx: &SessionContext,
+    rel: &Rel,
+    input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
+    extensions: &Extensions,
+) -> Result<LogicalPlan> {
+    let mut outer_schemas = outer_schemas.to_vec();
+    outer_schemas.push(Arc::new(input_schema.clone()));
+    from_substrait_rel(ctx, rel, &outer_schemas, extensions).await
+}
+
 fn from_substrait_field_reference(
     field_ref: &FieldReference,
     input_schema: &DFSchema,
+    outer_schemas: &[DFSchemaRef],
 ) -> Result<Expr> {
     match &field_ref.reference_type {
         Some(DirectReference(direct)) => match &direct.reference_type.as_ref() {
@@ -2247,9 +2479,28 @@ fn from_substrait_field_reference(
                 Some(_) => not_impl_err!(
                     "Direct reference StructField with child is not supported"
                 ),
-                None => Ok(Expr::Column(Column::from(
-                    input_schema.qualified_field(x.field as usize),
-                ))),
+                None => match &field_ref.root_type {
+                    Some(OuterReference(outer)) => {
+                        let steps_out = outer.steps_out as usize;
+                        if steps_out == 0 || steps_out > outer_schemas.len() {
+                            return substrait_err!(
+                                "Outer reference {steps_out} steps out of {} enclosing queries",
+                                outer_schemas.len()
+                            );
+                        }
+                        let outer_schema =
+                            &outer_schemas[outer_schemas.len() - steps_out];
+                        let (qualifier, field) =
+                            outer_schema.qualified_field(x.field as usize);
+                        Ok(Expr::OuterReferenceColumn(
+                            field.data_type().clone(),
+                            Column::from((qualifier, field)),
+                        ))
+                    }
+                    _ => Ok(Expr::Column(Column::from(
+                        input_schema.qualified_field(x.field as usize),
+                    ))),
+                },
             },
             _ => not_impl_err!(
                 "Direct reference with types other than StructField is not supported"
@@ -2281,19 +2532,43 @@ impl BuiltinExprBuilder {
         ctx: &SessionContext,
         f: &ScalarFunction,
         input_schema: &DFSchema,
+        outer_schemas: &[DFSchemaRef],
         extensions: &Extensions,
     ) -> Result<Expr> {
         match self.expr_name.as_str() {
             "like" => {
-                Self::build_like_expr(ctx, false, f, input_schema, extensions).await
+                Self::build_like_expr(
+                    ctx,
+                    false,
+                    f,
+                    input_schema,
+                    outer_schemas,
+                    extensions,
+                )
+                .await
             }
             "ilike" => {
-                Self::build_like_expr(ctx, true, f, input_schema, extensions).await
+                Self::build_like_expr(
+                    ctx,
+                    true,
+                    f,
+                    input_schema,
+                    outer_schemas,
+                    extensions,
+                )
+                .await
             }
             "not" | "negative" | "is_null" | "is_not_null" | "is_true" | "is_false"
             | "is_not_true" | "is_not_false" | "is_unknown" | "is_not_unknown" => {
-                Self::build_unary_expr(ctx, &self.expr_name, f, input_schema, extensions)
-                    .await
+                Self::build_unary_expr(
+                    ctx,
+                    &self.expr_name,
+                    f,
+                    input_schema,
+                    outer_schemas,
+                    extensions,
+                )
+                .await
             }
             _ => {
                 not_impl_err!("Unsupported builtin expression: {}", self.expr_name)
@@ -2306,6 +2581,7 @@ impl BuiltinExprBuilder {
         fn_name: &str,
         f: &ScalarFunction,
         input_schema: &DFSchema,
+        outer_schemas: &[DFSchemaRef],
         extensions: &Extensions,
     ) -> Result<Expr> {
         if f.arguments.len() != 1 {
@@ -2314,12 +2590,34 @@ impl BuiltinExprBuilder {
         let Some(ArgType::Value(expr_substrait)) = &f.arguments[0].arg_type else {
             return substrait_err!("Invalid arguments type for {fn_name} expr");
         };
-        let arg =
-            from_substrait_rex(ctx, expr_substrait, input_schema, extensions).await?;
+        let arg = from_substrait_rex(
+            ctx,
+            expr_substrait,
+            input_schema,
+            outer_schemas,
+            extensions,
+        )
+        .await?;
         let arg = Box::new(arg);
 
         let expr = match fn_name {
-            "not" => Expr::Not(arg),
+            // negated subqueries are produced as `not` of the subquery
+            "not" => match *arg {
+                Expr::Exists(Exists { subquery, negated }) => Expr::Exists(Exists {
+                    subquery,
+                    negated: !negated,
+                }),
+                Expr::InSubquery(InSubquery {
+                    expr,
+                    subquery,
+                    negated,
+                }) => Expr::InSubquery(InSubquery {
+                    expr,
+                    subquery,
+                    negated: !negated,
+                }),
+                arg => Expr::Not(Box::new(arg)),
+            },
             "negative" => Expr::Negative(arg),
             "is_null" => Expr::IsNull(arg),
             "is_not_null" => Expr::IsNotNull(arg),
@@ -2340,6 +2638,7 @@ impl BuiltinExprBuilder {
         case_insensitive: bool,
         f: &ScalarFunction,
         input_schema: &DFSchema,
+        outer_schemas: &[DFSchemaRef],
         extensions: &Extensions,
     ) -> Result<Expr> {
         let fn_name = if case_insensitive { "ILIKE" } else { "LIKE" };
@@ -2350,13 +2649,25 @@ impl BuiltinExprBuilder {
         let Some(ArgType::Value(expr_substrait)) = &f.arguments[0].arg_type else {
             return substrait_err!("Invalid arguments type for `{fn_name}` expr");
         };
-        let expr =
-            from_substrait_rex(ctx, expr_substrait, input_schema, extensions).await?;
+        let expr = from_substrait_rex(
+            ctx,
+            expr_substrait,
+            input_schema,
+            outer_schemas,
+            extensions,
+        )
+        .await?;
         let Some(ArgType::Value(pattern_substrait)) = &f.arguments[1].arg_type else {
             return substrait_err!("Invalid arguments type for `{fn_name}` expr");
         };
-        let pattern =
-            from_substrait_rex(ctx, pattern_substrait, input_schema, extensions).await?;
+        let pattern = from_substrait_rex(
+            ctx,
+            pattern_substrait,
+            input_schema,
+            outer_schemas,
+            extensions,
+        )
+        .await?;
 
         // Default case: escape character is Literal(Utf8(None))
         let escape_char = if f.arguments.len() == 3 {
@@ -2365,9 +2676,14 @@ impl BuiltinExprBuilder {
                 return substrait_err!("Invalid arguments type for `{fn_name}` expr");
             };
 
-            let escape_char_expr =
-                from_substrait_rex(ctx, escape_char_substrait, input_schema, extensions)
-                    .await?;
+            let escape_char_expr = from_substrait_rex(
+                ctx,
+                escape_char_substrait,
+                input_schema,
+                outer_schemas,
+                extensions,
+            )
+            .await?;
 
             match escape_char_expr {
                 Expr::Literal(ScalarValue::Utf8(escape_char_string)) => {
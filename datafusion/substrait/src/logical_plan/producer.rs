@@ -47,9 +47,10 @@ use datafusion::common::{
 use datafusion::common::{substrait_err, DFSchemaRef};
 #[allow(unused_imports)]
 use datafusion::logical_expr::expr::{
-    Alias, BinaryExpr, Case, Cast, GroupingSet, InList, InSubquery, Sort, WindowFunction,
+    Alias, BinaryExpr, Case, Cast, Exists, GroupingSet, InList, InSubquery, Sort,
+    WindowFunction,
 };
-use datafusion::logical_expr::{expr, Between, JoinConstraint, LogicalPlan, Operator};
+use datafusion::logical_expr::{expr, Between, LogicalPlan, Operator};
 use datafusion::prelude::Expr;
 use pbjson_types::Any as ProtoAny;
 use substrait::proto::exchange_rel::{ExchangeKind, RoundRobin, ScatterFields};
@@ -59,7 +60,10 @@ use substrait::proto::expression::literal::{
     user_defined, IntervalDayToSecond, IntervalYearToMonth, List, Map,
     PrecisionTimestamp, Struct, UserDefined,
 };
-use substrait::proto::expression::subquery::InPredicate;
+use substrait::proto::expression::subquery::set_predicate::PredicateOp;
+use substrait::proto::expression::subquery::{
+    self, InPredicate, SetPredicate, SubqueryType,
+};
 use substrait::proto::expression::window_function::BoundsType;
 use substrait::proto::read_rel::VirtualTable;
 use substrait::proto::{CrossRel, ExchangeRel};
@@ -68,7 +72,7 @@ use substrait::{
         aggregate_function::AggregationInvocation,
         aggregate_rel::{Grouping, Measure},
         expression::{
-            field_reference::ReferenceType,
+            field_reference::{OuterReference, ReferenceType, RootType},
             if_then::IfClause,
             literal::{Decimal, LiteralType},
             mask_expression::{StructItem, StructSelect},
@@ -102,7 +106,7 @@ pub fn to_substrait_plan(plan: &LogicalPlan, ctx: &SessionContext) -> Result<Box
     // Note: Only 1 relation tree is currently supported
     let plan_rels = vec![PlanRel {
         rel_type: Some(plan_rel::RelType::Root(RelRoot {
-            input: Some(*to_substrait_rel(plan, ctx, &mut extensions)?),
+            input: Some(*to_substrait_rel(plan, ctx, &[], &mut extensions)?),
             names: to_substrait_named_struct(plan.schema(), &mut extensions)?.names,
         })),
     }];
@@ -122,6 +126,7 @@ pub fn to_substrait_plan(plan: &LogicalPlan, ctx: &SessionContext) -> Result<Box
 pub fn to_substrait_rel(
     plan: &LogicalPlan,
     ctx: &SessionContext,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Box<Rel>> {
     match plan {
@@ -224,24 +229,40 @@ pub fn to_substrait_rel(
             let expressions = p
                 .expr
                 .iter()
-                .map(|e| to_substrait_rex(ctx, e, p.input.schema(), 0, extensions))
+                .map(|e| {
+                    to_substrait_rex(
+                        ctx,
+                        e,
+                        p.input.schema(),
+                        0,
+                        outer_schemas,
+                        extensions,
+                    )
+                })
                 .collect::<Result<Vec<_>>>()?;
             Ok(Box::new(Rel {
                 rel_type: Some(RelType::Project(Box::new(ProjectRel {
                     common: None,
-                    input: Some(to_substrait_rel(p.input.as_ref(), ctx, extensions)?),
+                    input: Some(to_substrait_rel(
+                        p.input.as_ref(),
+                        ctx,
+                        outer_schemas,
+                        extensions,
+                    )?),
                     expressions,
                     advanced_extension: None,
                 }))),
             }))
         }
         LogicalPlan::Filter(filter) => {
-            let input = to_substrait_rel(filter.input.as_ref(), ctx, extensions)?;
+            let input =
+                to_substrait_rel(filter.input.as_ref(), ctx, outer_schemas, extensions)?;
             let filter_expr = to_substrait_rex(
                 ctx,
                 &filter.predicate,
                 filter.input.schema(),
                 0,
+                outer_schemas,
                 extensions,
             )?;
             Ok(Box::new(Rel {
@@ -254,7 +275,8 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Limit(limit) => {
-            let input = to_substrait_rel(limit.input.as_ref(), ctx, extensions)?;
+            let input =
+                to_substrait_rel(limit.input.as_ref(), ctx, outer_schemas, extensions)?;
             // Since protobuf can't directly distinguish `None` vs `0` encode `None` as `MAX`
             let limit_fetch = limit.fetch.unwrap_or(usize::MAX);
             Ok(Box::new(Rel {
@@ -268,11 +290,20 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Sort(sort) => {
-            let input = to_substrait_rel(sort.input.as_ref(), ctx, extensions)?;
+            let input =
+                to_substrait_rel(sort.input.as_ref(), ctx, outer_schemas, extensions)?;
             let sort_fields = sort
                 .expr
                 .iter()
-                .map(|e| substrait_sort_field(ctx, e, sort.input.schema(), extensions))
+                .map(|e| {
+                    substrait_sort_field(
+                        ctx,
+                        e,
+                        sort.input.schema(),
+                        outer_schemas,
+                        extensions,
+                    )
+                })
                 .collect::<Result<Vec<_>>>()?;
             Ok(Box::new(Rel {
                 rel_type: Some(RelType::Sort(Box::new(SortRel {
@@ -284,17 +315,27 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Aggregate(agg) => {
-            let input = to_substrait_rel(agg.input.as_ref(), ctx, extensions)?;
+            let input =
+                to_substrait_rel(agg.input.as_ref(), ctx, outer_schemas, extensions)?;
             let groupings = to_substrait_groupings(
                 ctx,
                 &agg.group_expr,
                 agg.input.schema(),
+                outer_schemas,
                 extensions,
             )?;
             let measures = agg
                 .aggr_expr
                 .iter()
-                .map(|e| to_substrait_agg_measure(ctx, e, agg.input.schema(), extensions))
+                .map(|e| {
+                    to_substrait_agg_measure(
+                        ctx,
+                        e,
+                        agg.input.schema(),
+                        outer_schemas,
+                        extensions,
+                    )
+                })
                 .collect::<Result<Vec<_>>>()?;
 
             Ok(Box::new(Rel {
@@ -309,7 +350,7 @@ pub fn to_substrait_rel(
         }
         LogicalPlan::Distinct(Distinct::All(plan)) => {
             // Use Substrait's AggregateRel with empty measures to represent `select distinct`
-            let input = to_substrait_rel(plan.as_ref(), ctx, extensions)?;
+            let input = to_substrait_rel(plan.as_ref(), ctx, outer_schemas, extensions)?;
             // Get grouping keys from the input relation's number of output fields
             let grouping = (0..plan.schema().fields().len())
                 .map(substrait_field_ref)
@@ -328,16 +369,13 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Join(join) => {
-            let left = to_substrait_rel(join.left.as_ref(), ctx, extensions)?;
-            let right = to_substrait_rel(join.right.as_ref(), ctx, extensions)?;
+            let left =
+                to_substrait_rel(join.left.as_ref(), ctx, outer_schemas, extensions)?;
+            let right =
+                to_substrait_rel(join.right.as_ref(), ctx, outer_schemas, extensions)?;
             let join_type = to_substrait_jointype(join.join_type);
-            // we only support basic joins so return an error for anything not yet supported
-            match join.join_constraint {
-                JoinConstraint::On => {}
-                JoinConstraint::Using => {
-                    return not_impl_err!("join constraint: `using`")
-                }
-            }
+            // the columns of a USING join are also its `on` pairs, so it is
+            // encoded as the equivalent ON join
             // parse filter if exists
             let in_join_schema = join.left.schema().join(join.right.schema())?;
             let join_filter = match &join.filter {
@@ -346,6 +384,7 @@ pub fn to_substrait_rel(
                     filter,
                     &Arc::new(in_join_schema),
                     0,
+                    outer_schemas,
                     extensions,
                 )?),
                 None => None,
@@ -364,6 +403,7 @@ pub fn to_substrait_rel(
                 eq_op,
                 join.left.schema(),
                 join.right.schema(),
+                outer_schemas,
                 extensions,
             )?;
 
@@ -403,8 +443,8 @@ pub fn to_substrait_rel(
                 right,
                 schema: _,
             } = cross_join;
-            let left = to_substrait_rel(left.as_ref(), ctx, extensions)?;
-            let right = to_substrait_rel(right.as_ref(), ctx, extensions)?;
+            let left = to_substrait_rel(left.as_ref(), ctx, outer_schemas, extensions)?;
+            let right = to_substrait_rel(right.as_ref(), ctx, outer_schemas, extensions)?;
             Ok(Box::new(Rel {
                 rel_type: Some(RelType::Cross(Box::new(CrossRel {
                     common: None,
@@ -417,13 +457,15 @@ pub fn to_substrait_rel(
         LogicalPlan::SubqueryAlias(alias) => {
             // Do nothing if encounters SubqueryAlias
             // since there is no corresponding relation type in Substrait
-            to_substrait_rel(alias.input.as_ref(), ctx, extensions)
+            to_substrait_rel(alias.input.as_ref(), ctx, outer_schemas, extensions)
         }
         LogicalPlan::Union(union) => {
             let input_rels = union
                 .inputs
                 .iter()
-                .map(|input| to_substrait_rel(input.as_ref(), ctx, extensions))
+                .map(|input| {
+                    to_substrait_rel(input.as_ref(), ctx, outer_schemas, extensions)
+                })
                 .collect::<Result<Vec<_>>>()?
                 .into_iter()
                 .map(|ptr| *ptr)
@@ -438,7 +480,8 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Window(window) => {
-            let input = to_substrait_rel(window.input.as_ref(), ctx, extensions)?;
+            let input =
+                to_substrait_rel(window.input.as_ref(), ctx, outer_schemas, extensions)?;
             // If the input is a Project relation, we can just append the WindowFunction expressions
             // before returning
             // Otherwise, wrap the input in a Project relation before appending the WindowFunction
@@ -466,6 +509,7 @@ pub fn to_substrait_rel(
                     expr,
                     window.input.schema(),
                     0,
+                    outer_schemas,
                     extensions,
                 )?);
             }
@@ -476,7 +520,12 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Repartition(repartition) => {
-            let input = to_substrait_rel(repartition.input.as_ref(), ctx, extensions)?;
+            let input = to_substrait_rel(
+                repartition.input.as_ref(),
+                ctx,
+                outer_schemas,
+                extensions,
+            )?;
             let partition_count = match repartition.partitioning_scheme {
                 Partitioning::RoundRobinBatch(num) => num,
                 Partitioning::Hash(_, num) => num,
@@ -534,7 +583,7 @@ pub fn to_substrait_rel(
                 .node
                 .inputs()
                 .into_iter()
-                .map(|plan| to_substrait_rel(plan, ctx, extensions))
+                .map(|plan| to_substrait_rel(plan, ctx, outer_schemas, extensions))
                 .collect::<Result<Vec<_>>>()?;
             let rel_type = match inputs_rel.len() {
                 0 => RelType::ExtensionLeaf(ExtensionLeafRel {
@@ -626,19 +675,21 @@ fn to_substrait_join_expr(
     eq_op: Operator,
     left_schema: &DFSchemaRef,
     right_schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Option<Expression>> {
     // Only support AND conjunction for each binary expression in join conditions
     let mut exprs: Vec<Expression> = vec![];
     for (left, right) in join_conditions {
         // Parse left
-        let l = to_substrait_rex(ctx, left, left_schema, 0, extensions)?;
+        let l = to_substrait_rex(ctx, left, left_schema, 0, outer_schemas, extensions)?;
         // Parse right
         let r = to_substrait_rex(
             ctx,
             right,
             right_schema,
-            left_schema.fields().len(), // offset to return the correct index
+            left_schema.fields().len(),
+            outer_schemas, // offset to return the correct index
             extensions,
         )?;
         // AND with existing expression
@@ -651,7 +702,7 @@ fn to_substrait_join_expr(
     Ok(join_expr)
 }
 
-fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
+pub(crate) fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
     match join_type {
         JoinType::Inner => join_rel::JoinType::Inner,
         JoinType::Left => join_rel::JoinType::Left,
@@ -703,11 +754,12 @@ pub fn parse_flat_grouping_exprs(
     ctx: &SessionContext,
     exprs: &[Expr],
     schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Grouping> {
     let grouping_expressions = exprs
         .iter()
-        .map(|e| to_substrait_rex(ctx, e, schema, 0, extensions))
+        .map(|e| to_substrait_rex(ctx, e, schema, 0, outer_schemas, extensions))
         .collect::<Result<Vec<_>>>()?;
     Ok(Grouping {
         grouping_expressions,
@@ -718,6 +770,7 @@ pub fn to_substrait_groupings(
     ctx: &SessionContext,
     exprs: &[Expr],
     schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Vec<Grouping>> {
     match exprs.len() {
@@ -728,7 +781,15 @@ pub fn to_substrait_groupings(
                 )),
                 GroupingSet::GroupingSets(sets) => Ok(sets
                     .iter()
-                    .map(|set| parse_flat_grouping_exprs(ctx, set, schema, extensions))
+                    .map(|set| {
+                        parse_flat_grouping_exprs(
+                            ctx,
+                            set,
+                            schema,
+                            outer_schemas,
+                            extensions,
+                        )
+                    })
                     .collect::<Result<Vec<_>>>()?),
                 GroupingSet::Rollup(set) => {
                     let mut sets: Vec<Vec<Expr>> = vec![vec![]];
@@ -739,17 +800,31 @@ pub fn to_substrait_groupings(
                         .iter()
                         .rev()
                         .map(|set| {
-                            parse_flat_grouping_exprs(ctx, set, schema, extensions)
+                            parse_flat_grouping_exprs(
+                                ctx,
+                                set,
+                                schema,
+                                outer_schemas,
+                                extensions,
+                            )
                         })
                         .collect::<Result<Vec<_>>>()?)
                 }
             },
             _ => Ok(vec![parse_flat_grouping_exprs(
-                ctx, exprs, schema, extensions,
+                ctx,
+                exprs,
+                schema,
+                outer_schemas,
+                extensions,
             )?]),
         },
         _ => Ok(vec![parse_flat_grouping_exprs(
-            ctx, exprs, schema, extensions,
+            ctx,
+            exprs,
+            schema,
+            outer_schemas,
+            extensions,
         )?]),
     }
 }
@@ -759,18 +834,19 @@ pub fn to_substrait_agg_measure(
     ctx: &SessionContext,
     expr: &Expr,
     schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Measure> {
     match expr {
         Expr::AggregateFunction(expr::AggregateFunction { func, args, distinct, filter, order_by, null_treatment: _, }) => {
                     let sorts = if let Some(order_by) = order_by {
-                        order_by.iter().map(|expr| to_substrait_sort_field(ctx, expr, schema, extensions)).collect::<Result<Vec<_>>>()?
+                        order_by.iter().map(|expr| to_substrait_sort_field(ctx, expr, schema, outer_schemas, extensions)).collect::<Result<Vec<_>>>()?
                     } else {
                         vec![]
                     };
                     let mut arguments: Vec<FunctionArgument> = vec![];
                     for arg in args {
-                        arguments.push(FunctionArgument { arg_type: Some(ArgType::Value(to_substrait_rex(ctx, arg, schema, 0, extensions)?)) });
+                        arguments.push(FunctionArgument { arg_type: Some(ArgType::Value(to_substrait_rex(ctx, arg, schema, 0, outer_schemas, extensions)?)) });
                     }
                     let function_anchor = extensions.register_function(func.name().to_string());
                     Ok(Measure {
@@ -788,14 +864,14 @@ pub fn to_substrait_agg_measure(
                             options: vec![],
                         }),
                         filter: match filter {
-                            Some(f) => Some(to_substrait_rex(ctx, f, schema, 0, extensions)?),
+                            Some(f) => Some(to_substrait_rex(ctx, f, schema, 0, outer_schemas, extensions)?),
                             None => None
                         }
                     })
 
         }
         Expr::Alias(Alias{expr,..})=> {
-            to_substrait_agg_measure(ctx, expr, schema, extensions)
+            to_substrait_agg_measure(ctx, expr, schema, outer_schemas, extensions)
         }
         _ => internal_err!(
             "Expression must be compatible with aggregation. Unsupported expression: {:?}. ExpressionType: {:?}",
@@ -810,6 +886,7 @@ fn to_substrait_sort_field(
     ctx: &SessionContext,
     expr: &Expr,
     schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<SortField> {
     match expr {
@@ -826,6 +903,7 @@ fn to_substrait_sort_field(
                     sort.expr.deref(),
                     schema,
                     0,
+                    outer_schemas,
                     extensions,
                 )?),
                 sort_kind: Some(SortKind::Direction(sort_kind.into())),
@@ -884,6 +962,8 @@ pub fn make_binary_op_scalar_func(
 ///                     `col_ref(1) = col_ref(3 + 0)`
 ///                     , where `3` is the number of `left` columns (`col_ref_offset`) and `0` is the index
 ///                     of the join key column from `right`
+/// * `outer_schemas` - Schemas of the queries enclosing a subquery, the innermost
+///                     last, for encoding its outer references
 /// * `extensions` - Substrait extension info. Contains registered function information
 #[allow(deprecated)]
 pub fn to_substrait_rex(
@@ -891,6 +971,7 @@ pub fn to_substrait_rex(
     expr: &Expr,
     schema: &DFSchemaRef,
     col_ref_offset: usize,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Expression> {
     match expr {
@@ -901,10 +982,25 @@ pub fn to_substrait_rex(
         }) => {
             let substrait_list = list
                 .iter()
-                .map(|x| to_substrait_rex(ctx, x, schema, col_ref_offset, extensions))
+                .map(|x| {
+                    to_substrait_rex(
+                        ctx,
+                        x,
+                        schema,
+                        col_ref_offset,
+                        outer_schemas,
+                        extensions,
+                    )
+                })
                 .collect::<Result<Vec<Expression>>>()?;
-            let substrait_expr =
-                to_substrait_rex(ctx, expr, schema, col_ref_offset, extensions)?;
+            let substrait_expr = to_substrait_rex(
+                ctx,
+                expr,
+                schema,
+                col_ref_offset,
+                outer_schemas,
+                extensions,
+            )?;
 
             let substrait_or_list = Expression {
                 rex_type: Some(RexType::SingularOrList(Box::new(SingularOrList {
@@ -940,6 +1036,7 @@ pub fn to_substrait_rex(
                         arg,
                         schema,
                         col_ref_offset,
+                        outer_schemas,
                         extensions,
                     )?)),
                 });
@@ -964,12 +1061,30 @@ pub fn to_substrait_rex(
         }) => {
             if *negated {
                 // `expr NOT BETWEEN low AND high` can be translated into (expr < low OR high < expr)
-                let substrait_expr =
-                    to_substrait_rex(ctx, expr, schema, col_ref_offset, extensions)?;
-                let substrait_low =
-                    to_substrait_rex(ctx, low, schema, col_ref_offset, extensions)?;
-                let substrait_high =
-                    to_substrait_rex(ctx, high, schema, col_ref_offset, extensions)?;
+                let substrait_expr = to_substrait_rex(
+                    ctx,
+                    expr,
+                    schema,
+                    col_ref_offset,
+                    outer_schemas,
+                    extensions,
+                )?;
+                let substrait_low = to_substrait_rex(
+                    ctx,
+                    low,
+                    schema,
+                    col_ref_offset,
+                    outer_schemas,
+                    extensions,
+                )?;
+                let substrait_high = to_substrait_rex(
+                    ctx,
+                    high,
+                    schema,
+                    col_ref_offset,
+                    outer_schemas,
+                    extensions,
+                )?;
 
                 let l_expr = make_binary_op_scalar_func(
                     &substrait_expr,
@@ -992,12 +1107,30 @@ pub fn to_substrait_rex(
                 ))
             } else {
                 // `expr BETWEEN low AND high` can be translated into (low <= expr AND expr <= high)
-                let substrait_expr =
-                    to_substrait_rex(ctx, expr, schema, col_ref_offset, extensions)?;
-                let substrait_low =
-                    to_substrait_rex(ctx, low, schema, col_ref_offset, extensions)?;
-                let substrait_high =
-                    to_substrait_rex(ctx, high, schema, col_ref_offset, extensions)?;
+                let substrait_expr = to_substrait_rex(
+                    ctx,
+                    expr,
+                    schema,
+                    col_ref_offset,
+                    outer_schemas,
+                    extensions,
+                )?;
+                let substrait_low = to_substrait_rex(
+                    ctx,
+                    low,
+                    schema,
+                    col_ref_offset,
+                    outer_schemas,
+                    extensions,
+                )?;
+                let substrait_high = to_substrait_rex(
+                    ctx,
+                    high,
+                    schema,
+                    col_ref_offset,
+                    outer_schemas,
+                    extensions,
+                )?;
 
                 let l_expr = make_binary_op_scalar_func(
                     &substrait_low,
@@ -1024,9 +1157,39 @@ pub fn to_substrait_rex(
             let index = schema.index_of_column(col)?;
             substrait_field_ref(index + col_ref_offset)
         }
+        Expr::OuterReferenceColumn(_, col) => {
+            // refer to the innermost enclosing query that has the column
+            for (steps_out, outer_schema) in outer_schemas.iter().rev().enumerate() {
+                if let Some(index) = outer_schema.maybe_index_of_column(col) {
+                    let mut field_reference = substrait_field_reference(index);
+                    field_reference.root_type =
+                        Some(RootType::OuterReference(OuterReference {
+                            steps_out: steps_out as u32 + 1,
+                        }));
+                    return Ok(Expression {
+                        rex_type: Some(RexType::Selection(Box::new(field_reference))),
+                    });
+                }
+            }
+            plan_err!("Outer reference {col} is not a column of an enclosing query")
+        }
         Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
-            let l = to_substrait_rex(ctx, left, schema, col_ref_offset, extensions)?;
-            let r = to_substrait_rex(ctx, right, schema, col_ref_offset, extensions)?;
+            let l = to_substrait_rex(
+                ctx,
+                left,
+                schema,
+                col_ref_offset,
+                outer_schemas,
+                extensions,
+            )?;
+            let r = to_substrait_rex(
+                ctx,
+                right,
+                schema,
+                col_ref_offset,
+                outer_schemas,
+                extensions,
+            )?;
 
             Ok(make_binary_op_scalar_func(&l, &r, *op, extensions))
         }
@@ -1045,6 +1208,7 @@ pub fn to_substrait_rex(
                         e,
                         schema,
                         col_ref_offset,
+                        outer_schemas,
                         extensions,
                     )?),
                     then: None,
@@ -1058,6 +1222,7 @@ pub fn to_substrait_rex(
                         r#if,
                         schema,
                         col_ref_offset,
+                        outer_schemas,
                         extensions,
                     )?),
                     then: Some(to_substrait_rex(
@@ -1065,6 +1230,7 @@ pub fn to_substrait_rex(
                         then,
                         schema,
                         col_ref_offset,
+                        outer_schemas,
                         extensions,
                     )?),
                 });
@@ -1077,6 +1243,7 @@ pub fn to_substrait_rex(
                     e,
                     schema,
                     col_ref_offset,
+                    outer_schemas,
                     extensions,
                 )?)),
                 None => None,
@@ -1096,6 +1263,7 @@ pub fn to_substrait_rex(
                             expr,
                             schema,
                             col_ref_offset,
+                            outer_schemas,
                             extensions,
                         )?)),
                         failure_behavior: 0, // FAILURE_BEHAVIOR_UNSPECIFIED
@@ -1105,7 +1273,7 @@ pub fn to_substrait_rex(
         }
         Expr::Literal(value) => to_substrait_literal_expr(value, extensions),
         Expr::Alias(Alias { expr, .. }) => {
-            to_substrait_rex(ctx, expr, schema, col_ref_offset, extensions)
+            to_substrait_rex(ctx, expr, schema, col_ref_offset, outer_schemas, extensions)
         }
         Expr::WindowFunction(WindowFunction {
             fun,
@@ -1126,6 +1294,7 @@ pub fn to_substrait_rex(
                         arg,
                         schema,
                         col_ref_offset,
+                        outer_schemas,
                         extensions,
                     )?)),
                 });
@@ -1133,12 +1302,21 @@ pub fn to_substrait_rex(
             // partition by expressions
             let partition_by = partition_by
                 .iter()
-                .map(|e| to_substrait_rex(ctx, e, schema, col_ref_offset, extensions))
+                .map(|e| {
+                    to_substrait_rex(
+                        ctx,
+                        e,
+                        schema,
+                        col_ref_offset,
+                        outer_schemas,
+                        extensions,
+                    )
+                })
                 .collect::<Result<Vec<_>>>()?;
             // order by expressions
             let order_by = order_by
                 .iter()
-                .map(|e| substrait_sort_field(ctx, e, schema, extensions))
+                .map(|e| substrait_sort_field(ctx, e, schema, outer_schemas, extensions))
                 .collect::<Result<Vec<_>>>()?;
             // window frame
             let bounds = to_substrait_bounds(window_frame)?;
@@ -1167,29 +1345,93 @@ pub fn to_substrait_rex(
             *escape_char,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
+        Expr::ScalarSubquery(subquery) => {
+            let subquery_plan = to_substrait_subquery_rel(
+                subquery.subquery.as_ref(),
+                ctx,
+                schema,
+                outer_schemas,
+                extensions,
+            )?;
+            Ok(Expression {
+                rex_type: Some(RexType::Subquery(Box::new(Subquery {
+                    subquery_type: Some(SubqueryType::Scalar(Box::new(
+                        subquery::Scalar {
+                            input: Some(subquery_plan),
+                        },
+                    ))),
+                }))),
+            })
+        }
+        Expr::Exists(Exists { subquery, negated }) => {
+            let subquery_plan = to_substrait_subquery_rel(
+                subquery.subquery.as_ref(),
+                ctx,
+                schema,
+                outer_schemas,
+                extensions,
+            )?;
+            let substrait_subquery = Expression {
+                rex_type: Some(RexType::Subquery(Box::new(Subquery {
+                    subquery_type: Some(SubqueryType::SetPredicate(Box::new(
+                        SetPredicate {
+                            predicate_op: PredicateOp::Exists as i32,
+                            tuples: Some(subquery_plan),
+                        },
+                    ))),
+                }))),
+            };
+            if *negated {
+                let function_anchor = extensions.register_function("not".to_string());
+
+                Ok(Expression {
+                    rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+                        function_reference: function_anchor,
+                        arguments: vec![FunctionArgument {
+                            arg_type: Some(ArgType::Value(substrait_subquery)),
+                        }],
+                        output_type: None,
+                        args: vec![],
+                        options: vec![],
+                    })),
+                })
+            } else {
+                Ok(substrait_subquery)
+            }
+        }
         Expr::InSubquery(InSubquery {
             expr,
             subquery,
             negated,
         }) => {
-            let substrait_expr =
-                to_substrait_rex(ctx, expr, schema, col_ref_offset, extensions)?;
+            let substrait_expr = to_substrait_rex(
+                ctx,
+                expr,
+                schema,
+                col_ref_offset,
+                outer_schemas,
+                extensions,
+            )?;
 
-            let subquery_plan =
-                to_substrait_rel(subquery.subquery.as_ref(), ctx, extensions)?;
+            let subquery_plan = to_substrait_subquery_rel(
+                subquery.subquery.as_ref(),
+                ctx,
+                schema,
+                outer_schemas,
+                extensions,
+            )?;
 
             let substrait_subquery = Expression {
                 rex_type: Some(RexType::Subquery(Box::new(Subquery {
-                    subquery_type: Some(
-                        substrait::proto::expression::subquery::SubqueryType::InPredicate(
-                            Box::new(InPredicate {
-                                needles: (vec![substrait_expr]),
-                                haystack: Some(subquery_plan),
-                            }),
-                        ),
-                    ),
+                    subquery_type: Some(SubqueryType::InPredicate(Box::new(
+                        InPredicate {
+                            needles: (vec![substrait_expr]),
+                            haystack: Some(subquery_plan),
+                        },
+                    ))),
                 }))),
             };
             if *negated {
@@ -1216,6 +1458,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsNull(arg) => to_substrait_unary_scalar_fn(
@@ -1224,6 +1467,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsNotNull(arg) => to_substrait_unary_scalar_fn(
@@ -1232,6 +1476,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsTrue(arg) => to_substrait_unary_scalar_fn(
@@ -1240,6 +1485,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsFalse(arg) => to_substrait_unary_scalar_fn(
@@ -1248,6 +1494,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsUnknown(arg) => to_substrait_unary_scalar_fn(
@@ -1256,6 +1503,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsNotTrue(arg) => to_substrait_unary_scalar_fn(
@@ -1264,6 +1512,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsNotFalse(arg) => to_substrait_unary_scalar_fn(
@@ -1272,6 +1521,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::IsNotUnknown(arg) => to_substrait_unary_scalar_fn(
@@ -1280,6 +1530,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         Expr::Negative(arg) => to_substrait_unary_scalar_fn(
@@ -1288,6 +1539,7 @@ pub fn to_substrait_rex(
             arg,
             schema,
             col_ref_offset,
+            outer_schemas,
             extensions,
         ),
         _ => {
@@ -1296,7 +1548,21 @@ pub fn to_substrait_rex(
     }
 }
 
-fn to_substrait_type(
+/// Convert the plan of a subquery of an expression over `schema`, which is
+/// the innermost query its outer references can refer to
+fn to_substrait_subquery_rel(
+    subquery: &LogicalPlan,
+    ctx: &SessionContext,
+    schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
+    extensions: &mut Extensions,
+) -> Result<Box<Rel>> {
+    let mut outer_schemas = outer_schemas.to_vec();
+    outer_schemas.push(Arc::clone(schema));
+    to_substrait_rel(subquery, ctx, &outer_schemas, extensions)
+}
+
+pub(crate) fn to_substrait_type(
     dt: &DataType,
     nullable: bool,
     extensions: &mut Extensions,
@@ -1592,6 +1858,7 @@ fn make_substrait_like_expr(
     escape_char: Option<char>,
     schema: &DFSchemaRef,
     col_ref_offset: usize,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Expression> {
     let function_anchor = if ignore_case {
@@ -1599,8 +1866,16 @@ fn make_substrait_like_expr(
     } else {
         extensions.register_function("like".to_string())
     };
-    let expr = to_substrait_rex(ctx, expr, schema, col_ref_offset, extensions)?;
-    let pattern = to_substrait_rex(ctx, pattern, schema, col_ref_offset, extensions)?;
+    let expr =
+        to_substrait_rex(ctx, expr, schema, col_ref_offset, outer_schemas, extensions)?;
+    let pattern = to_substrait_rex(
+        ctx,
+        pattern,
+        schema,
+        col_ref_offset,
+        outer_schemas,
+        extensions,
+    )?;
     let escape_char = to_substrait_literal_expr(
         &ScalarValue::Utf8(escape_char.map(|c| c.to_string())),
         extensions,
@@ -2045,7 +2320,7 @@ fn convert_array_to_literal_list<T: OffsetSizeTrait>(
     }
 }
 
-fn to_substrait_literal_expr(
+pub(crate) fn to_substrait_literal_expr(
     value: &ScalarValue,
     extensions: &mut Extensions,
 ) -> Result<Expression> {
@@ -2062,10 +2337,12 @@ fn to_substrait_unary_scalar_fn(
     arg: &Expr,
     schema: &DFSchemaRef,
     col_ref_offset: usize,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<Expression> {
     let function_anchor = extensions.register_function(fn_name.to_string());
-    let substrait_expr = to_substrait_rex(ctx, arg, schema, col_ref_offset, extensions)?;
+    let substrait_expr =
+        to_substrait_rex(ctx, arg, schema, col_ref_offset, outer_schemas, extensions)?;
 
     Ok(Expression {
         rex_type: Some(RexType::ScalarFunction(ScalarFunction {
@@ -2089,17 +2366,7 @@ fn try_to_substrait_field_reference(
     match expr {
         Expr::Column(col) => {
             let index = schema.index_of_column(col)?;
-            Ok(FieldReference {
-                reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
-                    reference_type: Some(reference_segment::ReferenceType::StructField(
-                        Box::new(reference_segment::StructField {
-                            field: index as i32,
-                            child: None,
-                        }),
-                    )),
-                })),
-                root_type: None,
-            })
+            Ok(substrait_field_reference(index))
         }
         _ => substrait_err!("Expect a `Column` expr, but found {expr:?}"),
     }
@@ -2109,6 +2376,7 @@ fn substrait_sort_field(
     ctx: &SessionContext,
     expr: &Expr,
     schema: &DFSchemaRef,
+    outer_schemas: &[DFSchemaRef],
     extensions: &mut Extensions,
 ) -> Result<SortField> {
     match expr {
@@ -2117,7 +2385,7 @@ fn substrait_sort_field(
             asc,
             nulls_first,
         }) => {
-            let e = to_substrait_rex(ctx, expr, schema, 0, extensions)?;
+            let e = to_substrait_rex(ctx, expr, schema, 0, outer_schemas, extensions)?;
             let d = match (asc, nulls_first) {
                 (true, true) => SortDirection::AscNullsFirst,
                 (true, false) => SortDirection::AscNullsLast,
@@ -2133,22 +2401,29 @@ fn substrait_sort_field(
     }
 }
 
-fn substrait_field_ref(index: usize) -> Result<Expression> {
+pub(crate) fn substrait_field_ref(index: usize) -> Result<Expression> {
     Ok(Expression {
-        rex_type: Some(RexType::Selection(Box::new(FieldReference {
-            reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
-                reference_type: Some(reference_segment::ReferenceType::StructField(
-                    Box::new(reference_segment::StructField {
-                        field: index as i32,
-                        child: None,
-                    }),
-                )),
-            })),
-            root_type: None,
-        }))),
+        rex_type: Some(RexType::Selection(Box::new(substrait_field_reference(
+            index,
+        )))),
     })
 }
 
+/// A reference to the field at `index` of the input
+pub(crate) fn substrait_field_reference(index: usize) -> FieldReference {
+    FieldReference {
+        reference_type: Some(ReferenceType::DirectReference(ReferenceSegment {
+            reference_type: Some(reference_segment::ReferenceType::StructField(
+                Box::new(reference_segment::StructField {
+                    field: index as i32,
+                    child: None,
+                }),
+            )),
+        })),
+        root_type: None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -15,40 +15,92 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::{DataType, Field, Schema};
-use datafusion::common::{not_impl_err, substrait_err};
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::common::{not_impl_err, substrait_err, DFSchema, TableReference};
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{FileScanConfig, ParquetExec};
 use datafusion::error::{DataFusionError, Result};
-use datafusion::physical_plan::ExecutionPlan;
+use datafusion::logical_expr::{binary_expr, Expr, Operator};
+use datafusion::physical_expr::expressions::Column;
+use datafusion::physical_expr::{create_physical_expr, PhysicalExpr};
+use datafusion::physical_plan::aggregates::{
+    AggregateExec, AggregateMode, PhysicalGroupBy,
+};
+use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::joins::utils::JoinFilter;
+use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion::physical_plan::limit::GlobalLimitExec;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sorts::sort::SortExec;
+use datafusion::physical_plan::{ExecutionPlan, Partitioning};
+use datafusion::physical_planner::{
+    create_aggregate_expr_and_maybe_filter, create_physical_sort_exprs,
+};
 use datafusion::prelude::SessionContext;
 
+use crate::extensions::Extensions;
+use crate::logical_plan::consumer::{
+    from_substrait_agg_func, from_substrait_jointype, from_substrait_rex,
+    from_substrait_sorts, split_eq_and_noneq_join_predicate_with_nulls_equality,
+};
+use crate::physical_plan::{
+    AGGREGATE_MODE_TYPE_URL, COALESCE_BATCHES_TYPE_URL, PARTITION_MODE_TYPE_URL,
+};
 use async_recursion::async_recursion;
 use chrono::DateTime;
 use object_store::ObjectMeta;
+use substrait::proto::aggregate_function::AggregationInvocation;
+use substrait::proto::exchange_rel::ExchangeKind;
+use substrait::proto::expression::field_reference::ReferenceType;
+use substrait::proto::expression::{reference_segment, FieldReference};
+use substrait::proto::extensions::AdvancedExtension;
 use substrait::proto::r#type::{Kind, Nullability};
 use substrait::proto::read_rel::local_files::file_or_files::PathType;
-use substrait::proto::Type;
+use substrait::proto::rel_common::EmitKind;
 use substrait::proto::{
     expression::MaskExpression, read_rel::ReadType, rel::RelType, Rel,
 };
+use substrait::proto::{Expression, Type};
 
 /// Convert Substrait Rel to DataFusion ExecutionPlan
-#[async_recursion]
+///
+/// `extensions` maps the function anchors referenced by the relation to the
+/// function names. Joins are planned as a [`HashJoinExec`] and aggregations
+/// as an [`AggregateExec`], whose modes are read from the enhancements of the
+/// relations, if any (see [`crate::physical_plan::producer::to_substrait_rel`]).
 pub async fn from_substrait_rel(
-    _ctx: &SessionContext,
+    ctx: &SessionContext,
+    rel: &Rel,
+    extensions: &HashMap<u32, &String>,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let extensions = Extensions {
+        functions: extensions
+            .iter()
+            .map(|(anchor, name)| (*anchor, name.to_string()))
+            .collect(),
+        ..Default::default()
+    };
+    rel_to_plan(ctx, rel, &extensions).await
+}
+
+#[async_recursion]
+async fn rel_to_plan(
+    ctx: &SessionContext,
     rel: &Rel,
-    _extensions: &HashMap<u32, &String>,
+    extensions: &Extensions,
 ) -> Result<Arc<dyn ExecutionPlan>> {
     let mut base_config;
 
     match &rel.rel_type {
         Some(RelType::Read(read)) => {
-            if read.filter.is_some() || read.best_effort_filter.is_some() {
+            if read.filter.is_some() {
                 return not_impl_err!("Read with filter is not supported");
             }
 
@@ -144,18 +196,492 @@ pub async fn from_substrait_rel(
                         }
                     }
 
-                    Ok(ParquetExec::builder(base_config).build_arc()
-                        as Arc<dyn ExecutionPlan>)
+                    let file_schema = Arc::clone(&base_config.file_schema);
+                    let mut builder = ParquetExec::builder(base_config);
+                    if let Some(filter) = &read.best_effort_filter {
+                        let predicate = from_substrait_physical_rex(
+                            ctx,
+                            filter,
+                            &to_df_schema(&file_schema)?,
+                            extensions,
+                        )
+                        .await?;
+                        builder = builder.with_predicate(predicate);
+                    }
+
+                    Ok(builder.build_arc() as Arc<dyn ExecutionPlan>)
                 }
                 _ => not_impl_err!(
                     "Only LocalFile reads are supported when parsing physical"
                 ),
             }
         }
+        Some(RelType::Filter(filter)) => {
+            let Some(input) = filter.input.as_ref() else {
+                return substrait_err!("Filter without an input is not valid");
+            };
+            let Some(condition) = filter.condition.as_ref() else {
+                return substrait_err!("Filter without a condition is not valid");
+            };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            let schema = to_df_schema(&input.schema())?;
+            let predicate =
+                from_substrait_physical_rex(ctx, condition, &schema, extensions).await?;
+            Ok(Arc::new(FilterExec::try_new(predicate, input)?))
+        }
+        Some(RelType::Project(project)) => {
+            let Some(input) = project.input.as_ref() else {
+                return substrait_err!("Projection without an input is not valid");
+            };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            let schema = to_df_schema(&input.schema())?;
+            let mut exprs = vec![];
+            for e in &project.expressions {
+                let expr = from_substrait_rex(ctx, e, &schema, &[], extensions).await?;
+                let name = physical_name(&expr);
+                exprs.push((
+                    create_physical_expr(&expr, &schema, ctx.state().execution_props())?,
+                    name,
+                ));
+            }
+            Ok(Arc::new(ProjectionExec::try_new(exprs, input)?))
+        }
+        Some(RelType::Aggregate(agg)) => {
+            let Some(input) = agg.input.as_ref() else {
+                return substrait_err!("Aggregate without an input is not valid");
+            };
+            let mode =
+                match extension_mode(&agg.advanced_extension, AGGREGATE_MODE_TYPE_URL)? {
+                    None | Some("Single") => AggregateMode::Single,
+                    Some("SinglePartitioned") => AggregateMode::SinglePartitioned,
+                    Some("Partial") => AggregateMode::Partial,
+                    Some("Final") => AggregateMode::Final,
+                    Some("FinalPartitioned") => AggregateMode::FinalPartitioned,
+                    Some(mode) => return substrait_err!("Unknown aggregate mode {mode}"),
+                };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            let schema = to_df_schema(&input.schema())?;
+            // The aggregate functions of a final aggregation refer to the input
+            // of the partial aggregation it combines
+            let input_schema = match mode {
+                AggregateMode::Final | AggregateMode::FinalPartitioned => {
+                    partial_aggregate_input_schema(input.as_ref())?
+                }
+                _ => input.schema(),
+            };
+            let aggr_schema = to_df_schema(&input_schema)?;
+            let state = ctx.state();
+            let execution_props = state.execution_props();
+
+            let grouping_expressions = match agg.groupings.as_slice() {
+                [] => &[][..],
+                [grouping] => grouping.grouping_expressions.as_slice(),
+                _ => {
+                    return not_impl_err!(
+                        "Aggregate with grouping sets is not supported in Substrait physical plan consumer"
+                    )
+                }
+            };
+            let mut group_expr = vec![];
+            for e in grouping_expressions {
+                let expr = from_substrait_rex(ctx, e, &schema, &[], extensions).await?;
+                let name = physical_name(&expr);
+                group_expr
+                    .push((create_physical_expr(&expr, &schema, execution_props)?, name));
+            }
+
+            let mut aggr_expr = vec![];
+            let mut filter_expr = vec![];
+            for m in &agg.measures {
+                let Some(f) = m.measure.as_ref() else {
+                    return substrait_err!("Aggregate measure without a function");
+                };
+                let filter = match &m.filter {
+                    Some(filter) => Some(Box::new(
+                        from_substrait_rex(ctx, filter, &aggr_schema, &[], extensions)
+                            .await?,
+                    )),
+                    None => None,
+                };
+                let order_by = if f.sorts.is_empty() {
+                    None
+                } else {
+                    Some(
+                        from_substrait_sorts(
+                            ctx,
+                            &f.sorts,
+                            &aggr_schema,
+                            &[],
+                            extensions,
+                        )
+                        .await?,
+                    )
+                };
+                let distinct = f.invocation == AggregationInvocation::Distinct as i32;
+                let expr = from_substrait_agg_func(
+                    ctx,
+                    f,
+                    &aggr_schema,
+                    &[],
+                    extensions,
+                    filter,
+                    order_by,
+                    distinct,
+                )
+                .await?;
+                let (expr, filter, _) = create_aggregate_expr_and_maybe_filter(
+                    &expr,
+                    &aggr_schema,
+                    &input_schema,
+                    execution_props,
+                )?;
+                aggr_expr.push(expr);
+                filter_expr.push(filter);
+            }
+
+            Ok(Arc::new(AggregateExec::try_new(
+                mode,
+                PhysicalGroupBy::new_single(group_expr),
+                aggr_expr,
+                filter_expr,
+                input,
+                input_schema,
+            )?))
+        }
+        Some(RelType::Join(join)) => {
+            let (Some(left), Some(right)) = (join.left.as_ref(), join.right.as_ref())
+            else {
+                return substrait_err!("Join without two inputs is not valid");
+            };
+            let Some(expression) = join.expression.as_ref() else {
+                return not_impl_err!(
+                    "Join without a join expression is not supported in Substrait physical plan consumer"
+                );
+            };
+            let partition_mode = match extension_mode(
+                &join.advanced_extension,
+                PARTITION_MODE_TYPE_URL,
+            )? {
+                None | Some("CollectLeft") => PartitionMode::CollectLeft,
+                Some("Partitioned") => PartitionMode::Partitioned,
+                Some("Auto") => PartitionMode::Auto,
+                Some(mode) => return substrait_err!("Unknown partition mode {mode}"),
+            };
+            // the output mapping of the join is its projection
+            let projection = match join.common.as_ref().and_then(|c| c.emit_kind.as_ref())
+            {
+                Some(EmitKind::Emit(emit)) => Some(
+                    emit.output_mapping
+                        .iter()
+                        .map(|i| *i as usize)
+                        .collect::<Vec<_>>(),
+                ),
+                Some(EmitKind::Direct(_)) | None => None,
+            };
+            let left = rel_to_plan(ctx, left, extensions).await?;
+            let right = rel_to_plan(ctx, right, extensions).await?;
+            let join_type = from_substrait_jointype(join.r#type)?;
+            let left_schema = left.schema();
+            let left_width = left_schema.fields().len();
+            let join_schema = Arc::new(Schema::new(
+                left_schema
+                    .fields()
+                    .iter()
+                    .chain(right.schema().fields().iter())
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            ));
+            let schema = to_df_schema(&join_schema)?;
+            let state = ctx.state();
+            let execution_props = state.execution_props();
+
+            let expr =
+                from_substrait_rex(ctx, expression, &schema, &[], extensions).await?;
+            let (keys, null_equals_null, filter) =
+                split_eq_and_noneq_join_predicate_with_nulls_equality(&expr);
+            let eq_op = if null_equals_null {
+                Operator::IsNotDistinctFrom
+            } else {
+                Operator::Eq
+            };
+
+            // equalities between a left and a right column are the join keys,
+            // anything else is evaluated by the join filter
+            let mut on = vec![];
+            let mut filters = filter.into_iter().collect::<Vec<_>>();
+            for (l, r) in keys {
+                let l_index = schema.index_of_column(&l)?;
+                let r_index = schema.index_of_column(&r)?;
+                let key = match (l_index < left_width, r_index < left_width) {
+                    (true, false) => Some((l_index, r_index)),
+                    (false, true) => Some((r_index, l_index)),
+                    _ => None,
+                };
+                match key {
+                    Some((l_index, r_index)) => {
+                        let l_name = join_schema.field(l_index).name();
+                        let r_name = join_schema.field(r_index).name();
+                        on.push((
+                            Arc::new(Column::new(l_name, l_index))
+                                as Arc<dyn PhysicalExpr>,
+                            Arc::new(Column::new(r_name, r_index - left_width))
+                                as Arc<dyn PhysicalExpr>,
+                        ));
+                    }
+                    None => {
+                        filters.push(binary_expr(Expr::Column(l), eq_op, Expr::Column(r)))
+                    }
+                }
+            }
+            if on.is_empty() {
+                return not_impl_err!(
+                    "Join without equi-join keys is not supported in Substrait physical plan consumer"
+                );
+            }
+
+            let filter = match filters.into_iter().reduce(Expr::and) {
+                Some(filter) => {
+                    // the filter is evaluated on an intermediate schema holding
+                    // only the columns it references, left ones first
+                    let mut indices = filter
+                        .column_refs()
+                        .into_iter()
+                        .map(|c| schema.index_of_column(c))
+                        .collect::<Result<Vec<_>>>()?;
+                    indices.sort_unstable();
+                    let filter_df_schema = DFSchema::new_with_metadata(
+                        indices
+                            .iter()
+                            .map(|i| {
+                                let (qualifier, field) = schema.qualified_field(*i);
+                                (qualifier.cloned(), Arc::new(field.clone()))
+                            })
+                            .collect(),
+                        HashMap::new(),
+                    )?;
+                    let (left_indices, right_indices): (Vec<_>, Vec<_>) =
+                        indices.into_iter().partition(|i| *i < left_width);
+                    let right_indices =
+                        right_indices.into_iter().map(|i| i - left_width).collect();
+                    Some(JoinFilter::new(
+                        create_physical_expr(
+                            &filter,
+                            &filter_df_schema,
+                            execution_props,
+                        )?,
+                        JoinFilter::build_column_indices(left_indices, right_indices),
+                        filter_df_schema.as_arrow().clone(),
+                    ))
+                }
+                None => None,
+            };
+
+            Ok(Arc::new(HashJoinExec::try_new(
+                left,
+                right,
+                on,
+                filter,
+                &join_type,
+                projection,
+                partition_mode,
+                null_equals_null,
+            )?))
+        }
+        Some(RelType::Sort(sort)) => {
+            let Some(input) = sort.input.as_ref() else {
+                return substrait_err!("Sort without an input is not valid");
+            };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            let schema = to_df_schema(&input.schema())?;
+            let sorts =
+                from_substrait_sorts(ctx, &sort.sorts, &schema, &[], extensions).await?;
+            let sort_exprs = create_physical_sort_exprs(
+                &sorts,
+                &schema,
+                ctx.state().execution_props(),
+            )?;
+            Ok(Arc::new(SortExec::new(sort_exprs, input)))
+        }
+        Some(RelType::Fetch(fetch)) => {
+            let Some(input) = fetch.input.as_ref() else {
+                return substrait_err!("Fetch without an input is not valid");
+            };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            let offset = fetch.offset as usize;
+            // -1 means that ALL records should be returned
+            let count = if fetch.count as usize == usize::MAX {
+                None
+            } else {
+                Some(fetch.count as usize)
+            };
+            Ok(Arc::new(GlobalLimitExec::new(input, offset, count)))
+        }
+        Some(RelType::Exchange(exchange)) => {
+            let Some(input) = exchange.input.as_ref() else {
+                return substrait_err!("Exchange without an input is not valid");
+            };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            let partition_count = exchange.partition_count as usize;
+            // ref: https://substrait.io/relations/physical_relations/#exchange-types
+            let partitioning = match &exchange.exchange_kind {
+                // all the rows are sent to a single partition
+                Some(ExchangeKind::RoundRobin(_)) if partition_count == 1 => {
+                    return Ok(Arc::new(CoalescePartitionsExec::new(input)));
+                }
+                Some(ExchangeKind::RoundRobin(_)) => {
+                    Partitioning::RoundRobinBatch(partition_count)
+                }
+                Some(ExchangeKind::ScatterByFields(scatter_fields)) => {
+                    let schema = input.schema();
+                    let exprs = scatter_fields
+                        .fields
+                        .iter()
+                        .map(|field_ref| {
+                            let index = field_index(field_ref)?;
+                            let Some(field) = schema.fields().get(index) else {
+                                return substrait_err!(
+                                    "Exchange field reference {index} is out of bounds"
+                                );
+                            };
+                            Ok(Arc::new(Column::new(field.name(), index))
+                                as Arc<dyn PhysicalExpr>)
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Partitioning::Hash(exprs, partition_count)
+                }
+                exchange_kind => {
+                    return not_impl_err!("Unsupported exchange kind: {exchange_kind:?}")
+                }
+            };
+            Ok(Arc::new(RepartitionExec::try_new(input, partitioning)?))
+        }
+        Some(RelType::ExtensionSingle(extension)) => {
+            let Some(detail) = extension.detail.as_ref() else {
+                return substrait_err!("Unexpected empty detail in ExtensionSingleRel");
+            };
+            let Some(input) = extension.input.as_ref() else {
+                return substrait_err!(
+                    "ExtensionSingleRel without an input is not valid"
+                );
+            };
+            if detail.type_url != COALESCE_BATCHES_TYPE_URL {
+                return not_impl_err!(
+                    "Unsupported extension in Substrait physical plan consumer: {}",
+                    detail.type_url
+                );
+            }
+            let target_batch_size = std::str::from_utf8(&detail.value)
+                .ok()
+                .and_then(|size| size.parse().ok());
+            let Some(target_batch_size) = target_batch_size else {
+                return substrait_err!(
+                    "Invalid target batch size in {}",
+                    detail.type_url
+                );
+            };
+            let input = rel_to_plan(ctx, input, extensions).await?;
+            Ok(Arc::new(CoalesceBatchesExec::new(input, target_batch_size)))
+        }
         _ => not_impl_err!("Unsupported RelType: {:?}", rel.rel_type),
     }
 }
 
+/// Convert a Substrait Expression to a DataFusion PhysicalExpr evaluated
+/// against `input_schema`
+async fn from_substrait_physical_rex(
+    ctx: &SessionContext,
+    e: &Expression,
+    input_schema: &DFSchema,
+    extensions: &Extensions,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    let expr = from_substrait_rex(ctx, e, input_schema, &[], extensions).await?;
+    create_physical_expr(&expr, input_schema, ctx.state().execution_props())
+}
+
+/// Returns the mode recorded by the enhancement `type_url` of
+/// `advanced_extension`, if any
+fn extension_mode<'a>(
+    advanced_extension: &'a Option<AdvancedExtension>,
+    type_url: &str,
+) -> Result<Option<&'a str>> {
+    let Some(enhancement) = advanced_extension
+        .as_ref()
+        .and_then(|extension| extension.enhancement.as_ref())
+    else {
+        return Ok(None);
+    };
+    if enhancement.type_url != type_url {
+        return not_impl_err!(
+            "Unsupported enhancement in Substrait physical plan consumer: {}",
+            enhancement.type_url
+        );
+    }
+    match std::str::from_utf8(&enhancement.value) {
+        Ok(mode) => Ok(Some(mode)),
+        Err(e) => substrait_err!("Invalid mode in {type_url}: {e}"),
+    }
+}
+
+/// Returns the input schema of the partial aggregation combined by a final
+/// aggregation of `plan`
+fn partial_aggregate_input_schema(plan: &dyn ExecutionPlan) -> Result<SchemaRef> {
+    if let Some(agg) = plan.as_any().downcast_ref::<AggregateExec>() {
+        if *agg.mode() == AggregateMode::Partial {
+            return Ok(agg.input_schema());
+        }
+    }
+    match plan.children().as_slice() {
+        [child] => partial_aggregate_input_schema(child.as_ref()),
+        _ => substrait_err!("Final aggregate without a partial aggregate in its input"),
+    }
+}
+
+/// Returns the index of the input field referenced by `field_ref`
+fn field_index(field_ref: &FieldReference) -> Result<usize> {
+    match &field_ref.reference_type {
+        Some(ReferenceType::DirectReference(segment)) => match &segment.reference_type {
+            Some(reference_segment::ReferenceType::StructField(field))
+                if field.child.is_none() =>
+            {
+                Ok(field.field as usize)
+            }
+            _ => not_impl_err!(
+                "Direct reference with types other than StructField is not supported"
+            ),
+        },
+        _ => not_impl_err!("unsupported field ref type"),
+    }
+}
+
+/// Build the [`DFSchema`] Substrait expressions are converted against.
+///
+/// Substrait references fields by index, so when field names are not unique,
+/// as in the output of a join, each field is qualified by its index to keep
+/// the references unambiguous.
+fn to_df_schema(schema: &SchemaRef) -> Result<DFSchema> {
+    let names = schema
+        .fields()
+        .iter()
+        .map(|f| f.name())
+        .collect::<HashSet<_>>();
+    if names.len() == schema.fields().len() {
+        DFSchema::try_from(Arc::clone(schema))
+    } else {
+        let qualifiers = (0..schema.fields().len())
+            .map(|i| Some(TableReference::bare(i.to_string())))
+            .collect();
+        DFSchema::from_field_specific_qualified_schema(qualifiers, schema)
+    }
+}
+
+/// The output name of a projected or grouping expression
+fn physical_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Column(column) => column.name.clone(),
+        _ => expr.schema_name().to_string(),
+    }
+}
+
 fn to_field(name: &String, r#type: &Type) -> Result<Field> {
     let Some(kind) = r#type.kind.as_ref() else {
         return substrait_err!("Missing kind in the type with name {}", name);
@@ -17,3 +17,24 @@
 
 pub mod consumer;
 pub mod producer;
+
+/// Type URL of the enhancement of an `AggregateRel` holding the
+/// [`AggregateMode`] of a multi-stage aggregation, encoded as the name of the
+/// mode. Aggregations without this enhancement are done in a single stage.
+///
+/// [`AggregateMode`]: datafusion::physical_plan::aggregates::AggregateMode
+pub const AGGREGATE_MODE_TYPE_URL: &str = "datafusion.AggregateMode";
+
+/// Type URL of the enhancement of a `JoinRel` holding the [`PartitionMode`]
+/// of a hash join, encoded as the name of the mode. Joins without this
+/// enhancement collect their left input.
+///
+/// [`PartitionMode`]: datafusion::physical_plan::joins::PartitionMode
+pub const PARTITION_MODE_TYPE_URL: &str = "datafusion.PartitionMode";
+
+/// Type URL of the detail of an `ExtensionSingleRel` encoding a
+/// [`CoalesceBatchesExec`], whose target batch size is encoded as a decimal
+/// number.
+///
+/// [`CoalesceBatchesExec`]: datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec
+pub const COALESCE_BATCHES_TYPE_URL: &str = "datafusion.CoalesceBatchesExec";
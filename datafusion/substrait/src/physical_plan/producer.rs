@@ -15,13 +15,50 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::extensions::Extensions;
+use crate::logical_plan::producer::{
+    make_binary_op_scalar_func, substrait_field_ref, substrait_field_reference,
+    to_substrait_jointype, to_substrait_literal_expr,
+    to_substrait_type as to_substrait_expr_type,
+};
+use crate::physical_plan::{
+    AGGREGATE_MODE_TYPE_URL, COALESCE_BATCHES_TYPE_URL, PARTITION_MODE_TYPE_URL,
+};
 use datafusion::arrow::datatypes::DataType;
+use datafusion::common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion::common::{not_impl_err, JoinSide, JoinType};
 use datafusion::datasource::physical_plan::ParquetExec;
 use datafusion::error::{DataFusionError, Result};
-use datafusion::physical_plan::{displayable, ExecutionPlan};
-use std::collections::HashMap;
+use datafusion::logical_expr::Operator;
+use datafusion::physical_expr::expressions::{
+    BinaryExpr, CastExpr, Column, InListExpr, IsNotNullExpr, IsNullExpr, Literal,
+    NegativeExpr, NotExpr,
+};
+use datafusion::physical_expr::{PhysicalExpr, PhysicalSortExpr, ScalarFunctionExpr};
+use datafusion::physical_plan::aggregates::{AggregateExec, AggregateMode};
+use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion::physical_plan::limit::GlobalLimitExec;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sorts::sort::SortExec;
+use datafusion::physical_plan::{displayable, ExecutionPlan, Partitioning};
+use pbjson_types::Any as ProtoAny;
+use substrait::proto::aggregate_function::AggregationInvocation;
+use substrait::proto::aggregate_rel::{Grouping, Measure};
+use substrait::proto::exchange_rel::{ExchangeKind, RoundRobin, ScatterFields};
 use substrait::proto::expression::mask_expression::{StructItem, StructSelect};
-use substrait::proto::expression::MaskExpression;
+use substrait::proto::expression::{
+    MaskExpression, RexType, ScalarFunction, SingularOrList,
+};
+use substrait::proto::extensions::AdvancedExtension;
+use substrait::proto::function_argument::ArgType;
 use substrait::proto::r#type::{
     Boolean, Fp64, Kind, Nullability, String as SubstraitString, Struct, I64,
 };
@@ -31,17 +68,52 @@ use substrait::proto::read_rel::local_files::FileOrFiles;
 use substrait::proto::read_rel::LocalFiles;
 use substrait::proto::read_rel::ReadType;
 use substrait::proto::rel::RelType;
-use substrait::proto::ReadRel;
-use substrait::proto::Rel;
-use substrait::proto::{extensions, NamedStruct, Type};
+use substrait::proto::rel_common::{Emit, EmitKind};
+use substrait::proto::sort_field::{SortDirection, SortKind};
+use substrait::proto::{
+    extensions, AggregateFunction, AggregateRel, AggregationPhase, ExchangeRel,
+    Expression, ExtensionSingleRel, FetchRel, FilterRel, FunctionArgument, JoinRel,
+    NamedStruct, ProjectRel, ReadRel, Rel, RelCommon, SortField, SortRel, Type,
+};
 
 /// Convert DataFusion ExecutionPlan to Substrait Rel
+///
+/// Functions referenced by the plan are registered in `extension_info`, as
+/// the extension declarations of the plan and a map from function name to
+/// function anchor.
+///
+/// Substrait relations do not carry the names of the expressions they compute,
+/// so the names of projected, grouping and aggregate expressions are derived
+/// from the expressions by the consumer.
+///
+/// The stages of multi-stage aggregations and the partitioning of hash joins
+/// are recorded in enhancements of the relations (see
+/// [`AGGREGATE_MODE_TYPE_URL`] and [`PARTITION_MODE_TYPE_URL`]). Repartitions
+/// are encoded as exchanges, where an exchange to a single partition stands
+/// for a [`CoalescePartitionsExec`], and [`CoalesceBatchesExec`] is encoded
+/// as an extension (see [`COALESCE_BATCHES_TYPE_URL`]).
 pub fn to_substrait_rel(
     plan: &dyn ExecutionPlan,
-    _extension_info: &mut (
+    extension_info: &mut (
         Vec<extensions::SimpleExtensionDeclaration>,
         HashMap<String, u32>,
     ),
+) -> Result<Box<Rel>> {
+    let mut extensions = Extensions::try_from(&extension_info.0)?;
+    let rel = plan_to_substrait_rel(plan, &mut extensions)?;
+    extension_info.1 = extensions
+        .functions
+        .iter()
+        .map(|(anchor, name)| (name.clone(), *anchor))
+        .collect();
+    extension_info.0 = extensions.into();
+    Ok(rel)
+}
+
+#[allow(deprecated)]
+fn plan_to_substrait_rel(
+    plan: &dyn ExecutionPlan,
+    extensions: &mut Extensions,
 ) -> Result<Box<Rel>> {
     if let Some(scan) = plan.as_any().downcast_ref::<ParquetExec>() {
         let base_config = scan.base_config();
@@ -96,6 +168,11 @@ pub fn to_substrait_rel(
             select_struct = Some(StructSelect { struct_items });
         }
 
+        let best_effort_filter = scan
+            .predicate()
+            .map(|predicate| to_substrait_rex(predicate, extensions).map(Box::new))
+            .transpose()?;
+
         Ok(Box::new(Rel {
             rel_type: Some(RelType::Read(Box::new(ReadRel {
                 common: None,
@@ -104,7 +181,9 @@ pub fn to_substrait_rel(
                     r#struct: Some(type_info),
                 }),
                 filter: None,
-                best_effort_filter: None,
+                // the predicate of a parquet scan is only used for pruning, and
+                // rows that do not match it may still be returned
+                best_effort_filter,
                 projection: Some(MaskExpression {
                     select: select_struct,
                     // FIXME: duckdb set this to true, but it's not clear why.
@@ -118,6 +197,298 @@ pub fn to_substrait_rel(
                 })),
             }))),
         }))
+    } else if let Some(filter) = plan.as_any().downcast_ref::<FilterExec>() {
+        let input = plan_to_substrait_rel(filter.input().as_ref(), extensions)?;
+        let condition = to_substrait_rex(filter.predicate(), extensions)?;
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Filter(Box::new(FilterRel {
+                common: None,
+                input: Some(input),
+                condition: Some(Box::new(condition)),
+                advanced_extension: None,
+            }))),
+        }))
+    } else if let Some(projection) = plan.as_any().downcast_ref::<ProjectionExec>() {
+        let input = plan_to_substrait_rel(projection.input().as_ref(), extensions)?;
+        let expressions = projection
+            .expr()
+            .iter()
+            .map(|(expr, _)| to_substrait_rex(expr, extensions))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Project(Box::new(ProjectRel {
+                common: None,
+                input: Some(input),
+                expressions,
+                advanced_extension: None,
+            }))),
+        }))
+    } else if let Some(agg) = plan.as_any().downcast_ref::<AggregateExec>() {
+        if !agg.group_expr().is_single() {
+            return not_impl_err!(
+                "Aggregate with grouping sets is not supported in Substrait physical plan producer"
+            );
+        }
+        let input = plan_to_substrait_rel(agg.input().as_ref(), extensions)?;
+        let grouping_expressions = agg
+            .group_expr()
+            .expr()
+            .iter()
+            .map(|(expr, _)| to_substrait_rex(expr, extensions))
+            .collect::<Result<Vec<_>>>()?;
+        // The arguments of the aggregate functions of a final aggregation refer
+        // to the input of the partial aggregation it combines
+        let (phase, advanced_extension) = match agg.mode() {
+            AggregateMode::Single => (AggregationPhase::InitialToResult, None),
+            AggregateMode::SinglePartitioned => (
+                AggregationPhase::InitialToResult,
+                Some(mode_extension(AGGREGATE_MODE_TYPE_URL, agg.mode())),
+            ),
+            AggregateMode::Partial => (
+                AggregationPhase::InitialToIntermediate,
+                Some(mode_extension(AGGREGATE_MODE_TYPE_URL, agg.mode())),
+            ),
+            AggregateMode::Final | AggregateMode::FinalPartitioned => (
+                AggregationPhase::IntermediateToResult,
+                Some(mode_extension(AGGREGATE_MODE_TYPE_URL, agg.mode())),
+            ),
+        };
+        let groupings = if grouping_expressions.is_empty() {
+            vec![]
+        } else {
+            vec![Grouping {
+                grouping_expressions,
+            }]
+        };
+        let measures = agg
+            .aggr_expr()
+            .iter()
+            .zip(agg.filter_expr())
+            .map(|(aggr_expr, filter)| {
+                let arguments = aggr_expr
+                    .expressions()
+                    .iter()
+                    .map(|arg| to_substrait_argument(arg, extensions))
+                    .collect::<Result<Vec<_>>>()?;
+                let sorts = aggr_expr
+                    .order_bys()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|sort| to_substrait_sort_field(sort, extensions))
+                    .collect::<Result<Vec<_>>>()?;
+                let filter = filter
+                    .as_ref()
+                    .map(|filter| to_substrait_rex(filter, extensions))
+                    .transpose()?;
+                let function_anchor =
+                    extensions.register_function(aggr_expr.fun().name().to_string());
+                Ok(Measure {
+                    measure: Some(AggregateFunction {
+                        function_reference: function_anchor,
+                        arguments,
+                        sorts,
+                        output_type: None,
+                        invocation: if aggr_expr.is_distinct() {
+                            AggregationInvocation::Distinct as i32
+                        } else {
+                            AggregationInvocation::All as i32
+                        },
+                        phase: phase as i32,
+                        args: vec![],
+                        options: vec![],
+                    }),
+                    filter,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Aggregate(Box::new(AggregateRel {
+                common: None,
+                input: Some(input),
+                groupings,
+                measures,
+                advanced_extension,
+            }))),
+        }))
+    } else if let Some(join) = plan.as_any().downcast_ref::<HashJoinExec>() {
+        if matches!(join.join_type(), JoinType::RightSemi | JoinType::RightAnti) {
+            return not_impl_err!(
+                "Join type {} is not supported in Substrait physical plan producer",
+                join.join_type()
+            );
+        }
+        let left = plan_to_substrait_rel(join.left().as_ref(), extensions)?;
+        let right = plan_to_substrait_rel(join.right().as_ref(), extensions)?;
+
+        // Substrait evaluates the join expression on the concatenation of the
+        // left and right columns, so right columns are offset by the left width
+        let left_width = join.left().schema().fields().len();
+        let eq_op = if join.null_equals_null() {
+            Operator::IsNotDistinctFrom
+        } else {
+            Operator::Eq
+        };
+        let mut conditions = join
+            .on()
+            .iter()
+            .map(|(l, r)| {
+                let l = to_substrait_rex(l, extensions)?;
+                let r = to_substrait_rex(&offset_columns(r, left_width)?, extensions)?;
+                Ok(make_binary_op_scalar_func(&l, &r, eq_op, extensions))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(filter) = join.filter() {
+            // the filter is evaluated on an intermediate schema holding only the
+            // columns it references
+            let column_indices = filter.column_indices();
+            let expr = Arc::clone(filter.expression())
+                .transform(|expr| {
+                    let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+                        return Ok(Transformed::no(expr));
+                    };
+                    let column_index = &column_indices[column.index()];
+                    let index = match column_index.side {
+                        JoinSide::Left => column_index.index,
+                        JoinSide::Right => left_width + column_index.index,
+                    };
+                    Ok(Transformed::yes(
+                        Arc::new(Column::new(column.name(), index)) as _,
+                    ))
+                })
+                .data()?;
+            conditions.push(to_substrait_rex(&expr, extensions)?);
+        }
+        let expression = conditions
+            .into_iter()
+            .reduce(|l, r| make_binary_op_scalar_func(&l, &r, Operator::And, extensions))
+            .map(Box::new);
+
+        // the projection of the join is its output mapping
+        let common = join.projection.as_ref().map(|projection| RelCommon {
+            emit_kind: Some(EmitKind::Emit(Emit {
+                output_mapping: projection.iter().map(|i| *i as i32).collect(),
+            })),
+            ..Default::default()
+        });
+        let advanced_extension = match join.partition_mode() {
+            PartitionMode::CollectLeft => None,
+            mode => Some(mode_extension(PARTITION_MODE_TYPE_URL, mode)),
+        };
+
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Join(Box::new(JoinRel {
+                common,
+                left: Some(left),
+                right: Some(right),
+                r#type: to_substrait_jointype(*join.join_type()) as i32,
+                expression,
+                post_join_filter: None,
+                advanced_extension,
+            }))),
+        }))
+    } else if let Some(sort) = plan.as_any().downcast_ref::<SortExec>() {
+        if sort.preserve_partitioning() {
+            return not_impl_err!(
+                "SortExec preserving partitioning is not supported in Substrait physical plan producer"
+            );
+        }
+        let input = plan_to_substrait_rel(sort.input().as_ref(), extensions)?;
+        let sorts = sort
+            .expr()
+            .iter()
+            .map(|sort| to_substrait_sort_field(sort, extensions))
+            .collect::<Result<Vec<_>>>()?;
+        let sort_rel = Box::new(Rel {
+            rel_type: Some(RelType::Sort(Box::new(SortRel {
+                common: None,
+                input: Some(input),
+                sorts,
+                advanced_extension: None,
+            }))),
+        });
+        match sort.fetch() {
+            // a sort with a fetch is a top-k, which Substrait expresses as a
+            // fetch of the sorted input
+            Some(fetch) => Ok(Box::new(Rel {
+                rel_type: Some(RelType::Fetch(Box::new(FetchRel {
+                    common: None,
+                    input: Some(sort_rel),
+                    offset: 0,
+                    count: fetch as i64,
+                    advanced_extension: None,
+                }))),
+            })),
+            None => Ok(sort_rel),
+        }
+    } else if let Some(limit) = plan.as_any().downcast_ref::<GlobalLimitExec>() {
+        let input = plan_to_substrait_rel(limit.input().as_ref(), extensions)?;
+        // Since protobuf can't directly distinguish `None` vs `0` encode `None` as `MAX`
+        let count = limit.fetch().unwrap_or(usize::MAX);
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::Fetch(Box::new(FetchRel {
+                common: None,
+                input: Some(input),
+                offset: limit.skip() as i64,
+                count: count as i64,
+                advanced_extension: None,
+            }))),
+        }))
+    } else if let Some(repartition) = plan.as_any().downcast_ref::<RepartitionExec>() {
+        if repartition.preserve_order() {
+            return not_impl_err!(
+                "RepartitionExec preserving order is not supported in Substrait physical plan producer"
+            );
+        }
+        let input = plan_to_substrait_rel(repartition.input().as_ref(), extensions)?;
+        // ref: https://substrait.io/relations/physical_relations/#exchange-types
+        let (exchange_kind, partition_count) = match repartition.partitioning() {
+            Partitioning::RoundRobinBatch(n) => {
+                (ExchangeKind::RoundRobin(RoundRobin::default()), *n)
+            }
+            Partitioning::Hash(exprs, n) => {
+                let fields = exprs
+                    .iter()
+                    .map(|expr| match expr.as_any().downcast_ref::<Column>() {
+                        Some(column) => Ok(substrait_field_reference(column.index())),
+                        None => not_impl_err!(
+                            "Hash partitioning on {expr} is not supported in Substrait physical plan producer"
+                        ),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                (ExchangeKind::ScatterByFields(ScatterFields { fields }), *n)
+            }
+            partitioning => {
+                return not_impl_err!(
+                    "Partitioning {partitioning} is not supported in Substrait physical plan producer"
+                )
+            }
+        };
+        Ok(exchange_rel(input, exchange_kind, partition_count))
+    } else if let Some(coalesce) = plan.as_any().downcast_ref::<CoalescePartitionsExec>()
+    {
+        let input = plan_to_substrait_rel(coalesce.input().as_ref(), extensions)?;
+        Ok(exchange_rel(
+            input,
+            ExchangeKind::RoundRobin(RoundRobin::default()),
+            1,
+        ))
+    } else if let Some(coalesce) = plan.as_any().downcast_ref::<CoalesceBatchesExec>() {
+        if coalesce.fetch().is_some() {
+            return not_impl_err!(
+                "CoalesceBatchesExec with a fetch is not supported in Substrait physical plan producer"
+            );
+        }
+        let input = plan_to_substrait_rel(coalesce.input().as_ref(), extensions)?;
+        Ok(Box::new(Rel {
+            rel_type: Some(RelType::ExtensionSingle(Box::new(ExtensionSingleRel {
+                common: None,
+                detail: Some(ProtoAny {
+                    type_url: COALESCE_BATCHES_TYPE_URL.to_string(),
+                    value: coalesce.target_batch_size().to_string().into_bytes().into(),
+                }),
+                input: Some(input),
+            }))),
+        }))
     } else {
         Err(DataFusionError::Substrait(format!(
             "Unsupported plan in Substrait physical plan producer: {}",
@@ -126,6 +497,179 @@ pub fn to_substrait_rel(
     }
 }
 
+/// An exchange of the rows of `input` to `partition_count` partitions
+fn exchange_rel(
+    input: Box<Rel>,
+    exchange_kind: ExchangeKind,
+    partition_count: usize,
+) -> Box<Rel> {
+    Box::new(Rel {
+        rel_type: Some(RelType::Exchange(Box::new(ExchangeRel {
+            common: None,
+            input: Some(input),
+            exchange_kind: Some(exchange_kind),
+            advanced_extension: None,
+            partition_count: partition_count as i32,
+            targets: vec![],
+        }))),
+    })
+}
+
+/// An enhancement recording `mode`, by its name
+fn mode_extension(type_url: &str, mode: &impl Debug) -> AdvancedExtension {
+    AdvancedExtension {
+        enhancement: Some(ProtoAny {
+            type_url: type_url.to_string(),
+            value: format!("{mode:?}").into_bytes().into(),
+        }),
+        ..Default::default()
+    }
+}
+
+/// Convert a DataFusion PhysicalExpr to a Substrait Expression, where columns
+/// are referenced by their index in the input
+#[allow(deprecated)]
+fn to_substrait_rex(
+    expr: &Arc<dyn PhysicalExpr>,
+    extensions: &mut Extensions,
+) -> Result<Expression> {
+    let expr_any = expr.as_any();
+    if let Some(column) = expr_any.downcast_ref::<Column>() {
+        substrait_field_ref(column.index())
+    } else if let Some(literal) = expr_any.downcast_ref::<Literal>() {
+        to_substrait_literal_expr(literal.value(), extensions)
+    } else if let Some(binary) = expr_any.downcast_ref::<BinaryExpr>() {
+        let l = to_substrait_rex(binary.left(), extensions)?;
+        let r = to_substrait_rex(binary.right(), extensions)?;
+        Ok(make_binary_op_scalar_func(&l, &r, *binary.op(), extensions))
+    } else if let Some(not) = expr_any.downcast_ref::<NotExpr>() {
+        to_substrait_scalar_fn("not", &[Arc::clone(not.arg())], extensions)
+    } else if let Some(is_null) = expr_any.downcast_ref::<IsNullExpr>() {
+        to_substrait_scalar_fn("is_null", &[Arc::clone(is_null.arg())], extensions)
+    } else if let Some(is_not_null) = expr_any.downcast_ref::<IsNotNullExpr>() {
+        to_substrait_scalar_fn(
+            "is_not_null",
+            &[Arc::clone(is_not_null.arg())],
+            extensions,
+        )
+    } else if let Some(negative) = expr_any.downcast_ref::<NegativeExpr>() {
+        to_substrait_scalar_fn("negative", &[Arc::clone(negative.arg())], extensions)
+    } else if let Some(cast) = expr_any.downcast_ref::<CastExpr>() {
+        Ok(Expression {
+            rex_type: Some(RexType::Cast(Box::new(
+                substrait::proto::expression::Cast {
+                    r#type: Some(to_substrait_expr_type(
+                        cast.cast_type(),
+                        true,
+                        extensions,
+                    )?),
+                    input: Some(Box::new(to_substrait_rex(cast.expr(), extensions)?)),
+                    failure_behavior: 0, // FAILURE_BEHAVIOR_UNSPECIFIED
+                },
+            ))),
+        })
+    } else if let Some(in_list) = expr_any.downcast_ref::<InListExpr>() {
+        let options = in_list
+            .list()
+            .iter()
+            .map(|e| to_substrait_rex(e, extensions))
+            .collect::<Result<Vec<_>>>()?;
+        let value = to_substrait_rex(in_list.expr(), extensions)?;
+        let or_list = Expression {
+            rex_type: Some(RexType::SingularOrList(Box::new(SingularOrList {
+                value: Some(Box::new(value)),
+                options,
+            }))),
+        };
+        if in_list.negated() {
+            let function_anchor = extensions.register_function("not".to_string());
+            Ok(Expression {
+                rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+                    function_reference: function_anchor,
+                    arguments: vec![FunctionArgument {
+                        arg_type: Some(ArgType::Value(or_list)),
+                    }],
+                    output_type: None,
+                    args: vec![],
+                    options: vec![],
+                })),
+            })
+        } else {
+            Ok(or_list)
+        }
+    } else if let Some(func) = expr_any.downcast_ref::<ScalarFunctionExpr>() {
+        to_substrait_scalar_fn(func.name(), func.args(), extensions)
+    } else {
+        not_impl_err!(
+            "Unsupported expression in Substrait physical plan producer: {expr}"
+        )
+    }
+}
+
+/// Util to generate a Substrait [RexType::ScalarFunction]
+#[allow(deprecated)]
+fn to_substrait_scalar_fn(
+    fn_name: &str,
+    args: &[Arc<dyn PhysicalExpr>],
+    extensions: &mut Extensions,
+) -> Result<Expression> {
+    let arguments = args
+        .iter()
+        .map(|arg| to_substrait_argument(arg, extensions))
+        .collect::<Result<Vec<_>>>()?;
+    let function_anchor = extensions.register_function(fn_name.to_string());
+    Ok(Expression {
+        rex_type: Some(RexType::ScalarFunction(ScalarFunction {
+            function_reference: function_anchor,
+            arguments,
+            output_type: None,
+            args: vec![],
+            options: vec![],
+        })),
+    })
+}
+
+fn to_substrait_argument(
+    arg: &Arc<dyn PhysicalExpr>,
+    extensions: &mut Extensions,
+) -> Result<FunctionArgument> {
+    Ok(FunctionArgument {
+        arg_type: Some(ArgType::Value(to_substrait_rex(arg, extensions)?)),
+    })
+}
+
+fn to_substrait_sort_field(
+    sort: &PhysicalSortExpr,
+    extensions: &mut Extensions,
+) -> Result<SortField> {
+    let sort_kind = match (sort.options.descending, sort.options.nulls_first) {
+        (false, true) => SortDirection::AscNullsFirst,
+        (false, false) => SortDirection::AscNullsLast,
+        (true, true) => SortDirection::DescNullsFirst,
+        (true, false) => SortDirection::DescNullsLast,
+    };
+    Ok(SortField {
+        expr: Some(to_substrait_rex(&sort.expr, extensions)?),
+        sort_kind: Some(SortKind::Direction(sort_kind.into())),
+    })
+}
+
+/// Shift the indices of the columns in `expr` by `offset`
+fn offset_columns(
+    expr: &Arc<dyn PhysicalExpr>,
+    offset: usize,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    Arc::clone(expr)
+        .transform(|expr| match expr.as_any().downcast_ref::<Column>() {
+            Some(column) => Ok(Transformed::yes(Arc::new(Column::new(
+                column.name(),
+                column.index() + offset,
+            )) as _)),
+            None => Ok(Transformed::no(expr)),
+        })
+        .data()
+}
+
 // see https://github.com/duckdb/substrait/blob/b6f56643cb11d52de0e32c24a01dfd5947df62be/src/to_substrait.cpp#L954-L1094.
 fn to_substrait_type(data_type: &DataType, nullable: bool) -> Result<Type> {
     let nullability = if nullable {
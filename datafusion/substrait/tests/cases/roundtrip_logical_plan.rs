@@ -15,7 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use datafusion::arrow::array::ArrayRef;
+use datafusion::arrow::array::{ArrayRef, RecordBatch};
 use datafusion::physical_plan::Accumulator;
 use datafusion::scalar::ScalarValue;
 use datafusion_substrait::logical_plan::{
@@ -38,12 +38,22 @@ use datafusion::optimizer::simplify_expressions::expr_simplifier::THRESHOLD_INLI
 use datafusion::prelude::*;
 
 use datafusion::execution::session_state::SessionStateBuilder;
+use substrait::proto::expression::RexType;
 use substrait::proto::extensions::simple_extension_declaration::{
-    ExtensionType, MappingType,
+    ExtensionType, ExtensionTypeVariation, MappingType,
 };
 use substrait::proto::extensions::SimpleExtensionDeclaration;
+use substrait::proto::function_argument::ArgType;
+use substrait::proto::r#type::{Kind, Nullability, Struct, I64};
+use substrait::proto::read_rel::local_files::file_or_files::{
+    FileFormat, ParquetReadOptions, PathType,
+};
+use substrait::proto::read_rel::local_files::FileOrFiles;
+use substrait::proto::read_rel::{LocalFiles, ReadType};
 use substrait::proto::rel::RelType;
-use substrait::proto::{plan_rel, Plan, Rel};
+use substrait::proto::{
+    plan_rel, FunctionArgument, NamedStruct, Plan, PlanRel, ReadRel, Rel, RelRoot, Type,
+};
 
 struct MockSerializerRegistry;
 
@@ -221,6 +231,138 @@ async fn roundtrip_udt_extensions() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn type_variation_extensions() -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx
+        .sql("SELECT a, c FROM data")
+        .await?
+        .into_optimized_plan()?;
+    let mut proto = to_substrait_plan(&plan, &ctx)?;
+    proto.extensions.push(SimpleExtensionDeclaration {
+        mapping_type: Some(MappingType::ExtensionTypeVariation(
+            ExtensionTypeVariation {
+                extension_uri_reference: u32::MAX,
+                type_variation_anchor: 0,
+                name: "date32".to_string(),
+            },
+        )),
+    });
+
+    let plan2 = from_substrait_plan(&ctx, &proto).await?;
+    let plan2 = ctx.state().optimize(&plan2)?;
+    assert_eq!(format!("{plan}"), format!("{plan2}"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn local_files_read() -> Result<()> {
+    let ctx = SessionContext::new();
+    let i64_type = Type {
+        kind: Some(Kind::I64(I64 {
+            type_variation_reference: 0,
+            nullability: Nullability::Nullable as i32,
+        })),
+    };
+    // both items are read, whatever tables the context has
+    let path = format!(
+        "file://{}/tests/testdata/data.parquet",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let item = FileOrFiles {
+        path_type: Some(PathType::UriFile(path)),
+        file_format: Some(FileFormat::Parquet(ParquetReadOptions {})),
+        ..Default::default()
+    };
+    let read = ReadRel {
+        base_schema: Some(NamedStruct {
+            names: vec!["a".to_string(), "e".to_string()],
+            r#struct: Some(Struct {
+                types: vec![i64_type.clone(), i64_type],
+                ..Default::default()
+            }),
+        }),
+        read_type: Some(ReadType::LocalFiles(LocalFiles {
+            items: vec![item.clone(), item],
+            advanced_extension: None,
+        })),
+        ..Default::default()
+    };
+    let proto = Plan {
+        relations: vec![PlanRel {
+            rel_type: Some(plan_rel::RelType::Root(RelRoot {
+                input: Some(Rel {
+                    rel_type: Some(RelType::Read(Box::new(read))),
+                }),
+                names: vec!["a".to_string(), "e".to_string()],
+            })),
+        }],
+        ..Default::default()
+    };
+
+    let plan = from_substrait_plan(&ctx, &proto).await?;
+    let batches = DataFrame::new(ctx.state(), plan).collect().await?;
+    datafusion::assert_batches_sorted_eq!(
+        [
+            "+---+------------+",
+            "| a | e          |",
+            "+---+------------+",
+            "| 1 | 4294967295 |",
+            "| 1 | 4294967295 |",
+            "| 3 | 2147483648 |",
+            "| 3 | 2147483648 |",
+            "+---+------------+",
+        ],
+        &batches
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn extract_enum_argument() -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx
+        .sql("SELECT date_part('year', c) FROM data")
+        .await?
+        .into_optimized_plan()?;
+    let mut proto = to_substrait_plan(&plan, &ctx)?;
+
+    // turn `date_part('year', c)` into the standard `extract(YEAR, c)`
+    for extension in proto.extensions.iter_mut() {
+        if let Some(MappingType::ExtensionFunction(f)) = extension.mapping_type.as_mut() {
+            if f.name == "date_part" {
+                f.name = "extract:req_date".to_string();
+            }
+        }
+    }
+    let Some(plan_rel::RelType::Root(root)) = proto.relations[0].rel_type.as_mut() else {
+        panic!("Expected a root relation");
+    };
+    let Some(RelType::Project(project)) = root.input.as_mut().unwrap().rel_type.as_mut()
+    else {
+        panic!("Expected a projection");
+    };
+    let Some(RexType::ScalarFunction(f)) = project.expressions[0].rex_type.as_mut()
+    else {
+        panic!("Expected a scalar function");
+    };
+    f.arguments[0] = FunctionArgument {
+        arg_type: Some(ArgType::Enum("YEAR".to_string())),
+    };
+
+    let plan2 = from_substrait_plan(&ctx, &proto).await?;
+    let plan2str = format!("{plan2}");
+    assert!(
+        plan2str.contains("date_part(Utf8(\"YEAR\"), data.c)"),
+        "{plan2str}"
+    );
+
+    let expected = DataFrame::new(ctx.state(), plan).collect().await?;
+    let actual = DataFrame::new(ctx.state(), plan2).collect().await?;
+    assert_eq!(expected[0].column(0), actual[0].column(0));
+    Ok(())
+}
+
 #[tokio::test]
 async fn select_with_filter_date() -> Result<()> {
     roundtrip("SELECT * FROM data WHERE c > CAST('2020-01-01' AS DATE)").await
@@ -511,6 +653,56 @@ async fn inner_join() -> Result<()> {
     .await
 }
 
+#[tokio::test]
+async fn using_join() -> Result<()> {
+    assert_expected_plan(
+        "SELECT data.a FROM data JOIN data2 USING (a)",
+        "Projection: data.a\
+         \n  Inner Join: data.a = data2.a\
+         \n    TableScan: data projection=[a]\
+         \n    TableScan: data2 projection=[a]",
+        true,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn roundtrip_scalar_subquery() -> Result<()> {
+    roundtrip_subquery("SELECT a, (SELECT max(a) FROM data2) FROM data").await
+}
+
+#[tokio::test]
+async fn roundtrip_not_exists_subquery() -> Result<()> {
+    roundtrip_subquery(
+        "SELECT a FROM data WHERE NOT EXISTS (SELECT a FROM data2 WHERE data2.a > 5)",
+    )
+    .await
+}
+
+#[tokio::test]
+async fn roundtrip_correlated_exists_subquery() -> Result<()> {
+    roundtrip_correlated_subquery(
+        "SELECT a FROM data WHERE EXISTS (SELECT a FROM data2 WHERE data2.a = data.a)",
+    )
+    .await
+}
+
+#[tokio::test]
+async fn roundtrip_correlated_in_subquery() -> Result<()> {
+    roundtrip_correlated_subquery(
+        "SELECT a FROM data WHERE a NOT IN (SELECT a FROM data2 WHERE data2.a > data.a)",
+    )
+    .await
+}
+
+#[tokio::test]
+async fn roundtrip_correlated_scalar_subquery() -> Result<()> {
+    roundtrip_correlated_subquery(
+        "SELECT a, (SELECT max(a) FROM data2 WHERE data2.a < data.a) FROM data",
+    )
+    .await
+}
+
 #[tokio::test]
 async fn roundtrip_left_join() -> Result<()> {
     roundtrip("SELECT data.a FROM data LEFT JOIN data2 ON data.a = data2.a").await
@@ -1141,6 +1333,53 @@ async fn roundtrip_with_ctx(sql: &str, ctx: SessionContext) -> Result<Box<Plan>>
     Ok(proto)
 }
 
+/// Round trip the unoptimized plan of `sql`, which keeps its subqueries, and
+/// check that both plans return the same rows
+async fn roundtrip_subquery(sql: &str) -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx.sql(sql).await?.into_unoptimized_plan();
+    let proto = to_substrait_plan(&plan, &ctx)?;
+    let plan2 = from_substrait_plan(&ctx, &proto).await?;
+
+    println!("{plan}");
+    println!("{plan2}");
+
+    let plan2str = format!("{plan2}");
+    assert!(plan2str.contains("Subquery:"), "{plan2str}");
+
+    let expected = DataFrame::new(ctx.state(), plan).collect().await?;
+    let actual = DataFrame::new(ctx.state(), plan2).collect().await?;
+    let columns = |batches: &[RecordBatch]| {
+        batches
+            .iter()
+            .map(|batch| batch.columns().to_vec())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(columns(&expected), columns(&actual));
+    Ok(())
+}
+
+/// Round trip the unoptimized plan of `sql`, whose subqueries refer to the
+/// outer query, and check that both plans are the same once optimized
+async fn roundtrip_correlated_subquery(sql: &str) -> Result<()> {
+    let ctx = create_context().await?;
+    let plan = ctx.sql(sql).await?.into_unoptimized_plan();
+    let proto = to_substrait_plan(&plan, &ctx)?;
+    let plan2 = from_substrait_plan(&ctx, &proto).await?;
+
+    println!("{plan}");
+    println!("{plan2}");
+
+    let plan2str = format!("{plan2}");
+    assert!(plan2str.contains("outer_ref(data.a)"), "{plan2str}");
+
+    let plan = ctx.state().optimize(&plan)?;
+    let plan2 = ctx.state().optimize(&plan2)?;
+    assert_eq!(format!("{plan}"), format!("{plan2}"));
+    assert_eq!(plan.schema(), plan2.schema());
+    Ok(())
+}
+
 async fn roundtrip(sql: &str) -> Result<()> {
     roundtrip_with_ctx(sql, create_context().await?).await?;
     Ok(())
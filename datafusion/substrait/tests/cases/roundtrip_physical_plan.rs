@@ -18,14 +18,33 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use datafusion::arrow::datatypes::Schema;
+use datafusion::arrow::compute::SortOptions;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::common::{JoinSide, JoinType, ScalarValue};
 use datafusion::dataframe::DataFrame;
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{FileScanConfig, ParquetExec};
 use datafusion::error::Result;
-use datafusion::physical_plan::{displayable, ExecutionPlan};
-use datafusion::prelude::{ParquetReadOptions, SessionContext};
+use datafusion::functions_aggregate::count::count_udaf;
+use datafusion::logical_expr::Operator;
+use datafusion::physical_expr::aggregate::AggregateExprBuilder;
+use datafusion::physical_expr::expressions::{binary, col, is_not_null, lit, Column};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::aggregates::{
+    AggregateExec, AggregateMode, PhysicalGroupBy,
+};
+use datafusion::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::filter::FilterExec;
+use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
+use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
+use datafusion::physical_plan::limit::GlobalLimitExec;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::sorts::sort::SortExec;
+use datafusion::physical_plan::{displayable, ExecutionPlan, Partitioning};
+use datafusion::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
 use datafusion_substrait::physical_plan::{consumer, producer};
 
 use substrait::proto::extensions;
@@ -95,6 +114,183 @@ async fn wildcard_select_alltypes() -> Result<()> {
     roundtrip_alltypes("SELECT * FROM alltypes_plain").await
 }
 
+#[tokio::test]
+async fn parquet_exec_with_predicate() -> Result<()> {
+    let schema = test_schema();
+    let predicate = binary(
+        col("a", &schema)?,
+        Operator::Gt,
+        lit(ScalarValue::Int64(Some(1))),
+        &schema,
+    )?;
+    let scan = ParquetExec::builder(test_scan_config(schema))
+        .with_predicate(predicate)
+        .build_arc();
+
+    roundtrip_exec(scan).await
+}
+
+#[tokio::test]
+async fn filter_and_projection() -> Result<()> {
+    let schema = test_schema();
+    let scan = ParquetExec::builder(test_scan_config(Arc::clone(&schema))).build_arc();
+    let predicate = binary(
+        binary(
+            col("a", &schema)?,
+            Operator::Gt,
+            lit(ScalarValue::Int64(Some(1))),
+            &schema,
+        )?,
+        Operator::And,
+        is_not_null(col("b", &schema)?)?,
+        &schema,
+    )?;
+    let filter = Arc::new(FilterExec::try_new(predicate, scan)?);
+    let projection = Arc::new(ProjectionExec::try_new(
+        vec![
+            (col("b", &schema)?, "b".to_string()),
+            (
+                binary(
+                    col("a", &schema)?,
+                    Operator::Plus,
+                    lit(ScalarValue::Int64(Some(1))),
+                    &schema,
+                )?,
+                "a + Int64(1)".to_string(),
+            ),
+        ],
+        filter,
+    )?);
+
+    roundtrip_exec(projection).await
+}
+
+#[tokio::test]
+async fn aggregate() -> Result<()> {
+    let schema = test_schema();
+    let scan = ParquetExec::builder(test_scan_config(Arc::clone(&schema))).build_arc();
+    let count = AggregateExprBuilder::new(count_udaf(), vec![col("b", &schema)?])
+        .schema(Arc::clone(&schema))
+        .alias("count(b)")
+        .build()?;
+    let aggregate = Arc::new(AggregateExec::try_new(
+        AggregateMode::Single,
+        PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]),
+        vec![count],
+        vec![None],
+        scan,
+        schema,
+    )?);
+
+    roundtrip_exec(aggregate).await
+}
+
+#[tokio::test]
+async fn hash_join() -> Result<()> {
+    let schema = test_schema();
+    let left = ParquetExec::builder(test_scan_config(Arc::clone(&schema))).build_arc();
+    let right = ParquetExec::builder(test_scan_config(Arc::clone(&schema))).build_arc();
+
+    // the filter `left.b != right.b` on its own intermediate schema
+    let filter_schema = Schema::new(vec![
+        Field::new("b", DataType::Utf8, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let filter = JoinFilter::new(
+        binary(
+            Arc::new(Column::new("b", 0)),
+            Operator::NotEq,
+            Arc::new(Column::new("b", 1)),
+            &filter_schema,
+        )?,
+        vec![
+            ColumnIndex {
+                index: 1,
+                side: JoinSide::Left,
+            },
+            ColumnIndex {
+                index: 1,
+                side: JoinSide::Right,
+            },
+        ],
+        filter_schema,
+    );
+    let join = Arc::new(HashJoinExec::try_new(
+        left,
+        right,
+        vec![(col("a", &schema)?, col("a", &schema)?)],
+        Some(filter),
+        &JoinType::Inner,
+        None,
+        PartitionMode::CollectLeft,
+        false,
+    )?);
+
+    roundtrip_exec(join).await
+}
+
+#[tokio::test]
+async fn partitioned_aggregate() -> Result<()> {
+    // a multi-stage aggregation repartitioning its input
+    let config = SessionConfig::new().with_target_partitions(4);
+    let ctx = create_parquet_context_with_config(config).await?;
+    let df = ctx.sql("SELECT DISTINCT a FROM data").await?;
+
+    roundtrip_parquet(df).await
+}
+
+#[tokio::test]
+async fn partitioned_hash_join() -> Result<()> {
+    let schema = test_schema();
+    let partitioned_scan = || -> Result<Arc<dyn ExecutionPlan>> {
+        let scan =
+            ParquetExec::builder(test_scan_config(Arc::clone(&schema))).build_arc();
+        let repartition = Arc::new(RepartitionExec::try_new(
+            scan,
+            Partitioning::Hash(vec![col("a", &schema)?], 4),
+        )?);
+        Ok(Arc::new(CoalesceBatchesExec::new(repartition, 8192)))
+    };
+    let join = Arc::new(HashJoinExec::try_new(
+        partitioned_scan()?,
+        partitioned_scan()?,
+        vec![(col("a", &schema)?, col("a", &schema)?)],
+        None,
+        &JoinType::Inner,
+        Some(vec![0, 3]),
+        PartitionMode::Partitioned,
+        false,
+    )?);
+    let coalesce = Arc::new(CoalescePartitionsExec::new(join));
+
+    roundtrip_exec(coalesce).await
+}
+
+#[tokio::test]
+async fn sort_and_limit() -> Result<()> {
+    let schema = test_schema();
+    let scan = ParquetExec::builder(test_scan_config(Arc::clone(&schema))).build_arc();
+    let sort = Arc::new(SortExec::new(
+        vec![
+            PhysicalSortExpr {
+                expr: col("a", &schema)?,
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: false,
+                },
+            },
+            PhysicalSortExpr {
+                expr: col("b", &schema)?,
+                options: SortOptions::default(),
+            },
+        ],
+        scan,
+    ));
+    let limit = Arc::new(GlobalLimitExec::new(sort, 1, Some(10)));
+
+    roundtrip_exec(limit).await
+}
+
 async fn roundtrip(sql: &str) -> Result<()> {
     let ctx = create_parquet_context().await?;
     let df = ctx.sql(sql).await?;
@@ -116,6 +312,10 @@ async fn roundtrip_alltypes(sql: &str) -> Result<()> {
 async fn roundtrip_parquet(df: DataFrame) -> Result<()> {
     let physical_plan = df.create_physical_plan().await?;
 
+    roundtrip_exec(physical_plan).await
+}
+
+async fn roundtrip_exec(physical_plan: Arc<dyn ExecutionPlan>) -> Result<()> {
     // Convert the plan into a substrait (protobuf) Rel
     let mut extension_info = (vec![], HashMap::new());
     let substrait_plan =
@@ -123,9 +323,13 @@ async fn roundtrip_parquet(df: DataFrame) -> Result<()> {
 
     // Convert the substrait Rel back into a physical plan
     let ctx = create_parquet_context().await?;
+    let functions = extension_info
+        .1
+        .iter()
+        .map(|(name, anchor)| (*anchor, name))
+        .collect::<HashMap<_, _>>();
     let physical_plan_roundtrip =
-        consumer::from_substrait_rel(&ctx, substrait_plan.as_ref(), &HashMap::new())
-            .await?;
+        consumer::from_substrait_rel(&ctx, substrait_plan.as_ref(), &functions).await?;
 
     // Compare the original and roundtrip physical plans
     let expected = format!("{}", displayable(physical_plan.as_ref()).indent(true));
@@ -138,8 +342,30 @@ async fn roundtrip_parquet(df: DataFrame) -> Result<()> {
     Ok(())
 }
 
+fn test_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+    ]))
+}
+
+fn test_scan_config(schema: SchemaRef) -> FileScanConfig {
+    FileScanConfig::new(ObjectStoreUrl::local_filesystem(), schema).with_file_groups(
+        vec![vec![PartitionedFile::new(
+            "file://foo/part-0.parquet".to_string(),
+            123,
+        )]],
+    )
+}
+
 async fn create_parquet_context() -> Result<SessionContext> {
-    let ctx = SessionContext::new();
+    create_parquet_context_with_config(SessionConfig::new()).await
+}
+
+async fn create_parquet_context_with_config(
+    config: SessionConfig,
+) -> Result<SessionContext> {
+    let ctx = SessionContext::new_with_config(config);
     let explicit_options = ParquetReadOptions::default();
 
     ctx.register_parquet("data", "tests/testdata/data.parquet", explicit_options)